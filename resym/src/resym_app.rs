@@ -2,11 +2,18 @@ use anyhow::Result;
 use eframe::egui;
 use memory_logger::blocking::MemoryLogger;
 use resym_core::{
-    backend::{Backend, BackendCommand, PDBSlot, SymbolFilters, TypeFilters},
+    backend::{Backend, BackendCommand, PDBSlot, StreamSource, SymbolFilters, TypeFilters},
     frontend::FrontendCommand,
-    pdb_file::{SymbolIndex, TypeIndex},
+    hexdump::hexdump,
+    pdb_file::{SymbolIndex, SymbolXrefKind, TypeIndex},
+    pdb_types::{DataFormatConfiguration, HeaderSplitFlavor, OutputLanguage},
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+use resym_core::frontend::ReconstructedFiles;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
 #[cfg(target_arch = "wasm32")]
 use std::{cell::RefCell, rc::Rc};
 use std::{fmt::Write, sync::Arc, vec};
@@ -20,12 +27,15 @@ use crate::{
     settings::ResymAppSettings,
     ui_components::{
         CodeViewComponent, ConsoleComponent, IndexListComponent, IndexListOrdering,
-        ModuleTreeComponent, SearchFiltersComponent, SettingsComponent, TextSearchComponent,
+        InlinedCallsComponent, ModuleTreeComponent, PdbPropertiesComponent, SearchFiltersComponent,
+        SettingsComponent, TextSearchComponent, TypeGraphComponent,
     },
 };
 
 const PKG_NAME: &str = env!("CARGO_PKG_NAME");
 const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Maximum number of bytes shown when displaying the content of a stream
+const MAX_HEX_VIEW_SIZE: usize = 1024 * 1024;
 
 #[derive(Clone, Copy)]
 pub enum ResymPDBSlots {
@@ -47,6 +57,7 @@ enum LeftPanelTab {
     TypeSearch,
     SymbolSearch,
     ModuleBrowsing,
+    StreamBrowsing,
 }
 
 /// Tabs available for the bottom panel
@@ -55,6 +66,8 @@ enum BottomPanelTab {
     Console,
     XRefsTo,
     XRefsFrom,
+    SymbolXRefs,
+    TypeGraph,
 }
 
 /// Struct that represents our GUI application.
@@ -67,24 +80,42 @@ pub struct ResymApp {
     type_filters: SearchFiltersComponent<TypeFilters>,
     type_list: IndexListComponent<TypeIndex>,
     selected_type_index: Option<TypeIndex>,
+    /// Show the raw CodeView records of the selected type instead of its
+    /// reconstruction
+    show_raw_records: bool,
     symbol_search: TextSearchComponent,
     symbol_filters: SearchFiltersComponent<SymbolFilters>,
     symbol_list: IndexListComponent<SymbolIndex>,
     selected_symbol_index: Option<SymbolIndex>,
     module_search: TextSearchComponent,
     module_tree: ModuleTreeComponent,
+    stream_list: IndexListComponent<u16>,
+    /// File loaded in the main slot, whose streams are browsed
+    stream_source: Option<StreamSource>,
     code_view: CodeViewComponent,
+    inlined_calls: InlinedCallsComponent,
     // Components used in the bottom panel
     bottom_panel_selected_tab: BottomPanelTab,
     console: ConsoleComponent,
     xref_to_list: IndexListComponent<TypeIndex>,
     xref_from_list: IndexListComponent<TypeIndex>,
+    symbol_xref_list: IndexListComponent<SymbolIndex>,
+    type_graph: TypeGraphComponent,
     // Other components
     settings: SettingsComponent,
+    pdb_properties: PdbPropertiesComponent,
     #[cfg(feature = "http")]
     open_url: OpenURLComponent,
     frontend_controller: Arc<EguiFrontendController>,
     backend: Backend,
+    /// Path of the file the next reconstructed type should be saved to,
+    /// used when saving the current type as C or Rust.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_save_path: Option<String>,
+    /// Path of the directory the next reconstructed type files should be
+    /// saved to.
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_save_directory_path: Option<PathBuf>,
     /// Field used by wasm32 targets to store PDB file information
     /// temporarily when selecting a PDB file to open.
     #[cfg(target_arch = "wasm32")]
@@ -115,6 +146,9 @@ impl eframe::App for ResymApp {
         // Update the "Settings" window if open
         self.settings.update(ctx);
 
+        // Update the "PDB properties" window if open
+        self.pdb_properties.update(ctx);
+
         // Update "Open URL" window if open
         #[cfg(feature = "http")]
         self.open_url.update(ctx, &self.backend);
@@ -169,22 +203,33 @@ impl ResymApp {
             type_filters: SearchFiltersComponent::new("Search filters"),
             type_list: IndexListComponent::new(IndexListOrdering::Alphabetical),
             selected_type_index: None,
+            show_raw_records: false,
             symbol_search: TextSearchComponent::new(),
             symbol_filters: SearchFiltersComponent::new("Search filters"),
             symbol_list: IndexListComponent::new(IndexListOrdering::Alphabetical),
             selected_symbol_index: None,
             module_search: TextSearchComponent::new(),
             module_tree: ModuleTreeComponent::new(),
+            stream_list: IndexListComponent::new(IndexListOrdering::None),
+            stream_source: None,
             code_view: CodeViewComponent::new(),
+            inlined_calls: InlinedCallsComponent::new(),
             bottom_panel_selected_tab: BottomPanelTab::Console,
             console: ConsoleComponent::new(logger),
             xref_to_list: IndexListComponent::new(IndexListOrdering::Alphabetical),
             xref_from_list: IndexListComponent::new(IndexListOrdering::Alphabetical),
+            symbol_xref_list: IndexListComponent::new(IndexListOrdering::None),
+            type_graph: TypeGraphComponent::new(),
             settings: SettingsComponent::new(app_settings),
+            pdb_properties: PdbPropertiesComponent::new(),
             #[cfg(feature = "http")]
             open_url: OpenURLComponent::new(),
             frontend_controller,
             backend,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_save_path: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_save_directory_path: None,
             #[cfg(target_arch = "wasm32")]
             open_pdb_data: Rc::new(RefCell::new(None)),
         })
@@ -231,6 +276,11 @@ impl ResymApp {
                         LeftPanelTab::ModuleBrowsing,
                         "Browse modules",
                     );
+                    ui.selectable_value(
+                        &mut self.left_panel_selected_tab,
+                        LeftPanelTab::StreamBrowsing,
+                        "Browse streams",
+                    );
                 });
                 ui.separator();
 
@@ -260,6 +310,7 @@ impl ResymApp {
                                         self.settings.app_settings.search_use_regex,
                                         self.settings.app_settings.ignore_std_types,
                                         search_filters.clone(),
+                                        None,
                                     ))
                                 };
                                 if let Err(err) = result {
@@ -293,21 +344,13 @@ impl ResymApp {
 
                             match self.current_mode {
                                 ResymAppMode::Browsing(..) => {
-                                    if let Err(err) = self.backend.send_command(
-                                        BackendCommand::ReconstructTypeByIndex(
-                                            ResymPDBSlots::Main as usize,
+                                    if let Err(err) =
+                                        self.backend.send_command(browse_type_command(
                                             type_index,
-                                            self.settings.app_settings.primitive_types_flavor,
-                                            self.settings.app_settings.print_access_specifiers,
-                                            self.settings.app_settings.size_print_flavor,
-                                            self.settings.app_settings.print_header,
-                                            self.settings.app_settings.reconstruct_dependencies,
-                                            self.settings.app_settings.integers_as_hexadecimal,
-                                            self.settings.app_settings.print_offset_info,
-                                            self.settings.app_settings.print_brackets_new_line,
-                                            self.settings.app_settings.ignore_std_types,
-                                        ),
-                                    ) {
+                                            self.show_raw_records,
+                                            &self.settings.app_settings,
+                                        ))
+                                    {
                                         log::error!("Failed to reconstruct type: {}", err);
                                     }
                                 }
@@ -317,14 +360,9 @@ impl ResymApp {
                                             ResymPDBSlots::Main as usize,
                                             ResymPDBSlots::Diff as usize,
                                             type_name.to_string(),
-                                            self.settings.app_settings.primitive_types_flavor,
-                                            self.settings.app_settings.print_access_specifiers,
-                                            self.settings.app_settings.size_print_flavor,
+                                            data_format_configuration(&self.settings.app_settings),
                                             self.settings.app_settings.print_header,
                                             self.settings.app_settings.reconstruct_dependencies,
-                                            self.settings.app_settings.integers_as_hexadecimal,
-                                            self.settings.app_settings.print_offset_info,
-                                            self.settings.app_settings.print_brackets_new_line,
                                             self.settings.app_settings.ignore_std_types,
                                         ))
                                     {
@@ -396,19 +434,11 @@ impl ResymApp {
                                 self.selected_symbol_index = Some(symbol_index);
 
                                 match self.current_mode {
-                                    ResymAppMode::Browsing(..) => {
-                                        if let Err(err) = self.backend.send_command(
-                                            BackendCommand::ReconstructSymbolByIndex(
-                                                ResymPDBSlots::Main as usize,
-                                                symbol_index,
-                                                self.settings.app_settings.primitive_types_flavor,
-                                                self.settings.app_settings.print_access_specifiers,
-                                                self.settings.app_settings.print_header,
-                                            ),
-                                        ) {
-                                            log::error!("Failed to reconstruct type: {}", err);
-                                        }
-                                    }
+                                    ResymAppMode::Browsing(..) => browse_symbol(
+                                        &self.backend,
+                                        symbol_index,
+                                        &self.settings.app_settings,
+                                    ),
                                     ResymAppMode::Comparing(..) => {
                                         if let Err(err) = self.backend.send_command(
                                             BackendCommand::DiffSymbolByName(
@@ -495,6 +525,36 @@ impl ResymApp {
                         // Update the module list
                         self.module_tree.update(ctx, ui, &on_module_selected);
                     }
+
+                    LeftPanelTab::StreamBrowsing => {
+                        // Callback run when a stream is selected in the list
+                        let mut on_stream_selected =
+                            |_: &str, stream_index: u16| match self.current_mode {
+                                // Note: streams can be browsed even if the PDB
+                                // failed to load
+                                ResymAppMode::Idle | ResymAppMode::Browsing(..) => {
+                                    let Some(stream_source) = &self.stream_source else {
+                                        return;
+                                    };
+                                    if let Err(err) =
+                                        self.backend.send_command(BackendCommand::ReadStream(
+                                            stream_source.clone(),
+                                            stream_index,
+                                            Some(MAX_HEX_VIEW_SIZE),
+                                        ))
+                                    {
+                                        log::error!("Failed to read stream: {}", err);
+                                    }
+                                }
+
+                                ResymAppMode::Comparing(..) => {
+                                    log::warn!("Streams cannot be compared");
+                                }
+                            };
+
+                        // Update the stream list
+                        self.stream_list.update(ui, &mut on_stream_selected);
+                    }
                 }
             });
     }
@@ -527,32 +587,42 @@ impl ResymApp {
                                 BottomPanelTab::XRefsFrom,
                                 "XRefs from",
                             );
+                            ui.selectable_value(
+                                &mut self.bottom_panel_selected_tab,
+                                BottomPanelTab::SymbolXRefs,
+                                "Symbol xrefs",
+                            );
+                            ui.selectable_value(
+                                &mut self.bottom_panel_selected_tab,
+                                BottomPanelTab::TypeGraph,
+                                "Graph",
+                            );
                         }
                     });
                     ui.separator();
 
+                    // Start a new graph when a type which isn't part of the
+                    // current graph gets selected
+                    if self.bottom_panel_selected_tab == BottomPanelTab::TypeGraph {
+                        if let Some(selected_type_index) = self.selected_type_index {
+                            if !self.type_graph.contains(selected_type_index) {
+                                self.type_graph.reset(selected_type_index);
+                                request_type_neighbourhood(&self.backend, selected_type_index);
+                            }
+                        }
+                    }
+
                     let mut on_type_selected = |_: &str, type_index: TypeIndex| {
                         // Update currently selected type index
                         self.selected_type_index = Some(type_index);
 
                         // Note: only support "Browsing" mode
                         if let ResymAppMode::Browsing(..) = self.current_mode {
-                            if let Err(err) =
-                                self.backend
-                                    .send_command(BackendCommand::ReconstructTypeByIndex(
-                                        ResymPDBSlots::Main as usize,
-                                        type_index,
-                                        self.settings.app_settings.primitive_types_flavor,
-                                        self.settings.app_settings.print_access_specifiers,
-                                        self.settings.app_settings.size_print_flavor,
-                                        self.settings.app_settings.print_header,
-                                        self.settings.app_settings.reconstruct_dependencies,
-                                        self.settings.app_settings.integers_as_hexadecimal,
-                                        self.settings.app_settings.print_offset_info,
-                                        self.settings.app_settings.print_brackets_new_line,
-                                        self.settings.app_settings.ignore_std_types,
-                                    ))
-                            {
+                            if let Err(err) = self.backend.send_command(browse_type_command(
+                                type_index,
+                                self.show_raw_records,
+                                &self.settings.app_settings,
+                            )) {
                                 log::error!("Failed to reconstruct type: {}", err);
                             }
                         }
@@ -572,6 +642,36 @@ impl ResymApp {
                             // Update xref list
                             self.xref_from_list.update(ui, &mut on_type_selected);
                         }
+                        BottomPanelTab::SymbolXRefs => {
+                            // Callback run when a symbol is selected in the list
+                            let mut on_symbol_selected = |_: &str, symbol_index: SymbolIndex| {
+                                // Update currently selected symbol index
+                                self.selected_symbol_index = Some(symbol_index);
+
+                                // Note: only support "Browsing" mode
+                                if let ResymAppMode::Browsing(..) = self.current_mode {
+                                    browse_symbol(
+                                        &self.backend,
+                                        symbol_index,
+                                        &self.settings.app_settings,
+                                    );
+                                }
+                            };
+                            // Update symbol xref list
+                            self.symbol_xref_list.update(ui, &mut on_symbol_selected);
+                        }
+                        BottomPanelTab::TypeGraph => {
+                            // Callback run when a node of the graph is clicked
+                            let mut on_node_selected =
+                                |type_name: &str, type_index: TypeIndex, expand: bool| {
+                                    on_type_selected(type_name, type_index);
+                                    if expand {
+                                        request_type_neighbourhood(&self.backend, type_index);
+                                    }
+                                };
+                            // Update type graph
+                            self.type_graph.update(ui, &mut on_node_selected);
+                        }
                     }
                 });
             });
@@ -606,11 +706,33 @@ impl ResymApp {
                                 self.list_xrefs_for_type(selected_type_index);
                             }
                         }
+
+                        // Raw records toggle
+                        if let Some(selected_type_index) = self.selected_type_index {
+                            if ui
+                                .checkbox(&mut self.show_raw_records, "Show raw records")
+                                .changed()
+                            {
+                                if let Err(err) = self.backend.send_command(browse_type_command(
+                                    selected_type_index,
+                                    self.show_raw_records,
+                                    &self.settings.app_settings,
+                                )) {
+                                    log::error!("Failed to reconstruct type: {}", err);
+                                }
+                            }
+                        }
                     }
                 });
             });
             ui.separator();
 
+            // Show the calls inlined in the selected procedure, if any
+            if !self.inlined_calls.is_empty() {
+                self.inlined_calls.update(ui);
+                ui.separator();
+            }
+
             // Update the code view component
             self.code_view
                 .update(&self.settings.app_settings, &self.current_mode, ui);
@@ -700,6 +822,8 @@ impl ResymApp {
                             // Reset xref lists
                             self.xref_to_list.update_index_list(vec![]);
                             self.xref_from_list.update_index_list(vec![]);
+                            self.symbol_xref_list.update_index_list(vec![]);
+                            self.type_graph.clear();
 
                             // Request a type list update
                             if let Err(err) = self.backend.send_command(BackendCommand::ListTypes(
@@ -709,9 +833,19 @@ impl ResymApp {
                                 false,
                                 self.settings.app_settings.ignore_std_types,
                                 Default::default(),
+                                None,
                             )) {
                                 log::error!("Failed to update type filter value: {}", err);
                             }
+                            // Request the location of the declaration of types
+                            if let Err(err) =
+                                self.backend
+                                    .send_command(BackendCommand::ListTypeSourceLocations(
+                                        ResymPDBSlots::Main as usize,
+                                    ))
+                            {
+                                log::error!("Failed to list type source locations: {}", err);
+                            }
                             // Request a symbol list update
                             if let Err(err) =
                                 self.backend.send_command(BackendCommand::ListSymbols(
@@ -736,6 +870,28 @@ impl ResymApp {
                             {
                                 log::error!("Failed to update module list: {}", err);
                             }
+                            // Request the list of source files, shown in the module tree
+                            if let Err(err) = self.backend.send_command(
+                                BackendCommand::ListSourceFiles(ResymPDBSlots::Main as usize),
+                            ) {
+                                log::error!("Failed to list source files: {}", err);
+                            }
+                            // Request the section contributions, summarized in the module tree
+                            if let Err(err) =
+                                self.backend
+                                    .send_command(BackendCommand::ListSectionContributions(
+                                        ResymPDBSlots::Main as usize,
+                                        None,
+                                    ))
+                            {
+                                log::error!("Failed to list section contributions: {}", err);
+                            }
+                            // Request the PDB's metadata, shown in the "PDB properties" window
+                            if let Err(err) = self.backend.send_command(
+                                BackendCommand::GetPdbMetadata(ResymPDBSlots::Main as usize),
+                            ) {
+                                log::error!("Failed to retrieve PDB metadata: {}", err);
+                            }
                         } else if pdb_slot == ResymPDBSlots::Diff as usize {
                             // Reset current mode
                             self.current_mode = ResymAppMode::Comparing(
@@ -747,9 +903,13 @@ impl ResymApp {
                             );
                             // Reset selected type
                             self.selected_type_index = None;
+                            // Type indices aren't preserved in merged type lists
+                            self.type_list.update_index_tooltips(Default::default());
                             // Reset xref lists
                             self.xref_to_list.update_index_list(vec![]);
                             self.xref_from_list.update_index_list(vec![]);
+                            self.symbol_xref_list.update_index_list(vec![]);
+                            self.type_graph.clear();
 
                             // Request a type list update
                             if let Err(err) =
@@ -776,16 +936,50 @@ impl ResymApp {
                         log::error!("Failed to load URL: {}", err);
                     }
                     Ok((pdb_slot, file_name, data)) => {
-                        if let Err(err) = self
-                            .backend
-                            .send_command(BackendCommand::LoadPDBFromVec(pdb_slot, file_name, data))
-                        {
+                        let data: Arc<[u8]> = data.into();
+                        if let Err(err) = self.backend.send_command(
+                            BackendCommand::LoadPDBFromArray(pdb_slot, file_name, data.clone()),
+                        ) {
                             log::error!("Failed to load the PDB file: {err}");
                         }
+                        self.list_streams(pdb_slot, StreamSource::Array(data));
                     }
                 },
 
+                FrontendCommand::ReconstructTypeFilesResult(reconstructed_files_result) => {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(directory_path) = self.pending_save_directory_path.take() {
+                        match reconstructed_files_result {
+                            Err(err) => {
+                                log::error!("Failed to reconstruct types: {}", err);
+                            }
+                            Ok(reconstructed_files) => {
+                                save_reconstructed_files(&directory_path, reconstructed_files);
+                            }
+                        }
+                    }
+                    // Note: types cannot be saved as a directory on wasm32 targets
+                    #[cfg(target_arch = "wasm32")]
+                    let _ = reconstructed_files_result;
+                }
+
                 FrontendCommand::ReconstructTypeResult(type_reconstruction_result) => {
+                    // Type reconstructed to be saved as C or Rust, don't display it
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(file_path) = self.pending_save_path.take() {
+                        match type_reconstruction_result {
+                            Err(err) => {
+                                log::error!("Failed to reconstruct type: {}", err);
+                            }
+                            Ok((reconstructed_type, _)) => {
+                                save_reconstructed_content(&file_path, &reconstructed_type);
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Inlined calls are only relevant to symbols
+                    self.inlined_calls.set_inlined_calls(vec![]);
                     match type_reconstruction_result {
                         Err(err) => {
                             let error_msg = format!("Failed to reconstruct type: {}", err);
@@ -811,8 +1005,36 @@ impl ResymApp {
                             // Update xref lists
                             self.xref_to_list.update_index_list(vec![]);
                             self.xref_from_list.update_index_list(xrefs_from);
-                            // Switch to the "xref from" tab
-                            self.bottom_panel_selected_tab = BottomPanelTab::XRefsFrom;
+                            // Switch to the "xref from" tab, unless the user is
+                            // exploring the type graph
+                            if self.bottom_panel_selected_tab != BottomPanelTab::TypeGraph {
+                                self.bottom_panel_selected_tab = BottomPanelTab::XRefsFrom;
+                            }
+                        }
+                    }
+                }
+
+                FrontendCommand::DumpRecordsResult(records_result) => {
+                    // Inlined calls are only relevant to symbols
+                    self.inlined_calls.set_inlined_calls(vec![]);
+                    match records_result {
+                        Err(err) => {
+                            let error_msg = format!("Failed to dump records: {}", err);
+                            log::error!("{}", &error_msg);
+
+                            // Show an empty "reconstruted" view
+                            self.current_mode =
+                                ResymAppMode::Browsing(Default::default(), 0, error_msg);
+                        }
+                        Ok(records) => {
+                            let last_line_number = 1 + records.lines().count();
+                            let line_numbers =
+                                (1..last_line_number).fold(String::default(), |mut acc, e| {
+                                    let _r = writeln!(&mut acc, "{e}");
+                                    acc
+                                });
+                            self.current_mode =
+                                ResymAppMode::Browsing(line_numbers, last_line_number, records);
                         }
                     }
                 }
@@ -828,6 +1050,22 @@ impl ResymApp {
                     }
                 }
 
+                FrontendCommand::ListSourceFilesResult(source_file_list_result) => {
+                    match source_file_list_result {
+                        Err(err) => {
+                            log::error!("Failed to retrieve source file list: {}", err);
+                        }
+                        Ok(source_file_list) => {
+                            self.module_tree.set_source_file_list(source_file_list);
+                        }
+                    }
+                }
+
+                FrontendCommand::ListSectionContributionsResult(section_contributions) => {
+                    self.module_tree
+                        .set_section_contributions(section_contributions);
+                }
+
                 FrontendCommand::ReconstructSymbolResult(result) => {
                     match result {
                         Err(err) => {
@@ -855,6 +1093,8 @@ impl ResymApp {
                 }
 
                 FrontendCommand::ReconstructModuleResult(module_reconstruction_result) => {
+                    // Inlined calls are only relevant to symbols
+                    self.inlined_calls.set_inlined_calls(vec![]);
                     match module_reconstruction_result {
                         Err(err) => {
                             let error_msg = format!("Failed to reconstruct module: {}", err);
@@ -939,6 +1179,18 @@ impl ResymApp {
                     self.type_list.update_index_list(filtered_types);
                 }
 
+                FrontendCommand::ListTypeSourceLocationsResult(type_source_locations) => {
+                    // Show the declaration of types when hovering them
+                    self.type_list.update_index_tooltips(
+                        type_source_locations
+                            .into_iter()
+                            .map(|(type_index, source_location)| {
+                                (type_index, format!("Declared in {source_location}"))
+                            })
+                            .collect(),
+                    );
+                }
+
                 FrontendCommand::ListSymbolsResult(filtered_symbols) => {
                     // Update symbol list component
                     self.symbol_list.update_index_list(filtered_symbols);
@@ -960,6 +1212,149 @@ impl ResymApp {
                         }
                     }
                 }
+
+                FrontendCommand::ListSymbolCrossReferencesResult(xref_list_result) => {
+                    match xref_list_result {
+                        Err(err) => {
+                            log::error!("Failed to list symbol cross-references: {err}");
+                        }
+                        Ok(xref_list) => {
+                            let xref_count = xref_list.len();
+                            log::info!("{xref_count} symbol cross-references found!");
+
+                            // Update symbol xref list component
+                            self.symbol_xref_list.update_index_list(
+                                xref_list
+                                    .into_iter()
+                                    .map(|symbol_xref| {
+                                        let xref_desc = match symbol_xref.kind {
+                                            SymbolXrefKind::Variable => {
+                                                format!("{} (variable)", symbol_xref.symbol_name)
+                                            }
+                                            SymbolXrefKind::Parameter => {
+                                                format!("{} (parameter)", symbol_xref.symbol_name)
+                                            }
+                                            SymbolXrefKind::ReturnValue => format!(
+                                                "{} (return value)",
+                                                symbol_xref.symbol_name
+                                            ),
+                                            SymbolXrefKind::LocalVariable => format!(
+                                                "{} (local variable {})",
+                                                symbol_xref.symbol_name,
+                                                symbol_xref.variable_name.unwrap_or_default()
+                                            ),
+                                        };
+                                        (xref_desc, symbol_xref.symbol_index)
+                                    })
+                                    .collect(),
+                            );
+                        }
+                    }
+                }
+
+                FrontendCommand::ListStreamsResult(stream_list_result) => {
+                    match stream_list_result {
+                        Err(err) => {
+                            log::error!("Failed to retrieve stream list: {}", err);
+                        }
+                        Ok(stream_list) => {
+                            self.stream_list.update_index_list(
+                                stream_list
+                                    .into_iter()
+                                    .filter_map(|stream| {
+                                        let size = stream.size?;
+                                        let stream_desc = match stream.name {
+                                            Some(name) => format!(
+                                                "#{:03} {} ({} bytes)",
+                                                stream.index, name, size
+                                            ),
+                                            None => {
+                                                format!("#{:03} ({} bytes)", stream.index, size)
+                                            }
+                                        };
+                                        Some((stream_desc, stream.index))
+                                    })
+                                    .collect(),
+                            );
+                        }
+                    }
+                }
+
+                FrontendCommand::ReadStreamResult(stream_index, stream_data_result) => {
+                    // Inlined calls are only relevant to symbols
+                    self.inlined_calls.set_inlined_calls(vec![]);
+                    match stream_data_result {
+                        Err(err) => {
+                            let error_msg = format!("Failed to read stream: {}", err);
+                            log::error!("{}", &error_msg);
+
+                            // Show an empty "reconstruted" view
+                            self.current_mode =
+                                ResymAppMode::Browsing(Default::default(), 0, error_msg);
+                        }
+                        Ok(stream_data) => {
+                            let mut stream_dump = hexdump(&stream_data);
+                            if stream_data.len() >= MAX_HEX_VIEW_SIZE {
+                                let _r = writeln!(
+                                    &mut stream_dump,
+                                    "[Stream {stream_index} truncated to {MAX_HEX_VIEW_SIZE} bytes]"
+                                );
+                            }
+                            let last_line_number = 1 + stream_dump.lines().count();
+                            let line_numbers =
+                                (1..last_line_number).fold(String::default(), |mut acc, e| {
+                                    let _r = writeln!(&mut acc, "{e}");
+                                    acc
+                                });
+                            self.current_mode =
+                                ResymAppMode::Browsing(line_numbers, last_line_number, stream_dump);
+                        }
+                    }
+                }
+
+                FrontendCommand::GetTypeGraphResult(type_graph_result) => match type_graph_result {
+                    Err(err) => {
+                        log::error!("Failed to retrieve type graph: {}", err);
+                    }
+                    Ok(type_graph) => {
+                        self.type_graph.add_neighbourhood(type_graph);
+                    }
+                },
+
+                FrontendCommand::GetPdbMetadataResult(pdb_metadata_result) => {
+                    match pdb_metadata_result {
+                        Err(err) => {
+                            log::error!("Failed to retrieve PDB metadata: {}", err);
+                            self.pdb_properties.set_pdb_metadata(None);
+                        }
+                        Ok(pdb_metadata) => {
+                            self.pdb_properties.set_pdb_metadata(Some(pdb_metadata));
+                        }
+                    }
+                }
+
+                FrontendCommand::SymbolizeAddressesResult(_)
+                | FrontendCommand::ListInlineCallSitesResult(_)
+                | FrontendCommand::ListSourceFileFunctionsResult(_)
+                | FrontendCommand::FindSectionContributionResult(_)
+                | FrontendCommand::GenerateSizeReportResult(_)
+                | FrontendCommand::DiffSymbolSizesResult(_) => {
+                    // Address symbolization, inline call site, source file
+                    // function, contribution, size report and size diff
+                    // queries aren't available in the GUI
+                }
+
+                FrontendCommand::ListInlinedCallsResult(inlined_calls_result) => {
+                    match inlined_calls_result {
+                        Err(err) => {
+                            log::error!("Failed to list inlined calls: {err}");
+                            self.inlined_calls.set_inlined_calls(vec![]);
+                        }
+                        Ok(inlined_calls) => {
+                            self.inlined_calls.set_inlined_calls(inlined_calls);
+                        }
+                    }
+                }
             }
         }
     }
@@ -1005,7 +1400,37 @@ impl ResymApp {
                     self.open_url.open(ResymPDBSlots::Diff);
                 }
 
-                // Separate "Compare" from "Settings"
+                // Separate "Compare" from "Save"
+                ui.separator();
+
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui
+                    .add_enabled(
+                        matches!(self.current_mode, ResymAppMode::Browsing(..)),
+                        egui::Button::new("Save all as directory ..."),
+                    )
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.start_save_all_types_as_directory();
+                }
+
+                // Separate "Save" from "Properties"
+                #[cfg(not(target_arch = "wasm32"))]
+                ui.separator();
+
+                if ui
+                    .add_enabled(
+                        !matches!(self.current_mode, ResymAppMode::Idle),
+                        egui::Button::new("PDB properties"),
+                    )
+                    .clicked()
+                {
+                    ui.close_menu();
+                    self.pdb_properties.open();
+                }
+
+                // Separate "Properties" from "Settings"
                 ui.separator();
 
                 if ui.button("Settings").clicked() {
@@ -1030,12 +1455,14 @@ impl ResymApp {
             Some((&["*.pdb"], "PDB files (*.pdb)")),
         );
         if let Some(file_path) = file_path_opt {
+            let file_path = PathBuf::from(file_path);
             if let Err(err) = self
                 .backend
-                .send_command(BackendCommand::LoadPDBFromPath(pdb_slot, file_path.into()))
+                .send_command(BackendCommand::LoadPDBFromPath(pdb_slot, file_path.clone()))
             {
                 log::error!("Failed to load the PDB file: {err}");
             }
+            self.list_streams(pdb_slot, StreamSource::Path(file_path));
         }
     }
 
@@ -1056,16 +1483,39 @@ impl ResymApp {
     }
 
     #[cfg(target_arch = "wasm32")]
-    fn process_open_pdb_file_result(&self) {
+    fn process_open_pdb_file_result(&mut self) {
         // We unwrap() the return value to assert that we are not expecting
         // threads to ever fail while holding the lock.
-        if let Some((pdb_slot, pdb_name, pdb_bytes)) = self.open_pdb_data.borrow_mut().take() {
-            if let Err(err) = self.backend.send_command(BackendCommand::LoadPDBFromVec(
-                pdb_slot, pdb_name, pdb_bytes,
+        let open_pdb_data = self.open_pdb_data.borrow_mut().take();
+        if let Some((pdb_slot, pdb_name, pdb_bytes)) = open_pdb_data {
+            let pdb_bytes: Arc<[u8]> = pdb_bytes.into();
+            if let Err(err) = self.backend.send_command(BackendCommand::LoadPDBFromArray(
+                pdb_slot,
+                pdb_name,
+                pdb_bytes.clone(),
             )) {
                 log::error!("Failed to load the PDB file: {err}");
             }
+            self.list_streams(pdb_slot, StreamSource::Array(pdb_bytes));
+        }
+    }
+
+    /// Request the list of streams of the file loaded in the main slot. Note:
+    /// streams are read directly from the file, so that they can be browsed
+    /// even if the PDB fails to load.
+    fn list_streams(&mut self, pdb_slot: PDBSlot, stream_source: StreamSource) {
+        if pdb_slot != ResymPDBSlots::Main as usize {
+            return;
+        }
+
+        self.stream_list.update_index_list(vec![]);
+        if let Err(err) = self
+            .backend
+            .send_command(BackendCommand::ListStreams(stream_source.clone()))
+        {
+            log::error!("Failed to list streams: {}", err);
         }
+        self.stream_source = Some(stream_source);
     }
 
     /// Function invoked on 'Find XRefs to'
@@ -1086,68 +1536,236 @@ impl ResymApp {
                 type_index
             );
         }
+        if let Err(err) = self
+            .backend
+            .send_command(BackendCommand::ListSymbolCrossReferences(
+                ResymPDBSlots::Main as usize,
+                type_index,
+            ))
+        {
+            log::error!(
+                "Failed to list symbol cross-references to type #0x{:x}: {err}",
+                type_index
+            );
+        }
     }
 
     /// Function invoked on 'Save' or when the Ctrl+S shortcut is used
     #[cfg(not(target_arch = "wasm32"))]
-    fn start_save_reconstruted_content(&self) {
+    fn start_save_reconstruted_content(&mut self) {
         if let ResymAppMode::Browsing(_, _, ref reconstructed_type) = self.current_mode {
             let file_path_opt = tinyfiledialogs::save_file_dialog_with_filter(
                 "Save content to file",
                 "",
-                &["*.c", "*.cc", "*.cpp", "*.cxx", "*.h", "*.hpp", "*.hxx"],
-                "C/C++ Source File (*.c;*.cc;*.cpp;*.cxx;*.h;*.hpp;*.hxx)",
+                &[
+                    "*.c", "*.cc", "*.cpp", "*.cxx", "*.h", "*.hpp", "*.hxx", "*.rs",
+                ],
+                "C/C++ or Rust Source File (*.c;*.cc;*.cpp;*.cxx;*.h;*.hpp;*.hxx;*.rs)",
             );
             if let Some(file_path) = file_path_opt {
-                let write_result = std::fs::write(&file_path, reconstructed_type);
-                match write_result {
-                    Ok(()) => log::info!("Reconstructed content has been saved to '{file_path}'."),
-                    Err(err) => {
-                        log::error!("Failed to write reconstructed content to file: {err}");
+                let output_language = if file_path.ends_with(".rs") {
+                    Some(OutputLanguage::Rust)
+                } else if file_path.ends_with(".c") {
+                    Some(OutputLanguage::C)
+                } else {
+                    None
+                };
+                match (self.selected_type_index, output_language) {
+                    // Saving as C or Rust requires the selected type to be reconstructed again
+                    (Some(selected_type_index), Some(output_language)) => {
+                        if let Err(err) =
+                            self.backend
+                                .send_command(BackendCommand::ReconstructTypeByIndex(
+                                    ResymPDBSlots::Main as usize,
+                                    selected_type_index,
+                                    output_language,
+                                    data_format_configuration(&self.settings.app_settings),
+                                    self.settings.app_settings.print_header,
+                                    self.settings.app_settings.reconstruct_dependencies,
+                                    self.settings.app_settings.ignore_std_types,
+                                ))
+                        {
+                            log::error!("Failed to reconstruct type: {}", err);
+                        } else {
+                            self.pending_save_path = Some(file_path);
+                        }
                     }
+                    _ => save_reconstructed_content(&file_path, reconstructed_type),
                 }
             }
         }
     }
 
+    /// Function invoked on 'Save all as directory'. Types are saved as C++,
+    /// with one header per top-level namespace.
     #[cfg(not(target_arch = "wasm32"))]
-    fn handle_drag_and_drop(&self, ctx: &egui::Context) {
-        ctx.input(|i| {
-            // Handle dropped files
-            if !i.raw.dropped_files.is_empty() {
-                // Allow dropping 1 file (to just view it), or 2 files to diff them
-                let slots = [ResymPDBSlots::Main as usize, ResymPDBSlots::Diff as usize];
-                for (slot, file) in slots.iter().zip(i.raw.dropped_files.iter()) {
-                    if let Some(file_path) = &file.path {
-                        if let Err(err) = self
-                            .backend
-                            .send_command(BackendCommand::LoadPDBFromPath(*slot, file_path.into()))
-                        {
-                            log::error!("Failed to load the PDB file: {err}");
-                        }
-                    }
+    fn start_save_all_types_as_directory(&mut self) {
+        let directory_path_opt = tinyfiledialogs::select_folder_dialog("Save all types to", "");
+        if let Some(directory_path) = directory_path_opt {
+            if let Err(err) =
+                self.backend
+                    .send_command(BackendCommand::ReconstructAllTypesIntoHeaders(
+                        ResymPDBSlots::Main as usize,
+                        HeaderSplitFlavor::Namespace,
+                        OutputLanguage::Cpp,
+                        data_format_configuration(&self.settings.app_settings),
+                        self.settings.app_settings.print_header,
+                        self.settings.app_settings.ignore_std_types,
+                    ))
+            {
+                log::error!("Failed to reconstruct types: {}", err);
+            } else {
+                self.pending_save_directory_path = Some(directory_path.into());
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_drag_and_drop(&mut self, ctx: &egui::Context) {
+        // Handle dropped files
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+        // Allow dropping 1 file (to just view it), or 2 files to diff them
+        let slots = [ResymPDBSlots::Main as usize, ResymPDBSlots::Diff as usize];
+        for (slot, file) in slots.into_iter().zip(dropped_files) {
+            if let Some(file_path) = file.path {
+                if let Err(err) = self
+                    .backend
+                    .send_command(BackendCommand::LoadPDBFromPath(slot, file_path.clone()))
+                {
+                    log::error!("Failed to load the PDB file: {err}");
                 }
+                self.list_streams(slot, StreamSource::Path(file_path));
             }
-        });
+        }
     }
 
     #[cfg(target_arch = "wasm32")]
-    fn handle_drag_and_drop(&self, ctx: &egui::Context) {
-        ctx.input(|i| {
-            // Handle dropped files
-            if !i.raw.dropped_files.is_empty() {
-                // Allow dropping 1 file (to just view it), or 2 files to diff them
-                let slots = [ResymPDBSlots::Main as usize, ResymPDBSlots::Diff as usize];
-                for (slot, file) in slots.iter().zip(i.raw.dropped_files.iter()) {
-                    if let Some(file_bytes) = file.bytes.clone() {
-                        if let Err(err) = self.backend.send_command(
-                            BackendCommand::LoadPDBFromArray(*slot, file.name.clone(), file_bytes),
-                        ) {
-                            log::error!("Failed to load the PDB file: {err}");
-                        }
-                    }
+    fn handle_drag_and_drop(&mut self, ctx: &egui::Context) {
+        // Handle dropped files
+        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
+        // Allow dropping 1 file (to just view it), or 2 files to diff them
+        let slots = [ResymPDBSlots::Main as usize, ResymPDBSlots::Diff as usize];
+        for (slot, file) in slots.into_iter().zip(dropped_files) {
+            if let Some(file_bytes) = file.bytes {
+                if let Err(err) = self.backend.send_command(BackendCommand::LoadPDBFromArray(
+                    slot,
+                    file.name,
+                    file_bytes.clone(),
+                )) {
+                    log::error!("Failed to load the PDB file: {err}");
                 }
+                self.list_streams(slot, StreamSource::Array(file_bytes));
             }
-        });
+        }
+    }
+}
+
+/// Return the command used to display the type at `type_index` in the code
+/// view, either reconstructed as C++ or as raw CodeView records.
+fn browse_type_command(
+    type_index: TypeIndex,
+    show_raw_records: bool,
+    app_settings: &ResymAppSettings,
+) -> BackendCommand {
+    if show_raw_records {
+        return BackendCommand::DumpTypeRecordsByIndex(ResymPDBSlots::Main as usize, type_index);
+    }
+
+    BackendCommand::ReconstructTypeByIndex(
+        ResymPDBSlots::Main as usize,
+        type_index,
+        OutputLanguage::Cpp,
+        data_format_configuration(app_settings),
+        app_settings.print_header,
+        app_settings.reconstruct_dependencies,
+        app_settings.ignore_std_types,
+    )
+}
+
+/// Return the formatting options used to reconstruct types, as configured
+/// in the application settings.
+fn data_format_configuration(app_settings: &ResymAppSettings) -> DataFormatConfiguration {
+    DataFormatConfiguration {
+        print_access_specifiers: app_settings.print_access_specifiers,
+        size_print_flavor: app_settings.size_print_flavor,
+        integers_as_hexadecimal: app_settings.integers_as_hexadecimal,
+        print_offset_info: app_settings.print_offset_info,
+        print_brackets_new_line: app_settings.print_brackets_new_line,
+        print_vftables: app_settings.print_vftables,
+        print_padding: app_settings.print_padding,
+        print_offset_asserts: app_settings.print_offset_asserts,
+        print_namespaces: app_settings.print_namespaces,
+        print_source_locations: app_settings.print_source_locations,
+        primitive_flavor: app_settings.primitive_types_flavor,
+    }
+}
+
+/// Display the symbol at `symbol_index` in the code view, along with the calls
+/// inlined in it.
+fn browse_symbol(backend: &Backend, symbol_index: SymbolIndex, app_settings: &ResymAppSettings) {
+    if let Err(err) = backend.send_command(BackendCommand::ReconstructSymbolByIndex(
+        ResymPDBSlots::Main as usize,
+        symbol_index,
+        app_settings.primitive_types_flavor,
+        app_settings.print_access_specifiers,
+        app_settings.print_header,
+        app_settings.print_locals,
+        app_settings.print_locations,
+        app_settings.print_inlinees,
+    )) {
+        log::error!("Failed to reconstruct type: {}", err);
+    }
+    // Request the calls inlined in the symbol
+    if let Err(err) = backend.send_command(BackendCommand::ListInlinedCalls(
+        ResymPDBSlots::Main as usize,
+        symbol_index,
+    )) {
+        log::error!("Failed to list inlined calls: {}", err);
+    }
+}
+
+/// Request the types referenced by and referencing the type at `type_index`,
+/// to expand the type graph.
+fn request_type_neighbourhood(backend: &Backend, type_index: TypeIndex) {
+    if let Err(err) = backend.send_command(BackendCommand::GetTypeNeighbourhood(
+        ResymPDBSlots::Main as usize,
+        type_index,
+        true,
+    )) {
+        log::error!("Failed to retrieve type graph: {}", err);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_reconstructed_content(file_path: &str, reconstructed_content: &str) {
+    let write_result = std::fs::write(file_path, reconstructed_content);
+    match write_result {
+        Ok(()) => log::info!("Reconstructed content has been saved to '{file_path}'."),
+        Err(err) => {
+            log::error!("Failed to write reconstructed content to file: {err}");
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_reconstructed_files(directory_path: &Path, reconstructed_files: ReconstructedFiles) {
+    for (file_path, reconstructed_content) in &reconstructed_files {
+        let file_path = directory_path.join(file_path);
+        let write_result = file_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(&file_path, reconstructed_content));
+        if let Err(err) = write_result {
+            log::error!(
+                "Failed to write reconstructed content to '{}': {err}",
+                file_path.display()
+            );
+            return;
+        }
     }
+    log::info!(
+        "{} files have been saved to '{}'.",
+        reconstructed_files.len(),
+        directory_path.display()
+    );
 }
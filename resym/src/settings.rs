@@ -14,6 +14,14 @@ pub struct ResymAppSettings {
     pub integers_as_hexadecimal: bool,
     pub print_offset_info: bool,
     pub print_brackets_new_line: bool,
+    pub print_vftables: bool,
+    pub print_padding: bool,
+    pub print_offset_asserts: bool,
+    pub print_namespaces: bool,
+    pub print_source_locations: bool,
+    pub print_locals: bool,
+    pub print_locations: bool,
+    pub print_inlinees: bool,
     #[serde(with = "PrimitiveReconstructionFlavorDef")]
     pub primitive_types_flavor: PrimitiveReconstructionFlavor,
     #[serde(with = "AccessSpecifierReconstructionFlavorDef")]
@@ -38,6 +46,14 @@ impl Default for ResymAppSettings {
             integers_as_hexadecimal: true,
             print_offset_info: true,
             print_brackets_new_line: false,
+            print_vftables: false,
+            print_padding: false,
+            print_offset_asserts: false,
+            print_namespaces: false,
+            print_source_locations: false,
+            print_locals: false,
+            print_locations: false,
+            print_inlinees: false,
             primitive_types_flavor: PrimitiveReconstructionFlavor::Portable,
             print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
             size_print_flavor: SizePrintFlavor::Comment,
@@ -1,7 +1,11 @@
+use std::{collections::HashMap, hash::Hash};
+
 use eframe::egui::{self, ScrollArea, TextStyle};
 
-pub struct IndexListComponent<I: Copy> {
+pub struct IndexListComponent<I: Copy + Eq + Hash> {
     index_list: Vec<(String, I)>,
+    /// Text shown when hovering elements, by index
+    index_tooltips: HashMap<I, String>,
     selected_row: usize,
     list_ordering: IndexListOrdering,
     delayed_row_selection: Option<DelayedRowSelection>,
@@ -20,10 +24,11 @@ struct DelayedRowSelection {
     request_focus: bool,
 }
 
-impl<I: Copy> IndexListComponent<I> {
+impl<I: Copy + Eq + Hash> IndexListComponent<I> {
     pub fn new(ordering: IndexListOrdering) -> Self {
         Self {
             index_list: vec![],
+            index_tooltips: HashMap::new(),
             selected_row: usize::MAX,
             list_ordering: ordering,
             delayed_row_selection: None,
@@ -41,6 +46,10 @@ impl<I: Copy> IndexListComponent<I> {
         }
     }
 
+    pub fn update_index_tooltips(&mut self, index_tooltips: HashMap<I, String>) {
+        self.index_tooltips = index_tooltips;
+    }
+
     pub fn update<CB: FnMut(&str, I)>(&mut self, ui: &mut egui::Ui, on_element_selected: &mut CB) {
         let num_rows = self.index_list.len();
         const TEXT_STYLE: TextStyle = TextStyle::Body;
@@ -60,8 +69,11 @@ impl<I: Copy> IndexListComponent<I> {
                         for row_index in row_range.clone() {
                             let (type_name, type_index) = &self.index_list[row_index];
 
-                            let label =
+                            let mut label =
                                 ui.selectable_label(self.selected_row == row_index, type_name);
+                            if let Some(tooltip) = self.index_tooltips.get(type_index) {
+                                label = label.on_hover_text(tooltip);
+                            }
 
                             // If label was clicked this frame, select the corresponding element
                             if label.clicked() {
@@ -132,7 +144,7 @@ impl<I: Copy> IndexListComponent<I> {
     }
 }
 
-impl<I: Copy> Default for IndexListComponent<I> {
+impl<I: Copy + Eq + Hash> Default for IndexListComponent<I> {
     fn default() -> Self {
         Self::new(IndexListOrdering::None)
     }
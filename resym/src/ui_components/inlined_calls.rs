@@ -0,0 +1,72 @@
+use eframe::egui::{self, ScrollArea};
+
+use resym_core::pdb_file::InlinedCall;
+
+/// UI component in charge of rendering the tree of calls inlined in the
+/// currently selected procedure
+pub struct InlinedCallsComponent {
+    inlined_calls: Vec<InlinedCall>,
+}
+
+impl InlinedCallsComponent {
+    pub fn new() -> Self {
+        Self {
+            inlined_calls: vec![],
+        }
+    }
+
+    /// Update the list of inlined calls that the tree contains
+    pub fn set_inlined_calls(&mut self, inlined_calls: Vec<InlinedCall>) {
+        self.inlined_calls = inlined_calls;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inlined_calls.is_empty()
+    }
+
+    /// Update/render the UI component
+    pub fn update(&self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(format!("Inlined calls ({})", self.inlined_calls.len()))
+            .id_salt("inlined_calls")
+            .default_open(false)
+            .show(ui, |ui| {
+                ScrollArea::vertical()
+                    .max_height(ui.available_height() / 3.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        for (index, inlined_call) in self.inlined_calls.iter().enumerate() {
+                            Self::update_inlined_call(ui, ui.id().with(index), inlined_call);
+                        }
+                    });
+            });
+    }
+
+    fn update_inlined_call(ui: &mut egui::Ui, id: egui::Id, inlined_call: &InlinedCall) {
+        let code_ranges = inlined_call
+            .code_ranges
+            .iter()
+            .map(|(start_rva, end_rva)| format!("0x{start_rva:x}-0x{end_rva:x}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let description = if let Some(source_location) = &inlined_call.source_location {
+            format!(
+                "{} at {} (RVA={})",
+                inlined_call.function_name, source_location, code_ranges
+            )
+        } else {
+            format!("{} (RVA={})", inlined_call.function_name, code_ranges)
+        };
+
+        if inlined_call.inlined_calls.is_empty() {
+            ui.label(description);
+        } else {
+            egui::CollapsingHeader::new(description)
+                .id_salt(id)
+                .show(ui, |ui| {
+                    for (index, nested_call) in inlined_call.inlined_calls.iter().enumerate() {
+                        Self::update_inlined_call(ui, id.with(index), nested_call);
+                    }
+                });
+        }
+    }
+}
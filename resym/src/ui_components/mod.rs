@@ -1,19 +1,25 @@
 mod code_view;
 mod console;
 mod index_list;
+mod inlined_calls;
 mod module_tree;
 #[cfg(feature = "http")]
 mod open_url;
+mod pdb_properties;
 mod search_filters;
 mod settings;
 mod text_search;
+mod type_graph;
 
 pub use code_view::*;
 pub use console::*;
 pub use index_list::*;
+pub use inlined_calls::*;
 pub use module_tree::*;
 #[cfg(feature = "http")]
 pub use open_url::*;
+pub use pdb_properties::*;
 pub use search_filters::*;
 pub use settings::*;
 pub use text_search::*;
+pub use type_graph::*;
@@ -1,8 +1,11 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
 
 use eframe::egui::{self, ScrollArea};
 
-use resym_core::pdb_file::ModuleList;
+use resym_core::pdb_file::{ModuleIndex, ModuleList, SectionContribution, SourceFileInfo};
 
 use crate::{
     module_tree::{ModuleInfo, ModulePath, ModuleTreeNode},
@@ -16,6 +19,10 @@ pub struct ModuleTreeComponent {
     module_tree_view: ModuleTreeView,
     /// Index of the currently selected module
     selected_module: RefCell<usize>,
+    /// Source files referenced by each module
+    module_source_files: HashMap<ModuleIndex, Vec<String>>,
+    /// Size of the contributions of each module, per section
+    module_section_sizes: HashMap<ModuleIndex, BTreeMap<String, u32>>,
 }
 
 impl ModuleTreeComponent {
@@ -23,6 +30,8 @@ impl ModuleTreeComponent {
         Self {
             module_tree_view: ModuleTreeView::new(),
             selected_module: usize::MAX.into(),
+            module_source_files: HashMap::new(),
+            module_section_sizes: HashMap::new(),
         }
     }
 
@@ -47,6 +56,32 @@ impl ModuleTreeComponent {
         self.module_tree_view = ModuleTreeView::from_tree_node(root_tree_node);
     }
 
+    /// Update the list of source files shown under each module
+    pub fn set_source_file_list(&mut self, source_file_list: Vec<SourceFileInfo>) {
+        self.module_source_files.clear();
+        for source_file in source_file_list {
+            for (_, module_index) in source_file.modules {
+                self.module_source_files
+                    .entry(module_index)
+                    .or_default()
+                    .push(source_file.file_path.clone());
+            }
+        }
+    }
+
+    /// Update the section contributions summarized for each module
+    pub fn set_section_contributions(&mut self, section_contributions: Vec<SectionContribution>) {
+        self.module_section_sizes.clear();
+        for section_contribution in section_contributions {
+            *self
+                .module_section_sizes
+                .entry(section_contribution.module_index)
+                .or_default()
+                .entry(section_contribution.section_name)
+                .or_default() += section_contribution.size;
+        }
+    }
+
     /// Update/render the UI component
     pub fn update<CB: Fn(&ModulePath, &ModuleInfo)>(
         &self,
@@ -71,7 +106,7 @@ impl ModuleTreeComponent {
         on_module_selected: &CB,
     ) {
         if view_node.is_leaf() {
-            self.update_module_leaf(ui, view_node, on_module_selected);
+            self.update_module_leaf(ctx, ui, view_node, on_module_selected);
         } else {
             egui::collapsing_header::CollapsingState::load_with_default_open(
                 ctx,
@@ -91,22 +126,59 @@ impl ModuleTreeComponent {
 
     fn update_module_leaf<CB: Fn(&ModulePath, &ModuleInfo)>(
         &self,
+        ctx: &egui::Context,
         ui: &mut egui::Ui,
         view_node: &ModuleTreeViewNode,
         on_module_selected: &CB,
     ) {
         if let Some(ref module_info) = view_node.module_info() {
-            if ui
-                .selectable_label(
+            let section_sizes = self.module_section_sizes.get(&module_info.pdb_index);
+            let update_module_label = |ui: &mut egui::Ui| {
+                let label_response = ui.selectable_label(
                     *self.selected_module.borrow() == module_info.pdb_index,
                     &view_node.name,
-                )
-                .clicked()
-            {
-                *self.selected_module.borrow_mut() = module_info.pdb_index;
-                // Invoke event callback
-                on_module_selected(view_node.path(), module_info);
+                );
+                if label_response.clicked() {
+                    *self.selected_module.borrow_mut() = module_info.pdb_index;
+                    // Invoke event callback
+                    on_module_selected(view_node.path(), module_info);
+                }
+
+                // Show the size contributed by the module to each section
+                if let Some(section_sizes) = section_sizes {
+                    let total_size: u32 = section_sizes.values().sum();
+                    ui.weak(format!("0x{total_size:x} bytes"))
+                        .on_hover_text(fmt_section_sizes(section_sizes));
+                }
+            };
+
+            match self.module_source_files.get(&module_info.pdb_index) {
+                None => {
+                    ui.horizontal(update_module_label);
+                }
+                Some(source_files) => {
+                    // Modules can be expanded to show their source files
+                    egui::collapsing_header::CollapsingState::load_with_default_open(
+                        ctx,
+                        ui.id().with(view_node.path().hash()),
+                        false,
+                    )
+                    .show_header(ui, update_module_label)
+                    .body(|ui| {
+                        source_files.iter().for_each(|source_file| {
+                            ui.label(source_file);
+                        });
+                    });
+                }
             }
         }
     }
 }
+
+fn fmt_section_sizes(section_sizes: &BTreeMap<String, u32>) -> String {
+    section_sizes
+        .iter()
+        .map(|(section_name, size)| format!("{section_name}: 0x{size:x} bytes"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
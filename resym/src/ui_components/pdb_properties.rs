@@ -0,0 +1,102 @@
+use eframe::egui;
+use resym_core::pdb_file::PdbMetadata;
+
+/// UI component in charge of rendering a window showing information about the
+/// currently loaded PDB (e.g., GUID and age)
+pub struct PdbPropertiesComponent {
+    window_open: bool,
+    pdb_metadata: Option<PdbMetadata>,
+}
+
+impl PdbPropertiesComponent {
+    pub fn new() -> Self {
+        Self {
+            window_open: false,
+            pdb_metadata: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.window_open = true;
+    }
+
+    pub fn set_pdb_metadata(&mut self, pdb_metadata: Option<PdbMetadata>) {
+        self.pdb_metadata = pdb_metadata;
+    }
+
+    pub fn update(&mut self, ctx: &egui::Context) {
+        egui::Window::new("PDB properties")
+            .anchor(egui::Align2::CENTER_CENTER, [0.0; 2])
+            .open(&mut self.window_open)
+            .auto_sized()
+            .collapsible(false)
+            .show(ctx, |ui| {
+                let Some(pdb_metadata) = &self.pdb_metadata else {
+                    ui.label("No PDB loaded");
+                    return;
+                };
+
+                egui::Grid::new("pdb_properties_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        let properties = [
+                            ("GUID", pdb_metadata.guid.clone()),
+                            ("Age", pdb_metadata.age.to_string()),
+                            (
+                                "DBI age",
+                                pdb_metadata
+                                    .dbi_age
+                                    .map_or("None".to_string(), |dbi_age| dbi_age.to_string()),
+                            ),
+                            ("Signature", format!("0x{:08x}", pdb_metadata.signature)),
+                            ("Machine type", pdb_metadata.machine_type.clone()),
+                            ("PDB version", pdb_metadata.pdb_version.to_string()),
+                            ("DBI version", pdb_metadata.dbi_version.to_string()),
+                            ("Types", pdb_metadata.type_count.to_string()),
+                            ("Ids", pdb_metadata.id_count.to_string()),
+                            ("Global symbols", pdb_metadata.symbol_count.to_string()),
+                            ("Modules", pdb_metadata.module_count.to_string()),
+                            (
+                                "Streams",
+                                match (pdb_metadata.stream_count, pdb_metadata.total_stream_size) {
+                                    (Some(stream_count), Some(total_stream_size)) => {
+                                        format!("{stream_count} ({total_stream_size} bytes)")
+                                    }
+                                    _ => "Unknown".to_string(),
+                                },
+                            ),
+                        ];
+                        for (name, value) in properties {
+                            ui.label(name);
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                egui::Grid::new("pdb_streams_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for stream in &pdb_metadata.streams {
+                            ui.label(&stream.name);
+                            match stream.index {
+                                Some(index) => {
+                                    ui.label(format!("Stream {index}"));
+                                    match stream.size {
+                                        Some(size) => ui.label(format!("{size} bytes")),
+                                        None => ui.weak("Unknown size"),
+                                    };
+                                }
+                                None => {
+                                    ui.weak("Not present");
+                                    ui.label("");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
@@ -208,6 +208,35 @@ impl SettingsComponent {
                             &mut self.app_settings.integers_as_hexadecimal,
                             "Print integer values as hexadecimal",
                         );
+                        ui.checkbox(
+                            &mut self.app_settings.print_vftables,
+                            "Print virtual function tables",
+                        );
+                        ui.checkbox(
+                            &mut self.app_settings.print_padding,
+                            "Print padding members",
+                        );
+                        ui.checkbox(
+                            &mut self.app_settings.print_offset_asserts,
+                            "Print member offset assertions",
+                        );
+                        ui.checkbox(
+                            &mut self.app_settings.print_namespaces,
+                            "Print namespace blocks",
+                        );
+                        ui.checkbox(
+                            &mut self.app_settings.print_source_locations,
+                            "Print source locations",
+                        );
+
+                        ui.add_space(INTER_SECTION_SPACING);
+                        ui.label("Symbol reconstruction");
+                        ui.checkbox(&mut self.app_settings.print_locals, "Print local variables");
+                        ui.checkbox(
+                            &mut self.app_settings.print_locations,
+                            "Print variable live ranges",
+                        );
+                        ui.checkbox(&mut self.app_settings.print_inlinees, "Print inlined calls");
                     }
                 }
             });
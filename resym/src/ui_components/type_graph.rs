@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+
+use eframe::{
+    egui::{self, ScrollArea},
+    epaint::{CubicBezierShape, PathStroke},
+};
+use resym_core::pdb_file::{TypeDependencyKind, TypeGraph, TypeGraphEdge, TypeIndex};
+
+const NODE_WIDTH: f32 = 200.0;
+const NODE_HEIGHT: f32 = 22.0;
+const COLUMN_SPACING: f32 = 80.0;
+const ROW_SPACING: f32 = 10.0;
+const GRAPH_MARGIN: f32 = 8.0;
+const ARROW_HEAD_LENGTH: f32 = 8.0;
+
+/// Node of the graph, laid out on a grid
+struct TypeGraphViewNode {
+    index: TypeIndex,
+    name: String,
+    column: i32,
+    row: usize,
+    /// Whether the neighbours of the node have been requested
+    expanded: bool,
+}
+
+/// UI component in charge of rendering the types referenced by and
+/// referencing the selected type, as a graph which can be expanded
+/// interactively
+pub struct TypeGraphComponent {
+    nodes: Vec<TypeGraphViewNode>,
+    edges: Vec<TypeGraphEdge>,
+    /// Next free row of each column
+    column_heights: HashMap<i32, usize>,
+    selected_node: Option<TypeIndex>,
+}
+
+impl TypeGraphComponent {
+    pub fn new() -> Self {
+        Self {
+            nodes: vec![],
+            edges: vec![],
+            column_heights: HashMap::new(),
+            selected_node: None,
+        }
+    }
+
+    /// Remove all the nodes of the graph
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.column_heights.clear();
+        self.selected_node = None;
+    }
+
+    /// Whether the given type is part of the graph
+    pub fn contains(&self, type_index: TypeIndex) -> bool {
+        self.nodes.iter().any(|node| node.index == type_index)
+    }
+
+    /// Start a new graph from the given type. Its neighbours are expected to
+    /// be added with `add_neighbourhood`.
+    pub fn reset(&mut self, type_index: TypeIndex) {
+        self.clear();
+        self.add_node(type_index, format!("{type_index:#x}"), 0, 0);
+        self.nodes[0].expanded = true;
+        self.selected_node = Some(type_index);
+    }
+
+    /// Add the direct neighbours of a type to the graph. Types referenced by
+    /// the root of `type_graph` are placed on its right, types referencing it
+    /// on its left.
+    pub fn add_neighbourhood(&mut self, type_graph: TypeGraph) {
+        let type_names: HashMap<TypeIndex, String> = type_graph
+            .nodes
+            .into_iter()
+            .map(|node| (node.index, node.name))
+            .collect();
+        let root_name = type_names
+            .get(&type_graph.root)
+            .cloned()
+            .unwrap_or_else(|| format!("{:#x}", type_graph.root));
+        let (root_column, root_row) = match self
+            .nodes
+            .iter_mut()
+            .find(|node| node.index == type_graph.root)
+        {
+            Some(root_node) => {
+                root_node.name = root_name;
+                root_node.expanded = true;
+                (root_node.column, root_node.row)
+            }
+            None => {
+                // The graph was reset in the meantime, start over from this type
+                self.clear();
+                self.add_node(type_graph.root, root_name, 0, 0);
+                self.nodes[0].expanded = true;
+                (0, 0)
+            }
+        };
+
+        for edge in type_graph.edges {
+            let (neighbour_index, column) = if edge.from == type_graph.root {
+                (edge.to, root_column + 1)
+            } else {
+                (edge.from, root_column - 1)
+            };
+            if !self.contains(neighbour_index) {
+                let name = type_names
+                    .get(&neighbour_index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("{neighbour_index:#x}"));
+                self.add_node(neighbour_index, name, column, root_row);
+            }
+            if !self.edges.contains(&edge) {
+                self.edges.push(edge);
+            }
+        }
+    }
+
+    fn add_node(&mut self, type_index: TypeIndex, name: String, column: i32, min_row: usize) {
+        // Place the node as close as possible to the node it's connected to
+        let column_height = self.column_heights.entry(column).or_default();
+        let row = std::cmp::max(*column_height, min_row);
+        *column_height = row + 1;
+
+        self.nodes.push(TypeGraphViewNode {
+            index: type_index,
+            name,
+            column,
+            row,
+            expanded: false,
+        });
+    }
+
+    /// Update/render the UI component.
+    /// `on_node_selected` is called with the name and index of the type
+    /// which has been clicked, and whether its neighbours should be requested.
+    pub fn update<CB: FnMut(&str, TypeIndex, bool)>(
+        &mut self,
+        ui: &mut egui::Ui,
+        on_node_selected: &mut CB,
+    ) {
+        if self.nodes.is_empty() {
+            // Display a default message to make it obvious the graph is empty
+            ui.label("Select a type to display its graph");
+            return;
+        }
+        ui.label("Click on a type to show its neighbours. Solid edges: embedded types, dashed edges: pointers, colored edges: base classes.");
+
+        ScrollArea::both()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                let min_column = self.nodes.iter().map(|node| node.column).min().unwrap_or(0);
+                let max_column = self.nodes.iter().map(|node| node.column).max().unwrap_or(0);
+                let row_count = self
+                    .nodes
+                    .iter()
+                    .map(|node| node.row + 1)
+                    .max()
+                    .unwrap_or(0);
+                let column_count = (max_column - min_column + 1) as f32;
+                let graph_size = egui::vec2(
+                    column_count * (NODE_WIDTH + COLUMN_SPACING) - COLUMN_SPACING
+                        + 2.0 * GRAPH_MARGIN,
+                    row_count as f32 * (NODE_HEIGHT + ROW_SPACING) - ROW_SPACING
+                        + 2.0 * GRAPH_MARGIN,
+                );
+                let (graph_rect, _) = ui.allocate_exact_size(graph_size, egui::Sense::hover());
+                let node_rects: HashMap<TypeIndex, egui::Rect> = self
+                    .nodes
+                    .iter()
+                    .map(|node| {
+                        let node_min = graph_rect.min
+                            + egui::vec2(
+                                GRAPH_MARGIN
+                                    + (node.column - min_column) as f32
+                                        * (NODE_WIDTH + COLUMN_SPACING),
+                                GRAPH_MARGIN + node.row as f32 * (NODE_HEIGHT + ROW_SPACING),
+                            );
+                        (
+                            node.index,
+                            egui::Rect::from_min_size(
+                                node_min,
+                                egui::vec2(NODE_WIDTH, NODE_HEIGHT),
+                            ),
+                        )
+                    })
+                    .collect();
+
+                // Draw edges below nodes
+                let painter = ui.painter_at(graph_rect);
+                for edge in &self.edges {
+                    if let (Some(from_rect), Some(to_rect)) =
+                        (node_rects.get(&edge.from), node_rects.get(&edge.to))
+                    {
+                        paint_edge(ui, &painter, from_rect, to_rect, edge.kind);
+                    }
+                }
+
+                for node in self.nodes.iter_mut() {
+                    let node_rect = node_rects[&node.index];
+                    let button = egui::Button::new(&node.name)
+                        .truncate()
+                        .selected(self.selected_node == Some(node.index));
+                    let response = ui
+                        .put(node_rect, button)
+                        .on_hover_text(format!("{} ({:#x})", node.name, node.index));
+                    if response.clicked() {
+                        self.selected_node = Some(node.index);
+                        on_node_selected(&node.name, node.index, !node.expanded);
+                        node.expanded = true;
+                    }
+                }
+            });
+    }
+}
+
+impl Default for TypeGraphComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Draw an arrow between two nodes, using a style specific to the kind of
+/// dependency it represents
+fn paint_edge(
+    ui: &egui::Ui,
+    painter: &egui::Painter,
+    from_rect: &egui::Rect,
+    to_rect: &egui::Rect,
+    kind: TypeDependencyKind,
+) {
+    let stroke = match kind {
+        TypeDependencyKind::BaseClass => egui::Stroke::new(1.5, ui.visuals().hyperlink_color),
+        TypeDependencyKind::Embedded | TypeDependencyKind::Pointer => {
+            egui::Stroke::new(1.0, ui.visuals().text_color())
+        }
+    };
+
+    let points = if to_rect.center().x > from_rect.center().x {
+        vec![from_rect.right_center(), to_rect.left_center()]
+    } else if to_rect.center().x < from_rect.center().x {
+        vec![from_rect.left_center(), to_rect.right_center()]
+    } else {
+        // Nodes are in the same column (or are the same node), curve the
+        // edge on the right side
+        let start = from_rect.right_center() + egui::vec2(0.0, -NODE_HEIGHT / 4.0);
+        let end = to_rect.right_center() + egui::vec2(0.0, NODE_HEIGHT / 4.0);
+        let offset = egui::vec2(COLUMN_SPACING / 2.0, 0.0);
+        CubicBezierShape::from_points_stroke(
+            [start, start + offset, end + offset, end],
+            false,
+            egui::Color32::TRANSPARENT,
+            PathStroke::NONE,
+        )
+        .flatten(Some(0.5))
+    };
+
+    if let TypeDependencyKind::Pointer = kind {
+        painter.extend(egui::Shape::dashed_line(&points, stroke, 6.0, 4.0));
+    } else {
+        painter.add(egui::Shape::line(points.clone(), stroke));
+    }
+
+    // Arrow head
+    if let [.., before_tip, tip] = points[..] {
+        let direction = (tip - before_tip).normalized() * ARROW_HEAD_LENGTH;
+        let rotation = egui::emath::Rot2::from_angle(std::f32::consts::TAU / 12.0);
+        painter.line_segment([tip, tip - rotation * direction], stroke);
+        painter.line_segment([tip, tip - rotation.inverse() * direction], stroke);
+    }
+}
@@ -23,23 +23,49 @@ use std::{
 use std::{path::PathBuf, time::Instant};
 
 use crate::{
-    diffing::{diff_module_by_path, diff_symbol_by_name, diff_type_by_name},
+    diffing::{diff_module_by_path, diff_symbol_by_name, diff_symbol_sizes, diff_type_by_name},
     error::{Result, ResymCoreError},
-    frontend::{FrontendCommand, FrontendController, ReconstructedType},
-    par_iter_if_available, par_sort_by_if_available,
+    frontend::{FrontendCommand, FrontendController, ReconstructedFiles, ReconstructedType},
+    msf, par_iter_if_available, par_sort_by_if_available,
     pdb_file::{
         self, ModuleInfo, ModuleList, PDBDataSource, PdbFile, SymbolInfoEx, SymbolKind, SymbolList,
-        SymbolListExView, TypeInfoEx, TypeKind, TypeList, TypeListExView,
+        SymbolListExView, SymbolXref, TypeInfoEx, TypeKind, TypeList, TypeListExView,
     },
     pdb_types::{
-        include_headers_for_flavor, AccessSpecifierReconstructionFlavor,
-        PrimitiveReconstructionFlavor, SizePrintFlavor,
+        include_c_headers_for_flavor, include_headers_for_flavor,
+        AccessSpecifierReconstructionFlavor, DataFormatConfiguration, HeaderSplitFlavor,
+        OutputLanguage, PrimitiveReconstructionFlavor, RUST_FILE_ATTRIBUTES,
     },
     PKG_VERSION,
 };
 
 pub type PDBSlot = usize;
 
+/// File whose MSF streams are listed or read. Streams are read directly from
+/// the file, which doesn't have to be loaded as a PDB.
+#[derive(Debug, Clone)]
+pub enum StreamSource {
+    /// File given its path
+    #[cfg(not(target_arch = "wasm32"))]
+    Path(PathBuf),
+    /// File given its content
+    Array(Arc<[u8]>),
+}
+
+impl StreamSource {
+    fn open(&self) -> Result<PDBDataSource> {
+        match self {
+            #[cfg(not(target_arch = "wasm32"))]
+            StreamSource::Path(file_path) => {
+                Ok(PDBDataSource::File(std::fs::File::open(file_path)?))
+            }
+            StreamSource::Array(data) => {
+                Ok(PDBDataSource::SharedArray(io::Cursor::new(data.clone())))
+            }
+        }
+    }
+}
+
 pub enum BackendCommand {
     /// Load a PDB file given its path as a `PathBuf`.
     #[cfg(not(target_arch = "wasm32"))]
@@ -57,47 +83,56 @@ pub enum BackendCommand {
     ReconstructTypeByIndex(
         PDBSlot,
         pdb_file::TypeIndex,
-        PrimitiveReconstructionFlavor,       // primitive_types_flavor
-        AccessSpecifierReconstructionFlavor, // print_access_specifiers,
-        SizePrintFlavor,                     // size_print_flavor
-        bool,                                // print_header
-        bool,                                // reconstruct_dependencies
-        bool,                                // integers_as_hexadecimal
-        bool,                                // print_offset_info
-        bool,                                // print_brackets_new_line
-        bool,                                // ignore_std_types
+        OutputLanguage,          // output_language
+        DataFormatConfiguration, // fmt_configuration
+        bool,                    // print_header
+        bool,                    // reconstruct_dependencies
+        bool,                    // ignore_std_types
     ),
     /// Reconstruct a type given its name for a given PDB.
     ReconstructTypeByName(
         PDBSlot,
         String,
-        PrimitiveReconstructionFlavor,       // primitive_types_flavor
-        AccessSpecifierReconstructionFlavor, // print_access_specifiers,
-        SizePrintFlavor,                     // size_print_flavor
-        bool,                                // print_header
-        bool,                                // reconstruct_dependencies
-        bool,                                // integers_as_hexadecimal
-        bool,                                // print_offset_info
-        bool,                                // print_brackets_new_line
-        bool,                                // ignore_std_types
+        OutputLanguage,          // output_language
+        DataFormatConfiguration, // fmt_configuration
+        bool,                    // print_header
+        bool,                    // reconstruct_dependencies
+        bool,                    // ignore_std_types
     ),
     /// Reconstruct all types found in a given PDB.
     ReconstructAllTypes(
         PDBSlot,
-        PrimitiveReconstructionFlavor,       // primitive_types_flavor
-        AccessSpecifierReconstructionFlavor, // print_access_specifiers,
-        SizePrintFlavor,                     // size_print_flavor
-        bool,                                // print_header
-        bool,                                // integers_as_hexadecimal
-        bool,                                // print_offset_info
-        bool,                                // print_brackets_new_line
-        bool,                                // ignore_std_types
+        OutputLanguage,          // output_language
+        DataFormatConfiguration, // fmt_configuration
+        bool,                    // print_header
+        bool,                    // ignore_std_types
+    ),
+    /// Reconstruct all types found in a given PDB, split into several headers.
+    ReconstructAllTypesIntoHeaders(
+        PDBSlot,
+        HeaderSplitFlavor,       // header_split_flavor
+        OutputLanguage,          // output_language
+        DataFormatConfiguration, // fmt_configuration
+        bool,                    // print_header
+        bool,                    // ignore_std_types
     ),
     /// Retrieve a list of types that match the given filter for a given PDB.
-    ListTypes(PDBSlot, String, bool, bool, bool, TypeFilters),
+    /// Types can optionally be filtered by the path of the file declaring
+    /// them, with a glob pattern.
+    ListTypes(
+        PDBSlot,
+        String,
+        bool,
+        bool,
+        bool,
+        TypeFilters,
+        Option<String>,
+    ),
     /// Retrieve a list of types that match the given filter for multiple PDBs
     /// and merge the result.
     ListTypesMerged(Vec<PDBSlot>, String, bool, bool, bool, TypeFilters),
+    /// Retrieve the location of the declaration of the types of a given PDB.
+    ListTypeSourceLocations(PDBSlot),
     /// Retrieve a list of symbols that match the given filter for multiple PDBs
     /// and merge the result.
     ListSymbols(PDBSlot, String, bool, bool, bool, SymbolFilters),
@@ -111,6 +146,9 @@ pub enum BackendCommand {
         PrimitiveReconstructionFlavor,       // primitive_types_flavor
         AccessSpecifierReconstructionFlavor, // print_access_specifiers,
         bool,                                // print_header
+        bool,                                // print_locals
+        bool,                                // print_locations
+        bool,                                // print_inlinees
     ),
     /// Reconstruct a symbol given its name for a given PDB.
     ReconstructSymbolByName(
@@ -119,6 +157,9 @@ pub enum BackendCommand {
         PrimitiveReconstructionFlavor,       // primitive_types_flavor
         AccessSpecifierReconstructionFlavor, // print_access_specifiers,
         bool,                                // print_header
+        bool,                                // print_locals
+        bool,                                // print_locations
+        bool,                                // print_inlinees
     ),
     /// Reconstruct all symbols found in a given PDB.
     ReconstructAllSymbols(
@@ -126,6 +167,9 @@ pub enum BackendCommand {
         PrimitiveReconstructionFlavor,       // primitive_types_flavor
         AccessSpecifierReconstructionFlavor, // print_access_specifiers,
         bool,                                // print_header
+        bool,                                // print_locals
+        bool,                                // print_locations
+        bool,                                // print_inlinees
     ),
     /// Retrieve a list of modules that match the given filter for multiple PDBs
     /// and merge the result.
@@ -140,18 +184,13 @@ pub enum BackendCommand {
     ),
     /// Reconstruct the diff of a type given its name.
     DiffTypeByName(
-        PDBSlot,                             // pdb_from_slot
-        PDBSlot,                             // pdb_to_slot
-        String,                              // type_name
-        PrimitiveReconstructionFlavor,       // primitives_flavor
-        AccessSpecifierReconstructionFlavor, // print_access_specifiers,
-        SizePrintFlavor,                     // size_print_flavor
-        bool,                                // print_header
-        bool,                                // reconstruct_dependencies
-        bool,                                // integers_as_hexadecimal
-        bool,                                // print_offset_info
-        bool,                                // print_brackets_new_line
-        bool,                                // ignore_std_types
+        PDBSlot,                 // pdb_from_slot
+        PDBSlot,                 // pdb_to_slot
+        String,                  // type_name
+        DataFormatConfiguration, // fmt_configuration
+        bool,                    // print_header
+        bool,                    // reconstruct_dependencies
+        bool,                    // ignore_std_types
     ),
     /// Reconstruct the diff of a symbol given its name.
     DiffSymbolByName(
@@ -173,6 +212,75 @@ pub enum BackendCommand {
     ),
     /// Retrieve a list of all types that reference the given type
     ListTypeCrossReferences(PDBSlot, pdb_file::TypeIndex),
+    /// Retrieve a list of all types that reference the type with the given name
+    ListTypeCrossReferencesByName(PDBSlot, String),
+    /// Retrieve a list of all symbols that use the given type
+    ListSymbolCrossReferences(PDBSlot, pdb_file::TypeIndex),
+    /// Retrieve a list of all symbols that use the type with the given name
+    ListSymbolCrossReferencesByName(PDBSlot, String),
+    /// Map the given RVAs to symbols, source lines and inlined functions
+    SymbolizeAddresses(PDBSlot, Vec<u32>),
+    /// Retrieve the tree of functions inlined in the given procedure
+    ListInlinedCalls(PDBSlot, pdb_file::SymbolIndex),
+    /// Retrieve the procedures in which the given function has been inlined
+    ListInlineCallSites(PDBSlot, String),
+    /// Retrieve the source files referenced by the modules of the PDB
+    ListSourceFiles(PDBSlot),
+    /// Retrieve the procedures whose code comes from the given source file
+    ListSourceFileFunctions(PDBSlot, String),
+    /// Retrieve the section contribution containing the given RVA
+    FindSectionContribution(PDBSlot, u32),
+    /// Retrieve the section contributions of the given module (or of all
+    /// modules if `None`)
+    ListSectionContributions(PDBSlot, Option<String>),
+    /// Attribute the size of the image to modules, object files, namespaces
+    /// and template families
+    GenerateSizeReport(PDBSlot),
+    /// Retrieve information identifying the PDB and a summary of its content
+    GetPdbMetadata(PDBSlot),
+    /// Retrieve the list of MSF streams of a file
+    ListStreams(StreamSource),
+    /// Retrieve the content of an MSF stream of a file
+    ReadStream(
+        StreamSource,
+        u16,           // stream_index
+        Option<usize>, // max_size
+    ),
+    /// Compare the size of procedures and data symbols between two PDBs
+    DiffSymbolSizes(
+        PDBSlot, // pdb_from_slot
+        PDBSlot, // pdb_to_slot
+    ),
+    /// Dump raw CodeView records of the PDB
+    DumpRecords(
+        PDBSlot,
+        bool, // dump_types
+        bool, // dump_ids
+        bool, // dump_symbols
+    ),
+    /// Dump the raw record of a type, along with the records it references
+    DumpTypeRecordsByIndex(PDBSlot, pdb_file::TypeIndex),
+    /// Retrieve the graph of the types a type depends on
+    GetTypeGraphByIndex(
+        PDBSlot,
+        pdb_file::TypeIndex,
+        Option<usize>, // max_depth
+        bool,          // include_base_classes
+    ),
+    /// Retrieve the graph of the types a type depends on
+    GetTypeGraphByName(
+        PDBSlot,
+        String,        // type_name
+        Option<usize>, // max_depth
+        bool,          // include_base_classes
+    ),
+    /// Retrieve the types a type directly depends on, along with the types
+    /// which directly depend on it
+    GetTypeNeighbourhood(
+        PDBSlot,
+        pdb_file::TypeIndex,
+        bool, // include_base_classes
+    ),
 }
 
 /// Search filters for types
@@ -389,28 +497,20 @@ fn worker_thread_routine(
             BackendCommand::ReconstructTypeByIndex(
                 pdb_slot,
                 type_index,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                output_language,
+                fmt_configuration,
                 print_header,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
                     let reconstructed_type_result = reconstruct_type_by_index_command(
                         pdb_file,
                         type_index,
-                        primitives_flavor,
-                        print_access_specifiers,
-                        size_print_flavor,
+                        output_language,
+                        &fmt_configuration,
                         print_header,
                         reconstruct_dependencies,
-                        integers_as_hexadecimal,
-                        print_offset_info,
-                        print_brackets_new_line,
                         ignore_std_types,
                     );
                     frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
@@ -422,28 +522,20 @@ fn worker_thread_routine(
             BackendCommand::ReconstructTypeByName(
                 pdb_slot,
                 type_name,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                output_language,
+                fmt_configuration,
                 print_header,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
                     let reconstructed_type_result = reconstruct_type_by_name_command(
                         pdb_file,
                         &type_name,
-                        primitives_flavor,
-                        print_access_specifiers,
-                        size_print_flavor,
+                        output_language,
+                        &fmt_configuration,
                         print_header,
                         reconstruct_dependencies,
-                        integers_as_hexadecimal,
-                        print_offset_info,
-                        print_brackets_new_line,
                         ignore_std_types,
                     );
                     frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
@@ -454,25 +546,17 @@ fn worker_thread_routine(
 
             BackendCommand::ReconstructAllTypes(
                 pdb_slot,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                output_language,
+                fmt_configuration,
                 print_header,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
                     let reconstructed_type_result = reconstruct_all_types_command(
                         pdb_file,
-                        primitives_flavor,
-                        print_access_specifiers,
-                        size_print_flavor,
+                        output_language,
+                        &fmt_configuration,
                         print_header,
-                        integers_as_hexadecimal,
-                        print_offset_info,
-                        print_brackets_new_line,
                         ignore_std_types,
                     );
                     frontend_controller.send_command(FrontendCommand::ReconstructTypeResult(
@@ -482,6 +566,29 @@ fn worker_thread_routine(
                 }
             }
 
+            BackendCommand::ReconstructAllTypesIntoHeaders(
+                pdb_slot,
+                header_split_flavor,
+                output_language,
+                fmt_configuration,
+                print_header,
+                ignore_std_types,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let reconstructed_files_result = reconstruct_all_types_into_headers_command(
+                        pdb_file,
+                        header_split_flavor,
+                        output_language,
+                        &fmt_configuration,
+                        print_header,
+                        ignore_std_types,
+                    );
+                    frontend_controller.send_command(
+                        FrontendCommand::ReconstructTypeFilesResult(reconstructed_files_result),
+                    )?;
+                }
+            }
+
             BackendCommand::ListTypes(
                 pdb_slot,
                 search_query,
@@ -489,6 +596,7 @@ fn worker_thread_routine(
                 use_regex,
                 ignore_std_types,
                 search_filters,
+                source_path_filter,
             ) => {
                 if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
                     let filtered_type_list = update_type_filter_command(
@@ -499,12 +607,23 @@ fn worker_thread_routine(
                         ignore_std_types,
                         true,
                         search_filters,
+                        source_path_filter.as_deref(),
                     );
                     frontend_controller
                         .send_command(FrontendCommand::ListTypesResult(filtered_type_list))?;
                 }
             }
 
+            BackendCommand::ListTypeSourceLocations(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    frontend_controller.send_command(
+                        FrontendCommand::ListTypeSourceLocationsResult(
+                            pdb_file.type_source_locations().clone(),
+                        ),
+                    )?;
+                }
+            }
+
             BackendCommand::ListTypesMerged(
                 pdb_slots,
                 search_query,
@@ -524,6 +643,7 @@ fn worker_thread_routine(
                             ignore_std_types,
                             false,
                             search_filters.clone(),
+                            None,
                         );
                         filtered_type_set.extend(filtered_type_list.into_iter().map(|(s, _)| {
                             // Collapse all type indices to `default`. When merging
@@ -600,6 +720,9 @@ fn worker_thread_routine(
                 primitives_flavor,
                 print_access_specifiers,
                 print_header,
+                print_locals,
+                print_locations,
+                print_inlinees,
             ) => {
                 if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
                     let result = reconstruct_symbol_by_index_command(
@@ -608,6 +731,9 @@ fn worker_thread_routine(
                         primitives_flavor,
                         print_access_specifiers,
                         print_header,
+                        print_locals,
+                        print_locations,
+                        print_inlinees,
                     );
                     frontend_controller
                         .send_command(FrontendCommand::ReconstructSymbolResult(result))?;
@@ -620,6 +746,9 @@ fn worker_thread_routine(
                 primitives_flavor,
                 print_access_specifiers,
                 print_header,
+                print_locals,
+                print_locations,
+                print_inlinees,
             ) => {
                 if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
                     let result = reconstruct_symbol_by_name_command(
@@ -628,6 +757,9 @@ fn worker_thread_routine(
                         primitives_flavor,
                         print_access_specifiers,
                         print_header,
+                        print_locals,
+                        print_locations,
+                        print_inlinees,
                     );
                     frontend_controller
                         .send_command(FrontendCommand::ReconstructSymbolResult(result))?;
@@ -639,6 +771,9 @@ fn worker_thread_routine(
                 primitives_flavor,
                 print_access_specifiers,
                 print_header,
+                print_locals,
+                print_locations,
+                print_inlinees,
             ) => {
                 if let Some(pdb_file) = pdb_files.get_mut(&pdb_slot) {
                     let result = reconstruct_all_symbols_command(
@@ -646,6 +781,9 @@ fn worker_thread_routine(
                         primitives_flavor,
                         print_access_specifiers,
                         print_header,
+                        print_locals,
+                        print_locations,
+                        print_inlinees,
                     );
                     frontend_controller
                         .send_command(FrontendCommand::ReconstructSymbolResult(result))?;
@@ -720,14 +858,9 @@ fn worker_thread_routine(
                 pdb_from_slot,
                 pdb_to_slot,
                 type_name,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                fmt_configuration,
                 print_header,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             ) => {
                 if let Some(pdb_file_from) = pdb_files.get(&pdb_from_slot) {
@@ -736,14 +869,9 @@ fn worker_thread_routine(
                             pdb_file_from,
                             pdb_file_to,
                             &type_name,
-                            primitives_flavor,
-                            print_access_specifiers,
-                            size_print_flavor,
+                            &fmt_configuration,
                             print_header,
                             reconstruct_dependencies,
-                            integers_as_hexadecimal,
-                            print_offset_info,
-                            print_brackets_new_line,
                             ignore_std_types,
                         );
                         frontend_controller
@@ -783,23 +911,241 @@ fn worker_thread_routine(
                         .send_command(FrontendCommand::ListTypeCrossReferencesResult(xref_list))?;
                 }
             }
+
+            BackendCommand::ListTypeCrossReferencesByName(pdb_slot, type_name) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let xref_list = pdb_file
+                        .type_index_by_name(&type_name)
+                        .and_then(|type_index| list_type_xrefs_command(pdb_file, type_index));
+                    frontend_controller
+                        .send_command(FrontendCommand::ListTypeCrossReferencesResult(xref_list))?;
+                }
+            }
+
+            BackendCommand::ListSymbolCrossReferences(pdb_slot, type_index) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let xref_list = list_symbol_xrefs_command(pdb_file, type_index);
+                    frontend_controller.send_command(
+                        FrontendCommand::ListSymbolCrossReferencesResult(xref_list),
+                    )?;
+                }
+            }
+
+            BackendCommand::ListSymbolCrossReferencesByName(pdb_slot, type_name) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let xref_list = pdb_file
+                        .type_index_by_name(&type_name)
+                        .and_then(|type_index| list_symbol_xrefs_command(pdb_file, type_index));
+                    frontend_controller.send_command(
+                        FrontendCommand::ListSymbolCrossReferencesResult(xref_list),
+                    )?;
+                }
+            }
+
+            BackendCommand::SymbolizeAddresses(pdb_slot, rvas) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let symbolized_addresses = pdb_file.symbolize_addresses(&rvas);
+                    frontend_controller.send_command(FrontendCommand::SymbolizeAddressesResult(
+                        symbolized_addresses,
+                    ))?;
+                }
+            }
+
+            BackendCommand::ListInlinedCalls(pdb_slot, symbol_index) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let inlined_calls = pdb_file.procedure_inlined_calls(symbol_index);
+                    frontend_controller
+                        .send_command(FrontendCommand::ListInlinedCallsResult(inlined_calls))?;
+                }
+            }
+
+            BackendCommand::ListInlineCallSites(pdb_slot, function_name) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let inline_call_sites = pdb_file.find_inline_call_sites(&function_name);
+                    frontend_controller.send_command(
+                        FrontendCommand::ListInlineCallSitesResult(inline_call_sites),
+                    )?;
+                }
+            }
+
+            BackendCommand::ListSourceFiles(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let source_files = pdb_file.source_file_list();
+                    frontend_controller
+                        .send_command(FrontendCommand::ListSourceFilesResult(source_files))?;
+                }
+            }
+
+            BackendCommand::ListSourceFileFunctions(pdb_slot, file_path) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let functions = pdb_file.source_file_functions(&file_path);
+                    frontend_controller
+                        .send_command(FrontendCommand::ListSourceFileFunctionsResult(functions))?;
+                }
+            }
+
+            BackendCommand::FindSectionContribution(pdb_slot, rva) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let section_contribution =
+                        pdb_file.section_contributions.find_by_rva(rva).cloned();
+                    frontend_controller.send_command(
+                        FrontendCommand::FindSectionContributionResult(section_contribution),
+                    )?;
+                }
+            }
+
+            BackendCommand::ListSectionContributions(pdb_slot, module_path) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let section_contributions = if let Some(module_path) = module_path {
+                        pdb_file
+                            .section_contributions
+                            .find_by_module_path(&module_path)
+                            .cloned()
+                            .collect()
+                    } else {
+                        pdb_file.section_contributions.iter().cloned().collect()
+                    };
+                    frontend_controller.send_command(
+                        FrontendCommand::ListSectionContributionsResult(section_contributions),
+                    )?;
+                }
+            }
+
+            BackendCommand::GenerateSizeReport(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let size_report = pdb_file.size_report();
+                    frontend_controller
+                        .send_command(FrontendCommand::GenerateSizeReportResult(size_report))?;
+                }
+            }
+
+            BackendCommand::GetPdbMetadata(pdb_slot) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let pdb_metadata = pdb_file.metadata();
+                    frontend_controller
+                        .send_command(FrontendCommand::GetPdbMetadataResult(pdb_metadata))?;
+                }
+            }
+
+            BackendCommand::ListStreams(stream_source) => {
+                let stream_list = stream_source
+                    .open()
+                    .and_then(|mut reader| msf::stream_list(&mut reader));
+                frontend_controller
+                    .send_command(FrontendCommand::ListStreamsResult(stream_list))?;
+            }
+
+            BackendCommand::ReadStream(stream_source, stream_index, max_size) => {
+                let stream_data = stream_source
+                    .open()
+                    .and_then(|mut reader| msf::read_stream(&mut reader, stream_index, max_size));
+                frontend_controller
+                    .send_command(FrontendCommand::ReadStreamResult(stream_index, stream_data))?;
+            }
+
+            BackendCommand::DiffSymbolSizes(pdb_from_slot, pdb_to_slot) => {
+                if let Some(pdb_file_from) = pdb_files.get(&pdb_from_slot) {
+                    if let Some(pdb_file_to) = pdb_files.get(&pdb_to_slot) {
+                        let size_diff_result = diff_symbol_sizes(pdb_file_from, pdb_file_to);
+                        frontend_controller.send_command(
+                            FrontendCommand::DiffSymbolSizesResult(size_diff_result),
+                        )?;
+                    }
+                }
+            }
+
+            BackendCommand::DumpRecords(pdb_slot, dump_types, dump_ids, dump_symbols) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let records_result =
+                        dump_records_command(pdb_file, dump_types, dump_ids, dump_symbols);
+                    frontend_controller
+                        .send_command(FrontendCommand::DumpRecordsResult(records_result))?;
+                }
+            }
+
+            BackendCommand::DumpTypeRecordsByIndex(pdb_slot, type_index) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let records_result = pdb_file.dump_type_records_by_index(type_index);
+                    frontend_controller
+                        .send_command(FrontendCommand::DumpRecordsResult(records_result))?;
+                }
+            }
+
+            BackendCommand::GetTypeGraphByIndex(
+                pdb_slot,
+                type_index,
+                max_depth,
+                include_base_classes,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let type_graph_result =
+                        pdb_file.type_graph_by_index(type_index, max_depth, include_base_classes);
+                    frontend_controller
+                        .send_command(FrontendCommand::GetTypeGraphResult(type_graph_result))?;
+                }
+            }
+
+            BackendCommand::GetTypeGraphByName(
+                pdb_slot,
+                type_name,
+                max_depth,
+                include_base_classes,
+            ) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let type_graph_result =
+                        pdb_file.type_graph_by_name(&type_name, max_depth, include_base_classes);
+                    frontend_controller
+                        .send_command(FrontendCommand::GetTypeGraphResult(type_graph_result))?;
+                }
+            }
+
+            BackendCommand::GetTypeNeighbourhood(pdb_slot, type_index, include_base_classes) => {
+                if let Some(pdb_file) = pdb_files.get(&pdb_slot) {
+                    let type_graph_result =
+                        pdb_file.type_neighbourhood_by_index(type_index, include_base_classes);
+                    frontend_controller
+                        .send_command(FrontendCommand::GetTypeGraphResult(type_graph_result))?;
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+fn dump_records_command<'p, T>(
+    pdb_file: &PdbFile<'p, T>,
+    dump_types: bool,
+    dump_ids: bool,
+    dump_symbols: bool,
+) -> Result<String>
+where
+    T: io::Seek + io::Read + std::fmt::Debug + 'p,
+{
+    let mut records = String::new();
+    if dump_types {
+        records.push_str("*** TYPES\n\n");
+        records.push_str(&pdb_file.dump_type_records()?);
+    }
+    if dump_ids {
+        records.push_str("*** IDS\n\n");
+        records.push_str(&pdb_file.dump_id_records()?);
+    }
+    if dump_symbols {
+        records.push_str("*** SYMBOLS\n\n");
+        records.push_str(&pdb_file.dump_symbol_records()?);
+    }
+
+    Ok(records)
+}
+
 fn reconstruct_type_by_index_command<'p, T>(
     pdb_file: &PdbFile<'p, T>,
     type_index: pdb_file::TypeIndex,
-    primitives_flavor: PrimitiveReconstructionFlavor,
-    print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    size_print_flavor: SizePrintFlavor,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
     print_header: bool,
     reconstruct_dependencies: bool,
-    integers_as_hexadecimal: bool,
-    print_offset_info: bool,
-    print_brackets_new_line: bool,
     ignore_std_types: bool,
 ) -> Result<ReconstructedType>
 where
@@ -807,17 +1153,18 @@ where
 {
     let (data, xrefs_from) = pdb_file.reconstruct_type_by_index(
         type_index,
-        primitives_flavor,
-        print_access_specifiers,
-        size_print_flavor,
+        output_language,
+        fmt_configuration,
         reconstruct_dependencies,
-        integers_as_hexadecimal,
-        print_offset_info,
-        print_brackets_new_line,
         ignore_std_types,
     )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, ignore_std_types);
+        let file_header = generate_type_file_header(
+            pdb_file,
+            output_language,
+            fmt_configuration,
+            ignore_std_types,
+        );
         Ok((format!("{file_header}{data}"), xrefs_from))
     } else {
         Ok((data, xrefs_from))
@@ -827,14 +1174,10 @@ where
 fn reconstruct_type_by_name_command<'p, T>(
     pdb_file: &PdbFile<'p, T>,
     type_name: &str,
-    primitives_flavor: PrimitiveReconstructionFlavor,
-    print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    size_print_flavor: SizePrintFlavor,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
     print_header: bool,
     reconstruct_dependencies: bool,
-    integers_as_hexadecimal: bool,
-    print_offset_info: bool,
-    print_brackets_new_line: bool,
     ignore_std_types: bool,
 ) -> Result<ReconstructedType>
 where
@@ -842,17 +1185,18 @@ where
 {
     let (data, xrefs_from) = pdb_file.reconstruct_type_by_name(
         type_name,
-        primitives_flavor,
-        print_access_specifiers,
-        size_print_flavor,
+        output_language,
+        fmt_configuration,
         reconstruct_dependencies,
-        integers_as_hexadecimal,
-        print_offset_info,
-        print_brackets_new_line,
         ignore_std_types,
     )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, ignore_std_types);
+        let file_header = generate_type_file_header(
+            pdb_file,
+            output_language,
+            fmt_configuration,
+            ignore_std_types,
+        );
         Ok((format!("{file_header}{data}"), xrefs_from))
     } else {
         Ok((data, xrefs_from))
@@ -861,41 +1205,72 @@ where
 
 fn reconstruct_all_types_command<'p, T>(
     pdb_file: &PdbFile<'p, T>,
-    primitives_flavor: PrimitiveReconstructionFlavor,
-    print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    size_print_flavor: SizePrintFlavor,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
     print_header: bool,
-    integers_as_hexadecimal: bool,
-    print_offset_info: bool,
-    print_brackets_new_line: bool,
     ignore_std_types: bool,
 ) -> Result<String>
 where
     T: io::Seek + io::Read + std::fmt::Debug + 'p,
 {
-    let data = pdb_file.reconstruct_all_types(
-        primitives_flavor,
-        print_access_specifiers,
-        size_print_flavor,
-        integers_as_hexadecimal,
-        print_offset_info,
-        print_brackets_new_line,
-        ignore_std_types,
-    )?;
+    let data =
+        pdb_file.reconstruct_all_types(output_language, fmt_configuration, ignore_std_types)?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, ignore_std_types);
+        let file_header = generate_type_file_header(
+            pdb_file,
+            output_language,
+            fmt_configuration,
+            ignore_std_types,
+        );
         Ok(format!("{file_header}{data}"))
     } else {
         Ok(data)
     }
 }
 
+fn reconstruct_all_types_into_headers_command<'p, T>(
+    pdb_file: &PdbFile<'p, T>,
+    header_split_flavor: HeaderSplitFlavor,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
+    print_header: bool,
+    ignore_std_types: bool,
+) -> Result<ReconstructedFiles>
+where
+    T: io::Seek + io::Read + std::fmt::Debug + 'p,
+{
+    let mut reconstructed_files = pdb_file.reconstruct_all_types_into_headers(
+        header_split_flavor,
+        output_language,
+        fmt_configuration,
+        ignore_std_types,
+    )?;
+    if print_header {
+        let file_header = generate_type_file_header(
+            pdb_file,
+            output_language,
+            fmt_configuration,
+            ignore_std_types,
+        );
+        for data in reconstructed_files.values_mut() {
+            // Note: keep `#pragma once` at the top of headers
+            let header_offset = data.find('\n').map_or(0, |offset| offset + 1);
+            data.insert_str(header_offset, &file_header);
+        }
+    }
+
+    Ok(reconstructed_files)
+}
+
 fn reconstruct_symbol_by_index_command<'p, T>(
     pdb_file: &mut PdbFile<'p, T>,
     symbol_index: pdb_file::SymbolIndex,
     primitives_flavor: PrimitiveReconstructionFlavor,
     print_access_specifiers: AccessSpecifierReconstructionFlavor,
     print_header: bool,
+    print_locals: bool,
+    print_locations: bool,
+    print_inlinees: bool,
 ) -> Result<String>
 where
     T: io::Seek + io::Read + std::fmt::Debug + 'p,
@@ -904,9 +1279,12 @@ where
         symbol_index,
         primitives_flavor,
         print_access_specifiers,
+        print_locals,
+        print_locations,
+        print_inlinees,
     )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false);
+        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false, false);
         Ok(format!("{file_header}\n{data}"))
     } else {
         Ok(data)
@@ -919,6 +1297,9 @@ fn reconstruct_symbol_by_name_command<'p, T>(
     primitives_flavor: PrimitiveReconstructionFlavor,
     print_access_specifiers: AccessSpecifierReconstructionFlavor,
     print_header: bool,
+    print_locals: bool,
+    print_locations: bool,
+    print_inlinees: bool,
 ) -> Result<String>
 where
     T: io::Seek + io::Read + std::fmt::Debug + 'p,
@@ -927,9 +1308,12 @@ where
         &symbol_name,
         primitives_flavor,
         print_access_specifiers,
+        print_locals,
+        print_locations,
+        print_inlinees,
     )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false);
+        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false, false);
         Ok(format!("{file_header}\n{data}"))
     } else {
         Ok(data)
@@ -941,13 +1325,22 @@ fn reconstruct_all_symbols_command<'p, T>(
     primitives_flavor: PrimitiveReconstructionFlavor,
     print_access_specifiers: AccessSpecifierReconstructionFlavor,
     print_header: bool,
+    print_locals: bool,
+    print_locations: bool,
+    print_inlinees: bool,
 ) -> Result<String>
 where
     T: io::Seek + io::Read + std::fmt::Debug + 'p,
 {
-    let data = pdb_file.reconstruct_all_symbols(primitives_flavor, print_access_specifiers)?;
+    let data = pdb_file.reconstruct_all_symbols(
+        primitives_flavor,
+        print_access_specifiers,
+        print_locals,
+        print_locations,
+        print_inlinees,
+    )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false);
+        let file_header = generate_file_header(pdb_file, primitives_flavor, true, false, false);
         Ok(format!("{file_header}{data}"))
     } else {
         Ok(data)
@@ -971,7 +1364,8 @@ where
         print_access_specifiers,
     )?;
     if print_header {
-        let file_header = generate_file_header(pdb_file, primitives_flavor, true, ignore_std_types);
+        let file_header =
+            generate_file_header(pdb_file, primitives_flavor, true, ignore_std_types, false);
         Ok(format!("{file_header}\n{data}"))
     } else {
         Ok(data)
@@ -983,6 +1377,7 @@ fn generate_file_header<T>(
     primitives_flavor: PrimitiveReconstructionFlavor,
     include_header_files: bool,
     ignore_std_types: bool,
+    print_offset_asserts: bool,
 ) -> String
 where
     T: io::Seek + io::Read,
@@ -1003,7 +1398,11 @@ where
         if include_header_files {
             format!(
                 "\n{}",
-                include_headers_for_flavor(primitives_flavor, ignore_std_types)
+                include_headers_for_flavor(
+                    primitives_flavor,
+                    ignore_std_types,
+                    print_offset_asserts
+                )
             )
         } else {
             "".to_string()
@@ -1011,6 +1410,39 @@ where
     )
 }
 
+fn generate_type_file_header<T>(
+    pdb_file: &PdbFile<T>,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
+    ignore_std_types: bool,
+) -> String
+where
+    T: io::Seek + io::Read,
+{
+    let primitives_flavor = fmt_configuration.primitive_flavor;
+    match output_language {
+        OutputLanguage::Cpp => generate_file_header(
+            pdb_file,
+            primitives_flavor,
+            true,
+            ignore_std_types,
+            fmt_configuration.print_offset_asserts,
+        ),
+        OutputLanguage::C => format!(
+            "{}\n{}",
+            generate_file_header(pdb_file, primitives_flavor, false, ignore_std_types, false),
+            include_c_headers_for_flavor(primitives_flavor)
+        ),
+        OutputLanguage::Rust => format!(
+            "{}\n{}",
+            generate_file_header(pdb_file, primitives_flavor, false, ignore_std_types, false),
+            RUST_FILE_ATTRIBUTES
+        ),
+        // Comments aren't allowed in JSON documents
+        OutputLanguage::Json => String::new(),
+    }
+}
+
 fn update_type_filter_command<T>(
     pdb_file: &PdbFile<T>,
     search_query: &str,
@@ -1019,6 +1451,7 @@ fn update_type_filter_command<T>(
     ignore_std_types: bool,
     sort_by_index: bool,
     search_filters: TypeFilters,
+    source_path_filter: Option<&str>,
 ) -> TypeList
 where
     T: io::Seek + io::Read + std::fmt::Debug,
@@ -1041,6 +1474,13 @@ where
     // Apply filters by type kind
     let filtered_type_list = filter_types_kind(&filtered_type_list, &search_filters);
 
+    // Apply filter by source file path
+    let filtered_type_list = if let Some(source_path_filter) = source_path_filter {
+        filter_types_source_path(pdb_file, &filtered_type_list, source_path_filter)
+    } else {
+        filtered_type_list
+    };
+
     // Filter out std types if needed
     let mut filtered_type_list = if ignore_std_types {
         filter_std_types(&filtered_type_list)
@@ -1104,6 +1544,52 @@ fn filter_types_regular<'s>(
     }
 }
 
+/// Filter type list with a glob pattern matched against the path of the file
+/// declaring each type. Types without a known declaration are filtered out.
+fn filter_types_source_path<'s, T>(
+    pdb_file: &PdbFile<T>,
+    type_list: &'s [&TypeInfoEx],
+    source_path_filter: &str,
+) -> TypeListExView<'s>
+where
+    T: io::Seek + io::Read + std::fmt::Debug,
+{
+    match glob_to_regex(source_path_filter) {
+        // In case of error, return an empty result
+        Err(_) => vec![],
+        Ok(regex) => type_list
+            .iter()
+            .filter(|r| {
+                pdb_file
+                    .type_source_location(r.1)
+                    .is_some_and(|source_location| regex.is_match(&source_location.file_path))
+            })
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Convert a glob pattern into a regular expression matching file paths.
+/// `*` matches any sequence of characters (including path separators) and `?`
+/// matches any character. Separators are interchangeable and the matching is
+/// case-insensitive, as paths found in PDBs are usually Windows paths.
+fn glob_to_regex(glob: &str) -> std::result::Result<regex::Regex, regex::Error> {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            '/' | '\\' => pattern.push_str(r"[/\\]"),
+            _ => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+
+    regex::RegexBuilder::new(&pattern)
+        .case_insensitive(true)
+        .build()
+}
+
 /// Filter type list to remove types in the `std` namespace
 fn filter_std_types<'s>(type_list: &'s [&TypeInfoEx]) -> TypeListExView<'s> {
     par_iter_if_available!(type_list)
@@ -1342,3 +1828,20 @@ where
 
     Ok(xref_list)
 }
+
+fn list_symbol_xrefs_command<'p, T>(
+    pdb_file: &PdbFile<'p, T>,
+    type_index: pdb_file::TypeIndex,
+) -> Result<Vec<SymbolXref>>
+where
+    T: io::Seek + io::Read + std::fmt::Debug + 'p,
+{
+    let xref_start = Instant::now();
+    let xref_list = pdb_file.get_symbol_xrefs_for_type(type_index)?;
+    log::debug!(
+        "Symbol xref resolution took {} ms",
+        xref_start.elapsed().as_millis()
+    );
+
+    Ok(xref_list)
+}
@@ -1,16 +1,22 @@
 #[cfg(target_arch = "wasm32")]
 use instant::Instant;
+use serde::Serialize;
 use similar::{ChangeTag, TextDiff};
 
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
-use std::{fmt::Write, io};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    io,
+};
 
 use crate::{
     error::{Result, ResymCoreError},
-    pdb_file::PdbFile,
+    pdb_file::{symbol_size_namespace, PdbFile, SymbolKind, SymbolSize},
     pdb_types::{
-        AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor, SizePrintFlavor,
+        AccessSpecifierReconstructionFlavor, DataFormatConfiguration, OutputLanguage,
+        PrimitiveReconstructionFlavor,
     },
     PKG_VERSION,
 };
@@ -29,19 +35,73 @@ pub struct DiffLine {
     pub line: String,
 }
 
+/// How the size of a symbol changed between two PDBs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum SizeChange {
+    Added,
+    Removed,
+    Grown,
+    Shrunk,
+}
+
+/// Size change of a procedure or data symbol
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolSizeDiff {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Module containing the symbol, if known
+    pub module_name: Option<String>,
+    pub size_from: u64,
+    pub size_to: u64,
+    pub change: SizeChange,
+}
+
+impl SymbolSizeDiff {
+    pub fn delta(&self) -> i64 {
+        self.size_to as i64 - self.size_from as i64
+    }
+}
+
+/// Size change of a module or namespace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeDiffEntry {
+    pub name: String,
+    pub size_from: u64,
+    pub size_to: u64,
+}
+
+impl SizeDiffEntry {
+    pub fn delta(&self) -> i64 {
+        self.size_to as i64 - self.size_from as i64
+    }
+}
+
+/// Size changes between two PDBs. Unchanged symbols, modules and namespaces
+/// are omitted, the largest changes come first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SizeDiff {
+    pub symbols: Vec<SymbolSizeDiff>,
+    pub modules: Vec<SizeDiffEntry>,
+    pub namespaces: Vec<SizeDiffEntry>,
+    /// Total size of the section contributions of each PDB
+    pub total_from: u64,
+    pub total_to: u64,
+}
+
+impl SizeDiff {
+    pub fn total_delta(&self) -> i64 {
+        self.total_to as i64 - self.total_from as i64
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn diff_type_by_name<'p, T>(
     pdb_file_from: &PdbFile<'p, T>,
     pdb_file_to: &PdbFile<'p, T>,
     type_name: &str,
-    primitives_flavor: PrimitiveReconstructionFlavor,
-    print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    size_print_flavor: SizePrintFlavor,
+    fmt_configuration: &DataFormatConfiguration,
     print_header: bool,
     reconstruct_dependencies: bool,
-    integers_as_hexadecimal: bool,
-    print_offset_info: bool,
-    print_brackets_new_line: bool,
     ignore_std_types: bool,
 ) -> Result<Diff>
 where
@@ -62,26 +122,18 @@ where
         let (reconstructed_type_from_tmp, _) = pdb_file_from
             .reconstruct_type_by_name(
                 type_name,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                OutputLanguage::Cpp,
+                fmt_configuration,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             )
             .unwrap_or_default();
         let (reconstructed_type_to_tmp, _) = pdb_file_to
             .reconstruct_type_by_name(
                 type_name,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                OutputLanguage::Cpp,
+                fmt_configuration,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             )
             .unwrap_or_default();
@@ -173,10 +225,24 @@ where
     // Reconstruct modules from both PDBs
     {
         let reconstructed_symbol_from_tmp = pdb_file_from
-            .reconstruct_symbol_by_name(symbol_name, primitives_flavor, print_access_specifiers)
+            .reconstruct_symbol_by_name(
+                symbol_name,
+                primitives_flavor,
+                print_access_specifiers,
+                false,
+                false,
+                false,
+            )
             .unwrap_or_default();
         let reconstructed_symbol_to_tmp = pdb_file_to
-            .reconstruct_symbol_by_name(symbol_name, primitives_flavor, print_access_specifiers)
+            .reconstruct_symbol_by_name(
+                symbol_name,
+                primitives_flavor,
+                print_access_specifiers,
+                false,
+                false,
+                false,
+            )
             .unwrap_or_default();
         if reconstructed_symbol_from_tmp.is_empty() && reconstructed_symbol_to_tmp.is_empty() {
             // Make it obvious an error occured
@@ -196,6 +262,202 @@ where
     Ok(diff)
 }
 
+/// Compare the size of procedures and data symbols between two PDBs.
+/// Symbols are matched by name first, then by demangled name to follow
+/// symbols whose decorated name changed (e.g., a different calling convention
+/// or return type).
+pub fn diff_symbol_sizes<'p, T>(
+    pdb_file_from: &PdbFile<'p, T>,
+    pdb_file_to: &PdbFile<'p, T>,
+) -> Result<SizeDiff>
+where
+    T: io::Seek + io::Read + std::fmt::Debug + 'p,
+{
+    let diff_start = Instant::now();
+
+    let mut sizes_from = aggregate_symbol_sizes(pdb_file_from, pdb_file_from.symbol_sizes()?);
+    let mut sizes_to = aggregate_symbol_sizes(pdb_file_to, pdb_file_to.symbol_sizes()?);
+
+    // Symbols matched by name
+    let mut symbol_pairs = vec![];
+    sizes_from.retain(|name, size_from| match sizes_to.remove(name) {
+        Some(size_to) => {
+            symbol_pairs.push((Some(size_from.clone()), Some(size_to)));
+            false
+        }
+        None => true,
+    });
+
+    // Symbols matched by demangled name, when it's unambiguous
+    let demangled_names_from = unique_demangled_names(sizes_from.keys());
+    let demangled_names_to = unique_demangled_names(sizes_to.keys());
+    for (demangled_name, name_from) in demangled_names_from {
+        if let Some(name_to) = demangled_names_to.get(&demangled_name) {
+            symbol_pairs.push((sizes_from.remove(&name_from), sizes_to.remove(name_to)));
+        }
+    }
+
+    // Remaining symbols have been added or removed
+    symbol_pairs.extend(sizes_from.into_values().map(|size| (Some(size), None)));
+    symbol_pairs.extend(sizes_to.into_values().map(|size| (None, Some(size))));
+
+    // Classes declared in either PDB, to attribute methods to their namespace
+    let mut class_names = pdb_file_from.class_names();
+    class_names.extend(pdb_file_to.class_names());
+
+    let mut namespace_sizes: HashMap<String, (u64, u64)> = HashMap::new();
+    let mut symbols = vec![];
+    for (symbol_from, symbol_to) in symbol_pairs {
+        let size_from = symbol_from.as_ref().map_or(0, |symbol| symbol.size);
+        let size_to = symbol_to.as_ref().map_or(0, |symbol| symbol.size);
+        let change = match (&symbol_from, &symbol_to) {
+            (None, _) => Some(SizeChange::Added),
+            (_, None) => Some(SizeChange::Removed),
+            _ if size_to > size_from => Some(SizeChange::Grown),
+            _ if size_to < size_from => Some(SizeChange::Shrunk),
+            _ => None,
+        };
+        // Report symbols under their most recent name
+        let Some(symbol) = symbol_to.or(symbol_from) else {
+            continue;
+        };
+        // Note: unchanged symbols are accounted for in the size of their
+        // namespace
+        let namespace_size = namespace_sizes
+            .entry(symbol_size_namespace(&symbol.name, &class_names).to_string())
+            .or_default();
+        namespace_size.0 += size_from;
+        namespace_size.1 += size_to;
+        let Some(change) = change else {
+            continue;
+        };
+
+        symbols.push(SymbolSizeDiff {
+            name: symbol.name,
+            kind: symbol.kind,
+            module_name: symbol.module_name,
+            size_from,
+            size_to,
+            change,
+        });
+    }
+    symbols.sort_by(|lhs, rhs| {
+        rhs.delta()
+            .abs()
+            .cmp(&lhs.delta().abs())
+            .then_with(|| lhs.name.cmp(&rhs.name))
+    });
+
+    let mut module_sizes: HashMap<String, (u64, u64)> = HashMap::new();
+    for section_contribution in pdb_file_from.section_contributions.iter() {
+        module_sizes
+            .entry(section_contribution.module_name.clone())
+            .or_default()
+            .0 += section_contribution.size as u64;
+    }
+    for section_contribution in pdb_file_to.section_contributions.iter() {
+        module_sizes
+            .entry(section_contribution.module_name.clone())
+            .or_default()
+            .1 += section_contribution.size as u64;
+    }
+    let total_from = module_sizes.values().map(|(size_from, _)| size_from).sum();
+    let total_to = module_sizes.values().map(|(_, size_to)| size_to).sum();
+
+    let size_diff = SizeDiff {
+        symbols,
+        modules: sorted_size_diff_entries(module_sizes),
+        namespaces: sorted_size_diff_entries(namespace_sizes),
+        total_from,
+        total_to,
+    };
+    log::debug!(
+        "Symbol size diffing took {} ms",
+        diff_start.elapsed().as_millis()
+    );
+
+    Ok(size_diff)
+}
+
+/// Symbol sizes summed by name, as symbols local to different modules may
+/// share the same name
+#[derive(Clone)]
+struct NamedSymbolSize {
+    name: String,
+    kind: SymbolKind,
+    module_name: Option<String>,
+    size: u64,
+}
+
+fn aggregate_symbol_sizes<'p, T>(
+    pdb_file: &PdbFile<'p, T>,
+    symbol_sizes: Vec<SymbolSize>,
+) -> BTreeMap<String, NamedSymbolSize>
+where
+    T: io::Seek + io::Read + 'p,
+{
+    let mut named_symbol_sizes: BTreeMap<String, NamedSymbolSize> = BTreeMap::new();
+    for symbol_size in symbol_sizes {
+        named_symbol_sizes
+            .entry(symbol_size.name.clone())
+            .or_insert_with(|| NamedSymbolSize {
+                module_name: symbol_size
+                    .rva
+                    .and_then(|rva| pdb_file.section_contributions.find_by_rva(rva))
+                    .map(|section_contribution| section_contribution.module_name.clone()),
+                name: symbol_size.name,
+                kind: symbol_size.kind,
+                size: 0,
+            })
+            .size += symbol_size.size;
+    }
+
+    named_symbol_sizes
+}
+
+/// Map demangled names to the symbol names they were demangled from. Names
+/// shared by several symbols (e.g., overloads) are ignored.
+fn unique_demangled_names<'a>(
+    symbol_names: impl Iterator<Item = &'a String>,
+) -> HashMap<String, String> {
+    let mut demangled_names: HashMap<String, Option<String>> = HashMap::new();
+    for symbol_name in symbol_names {
+        // Undecorated names are kept as is (e.g., `extern "C"` functions)
+        let demangled_name =
+            msvc_demangler::demangle(symbol_name, msvc_demangler::DemangleFlags::NAME_ONLY)
+                .unwrap_or_else(|_| symbol_name.clone());
+        demangled_names
+            .entry(demangled_name)
+            .and_modify(|name| *name = None)
+            .or_insert_with(|| Some(symbol_name.clone()));
+    }
+
+    demangled_names
+        .into_iter()
+        .filter_map(|(demangled_name, symbol_name)| Some((demangled_name, symbol_name?)))
+        .collect()
+}
+
+fn sorted_size_diff_entries(sizes: HashMap<String, (u64, u64)>) -> Vec<SizeDiffEntry> {
+    let mut entries: Vec<SizeDiffEntry> = sizes
+        .into_iter()
+        .filter(|(_, (size_from, size_to))| size_from != size_to)
+        .map(|(name, (size_from, size_to))| SizeDiffEntry {
+            name,
+            size_from,
+            size_to,
+        })
+        .collect();
+    entries.sort_by(|lhs, rhs| {
+        rhs.delta()
+            .abs()
+            .cmp(&lhs.delta().abs())
+            .then_with(|| lhs.name.cmp(&rhs.name))
+    });
+
+    entries
+}
+
 fn generate_diff_header<'p, T>(
     pdb_file_from: &PdbFile<'p, T>,
     pdb_file_to: &PdbFile<'p, T>,
@@ -13,6 +13,10 @@ pub enum ResymCoreError {
     #[error(transparent)]
     FmtError(#[from] std::fmt::Error),
 
+    /// Error reported from `serde_json`.
+    #[error("json error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
     /// Error reported from `pdb`.
     #[error("pdb error: {0}")]
     PdbError(#[from] pdb::Error),
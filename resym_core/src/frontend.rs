@@ -1,13 +1,21 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
 use crate::{
     backend::PDBSlot,
-    diffing::Diff,
+    diffing::{Diff, SizeDiff},
     error::Result,
-    pdb_file::{ModuleList, SymbolList, TypeList},
+    pdb_file::{
+        InlineCallSite, InlinedCall, ModuleList, PdbMetadata, SectionContribution, SizeReport,
+        SourceFileInfo, StreamInfo, SymbolList, SymbolXref, SymbolizedAddress, TypeGraph, TypeList,
+        TypeSourceLocationMap,
+    },
 };
 
 /// Tuple containing the reconstructed type as a `String`
 /// and the list of directly referenced types as a `TypeList`
 pub type ReconstructedType = (String, TypeList);
+/// Reconstructed files, mapped to their path relative to the output directory
+pub type ReconstructedFiles = BTreeMap<PathBuf, String>;
 
 pub enum FrontendCommand {
     LoadPDBResult(Result<PDBSlot>),
@@ -17,20 +25,43 @@ pub enum FrontendCommand {
 
     // Types
     ListTypesResult(TypeList),
+    ListTypeSourceLocationsResult(TypeSourceLocationMap),
     ReconstructTypeResult(Result<ReconstructedType>),
+    ReconstructTypeFilesResult(Result<ReconstructedFiles>),
+    GetTypeGraphResult(Result<TypeGraph>),
 
     // Symbols
     ListSymbolsResult(SymbolList),
     ReconstructSymbolResult(Result<String>),
+    ListInlinedCallsResult(Result<Vec<InlinedCall>>),
+    ListInlineCallSitesResult(Result<Vec<InlineCallSite>>),
 
     // Modules
     ListModulesResult(Result<ModuleList>),
     ReconstructModuleResult(Result<String>),
 
+    // Source files
+    ListSourceFilesResult(Result<Vec<SourceFileInfo>>),
+    ListSourceFileFunctionsResult(Result<SymbolList>),
+
+    // Section contributions
+    FindSectionContributionResult(Option<SectionContribution>),
+    ListSectionContributionsResult(Vec<SectionContribution>),
+    GenerateSizeReportResult(Result<SizeReport>),
+    GetPdbMetadataResult(Result<PdbMetadata>),
+    ListStreamsResult(Result<Vec<StreamInfo>>),
+    /// Index of the stream and its content
+    ReadStreamResult(u16, Result<Vec<u8>>),
+    DumpRecordsResult(Result<String>),
+
     // Diff
     DiffResult(Result<Diff>),
+    DiffSymbolSizesResult(Result<SizeDiff>),
     // Xrefs
     ListTypeCrossReferencesResult(Result<TypeList>),
+    ListSymbolCrossReferencesResult(Result<Vec<SymbolXref>>),
+    // Addresses
+    SymbolizeAddressesResult(Result<Vec<SymbolizedAddress>>),
 }
 
 pub trait FrontendController {
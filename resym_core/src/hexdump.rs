@@ -0,0 +1,36 @@
+//! Hexadecimal representation of binary data, as used to inspect raw streams.
+use std::fmt::Write;
+
+const BYTES_PER_LINE: usize = 16;
+
+/// Format `data` as lines of 16 bytes, prefixed with their offset and
+/// followed by their ASCII representation.
+pub fn hexdump(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(BYTES_PER_LINE) * 78);
+    for (line_index, line) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let _ = write!(&mut output, "{:08x}: ", line_index * BYTES_PER_LINE);
+        for column in 0..BYTES_PER_LINE {
+            match line.get(column) {
+                Some(byte) => {
+                    let _ = write!(&mut output, "{byte:02x} ");
+                }
+                None => output.push_str("   "),
+            }
+            // Separate the two halves of the line
+            if column == BYTES_PER_LINE / 2 - 1 {
+                output.push(' ');
+            }
+        }
+        output.push(' ');
+        output.extend(line.iter().map(|byte| {
+            if byte.is_ascii_graphic() || *byte == b' ' {
+                *byte as char
+            } else {
+                '.'
+            }
+        }));
+        output.push('\n');
+    }
+
+    output
+}
@@ -2,9 +2,13 @@ pub mod backend;
 pub mod diffing;
 mod error;
 pub mod frontend;
+pub mod hexdump;
+pub mod msf;
 pub mod pdb_file;
 pub mod pdb_types;
+mod procedure;
 pub mod rayon_utils;
+mod records;
 pub mod syntax_highlighting;
 
 pub use error::*;
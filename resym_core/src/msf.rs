@@ -0,0 +1,506 @@
+//! Minimal parsing of the MSF container and of the headers of the PDB's
+//! streams, for the information the `pdb` crate doesn't expose (e.g., stream
+//! sizes). Streams can be listed and read without loading the PDB, which lets
+//! users inspect PDBs the `pdb` crate cannot parse.
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, SeekFrom};
+
+use crate::error::{Result, ResymCoreError};
+use crate::pdb_file::StreamInfo;
+
+const MSF_MAGIC: &[u8] = b"Microsoft C/C++ MSF 7.00\r\n\x1a\x44\x53\x00\x00\x00";
+const NIL_STREAM_SIZE: u32 = u32::MAX;
+const NIL_STREAM_INDEX: u16 = u16::MAX;
+const TYPE_STREAM_HEADER_SIZE: usize = 56;
+
+/// Indices of the MSF streams with a fixed index
+pub(crate) const OLD_DIRECTORY_STREAM_INDEX: u16 = 0;
+pub(crate) const PDB_STREAM_INDEX: u16 = 1;
+pub(crate) const TPI_STREAM_INDEX: u16 = 2;
+pub(crate) const DBI_STREAM_INDEX: u16 = 3;
+pub(crate) const IPI_STREAM_INDEX: u16 = 4;
+
+/// Indices of the streams referenced by the DBI stream's optional debug header
+pub(crate) const DBI_FPO_STREAM: usize = 0;
+pub(crate) const DBI_OMAP_TO_SOURCE_STREAM: usize = 3;
+pub(crate) const DBI_OMAP_FROM_SOURCE_STREAM: usize = 4;
+pub(crate) const DBI_SECTION_HEADERS_STREAM: usize = 5;
+pub(crate) const DBI_NEW_FPO_STREAM: usize = 9;
+
+/// Stream directory of an MSF file
+pub(crate) struct StreamDirectory {
+    block_size: u64,
+    file_size: u64,
+    /// Size and blocks of each stream, `None` for streams which aren't present
+    streams: Vec<Option<(u32, Vec<u32>)>>,
+}
+
+impl StreamDirectory {
+    /// Read the stream directory of the MSF file read by `reader`
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        // Parse the super block
+        let mut super_block = [0u8; 56];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut super_block)?;
+        if !super_block.starts_with(MSF_MAGIC) {
+            return Err(ResymCoreError::InvalidParameterError(
+                "unsupported MSF format".to_string(),
+            ));
+        }
+        let block_size = read_u32(&super_block, 32) as u64;
+        let directory_size = read_u32(&super_block, 44) as usize;
+        let block_map_address = read_u32(&super_block, 52) as u64;
+        if block_size == 0 {
+            return Err(ResymCoreError::InvalidParameterError(
+                "invalid MSF block size".to_string(),
+            ));
+        }
+
+        // Note: sizes are checked against the file's before allocating anything
+        let file_size = reader.seek(SeekFrom::End(0))?;
+        if directory_size as u64 > file_size {
+            return Err(unexpected_eof());
+        }
+
+        // Read the blocks making up the stream directory
+        let directory_block_count = directory_size.div_ceil(block_size as usize);
+        let mut block_map = vec![0u8; directory_block_count * 4];
+        reader.seek(SeekFrom::Start(
+            block_map_address
+                .checked_mul(block_size)
+                .ok_or_else(unexpected_eof)?,
+        ))?;
+        reader.read_exact(&mut block_map)?;
+        let mut directory = Vec::with_capacity(directory_size);
+        for block_index in block_map.chunks_exact(4) {
+            let block_index = read_u32(block_index, 0) as u64;
+            reader.seek(SeekFrom::Start(block_index * block_size))?;
+            reader
+                .by_ref()
+                .take(block_size)
+                .read_to_end(&mut directory)?;
+        }
+        if directory.len() < directory_size.max(4) {
+            return Err(unexpected_eof());
+        }
+        directory.truncate(directory_size.max(4));
+
+        // The directory starts with the number of streams, followed by their
+        // sizes and then by their blocks
+        let stream_count = read_u32(&directory, 0) as usize;
+        let stream_sizes_end = stream_count
+            .checked_mul(4)
+            .and_then(|stream_sizes_size| stream_sizes_size.checked_add(4))
+            .ok_or_else(unexpected_eof)?;
+        let stream_sizes = directory
+            .get(4..stream_sizes_end)
+            .ok_or_else(unexpected_eof)?;
+        // Note: streams whose blocks aren't all listed in the directory can
+        // still be listed, reading them fails
+        let mut stream_blocks = directory[stream_sizes_end..]
+            .chunks_exact(4)
+            .map(|block_index| read_u32(block_index, 0));
+        let streams = stream_sizes
+            .chunks_exact(4)
+            .map(|size| match read_u32(size, 0) {
+                NIL_STREAM_SIZE => None,
+                size => {
+                    let block_count = (size as u64).div_ceil(block_size) as usize;
+                    Some((size, stream_blocks.by_ref().take(block_count).collect()))
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            block_size,
+            file_size,
+            streams,
+        })
+    }
+
+    /// Return the size of each stream, `None` for streams which aren't present
+    pub fn stream_sizes(&self) -> Vec<Option<u32>> {
+        self.streams
+            .iter()
+            .map(|stream| stream.as_ref().map(|(size, _)| *size))
+            .collect()
+    }
+
+    /// Return the content of the given stream, truncated to `max_size` bytes
+    /// if needed.
+    pub fn read_stream<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        stream_index: u16,
+        max_size: Option<usize>,
+    ) -> Result<Vec<u8>> {
+        let Some(Some((size, blocks))) = self.streams.get(stream_index as usize) else {
+            return Err(ResymCoreError::PdbError(pdb::Error::StreamNotFound(
+                stream_index.into(),
+            )));
+        };
+        let size = *size as usize;
+        let data_size = max_size.map_or(size, |max_size| size.min(max_size));
+        // Note: the size is checked against the file's before allocating anything
+        if data_size as u64 > self.file_size {
+            return Err(unexpected_eof());
+        }
+
+        let mut data = Vec::with_capacity(data_size);
+        for block_index in blocks {
+            if data.len() == data_size {
+                break;
+            }
+            let offset = data.len();
+            let block_data_size = (data_size - offset).min(self.block_size as usize);
+            data.resize(offset + block_data_size, 0);
+            reader.seek(SeekFrom::Start(*block_index as u64 * self.block_size))?;
+            reader.read_exact(&mut data[offset..])?;
+        }
+        if data.len() < data_size {
+            return Err(unexpected_eof());
+        }
+
+        Ok(data)
+    }
+}
+
+/// Return the size of each stream of the MSF file read by `reader`. Streams
+/// which aren't present are `None`.
+pub(crate) fn read_stream_sizes<R: Read + Seek>(reader: &mut R) -> Result<Vec<Option<u32>>> {
+    Ok(StreamDirectory::read(reader)?.stream_sizes())
+}
+
+/// Return every stream of the MSF file read by `reader`, named after the
+/// structure which references it when known. Only the stream directory has to
+/// be valid, streams which cannot be parsed are left unnamed.
+pub fn stream_list<R: Read + Seek>(reader: &mut R) -> Result<Vec<StreamInfo>> {
+    let stream_directory = StreamDirectory::read(reader)?;
+    let mut stream_names = stream_names(reader, &stream_directory);
+
+    Ok(stream_directory
+        .stream_sizes()
+        .into_iter()
+        .enumerate()
+        .map(|(stream_index, size)| StreamInfo {
+            index: stream_index as u16,
+            size,
+            name: stream_names.remove(&(stream_index as u16)),
+        })
+        .collect())
+}
+
+/// Return the content of the given stream of the MSF file read by `reader`,
+/// truncated to `max_size` bytes if needed.
+pub fn read_stream<R: Read + Seek>(
+    reader: &mut R,
+    stream_index: u16,
+    max_size: Option<usize>,
+) -> Result<Vec<u8>> {
+    StreamDirectory::read(reader)?.read_stream(reader, stream_index, max_size)
+}
+
+/// Name the streams referenced by the named stream map and by the headers of
+/// the TPI, IPI and DBI streams.
+fn stream_names<R: Read + Seek>(
+    reader: &mut R,
+    stream_directory: &StreamDirectory,
+) -> HashMap<u16, String> {
+    let mut stream_names: HashMap<u16, String> = HashMap::new();
+    let mut name_stream = |stream_index: Option<u16>, name: &str| {
+        if let Some(stream_index) = stream_index {
+            stream_names
+                .entry(stream_index)
+                .or_insert_with(|| name.to_string());
+        }
+    };
+
+    // Named streams come first, as their names are the most specific
+    match stream_directory
+        .read_stream(reader, PDB_STREAM_INDEX, None)
+        .and_then(|pdb_stream| named_streams(&pdb_stream))
+    {
+        Ok(named_streams) => {
+            for (name, stream_index) in &named_streams {
+                name_stream(Some(*stream_index), name);
+            }
+        }
+        Err(err) => log::warn!("Failed to read the named stream map: {err}"),
+    }
+    name_stream(Some(OLD_DIRECTORY_STREAM_INDEX), "Old MSF directory");
+    name_stream(Some(PDB_STREAM_INDEX), "PDB");
+    name_stream(Some(TPI_STREAM_INDEX), "TPI");
+    name_stream(Some(DBI_STREAM_INDEX), "DBI");
+    name_stream(Some(IPI_STREAM_INDEX), "IPI");
+    for (type_stream_index, hash_stream_names) in [
+        (TPI_STREAM_INDEX, ["TPI hash", "TPI auxiliary hash"]),
+        (IPI_STREAM_INDEX, ["IPI hash", "IPI auxiliary hash"]),
+    ] {
+        // Note: the IPI stream is optional
+        if let Ok(type_stream_header) =
+            stream_directory.read_stream(reader, type_stream_index, Some(TYPE_STREAM_HEADER_SIZE))
+        {
+            let (hash_stream, auxiliary_hash_stream) =
+                type_stream_hash_streams(&type_stream_header);
+            name_stream(hash_stream, hash_stream_names[0]);
+            name_stream(auxiliary_hash_stream, hash_stream_names[1]);
+        }
+    }
+    match stream_directory
+        .read_stream(reader, DBI_STREAM_INDEX, None)
+        .and_then(|dbi_stream| DbiStreamHeader::parse(&dbi_stream))
+    {
+        Ok(dbi_header) => {
+            name_stream(dbi_header.global_symbols_stream, "Global symbols");
+            name_stream(dbi_header.public_symbols_stream, "Public symbols");
+            name_stream(dbi_header.symbol_records_stream, "Symbol records");
+            for (debug_stream, name) in [
+                (DBI_FPO_STREAM, "FPO"),
+                (DBI_OMAP_TO_SOURCE_STREAM, "OMAP to source"),
+                (DBI_OMAP_FROM_SOURCE_STREAM, "OMAP from source"),
+                (DBI_SECTION_HEADERS_STREAM, "Section headers"),
+                (DBI_NEW_FPO_STREAM, "New FPO"),
+            ] {
+                name_stream(dbi_header.debug_stream(debug_stream), name);
+            }
+            for (module_name, module_stream) in &dbi_header.modules {
+                name_stream(*module_stream, &format!("Module '{module_name}'"));
+            }
+        }
+        Err(err) => log::warn!("Failed to parse the DBI stream header: {err}"),
+    }
+
+    stream_names
+}
+
+/// Return the name and index of the streams of the PDB information stream's
+/// named stream map
+fn named_streams(pdb_stream: &[u8]) -> Result<Vec<(String, u16)>> {
+    // The map follows the version, signature, age and GUID of the PDB, it
+    // starts with a buffer containing the names of the streams
+    const NAMES_OFFSET: usize = 28;
+    let names_size = try_read_u32(pdb_stream, NAMES_OFFSET)? as usize;
+    let names = pdb_stream
+        .get(NAMES_OFFSET + 4..)
+        .and_then(|names| names.get(..names_size))
+        .ok_or_else(unexpected_eof)?;
+
+    // The hash table starts with its size and capacity, followed by the bit
+    // vectors of present and deleted entries
+    let mut offset = NAMES_OFFSET + 4 + names_size;
+    let entry_count = try_read_u32(pdb_stream, offset)? as usize;
+    offset += 8;
+    for _ in 0..2 {
+        let word_count = try_read_u32(pdb_stream, offset)? as usize;
+        offset = word_count
+            .checked_mul(4)
+            .and_then(|bit_vector_size| bit_vector_size.checked_add(offset + 4))
+            .ok_or_else(unexpected_eof)?;
+    }
+    let entries = entry_count
+        .checked_mul(8)
+        .and_then(|entries_size| pdb_stream.get(offset..)?.get(..entries_size))
+        .ok_or_else(unexpected_eof)?;
+
+    entries
+        .chunks_exact(8)
+        .map(|entry| {
+            let name = names
+                .get(read_u32(entry, 0) as usize..)
+                .and_then(|name| name.split(|c| *c == 0).next())
+                .ok_or_else(unexpected_eof)?;
+            Ok((
+                String::from_utf8_lossy(name).into_owned(),
+                read_u32(entry, 4) as u16,
+            ))
+        })
+        .collect()
+}
+
+/// Fields of the DBI stream's header
+pub(crate) struct DbiStreamHeader {
+    pub version: u32,
+    pub global_symbols_stream: Option<u16>,
+    pub public_symbols_stream: Option<u16>,
+    pub symbol_records_stream: Option<u16>,
+    /// Streams referenced by the optional debug header (e.g., FPO, OMAP)
+    pub debug_streams: Vec<Option<u16>>,
+    /// Name and symbol stream of the modules, in the order modules are
+    /// declared
+    pub modules: Vec<(String, Option<u16>)>,
+}
+
+impl DbiStreamHeader {
+    pub fn parse(dbi_stream: &[u8]) -> Result<Self> {
+        const HEADER_SIZE: usize = 64;
+        if dbi_stream.len() < HEADER_SIZE {
+            return Err(unexpected_eof());
+        }
+
+        // The optional debug header comes after all the other substreams
+        let debug_header_offset = [24, 28, 32, 36, 40, 52]
+            .into_iter()
+            .map(|offset| read_u32(dbi_stream, offset) as usize)
+            .try_fold(HEADER_SIZE, usize::checked_add)
+            .ok_or_else(unexpected_eof)?;
+        let debug_header_size = read_u32(dbi_stream, 48) as usize;
+        let debug_header_end = debug_header_offset
+            .checked_add(debug_header_size)
+            .ok_or_else(unexpected_eof)?;
+        let module_info_size = read_u32(dbi_stream, 24) as usize;
+        let debug_streams = dbi_stream
+            .get(debug_header_offset..debug_header_end)
+            .unwrap_or_default()
+            .chunks_exact(2)
+            .map(|index| stream_index(read_u16(index, 0)))
+            .collect();
+
+        // Module records are variable-sized, as they end with two strings
+        const MODULE_RECORD_SIZE: usize = 64;
+        const MODULE_STREAM_OFFSET: usize = 34;
+        // Note: the module info substream is the first one, its end cannot
+        // overflow if the debug header's doesn't
+        let module_info = dbi_stream
+            .get(HEADER_SIZE..HEADER_SIZE + module_info_size)
+            .unwrap_or_default();
+        let mut modules = vec![];
+        let mut module_offset = 0;
+        while module_offset + MODULE_RECORD_SIZE <= module_info.len() {
+            let module_stream =
+                stream_index(read_u16(module_info, module_offset + MODULE_STREAM_OFFSET));
+            let names = &module_info[module_offset + MODULE_RECORD_SIZE..];
+            let module_name = names.split(|c| *c == 0).next().unwrap_or_default();
+            modules.push((
+                String::from_utf8_lossy(module_name).into_owned(),
+                module_stream,
+            ));
+            // Skip the module and object file names
+            let mut names_end = module_offset + MODULE_RECORD_SIZE;
+            for _ in 0..2 {
+                match module_info
+                    .get(names_end..)
+                    .and_then(|names| names.iter().position(|c| *c == 0))
+                {
+                    Some(name_len) => names_end += name_len + 1,
+                    None => names_end = module_info.len(),
+                }
+            }
+            module_offset = names_end.next_multiple_of(4);
+        }
+
+        Ok(Self {
+            version: read_u32(dbi_stream, 4),
+            global_symbols_stream: stream_index(read_u16(dbi_stream, 12)),
+            public_symbols_stream: stream_index(read_u16(dbi_stream, 16)),
+            symbol_records_stream: stream_index(read_u16(dbi_stream, 20)),
+            debug_streams,
+            modules,
+        })
+    }
+
+    pub fn debug_stream(&self, debug_stream_index: usize) -> Option<u16> {
+        self.debug_streams
+            .get(debug_stream_index)
+            .copied()
+            .flatten()
+    }
+}
+
+/// Return the hash streams referenced by the header of a TPI or IPI stream
+pub(crate) fn type_stream_hash_streams(type_stream: &[u8]) -> (Option<u16>, Option<u16>) {
+    if type_stream.len() < 24 {
+        return (None, None);
+    }
+
+    (
+        stream_index(read_u16(type_stream, 20)),
+        stream_index(read_u16(type_stream, 22)),
+    )
+}
+
+fn try_read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    match data.get(offset..) {
+        Some(value) if value.len() >= 4 => Ok(read_u32(value, 0)),
+        _ => Err(unexpected_eof()),
+    }
+}
+
+fn unexpected_eof() -> ResymCoreError {
+    ResymCoreError::IoError(io::ErrorKind::UnexpectedEof.into())
+}
+
+fn stream_index(index: u16) -> Option<u16> {
+    match index {
+        NIL_STREAM_INDEX => None,
+        index => Some(index),
+    }
+}
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// Return an MSF super block with the given fields, padded to one block.
+    fn super_block(block_size: u32, directory_size: u32, block_map_address: u32) -> Vec<u8> {
+        let mut data = MSF_MAGIC.to_vec();
+        data.resize(32, 0);
+        for value in [block_size, 0, 2, directory_size, 0, block_map_address] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.resize(block_size as usize, 0);
+        data
+    }
+
+    #[test]
+    fn test_read_stream_sizes_truncated_directory() {
+        // The directory is larger than the file
+        let mut data = Cursor::new(super_block(512, u32::MAX, 1));
+        assert!(read_stream_sizes(&mut data).is_err());
+
+        // The block map is located past the end of the file
+        let mut data = Cursor::new(super_block(512, 8, u32::MAX));
+        assert!(read_stream_sizes(&mut data).is_err());
+    }
+
+    #[test]
+    fn test_read_stream_sizes_invalid_stream_count() {
+        // One block for the super block, the block map and the directory
+        let mut data = super_block(64, 8, 1);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.resize(128, 0);
+        data.extend_from_slice(&u32::MAX.to_le_bytes());
+        data.extend_from_slice(&NIL_STREAM_SIZE.to_le_bytes());
+        data.resize(192, 0);
+        assert!(read_stream_sizes(&mut Cursor::new(data)).is_err());
+    }
+
+    #[test]
+    fn test_parse_dbi_stream_header_invalid_substream_sizes() {
+        let mut dbi_stream = vec![0u8; 64];
+        dbi_stream[24..28].copy_from_slice(&u32::MAX.to_le_bytes());
+        dbi_stream[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+        dbi_stream[48..52].copy_from_slice(&u32::MAX.to_le_bytes());
+        if usize::BITS == 32 {
+            assert!(DbiStreamHeader::parse(&dbi_stream).is_err());
+        } else {
+            let header = DbiStreamHeader::parse(&dbi_stream).expect("parse DBI header");
+            assert!(header.debug_streams.is_empty());
+            assert!(header.modules.is_empty());
+        }
+    }
+}
@@ -5,24 +5,32 @@ use pdb::FallibleIterator;
 #[cfg(feature = "rayon")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
+use serde::Serialize;
 use std::{
-    collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque},
-    fmt::Write,
+    collections::{hash_map, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt::{self, Write},
     io::{self, Read, Seek},
-    path::PathBuf,
-    sync::{Arc, RwLock},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, OnceLock, RwLock},
 };
 #[cfg(not(target_arch = "wasm32"))]
-use std::{fs::File, path::Path, time::Instant};
+use std::{fs::File, time::Instant};
 
 use crate::{
     error::{Result, ResymCoreError},
-    frontend::ReconstructedType,
+    frontend::{ReconstructedFiles, ReconstructedType},
+    msf::{
+        self, DbiStreamHeader, DBI_STREAM_INDEX, IPI_STREAM_INDEX, PDB_STREAM_INDEX,
+        TPI_STREAM_INDEX,
+    },
     par_iter_if_available,
     pdb_types::{
         self, is_unnamed_type, type_name, AccessSpecifierReconstructionFlavor,
-        DataFormatConfiguration, PrimitiveReconstructionFlavor, SizePrintFlavor,
+        DataFormatConfiguration, HeaderSplitFlavor, OutputLanguage, PrimitiveReconstructionFlavor,
+        SourceLocation,
     },
+    procedure::{register_name, ProcedureScope, ScopeVariable, VariableLocation},
+    records,
 };
 
 // Type
@@ -34,6 +42,10 @@ pub type TypeList = Vec<TypeInfo>;
 pub type TypeInfoEx = (String, TypeIndex, TypeKind);
 pub type TypeListEx = Vec<TypeInfoEx>;
 pub type TypeListExView<'t> = Vec<&'t TypeInfoEx>;
+/// Location of the declaration of user-defined types
+pub type TypeSourceLocationMap = HashMap<TypeIndex, SourceLocation>;
+/// Maps a type to the types it depends on (`true` if needed by value).
+type TypeDependencyMap = HashMap<TypeIndex, Vec<(TypeIndex, bool)>>;
 #[derive(Eq, PartialEq)]
 pub enum TypeKind {
     Class,
@@ -52,7 +64,7 @@ pub type SymbolList = Vec<SymbolInfo>;
 pub type SymbolInfoEx = (String, SymbolIndex, SymbolKind);
 pub type SymbolListEx = Vec<SymbolInfoEx>;
 pub type SymbolListExView<'t> = Vec<&'t SymbolInfoEx>;
-#[derive(Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
 pub enum SymbolKind {
     Function,
     Variable,
@@ -60,12 +72,290 @@ pub enum SymbolKind {
     Unknown,
 }
 
+/// Parameter or local variable of a procedure, with the address ranges in
+/// which it's live
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcedureVariable {
+    pub name: String,
+    pub type_name: String,
+    pub is_parameter: bool,
+    pub live_ranges: Vec<VariableLiveRange>,
+}
+/// Address range (`start_rva..end_rva`) in which a variable is stored at
+/// `location`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableLiveRange {
+    pub start_rva: u32,
+    pub end_rva: u32,
+    pub location: String,
+}
+
+/// Frame of a symbolized address. Inlined functions have their own frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolizedFrame {
+    pub function_name: String,
+    /// Offset of the address in the function, for functions which weren't
+    /// inlined
+    pub function_offset: Option<u32>,
+    pub source_location: Option<SourceLocation>,
+    pub is_inlined: bool,
+}
+/// Address and the frames it belongs to, innermost first (no frames if the
+/// address couldn't be symbolized)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolizedAddress {
+    pub rva: u32,
+    pub frames: Vec<SymbolizedFrame>,
+}
+
+/// Function inlined in a procedure, with the functions inlined in it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InlinedCall {
+    pub function_name: String,
+    /// Location of the first line of the inlined code
+    pub source_location: Option<SourceLocation>,
+    /// RVA ranges (`start..end`) of the inlined code
+    pub code_ranges: Vec<(u32, u32)>,
+    pub inlined_calls: Vec<InlinedCall>,
+}
+
+/// Procedure in which a function has been inlined
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InlineCallSite {
+    pub caller_name: String,
+    pub caller_index: SymbolIndex,
+    pub inlined_call: InlinedCall,
+}
+
 // Module
 pub type ModuleIndex = usize;
 pub type ModuleInfo = (String, ModuleIndex);
 pub type ModuleList = Vec<ModuleInfo>;
+/// Size of a procedure or data symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolSize {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub rva: Option<u32>,
+    pub size: u64,
+}
+
+/// Size attributed to a group of symbols or contributions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SizeReportEntry {
+    pub name: String,
+    pub size: u64,
+    /// Number of symbols or contributions in the group
+    pub count: usize,
+}
+/// Size of the image attributed to modules, object files, namespaces and
+/// template families, largest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SizeReport {
+    pub modules: Vec<SizeReportEntry>,
+    pub object_files: Vec<SizeReportEntry>,
+    pub namespaces: Vec<SizeReportEntry>,
+    pub template_families: Vec<SizeReportEntry>,
+}
+
+/// Well-known stream of a PDB
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct KnownStreamInfo {
+    pub name: String,
+    /// Index of the stream, `None` if the stream isn't present
+    pub index: Option<u16>,
+    /// Size of the stream, `None` if it's unknown
+    pub size: Option<u32>,
+}
+
+/// MSF stream of a PDB
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StreamInfo {
+    pub index: u16,
+    /// Size of the stream, `None` if the stream isn't present or if its size
+    /// is unknown
+    pub size: Option<u32>,
+    /// Name of the stream, if it's referenced by a known structure
+    pub name: Option<String>,
+}
+
+/// Summary of the information identifying a PDB and of its content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PdbMetadata {
+    pub guid: String,
+    /// Age of the PDB, bumped every time the PDB is written
+    pub age: u32,
+    /// Age written by the linker in the DBI stream, should match the image's
+    pub dbi_age: Option<u32>,
+    /// Signature of the PDB, usually a timestamp
+    pub signature: u32,
+    pub machine_type: String,
+    pub pdb_version: u32,
+    pub dbi_version: u32,
+    pub type_count: usize,
+    pub id_count: usize,
+    /// Number of records in the global symbol table
+    pub symbol_count: usize,
+    pub module_count: usize,
+    /// Number of streams, `None` if the stream directory couldn't be read
+    pub stream_count: Option<usize>,
+    /// Total size of the streams, `None` if the stream directory couldn't be
+    /// read
+    pub total_stream_size: Option<u64>,
+    pub streams: Vec<KnownStreamInfo>,
+}
+
+/// How a type depends on another type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum TypeDependencyKind {
+    /// The type is embedded by value (e.g., as a field or an array element)
+    Embedded,
+    /// The type is referenced through a pointer or a C++ reference
+    Pointer,
+    /// The type is a base class
+    BaseClass,
+}
+
+/// Node of a type dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeGraphNode {
+    pub index: TypeIndex,
+    pub name: String,
+    /// Distance from the root of the graph
+    pub depth: usize,
+}
+
+/// Dependency between two types of a type dependency graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeGraphEdge {
+    pub from: TypeIndex,
+    pub to: TypeIndex,
+    pub kind: TypeDependencyKind,
+}
+
+/// Types a type depends on, directly or not
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TypeGraph {
+    pub root: TypeIndex,
+    /// Nodes sorted by depth, the root first
+    pub nodes: Vec<TypeGraphNode>,
+    pub edges: Vec<TypeGraphEdge>,
+}
+
+/// How a symbol uses a type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum SymbolXrefKind {
+    /// Global or static variable of the type
+    Variable,
+    /// Function taking the type as a parameter
+    Parameter,
+    /// Function returning the type
+    ReturnValue,
+    /// Function with a local variable of the type
+    LocalVariable,
+}
+
+/// Symbol using a type, directly or through pointers, arrays or modifiers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SymbolXref {
+    /// Name of the variable or function
+    pub symbol_name: String,
+    pub symbol_index: SymbolIndex,
+    pub kind: SymbolXrefKind,
+    /// Name of the local variable, for `SymbolXrefKind::LocalVariable`
+    pub variable_name: Option<String>,
+}
+
+/// Source file referenced by the line information of modules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceFileInfo {
+    pub file_path: String,
+    pub checksum_kind: ChecksumKind,
+    /// Checksum of the file's content, as an hexadecimal string
+    pub checksum: String,
+    /// Modules whose code comes from this file
+    pub modules: ModuleList,
+}
+/// Range of the image contributed by a module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionContribution {
+    pub section_name: String,
+    pub rva: u32,
+    pub size: u32,
+    /// Characteristics of the contribution (`IMAGE_SCN_*` flags)
+    pub characteristics: u32,
+    pub module_index: ModuleIndex,
+    pub module_name: String,
+    /// Object file (or static library) the module comes from
+    pub object_file_name: String,
+}
+/// Contributions of modules to the sections of the image, sorted by RVA
+#[derive(Debug, Default)]
+pub struct SectionContributionMap {
+    contributions: Vec<SectionContribution>,
+}
+
+impl SectionContributionMap {
+    /// Return the contribution containing the given RVA, if any
+    pub fn find_by_rva(&self, rva: u32) -> Option<&SectionContribution> {
+        let contribution_index = self
+            .contributions
+            .partition_point(|contribution| contribution.rva <= rva)
+            .checked_sub(1)?;
+        let contribution = &self.contributions[contribution_index];
+
+        (rva - contribution.rva < contribution.size).then_some(contribution)
+    }
+
+    /// Return the contributions of the modules whose path or object file
+    /// (e.g., static library) is `module_path`
+    pub fn find_by_module_path<'a>(
+        &'a self,
+        module_path: &'a str,
+    ) -> impl Iterator<Item = &'a SectionContribution> {
+        self.contributions.iter().filter(move |contribution| {
+            contribution.module_name == module_path || contribution.object_file_name == module_path
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SectionContribution> {
+        self.contributions.iter()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChecksumKind {
+    None,
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl fmt::Display for ChecksumKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChecksumKind::None => write!(f, "None"),
+            ChecksumKind::Md5 => write!(f, "MD5"),
+            ChecksumKind::Sha1 => write!(f, "SHA1"),
+            ChecksumKind::Sha256 => write!(f, "SHA256"),
+        }
+    }
+}
 
 const GLOBAL_MODULE_INDEX: usize = usize::MAX;
+/// Name under which symbols of the global namespace are reported
+const GLOBAL_NAMESPACE_NAME: &str = "(global namespace)";
+/// Header in which types with an unknown declaration are reconstructed
+const UNKNOWN_SOURCE_HEADER_PATH: &str = "unknown_source.h";
+/// Header declaring the types of the global namespace, when splitting types by
+/// namespace.
+const GLOBAL_NAMESPACE_HEADER_PATH: &str = "global_namespace.h";
+
+/// Streams referenced by the named stream map and by the DBI stream's header
+struct StreamReferences {
+    named_streams: Vec<(String, u16)>,
+    dbi_header: DbiStreamHeader,
+}
 
 /// Wrapper for different buffer types processed by `resym`
 #[derive(Debug)]
@@ -125,11 +415,24 @@ where
     pub symbol_list: SymbolListEx,
     pub machine_type: pdb::MachineType,
     pub type_information: pdb::TypeInformation<'p>,
+    /// Id information (IPI stream), if present in the PDB
+    pub id_information: Option<pdb::IdInformation<'p>>,
+    /// Names of source files, if present in the PDB
+    string_table: Option<pdb::StringTable<'p>>,
     pub debug_information: pdb::DebugInformation<'p>,
     pub global_symbols: pdb::SymbolTable<'p>,
     pub sections: Vec<pdb::ImageSectionHeader>,
+    pub section_contributions: SectionContributionMap,
+    /// Size of each MSF stream, `None` for streams which aren't present.
+    /// `None` if the stream directory couldn't be read.
+    stream_sizes: Option<Vec<Option<u32>>>,
     pub file_path: PathBuf,
-    pub xref_to_map: RwLock<DashMap<TypeIndex, Vec<TypeIndex>>>,
+    /// Types referencing a type, with whether they reference it through a
+    /// pointer
+    pub xref_to_map: RwLock<DashMap<TypeIndex, Vec<(TypeIndex, bool)>>>,
+    /// Source locations of the types, loaded on first use
+    type_source_locations: OnceLock<TypeSourceLocationMap>,
+    virtual_table_records: pdb_types::VirtualTableRecords,
     pdb: RwLock<pdb::PDB<'p, T>>,
 }
 
@@ -137,9 +440,18 @@ where
 impl<'p> PdbFile<'p, File> {
     /// Create `PdbFile` from an `std::path::Path`
     pub fn load_from_file(pdb_file_path: &Path) -> Result<PdbFile<'p, PDBDataSource>> {
-        let file = PDBDataSource::File(File::open(pdb_file_path)?);
+        let mut file = PDBDataSource::File(File::open(pdb_file_path)?);
+        // Note: stream sizes are only used to describe streams, PDBs whose
+        // stream directory cannot be read by `msf` can still be loaded
+        let stream_sizes = msf::read_stream_sizes(&mut file)
+            .map_err(|err| log::warn!("Failed to read stream sizes: {err}"))
+            .ok();
         let mut pdb = pdb::PDB::open(file)?;
         let type_information = pdb.type_information()?;
+        // Note: the IPI stream is optional
+        let id_information = pdb.id_information().ok();
+        // Note: the string table is optional as well
+        let string_table = pdb.string_table().ok();
         let debug_information = pdb.debug_information()?;
         let global_symbols = pdb.global_symbols()?;
         let sections = pdb.sections().unwrap_or_default().unwrap_or_default();
@@ -151,14 +463,22 @@ impl<'p> PdbFile<'p, File> {
             symbol_list: Default::default(),
             machine_type,
             type_information,
+            id_information,
+            string_table,
             debug_information,
             global_symbols,
             sections,
+            section_contributions: Default::default(),
+            stream_sizes,
             file_path: pdb_file_path.to_owned(),
             xref_to_map: DashMap::default().into(),
+            type_source_locations: OnceLock::new(),
+            virtual_table_records: Default::default(),
             pdb: pdb.into(),
         };
         pdb_file.load_symbols()?;
+        pdb_file.load_virtual_table_records();
+        pdb_file.load_section_contributions();
 
         Ok(pdb_file)
     }
@@ -170,9 +490,18 @@ impl<'p> PdbFile<'p, PDBDataSource> {
         pdb_file_name: String,
         pdb_file_data: Vec<u8>,
     ) -> Result<PdbFile<'p, PDBDataSource>> {
-        let reader = PDBDataSource::Vec(io::Cursor::new(pdb_file_data));
+        let mut reader = PDBDataSource::Vec(io::Cursor::new(pdb_file_data));
+        // Note: stream sizes are only used to describe streams, PDBs whose
+        // stream directory cannot be read by `msf` can still be loaded
+        let stream_sizes = msf::read_stream_sizes(&mut reader)
+            .map_err(|err| log::warn!("Failed to read stream sizes: {err}"))
+            .ok();
         let mut pdb = pdb::PDB::open(reader)?;
         let type_information = pdb.type_information()?;
+        // Note: the IPI stream is optional
+        let id_information = pdb.id_information().ok();
+        // Note: the string table is optional as well
+        let string_table = pdb.string_table().ok();
         let debug_information = pdb.debug_information()?;
         let global_symbols = pdb.global_symbols()?;
         let sections = pdb.sections().unwrap_or_default().unwrap_or_default();
@@ -184,14 +513,22 @@ impl<'p> PdbFile<'p, PDBDataSource> {
             symbol_list: Default::default(),
             machine_type,
             type_information,
+            id_information,
+            string_table,
             debug_information,
             global_symbols,
             sections,
+            section_contributions: Default::default(),
+            stream_sizes,
             file_path: pdb_file_name.into(),
             xref_to_map: DashMap::default().into(),
+            type_source_locations: OnceLock::new(),
+            virtual_table_records: Default::default(),
             pdb: pdb.into(),
         };
         pdb_file.load_symbols()?;
+        pdb_file.load_virtual_table_records();
+        pdb_file.load_section_contributions();
 
         Ok(pdb_file)
     }
@@ -201,9 +538,18 @@ impl<'p> PdbFile<'p, PDBDataSource> {
         pdb_file_name: String,
         pdb_file_data: Arc<[u8]>,
     ) -> Result<PdbFile<'p, PDBDataSource>> {
-        let reader = PDBDataSource::SharedArray(io::Cursor::new(pdb_file_data));
+        let mut reader = PDBDataSource::SharedArray(io::Cursor::new(pdb_file_data));
+        // Note: stream sizes are only used to describe streams, PDBs whose
+        // stream directory cannot be read by `msf` can still be loaded
+        let stream_sizes = msf::read_stream_sizes(&mut reader)
+            .map_err(|err| log::warn!("Failed to read stream sizes: {err}"))
+            .ok();
         let mut pdb = pdb::PDB::open(reader)?;
         let type_information = pdb.type_information()?;
+        // Note: the IPI stream is optional
+        let id_information = pdb.id_information().ok();
+        // Note: the string table is optional as well
+        let string_table = pdb.string_table().ok();
         let debug_information = pdb.debug_information()?;
         let global_symbols = pdb.global_symbols()?;
         let sections = pdb.sections().unwrap_or_default().unwrap_or_default();
@@ -215,14 +561,22 @@ impl<'p> PdbFile<'p, PDBDataSource> {
             symbol_list: Default::default(),
             machine_type,
             type_information,
+            id_information,
+            string_table,
             debug_information,
             global_symbols,
             sections,
+            section_contributions: Default::default(),
+            stream_sizes,
             file_path: pdb_file_name.into(),
             xref_to_map: DashMap::default().into(),
+            type_source_locations: OnceLock::new(),
+            virtual_table_records: Default::default(),
             pdb: pdb.into(),
         };
         pdb_file.load_symbols()?;
+        pdb_file.load_virtual_table_records();
+        pdb_file.load_section_contributions();
 
         Ok(pdb_file)
     }
@@ -322,16 +676,166 @@ where
         Ok(())
     }
 
+    /// Load the virtual function table records from the raw TPI stream. These
+    /// are optional, errors are logged and ignored.
+    fn load_virtual_table_records(&mut self) {
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        match pdb.raw_stream(pdb::StreamIndex(TPI_STREAM_INDEX)) {
+            Ok(Some(type_stream)) => {
+                self.virtual_table_records = pdb_types::VirtualTableRecords::parse(
+                    type_stream.as_slice(),
+                    &self.forwarder_to_complete_type,
+                );
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!("Failed to read virtual function table records: {err}"),
+        }
+    }
+
+    /// Read the source locations of the types from the IPI stream. Invalid
+    /// records are skipped, and reading stops at the first record which
+    /// cannot be read, keeping the locations found until then.
+    fn read_type_source_locations(&self) -> TypeSourceLocationMap {
+        let Some(id_information) = &self.id_information else {
+            // No IPI stream, nothing to do
+            return TypeSourceLocationMap::new();
+        };
+        let udt_start = Instant::now();
+
+        let mut id_finder = id_information.finder();
+        let mut udt_sources = vec![];
+        let mut id_iter = id_information.iter();
+        loop {
+            let id = match id_iter.next() {
+                Ok(Some(id)) => id,
+                Ok(None) => break,
+                Err(err) => {
+                    log::warn!("Failed to read type source locations: {err}");
+                    break;
+                }
+            };
+            // keep building the index
+            id_finder.update(&id_iter);
+
+            if let Ok(pdb::IdData::UserDefinedTypeSource(data)) = id.parse() {
+                udt_sources.push(data);
+            }
+        }
+
+        let mut type_source_locations = HashMap::new();
+        for udt_source in udt_sources {
+            let file_path = match udt_source.source_file {
+                pdb::UserDefinedTypeSourceFileRef::Local(id_index) => {
+                    match id_finder.find(id_index).and_then(|id| id.parse()) {
+                        Ok(pdb::IdData::String(data)) => data.name.to_string().into_owned(),
+                        _ => continue,
+                    }
+                }
+                // Note: the string table is only needed to resolve the locations
+                // of types defined in several modules (i.e., `LF_UDT_MOD_SRC_LINE`)
+                pdb::UserDefinedTypeSourceFileRef::Remote(_, string_ref) => {
+                    match self
+                        .string_table
+                        .as_ref()
+                        .map(|string_table| string_ref.to_string_lossy(string_table))
+                    {
+                        Some(Ok(file_path)) => file_path.into_owned(),
+                        _ => continue,
+                    }
+                }
+            };
+            // Records may reference the forward declaration of the type
+            let type_index = self
+                .forwarder_to_complete_type
+                .get(&udt_source.udt)
+                .map(|e| *e)
+                .unwrap_or(udt_source.udt);
+            // Note: types defined in several modules have one record per module,
+            // keep the first one
+            type_source_locations
+                .entry(type_index.0)
+                .or_insert(SourceLocation {
+                    file_path,
+                    line: udt_source.line,
+                });
+        }
+        log::debug!(
+            "Type source location loading took {} ms",
+            udt_start.elapsed().as_millis()
+        );
+
+        type_source_locations
+    }
+
+    /// Load the section contributions of the modules. Errors are logged and
+    /// leave the contributions empty, as they're not needed to load the PDB.
+    fn load_section_contributions(&mut self) {
+        match self.read_section_contributions() {
+            Ok(contributions) => {
+                self.section_contributions = SectionContributionMap { contributions };
+            }
+            Err(err) => log::warn!("Failed to read section contributions: {err}"),
+        }
+    }
+
+    fn read_section_contributions(&self) -> Result<Vec<SectionContribution>> {
+        let module_names: Vec<(String, String)> = self
+            .debug_information
+            .modules()?
+            .map(|module| {
+                Ok((
+                    module.module_name().into_owned(),
+                    module.object_file_name().into_owned(),
+                ))
+            })
+            .collect()?;
+
+        let mut contributions = vec![];
+        let mut contribution_iter = self.debug_information.section_contributions()?;
+        while let Some(contribution) = contribution_iter.next()? {
+            let Some(rva) = symbol_rva(&contribution.offset, &self.sections) else {
+                continue;
+            };
+            let section_name = self
+                .sections
+                .get(contribution.offset.section as usize - 1)
+                .map(|section_header| section_header.name().to_string())
+                .unwrap_or_default();
+            let (module_name, object_file_name) = module_names
+                .get(contribution.module)
+                .cloned()
+                .unwrap_or_default();
+            contributions.push(SectionContribution {
+                section_name,
+                rva,
+                size: contribution.size,
+                characteristics: contribution.characteristics.0,
+                module_index: contribution.module,
+                module_name,
+                object_file_name,
+            });
+        }
+        contributions.sort_by_key(|contribution| contribution.rva);
+
+        Ok(contributions)
+    }
+
+    /// Return the location of the declaration of the given type, if known.
+    pub fn type_source_location(&self, type_index: TypeIndex) -> Option<&SourceLocation> {
+        self.type_source_locations().get(&type_index)
+    }
+
+    pub fn type_source_locations(&self) -> &TypeSourceLocationMap {
+        self.type_source_locations
+            .get_or_init(|| self.read_type_source_locations())
+    }
+
     pub fn reconstruct_type_by_name(
         &self,
         type_name: &str,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
         reconstruct_dependencies: bool,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
         ignore_std_types: bool,
     ) -> Result<ReconstructedType> {
         // Populate our `TypeFinder` and find the right type index
@@ -418,13 +922,9 @@ where
             self.reconstruct_type_by_type_index_internal(
                 &type_finder,
                 type_index,
-                primitives_flavor,
-                print_access_specifiers,
-                size_print_flavor,
+                output_language,
+                fmt_configuration,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             )
         }
@@ -433,13 +933,9 @@ where
     pub fn reconstruct_type_by_index(
         &self,
         type_index: TypeIndex,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
         reconstruct_dependencies: bool,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
         ignore_std_types: bool,
     ) -> Result<ReconstructedType> {
         // Populate our `TypeFinder`
@@ -454,17 +950,211 @@ where
         self.reconstruct_type_by_type_index_internal(
             &type_finder,
             type_index,
-            primitives_flavor,
-            print_access_specifiers,
-            size_print_flavor,
+            output_language,
+            fmt_configuration,
             reconstruct_dependencies,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
             ignore_std_types,
         )
     }
 
+    /// Return the graph of the types the type named `type_name` depends on.
+    /// See `type_graph_by_index`.
+    pub fn type_graph_by_name(
+        &self,
+        type_name: &str,
+        max_depth: Option<usize>,
+        include_base_classes: bool,
+    ) -> Result<TypeGraph> {
+        let type_index = self.type_index_by_name(type_name)?;
+
+        self.type_graph_by_index(type_index, max_depth, include_base_classes)
+    }
+
+    /// Return the index of the complete type named `type_name`.
+    pub fn type_index_by_name(&self, type_name: &str) -> Result<TypeIndex> {
+        self.complete_type_list
+            .iter()
+            .find(|(name, _, _)| name == type_name)
+            .map(|(_, type_index, _)| *type_index)
+            .ok_or_else(|| ResymCoreError::TypeNameNotFoundError(type_name.to_owned()))
+    }
+
+    /// Return the graph of the types the type at `type_index` depends on, up
+    /// to `max_depth` levels of dependencies. Edges to base classes are only
+    /// included if `include_base_classes` is set.
+    pub fn type_graph_by_index(
+        &self,
+        type_index: TypeIndex,
+        max_depth: Option<usize>,
+        include_base_classes: bool,
+    ) -> Result<TypeGraph> {
+        let type_finder = self.complete_type_finder()?;
+
+        // Walk dependencies breadth-first, to compute the depth of types.
+        // Note: only the dependencies of types above `max_depth` are resolved
+        let mut depth_map: HashMap<TypeIndex, usize> = HashMap::from([(type_index, 0)]);
+        let mut types_to_process = VecDeque::from([type_index]);
+        let mut edges = vec![];
+        while let Some(current_type_index) = types_to_process.pop_front() {
+            let depth = depth_map[&current_type_index];
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+
+            let base_classes = self.base_class_type_indices(&type_finder, current_type_index)?;
+            let dependencies = self.direct_type_dependencies(&type_finder, current_type_index)?;
+            for (dependency_index, is_pointer) in dependencies {
+                let kind = if is_pointer {
+                    TypeDependencyKind::Pointer
+                } else if base_classes.contains(&dependency_index) {
+                    if !include_base_classes {
+                        continue;
+                    }
+                    TypeDependencyKind::BaseClass
+                } else {
+                    TypeDependencyKind::Embedded
+                };
+                edges.push(TypeGraphEdge {
+                    from: current_type_index,
+                    to: dependency_index,
+                    kind,
+                });
+                if let hash_map::Entry::Vacant(entry) = depth_map.entry(dependency_index) {
+                    entry.insert(depth + 1);
+                    types_to_process.push_back(dependency_index);
+                }
+            }
+        }
+
+        let type_names: HashMap<TypeIndex, String> = self
+            .complete_type_list
+            .iter()
+            .filter(|(_, type_index, _)| depth_map.contains_key(type_index))
+            .map(|(type_name, type_index, _)| (*type_index, type_name.clone()))
+            .collect();
+        let mut nodes: Vec<TypeGraphNode> = depth_map
+            .into_iter()
+            .map(|(type_index, depth)| TypeGraphNode {
+                index: type_index,
+                name: match type_names.get(&type_index) {
+                    Some(type_name) => type_name.clone(),
+                    None => type_name(
+                        &type_finder,
+                        &self.forwarder_to_complete_type,
+                        type_index.into(),
+                        &PrimitiveReconstructionFlavor::Portable,
+                        &mut pdb_types::NeededTypeSet::new(),
+                    )
+                    .map(|(type_left, type_right)| format!("{type_left}{type_right}"))
+                    .unwrap_or_else(|_| format!("_unnamed_{type_index:#x}")),
+                },
+                depth,
+            })
+            .collect();
+        nodes.sort_by(|lhs, rhs| lhs.depth.cmp(&rhs.depth).then(lhs.index.cmp(&rhs.index)));
+
+        Ok(TypeGraph {
+            root: type_index,
+            nodes,
+            edges,
+        })
+    }
+
+    /// Return the types the type at `type_index` directly depends on, along
+    /// with whether they're referenced through a pointer.
+    fn direct_type_dependencies(
+        &self,
+        type_finder: &pdb::TypeFinder<'_>,
+        type_index: TypeIndex,
+    ) -> Result<Vec<(TypeIndex, bool)>> {
+        let mut needed_types = pdb_types::NeededTypeSet::new();
+        pdb_types::Data::new(false).add(
+            type_finder,
+            &self.forwarder_to_complete_type,
+            &self.virtual_table_records,
+            type_index.into(),
+            &PrimitiveReconstructionFlavor::Portable,
+            &mut needed_types,
+        )?;
+        let mut dependencies: Vec<(TypeIndex, bool)> = needed_types
+            .into_iter()
+            .map(|(type_index, is_pointer)| (type_index.0, is_pointer))
+            .collect();
+        dependencies.sort();
+
+        Ok(dependencies)
+    }
+
+    /// Return the graph of the types the given type directly depends on and
+    /// of the types which directly depend on it.
+    pub fn type_neighbourhood_by_index(
+        &self,
+        type_index: TypeIndex,
+        include_base_classes: bool,
+    ) -> Result<TypeGraph> {
+        // Outgoing references
+        let mut type_graph = self.type_graph_by_index(type_index, Some(1), include_base_classes)?;
+
+        // Incoming references, only from complete types
+        let mut xrefs = self.type_xrefs(type_index)?;
+        xrefs.sort();
+        xrefs.dedup();
+        let type_names: HashMap<TypeIndex, String> = self
+            .complete_type_list
+            .iter()
+            .filter(|(_, xref_type_index, _)| {
+                xrefs
+                    .iter()
+                    .any(|(xref_index, _)| xref_index == xref_type_index)
+            })
+            .map(|(type_name, type_index, _)| (*type_index, type_name.clone()))
+            .collect();
+        let type_finder = self.complete_type_finder()?;
+        for (xref_type_index, is_pointer) in xrefs {
+            let Some(type_name) = type_names.get(&xref_type_index) else {
+                continue;
+            };
+            let kind = if is_pointer {
+                TypeDependencyKind::Pointer
+            } else if self
+                .base_class_type_indices(&type_finder, xref_type_index)?
+                .contains(&type_index)
+            {
+                if !include_base_classes {
+                    continue;
+                }
+                TypeDependencyKind::BaseClass
+            } else {
+                TypeDependencyKind::Embedded
+            };
+            let edge = TypeGraphEdge {
+                from: xref_type_index,
+                to: type_index,
+                kind,
+            };
+            if type_graph.edges.contains(&edge) {
+                continue;
+            }
+            type_graph.edges.push(edge);
+            if !type_graph
+                .nodes
+                .iter()
+                .any(|node| node.index == xref_type_index)
+            {
+                type_graph.nodes.push(TypeGraphNode {
+                    index: xref_type_index,
+                    name: type_name.clone(),
+                    depth: 1,
+                });
+            }
+        }
+        type_graph
+            .nodes
+            .sort_by(|lhs, rhs| lhs.depth.cmp(&rhs.depth).then(lhs.index.cmp(&rhs.index)));
+
+        Ok(type_graph)
+    }
+
     pub fn type_list(&self) -> TypeListExView {
         self.complete_type_list.iter().collect()
     }
@@ -554,6 +1244,9 @@ where
         symbol_index: SymbolIndex,
         primitives_flavor: PrimitiveReconstructionFlavor,
         print_access_specifiers: AccessSpecifierReconstructionFlavor,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
     ) -> Result<String> {
         // Populate our `TypeFinder`
         let mut type_finder = self.type_information.finder();
@@ -563,6 +1256,12 @@ where
                 type_finder.update(&type_iter);
             }
         }
+        // Note: the IPI stream is only needed to name inlined functions
+        let id_finder = if print_inlinees {
+            self.id_finder()?
+        } else {
+            None
+        };
 
         // Check which module the symbol is from
         if symbol_index.0 == GLOBAL_MODULE_INDEX {
@@ -573,9 +1272,14 @@ where
                     return Ok(self
                         .reconstruct_symbol(
                             &type_finder,
+                            id_finder.as_ref(),
+                            None,
                             &symbol,
                             primitives_flavor,
                             print_access_specifiers,
+                            print_locals,
+                            print_locations,
+                            print_inlinees,
                         )
                         .unwrap_or_default());
                 }
@@ -590,9 +1294,14 @@ where
                         return Ok(self
                             .reconstruct_symbol(
                                 &type_finder,
+                                id_finder.as_ref(),
+                                Some(&module_info),
                                 &symbol,
                                 primitives_flavor,
                                 print_access_specifiers,
+                                print_locals,
+                                print_locations,
+                                print_inlinees,
                             )
                             .unwrap_or_default());
                     }
@@ -611,6 +1320,9 @@ where
         symbol_name: &str,
         primitives_flavor: PrimitiveReconstructionFlavor,
         print_access_specifiers: AccessSpecifierReconstructionFlavor,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
     ) -> Result<String> {
         // Populate our `TypeFinder`
         let mut type_finder = self.type_information.finder();
@@ -620,6 +1332,12 @@ where
                 type_finder.update(&type_iter);
             }
         }
+        // Note: the IPI stream is only needed to name inlined functions
+        let id_finder = if print_inlinees {
+            self.id_finder()?
+        } else {
+            None
+        };
 
         // Global symbols
         let mut symbol_table = self.global_symbols.iter();
@@ -630,9 +1348,14 @@ where
                         return Ok(self
                             .reconstruct_symbol(
                                 &type_finder,
+                                id_finder.as_ref(),
+                                None,
                                 &symbol,
                                 primitives_flavor,
                                 print_access_specifiers,
+                                print_locals,
+                                print_locations,
+                                print_inlinees,
                             )
                             .unwrap_or_default());
                     }
@@ -654,9 +1377,14 @@ where
                                     return Ok(self
                                         .reconstruct_symbol(
                                             &type_finder,
+                                            id_finder.as_ref(),
+                                            Some(&module_info),
                                             &symbol,
                                             primitives_flavor,
                                             print_access_specifiers,
+                                            print_locals,
+                                            print_locations,
+                                            print_inlinees,
                                         )
                                         .unwrap_or_default());
                                 }
@@ -677,6 +1405,9 @@ where
         &self,
         primitives_flavor: PrimitiveReconstructionFlavor,
         print_access_specifiers: AccessSpecifierReconstructionFlavor,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
     ) -> Result<String> {
         // Populate our `TypeFinder`
         let mut type_finder = self.type_information.finder();
@@ -686,6 +1417,12 @@ where
                 type_finder.update(&type_iter);
             }
         }
+        // Note: the IPI stream is only needed to name inlined functions
+        let id_finder = if print_inlinees {
+            self.id_finder()?
+        } else {
+            None
+        };
 
         let mut reconstruction_output = String::new();
 
@@ -696,9 +1433,14 @@ where
                 if get_symbol_name(&symbol_data).is_some() {
                     if let Some(reconstructed_symbol) = self.reconstruct_symbol(
                         &type_finder,
+                        id_finder.as_ref(),
+                        None,
                         &symbol,
                         primitives_flavor,
                         print_access_specifiers,
+                        print_locals,
+                        print_locations,
+                        print_inlinees,
                     ) {
                         writeln!(&mut reconstruction_output, "{}", reconstructed_symbol)?;
                     }
@@ -718,9 +1460,14 @@ where
                             if get_symbol_name(&symbol_data).is_some() {
                                 if let Some(reconstructed_symbol) = self.reconstruct_symbol(
                                     &type_finder,
+                                    id_finder.as_ref(),
+                                    Some(&module_info),
                                     &symbol,
                                     primitives_flavor,
                                     print_access_specifiers,
+                                    print_locals,
+                                    print_locations,
+                                    print_inlinees,
                                 ) {
                                     writeln!(
                                         &mut reconstruction_output,
@@ -738,52 +1485,14 @@ where
         Ok(reconstruction_output)
     }
 
-    pub fn reconstruct_module_by_path(
-        &self,
-        module_path: &str,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    ) -> Result<String> {
-        // Find index for module
-        let mut modules = self.debug_information.modules()?;
-        let module_index = modules.position(|module| Ok(module.module_name() == module_path))?;
-
-        match module_index {
-            None => Err(ResymCoreError::ModuleNotFoundError(format!(
-                "Module '{}' not found",
-                module_path
-            ))),
-            Some(module_index) => self.reconstruct_module_by_index(
-                module_index,
-                primitives_flavor,
-                print_access_specifiers,
-            ),
-        }
-    }
-
-    pub fn reconstruct_module_by_index(
+    /// Return the parameters and local variables of the procedure at
+    /// `symbol_index`, with the address ranges in which they're live and
+    /// their location in each of these ranges.
+    pub fn procedure_variables(
         &self,
-        module_index: usize,
+        symbol_index: SymbolIndex,
         primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    ) -> Result<String> {
-        let mut modules = self.debug_information.modules()?;
-        let module = modules.nth(module_index)?.ok_or_else(|| {
-            ResymCoreError::ModuleInfoNotFoundError(format!("Module #{} not found", module_index))
-        })?;
-
-        let module_info = self
-            .pdb
-            .write()
-            .expect("lock shouldn't be poisoned")
-            .module_info(&module)?
-            .ok_or_else(|| {
-                ResymCoreError::ModuleInfoNotFoundError(format!(
-                    "No module information present for '{}'",
-                    module.object_file_name()
-                ))
-            })?;
-
+    ) -> Result<Vec<ProcedureVariable>> {
         // Populate our `TypeFinder`
         let mut type_finder = self.type_information.finder();
         {
@@ -793,102 +1502,1352 @@ where
             }
         }
 
-        let mut result = String::default();
-        module_info.symbols()?.for_each(|symbol| {
-            let reconstructed_symbol = self.reconstruct_symbol(
-                &type_finder,
-                &symbol,
-                primitives_flavor,
-                print_access_specifiers,
-            );
-            if let Some(reconstructed_symbol) = reconstructed_symbol {
-                result += &reconstructed_symbol;
-                result.push('\n');
+        // Note: procedures are always declared in modules
+        let module = self
+            .debug_information
+            .modules()?
+            .nth(symbol_index.0)?
+            .ok_or_else(|| {
+                ResymCoreError::SymbolNotFoundError(format!("Symbol #{:?} not found", symbol_index))
+            })?;
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let module_info = pdb.module_info(&module)?.ok_or_else(|| {
+            ResymCoreError::SymbolNotFoundError(format!("Symbol #{:?} not found", symbol_index))
+        })?;
+        let procedure_index = pdb::SymbolIndex(symbol_index.1);
+        let procedure = match module_info
+            .symbols_at(procedure_index)?
+            .next()?
+            .map(|symbol| symbol.parse())
+        {
+            Some(Ok(pdb::SymbolData::Procedure(procedure))) => procedure,
+            _ => {
+                return Err(ResymCoreError::SymbolNotFoundError(format!(
+                    "Procedure #{:?} not found",
+                    symbol_index
+                )))
             }
+        };
 
-            Ok(())
-        })?;
+        let parameter_count = match type_finder
+            .find(procedure.type_index)
+            .and_then(|type_item| type_item.parse())
+        {
+            Ok(pdb::TypeData::Procedure(data)) => Some((data.argument_list, false)),
+            Ok(pdb::TypeData::MemberFunction(data)) => {
+                Some((data.argument_list, data.this_pointer_type.is_some()))
+            }
+            _ => None,
+        }
+        .and_then(|(argument_list, has_this_pointer)| {
+            match type_finder.find(argument_list).ok()?.parse().ok()? {
+                pdb::TypeData::ArgumentList(data) => Some(
+                    data.arguments
+                        .iter()
+                        .filter(|type_index| type_index.0 != 0)
+                        .count()
+                        + usize::from(has_this_pointer),
+                ),
+                _ => None,
+            }
+        })
+        .unwrap_or_default();
+        let procedure_scope = ProcedureScope::parse(
+            &module_info,
+            procedure_index,
+            parameter_count,
+            self.machine_type,
+        )?;
 
-        Ok(result)
+        let mut procedure_variables = vec![];
+        let mut scopes_to_visit = vec![&procedure_scope];
+        while let Some(scope) = scopes_to_visit.pop() {
+            for variable in &scope.variables {
+                let (type_left, type_right) = type_name(
+                    &type_finder,
+                    &self.forwarder_to_complete_type,
+                    variable.type_index,
+                    &primitives_flavor,
+                    &mut pdb_types::NeededTypeSet::new(),
+                )?;
+                procedure_variables.push(ProcedureVariable {
+                    name: variable.name.clone(),
+                    type_name: format!("{type_left}{type_right}"),
+                    is_parameter: variable.is_parameter,
+                    live_ranges: self.variable_live_ranges(scope, variable),
+                });
+            }
+            // Visit blocks in declaration order
+            scopes_to_visit.extend(scope.blocks.iter().rev());
+        }
+
+        Ok(procedure_variables)
     }
 
-    fn reconstruct_type_by_type_index_internal(
-        &self,
-        type_finder: &pdb::TypeFinder,
-        type_index: TypeIndex,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
-        reconstruct_dependencies: bool,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
-        ignore_std_types: bool,
+    /// Return information identifying the PDB (e.g., GUID and age) and a
+    /// summary of its content.
+    pub fn metadata(&self) -> Result<PdbMetadata> {
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let pdb_information = pdb.pdb_information()?;
+        let pdb_version = pdb
+            .raw_stream(pdb::StreamIndex(PDB_STREAM_INDEX))?
+            .and_then(|stream| stream.as_slice().get(..4).map(|version| version.to_vec()))
+            .map_or(0, |version| {
+                u32::from_le_bytes([version[0], version[1], version[2], version[3]])
+            });
+        drop(pdb);
+
+        let stream_references = self.stream_references()?;
+        let named_stream = |name: &str| {
+            stream_references
+                .named_streams
+                .iter()
+                .find(|(stream_name, _)| stream_name == name)
+                .map(|(_, stream_index)| *stream_index)
+        };
+        let dbi_header = &stream_references.dbi_header;
+        let known_streams = [
+            ("PDB", Some(PDB_STREAM_INDEX)),
+            ("TPI", Some(TPI_STREAM_INDEX)),
+            ("DBI", Some(DBI_STREAM_INDEX)),
+            ("IPI", Some(IPI_STREAM_INDEX)),
+            ("/names", named_stream("/names")),
+            ("Global symbols", dbi_header.global_symbols_stream),
+            ("Public symbols", dbi_header.public_symbols_stream),
+            ("Symbol records", dbi_header.symbol_records_stream),
+            (
+                "Section headers",
+                dbi_header.debug_stream(msf::DBI_SECTION_HEADERS_STREAM),
+            ),
+            (
+                "OMAP to source",
+                dbi_header.debug_stream(msf::DBI_OMAP_TO_SOURCE_STREAM),
+            ),
+            (
+                "OMAP from source",
+                dbi_header.debug_stream(msf::DBI_OMAP_FROM_SOURCE_STREAM),
+            ),
+            ("FPO", dbi_header.debug_stream(msf::DBI_FPO_STREAM)),
+            ("New FPO", dbi_header.debug_stream(msf::DBI_NEW_FPO_STREAM)),
+            ("Source link", named_stream("sourcelink")),
+            ("Source server", named_stream("srcsrv")),
+        ];
+        let streams = known_streams
+            .into_iter()
+            .map(|(name, index)| match &self.stream_sizes {
+                Some(_) => {
+                    let size = index.and_then(|index| self.stream_size(index));
+                    KnownStreamInfo {
+                        name: name.to_string(),
+                        // Streams can be referenced but empty
+                        index: size.and(index),
+                        size: Some(size.unwrap_or_default()),
+                    }
+                }
+                None => KnownStreamInfo {
+                    name: name.to_string(),
+                    index,
+                    size: None,
+                },
+            })
+            .collect();
+
+        let mut symbol_count = 0;
+        let mut global_symbols = self.global_symbols.iter();
+        while global_symbols.next()?.is_some() {
+            symbol_count += 1;
+        }
+
+        Ok(PdbMetadata {
+            guid: format!("{:X}", pdb_information.guid),
+            age: pdb_information.age,
+            dbi_age: self.debug_information.age(),
+            signature: pdb_information.signature,
+            machine_type: self.machine_type.to_string(),
+            pdb_version,
+            dbi_version: dbi_header.version,
+            type_count: self.type_information.len(),
+            id_count: self
+                .id_information
+                .as_ref()
+                .map_or(0, |id_information| id_information.len()),
+            symbol_count,
+            module_count: self.debug_information.modules()?.count()?,
+            stream_count: self.stream_sizes.as_ref().map(Vec::len),
+            total_stream_size: self.stream_sizes.as_ref().map(|stream_sizes| {
+                stream_sizes
+                    .iter()
+                    .map(|size| size.unwrap_or_default() as u64)
+                    .sum()
+            }),
+            streams,
+        })
+    }
+
+    /// Return the streams referenced by the named stream map and by the DBI
+    /// stream's header.
+    fn stream_references(&self) -> Result<StreamReferences> {
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let named_streams = pdb
+            .pdb_information()?
+            .stream_names()?
+            .iter()
+            .map(|stream_name| {
+                (
+                    stream_name.name.to_string().into_owned(),
+                    stream_name.stream_id.0,
+                )
+            })
+            .collect();
+        let dbi_header = match pdb.raw_stream(pdb::StreamIndex(DBI_STREAM_INDEX))? {
+            Some(dbi_stream) => DbiStreamHeader::parse(dbi_stream.as_slice())?,
+            None => {
+                return Err(ResymCoreError::PdbError(pdb::Error::StreamNotFound(
+                    DBI_STREAM_INDEX.into(),
+                )))
+            }
+        };
+        Ok(StreamReferences {
+            named_streams,
+            dbi_header,
+        })
+    }
+
+    /// Return the size of the given MSF stream, `None` if the stream isn't
+    /// present.
+    fn stream_size(&self, stream_index: u16) -> Option<u32> {
+        self.stream_sizes
+            .as_ref()?
+            .get(stream_index as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Return the raw records of the TPI stream, one record per type index.
+    pub fn dump_type_records(&self) -> Result<String> {
+        let mut output = String::new();
+        let mut type_iter = self.type_information.iter();
+        while let Some(item) = type_iter.next()? {
+            records::fmt_type_record(&mut output, &item);
+        }
+
+        Ok(output)
+    }
+
+    /// Return the raw record of the type at `type_index`, followed by the
+    /// records it directly references (e.g., its field list).
+    pub fn dump_type_records_by_index(&self, type_index: TypeIndex) -> Result<String> {
+        let type_finder = self.complete_type_finder()?;
+        let item = type_finder.find(pdb::TypeIndex(type_index))?;
+        let mut output = String::new();
+        records::fmt_type_record(&mut output, &item);
+
+        // Note: primitive types have no record
+        let mut referenced_types: Vec<pdb::TypeIndex> = item
+            .parse()
+            .map(|type_data| records::type_data_references(&type_data))
+            .unwrap_or_default();
+        referenced_types.retain(|referenced_type| referenced_type.0 >= 0x1000);
+        referenced_types.sort();
+        referenced_types.dedup();
+        for referenced_type in referenced_types {
+            records::fmt_type_record(&mut output, &type_finder.find(referenced_type)?);
+        }
+
+        Ok(output)
+    }
+
+    /// Return the raw records of the IPI stream, one record per id index.
+    pub fn dump_id_records(&self) -> Result<String> {
+        let mut output = String::new();
+        let Some(id_information) = &self.id_information else {
+            return Ok(output);
+        };
+        let mut id_iter = id_information.iter();
+        while let Some(item) = id_iter.next()? {
+            records::fmt_id_record(&mut output, &item);
+        }
+
+        Ok(output)
+    }
+
+    /// Return the raw symbol records of each module.
+    pub fn dump_symbol_records(&self) -> Result<String> {
+        let mut output = String::new();
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let mut modules = self.debug_information.modules()?;
+        while let Some(module) = modules.next()? {
+            let _ = writeln!(&mut output, "** Module: \"{}\"\n", module.module_name());
+            let Some(module_info) = pdb.module_info(&module)? else {
+                continue;
+            };
+            let mut module_symbols = module_info.symbols()?;
+            while let Some(symbol) = module_symbols.next()? {
+                records::fmt_symbol_record(&mut output, &symbol, self.machine_type);
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    /// Attribute the size of the image to modules and object files (e.g.,
+    /// static libraries), using section contributions, and to namespaces and
+    /// template families, using the size of procedures and data symbols.
+    pub fn size_report(&self) -> Result<SizeReport> {
+        let mut module_sizes = HashMap::new();
+        let mut object_file_sizes = HashMap::new();
+        for section_contribution in self.section_contributions.iter() {
+            let size = section_contribution.size as u64;
+            add_to_size_group(&mut module_sizes, &section_contribution.module_name, size);
+            // Note: contributions of the linker don't come from object files
+            let object_file_name = if section_contribution.object_file_name.is_empty() {
+                &section_contribution.module_name
+            } else {
+                &section_contribution.object_file_name
+            };
+            add_to_size_group(&mut object_file_sizes, object_file_name, size);
+        }
+
+        let class_names = self.class_names();
+        let mut namespace_sizes = HashMap::new();
+        let mut template_family_sizes = HashMap::new();
+        for symbol_size in self.symbol_sizes()? {
+            add_to_size_group(
+                &mut namespace_sizes,
+                symbol_size_namespace(&symbol_size.name, &class_names),
+                symbol_size.size,
+            );
+            if let Some(template_family) = pdb_types::template_family(&symbol_size.name) {
+                add_to_size_group(
+                    &mut template_family_sizes,
+                    &template_family,
+                    symbol_size.size,
+                );
+            }
+        }
+
+        Ok(SizeReport {
+            modules: sorted_size_report_entries(module_sizes),
+            object_files: sorted_size_report_entries(object_file_sizes),
+            namespaces: sorted_size_report_entries(namespace_sizes),
+            template_families: sorted_size_report_entries(template_family_sizes),
+        })
+    }
+
+    /// Return the names of the complete classes and unions declared in the PDB.
+    pub(crate) fn class_names(&self) -> HashSet<&str> {
+        self.complete_type_list
+            .iter()
+            .filter(|(_, _, kind)| matches!(kind, TypeKind::Class | TypeKind::Union))
+            .map(|(name, _, _)| name.as_str())
+            .collect()
+    }
+
+    /// Return the size of procedures and data symbols. Symbols present in
+    /// several streams, or folded together by the linker, are only returned
+    /// once. Symbols with no size are ignored.
+    pub fn symbol_sizes(&self) -> Result<Vec<SymbolSize>> {
+        // Symbols indexed by address
+        let mut symbol_sizes: HashMap<pdb::PdbInternalSectionOffset, SymbolSize> = HashMap::new();
+        let mut add_symbol_size = |offset: pdb::PdbInternalSectionOffset,
+                                   name: pdb::RawString,
+                                   kind: SymbolKind,
+                                   size: u64| {
+            symbol_sizes.entry(offset).or_insert_with(|| SymbolSize {
+                name: name.to_string().into_owned(),
+                kind,
+                rva: symbol_rva(&offset, &self.sections),
+                size,
+            });
+        };
+
+        let type_finder = self.complete_type_finder()?;
+        let data_size = |data: &pdb::DataSymbol| -> u64 {
+            let type_index = self
+                .forwarder_to_complete_type
+                .get(&data.type_index)
+                .map_or(data.type_index, |type_index| *type_index);
+            pdb_types::type_size(&type_finder, type_index).unwrap_or_default() as u64
+        };
+        {
+            let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+            let mut modules = self.debug_information.modules()?;
+            while let Some(module) = modules.next()? {
+                let Some(module_info) = pdb.module_info(&module)? else {
+                    continue;
+                };
+                let mut module_symbols = module_info.symbols()?;
+                while let Some(symbol) = module_symbols.next()? {
+                    match symbol.parse() {
+                        Ok(pdb::SymbolData::Procedure(procedure)) => add_symbol_size(
+                            procedure.offset,
+                            procedure.name,
+                            SymbolKind::Function,
+                            procedure.len as u64,
+                        ),
+                        Ok(pdb::SymbolData::Data(data)) => add_symbol_size(
+                            data.offset,
+                            data.name,
+                            SymbolKind::Variable,
+                            data_size(&data),
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        let mut global_symbols = self.global_symbols.iter();
+        while let Some(symbol) = global_symbols.next()? {
+            if let Ok(pdb::SymbolData::Data(data)) = symbol.parse() {
+                add_symbol_size(
+                    data.offset,
+                    data.name,
+                    SymbolKind::Variable,
+                    data_size(&data),
+                );
+            }
+        }
+
+        Ok(symbol_sizes
+            .into_values()
+            .filter(|symbol_size| symbol_size.size != 0)
+            .collect())
+    }
+
+    /// Return the source files referenced by the line information of the
+    /// modules, with their checksum and the modules which reference them.
+    pub fn source_file_list(&self) -> Result<Vec<SourceFileInfo>> {
+        let Some(string_table) = &self.string_table else {
+            // Source file names are stored in the string table
+            return Ok(vec![]);
+        };
+
+        let mut source_files: BTreeMap<String, SourceFileInfo> = BTreeMap::new();
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let mut modules = self.debug_information.modules()?.enumerate();
+        while let Some((module_index, module)) = modules.next()? {
+            // Note: modules and files which cannot be read are skipped, so that
+            // a single corrupted module doesn't prevent listing the others
+            let module_info = match pdb.module_info(&module) {
+                Ok(Some(module_info)) => module_info,
+                Ok(None) => continue,
+                Err(err) => {
+                    log::warn!("Failed to read module '{}': {err}", module.module_name());
+                    continue;
+                }
+            };
+            let line_program = match module_info.line_program() {
+                Ok(line_program) => line_program,
+                Err(err) => {
+                    log::warn!(
+                        "Failed to read the line information of module '{}': {err}",
+                        module.module_name()
+                    );
+                    continue;
+                }
+            };
+            let mut files = line_program.files();
+            loop {
+                let file_info = match files.next() {
+                    Ok(Some(file_info)) => file_info,
+                    Ok(None) => break,
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to read the source files of module '{}': {err}",
+                            module.module_name()
+                        );
+                        break;
+                    }
+                };
+                let file_path = match file_info.name.to_string_lossy(string_table) {
+                    Ok(file_path) => file_path.into_owned(),
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to read the name of a source file of module '{}': {err}",
+                            module.module_name()
+                        );
+                        continue;
+                    }
+                };
+                let source_file = source_files.entry(file_path.clone()).or_insert_with(|| {
+                    let (checksum_kind, checksum) = match file_info.checksum {
+                        pdb::FileChecksum::None => (ChecksumKind::None, &[][..]),
+                        pdb::FileChecksum::Md5(checksum) => (ChecksumKind::Md5, checksum),
+                        pdb::FileChecksum::Sha1(checksum) => (ChecksumKind::Sha1, checksum),
+                        pdb::FileChecksum::Sha256(checksum) => (ChecksumKind::Sha256, checksum),
+                    };
+                    SourceFileInfo {
+                        file_path,
+                        checksum_kind,
+                        checksum: checksum.iter().fold(String::default(), |mut acc, byte| {
+                            let _ = write!(&mut acc, "{byte:02x}");
+                            acc
+                        }),
+                        modules: vec![],
+                    }
+                });
+                // Note: a module may reference the same file more than once
+                if !source_file
+                    .modules
+                    .iter()
+                    .any(|(_, index)| *index == module_index)
+                {
+                    source_file
+                        .modules
+                        .push((module.module_name().into_owned(), module_index));
+                }
+            }
+        }
+
+        Ok(source_files.into_values().collect())
+    }
+
+    /// Return the procedures whose code comes from the source file at
+    /// `file_path`. The file can also be designated by the trailing
+    /// components of its path (e.g., its name).
+    pub fn source_file_functions(&self, file_path: &str) -> Result<SymbolList> {
+        let Some(string_table) = &self.string_table else {
+            // Source file names are stored in the string table
+            return Ok(vec![]);
+        };
+
+        let mut functions = vec![];
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let mut modules = self.debug_information.modules()?.enumerate();
+        while let Some((module_index, module)) = modules.next()? {
+            // Note: modules and files which cannot be read are skipped, so that
+            // a single corrupted module doesn't prevent searching the others
+            let module_info = match pdb.module_info(&module) {
+                Ok(Some(module_info)) => module_info,
+                Ok(None) => continue,
+                Err(err) => {
+                    log::warn!("Failed to read module '{}': {err}", module.module_name());
+                    continue;
+                }
+            };
+            let line_program = match module_info.line_program() {
+                Ok(line_program) => line_program,
+                Err(err) => {
+                    log::warn!(
+                        "Failed to read the line information of module '{}': {err}",
+                        module.module_name()
+                    );
+                    continue;
+                }
+            };
+            // Cache whether each of the module's files matches `file_path`
+            let mut file_matches: HashMap<pdb::FileIndex, bool> = HashMap::new();
+            let mut file_index_matches = |file_index: pdb::FileIndex| -> bool {
+                *file_matches.entry(file_index).or_insert_with(|| {
+                    line_program
+                        .get_file_info(file_index)
+                        .and_then(|file_info| file_info.name.to_string_lossy(string_table))
+                        .map(|source_path| source_path_matches(&source_path, file_path))
+                        .unwrap_or_else(|err| {
+                            log::warn!(
+                                "Failed to read source file #{} of module '{}': {err}",
+                                file_index.0,
+                                module.module_name()
+                            );
+                            false
+                        })
+                })
+            };
+
+            let mut module_symbols = match module_info.symbols() {
+                Ok(module_symbols) => module_symbols,
+                Err(err) => {
+                    log::warn!(
+                        "Failed to read the symbols of module '{}': {err}",
+                        module.module_name()
+                    );
+                    continue;
+                }
+            };
+            loop {
+                let symbol = match module_symbols.next() {
+                    Ok(Some(symbol)) => symbol,
+                    Ok(None) => break,
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to read the symbols of module '{}': {err}",
+                            module.module_name()
+                        );
+                        break;
+                    }
+                };
+                let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() else {
+                    continue;
+                };
+                let mut lines = line_program.lines_for_symbol(procedure.offset);
+                loop {
+                    match lines.next() {
+                        Ok(Some(line)) if file_index_matches(line.file_index) => {
+                            functions.push((
+                                procedure.name.to_string().into_owned(),
+                                (module_index, symbol.index().0),
+                            ));
+                            break;
+                        }
+                        Ok(Some(_)) => {}
+                        Ok(None) => break,
+                        Err(err) => {
+                            log::warn!("Failed to read the lines of '{}': {err}", procedure.name);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(functions)
+    }
+
+    /// Return the tree of functions inlined in the procedure at
+    /// `symbol_index`, with their source location and code ranges.
+    pub fn procedure_inlined_calls(&self, symbol_index: SymbolIndex) -> Result<Vec<InlinedCall>> {
+        // Populate our `TypeFinder`
+        let mut type_finder = self.type_information.finder();
+        {
+            let mut type_iter = self.type_information.iter();
+            while (type_iter.next()?).is_some() {
+                type_finder.update(&type_iter);
+            }
+        }
+        let id_finder = self.id_finder()?;
+
+        // Note: procedures are always declared in modules
+        let module = self
+            .debug_information
+            .modules()?
+            .nth(symbol_index.0)?
+            .ok_or_else(|| {
+                ResymCoreError::SymbolNotFoundError(format!("Symbol #{:?} not found", symbol_index))
+            })?;
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let module_info = pdb.module_info(&module)?.ok_or_else(|| {
+            ResymCoreError::SymbolNotFoundError(format!("Symbol #{:?} not found", symbol_index))
+        })?;
+        let procedure_index = pdb::SymbolIndex(symbol_index.1);
+        let procedure = match module_info
+            .symbols_at(procedure_index)?
+            .next()?
+            .map(|symbol| symbol.parse())
+        {
+            Some(Ok(pdb::SymbolData::Procedure(procedure))) => procedure,
+            _ => {
+                return Err(ResymCoreError::SymbolNotFoundError(format!(
+                    "Procedure #{:?} not found",
+                    symbol_index
+                )))
+            }
+        };
+
+        self.procedure_inlined_calls_internal(
+            &type_finder,
+            id_finder.as_ref(),
+            &module_info,
+            &module_inlinees(&module_info)?,
+            procedure_index,
+            &procedure,
+        )
+    }
+
+    /// Return the procedures in which the function named `function_name`
+    /// has been inlined, directly or not.
+    pub fn find_inline_call_sites(&self, function_name: &str) -> Result<Vec<InlineCallSite>> {
+        // Populate our `TypeFinder`
+        let mut type_finder = self.type_information.finder();
+        {
+            let mut type_iter = self.type_information.iter();
+            while (type_iter.next()?).is_some() {
+                type_finder.update(&type_iter);
+            }
+        }
+        let id_finder = self.id_finder()?;
+
+        let mut inline_call_sites = vec![];
+        let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+        let mut modules = self.debug_information.modules()?.enumerate();
+        while let Some((module_index, module)) = modules.next()? {
+            let Some(module_info) = pdb.module_info(&module)? else {
+                continue;
+            };
+            let module_inlinees = module_inlinees(&module_info)?;
+            if module_inlinees.is_empty() {
+                // Nothing has been inlined in this module
+                continue;
+            }
+
+            let mut module_symbols = module_info.symbols()?;
+            while let Some(symbol) = module_symbols.next()? {
+                let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() else {
+                    continue;
+                };
+                let inlined_calls = self.procedure_inlined_calls_internal(
+                    &type_finder,
+                    id_finder.as_ref(),
+                    &module_info,
+                    &module_inlinees,
+                    symbol.index(),
+                    &procedure,
+                )?;
+
+                let mut calls_to_visit: Vec<&InlinedCall> = inlined_calls.iter().rev().collect();
+                while let Some(inlined_call) = calls_to_visit.pop() {
+                    if inlined_call.function_name == function_name {
+                        inline_call_sites.push(InlineCallSite {
+                            caller_name: procedure.name.to_string().into_owned(),
+                            caller_index: (module_index, symbol.index().0),
+                            inlined_call: inlined_call.clone(),
+                        });
+                    }
+                    calls_to_visit.extend(inlined_call.inlined_calls.iter().rev());
+                }
+            }
+        }
+
+        Ok(inline_call_sites)
+    }
+
+    /// Symbolize the given RVAs: find the procedure (or public symbol)
+    /// containing each address, the corresponding source file and line, and
+    /// the functions inlined at this address.
+    pub fn symbolize_addresses(&self, rvas: &[u32]) -> Result<Vec<SymbolizedAddress>> {
+        let mut symbolized_addresses: Vec<SymbolizedAddress> = rvas
+            .iter()
+            .map(|rva| SymbolizedAddress {
+                rva: *rva,
+                frames: vec![],
+            })
+            .collect();
+        // Addresses sorted by section offset, to look up the addresses
+        // contained in each function
+        let mut sorted_address_offsets: Vec<(pdb::PdbInternalSectionOffset, usize)> = rvas
+            .iter()
+            .enumerate()
+            .filter_map(|(address_index, rva)| {
+                Some((rva_to_section_offset(*rva, &self.sections)?, address_index))
+            })
+            .collect();
+        sorted_address_offsets
+            .sort_by_key(|(address_offset, _)| section_offset_key(address_offset));
+        // Return the addresses located in the given range, with their index
+        let address_offsets_in_range = |range_start: pdb::PdbInternalSectionOffset,
+                                        range_len: u32| {
+            let first_address = sorted_address_offsets.partition_point(|(address_offset, _)| {
+                section_offset_key(address_offset) < section_offset_key(&range_start)
+            });
+            sorted_address_offsets[first_address..]
+                .iter()
+                .take_while(move |(address_offset, _)| {
+                    offset_in_range(address_offset, &range_start, range_len).is_some()
+                })
+        };
+
+        // Populate our `TypeFinder` and `IdFinder`, used to name inlined functions
+        let mut type_finder = self.type_information.finder();
+        {
+            let mut type_iter = self.type_information.iter();
+            while (type_iter.next()?).is_some() {
+                type_finder.update(&type_iter);
+            }
+        }
+        let id_finder = self.id_finder()?;
+
+        // Procedures
+        {
+            let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+            let mut modules = self.debug_information.modules()?;
+            while let Some(module) = modules.next()? {
+                let Some(module_info) = pdb.module_info(&module)? else {
+                    continue;
+                };
+                // Note: line information is only read for modules containing
+                // some of the addresses
+                let mut module_lines = None;
+                let mut module_symbols = module_info.symbols()?;
+                while let Some(symbol) = module_symbols.next()? {
+                    let Ok(pdb::SymbolData::Procedure(procedure)) = symbol.parse() else {
+                        continue;
+                    };
+                    for (address_offset, address_index) in
+                        address_offsets_in_range(procedure.offset, procedure.len)
+                    {
+                        let symbolized_address = &mut symbolized_addresses[*address_index];
+                        if !symbolized_address.frames.is_empty() {
+                            continue;
+                        }
+                        if module_lines.is_none() {
+                            module_lines =
+                                Some((module_info.line_program()?, module_inlinees(&module_info)?));
+                        }
+                        let Some((line_program, inlinees)) = &module_lines else {
+                            continue;
+                        };
+                        symbolized_address.frames = self.procedure_frames(
+                            &type_finder,
+                            id_finder.as_ref(),
+                            &module_info,
+                            line_program,
+                            inlinees,
+                            symbol.index(),
+                            &procedure,
+                            address_offset,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        // Public symbols, for addresses which aren't described by procedures
+        if symbolized_addresses
+            .iter()
+            .all(|symbolized_address| !symbolized_address.frames.is_empty())
+        {
+            return Ok(symbolized_addresses);
+        }
+        let mut public_symbols: Vec<(pdb::PdbInternalSectionOffset, String)> = vec![];
+        let mut symbol_table = self.global_symbols.iter();
+        while let Some(symbol) = symbol_table.next()? {
+            if let Ok(pdb::SymbolData::Public(data)) = symbol.parse() {
+                public_symbols.push((data.offset, data.name.to_string().into_owned()));
+            }
+        }
+        // Note: the first of the symbols sharing an address is kept
+        public_symbols.sort_by_key(|(symbol_offset, _)| section_offset_key(symbol_offset));
+        public_symbols.dedup_by_key(|(symbol_offset, _)| section_offset_key(symbol_offset));
+        for (public_symbol_index, (symbol_offset, symbol_name)) in public_symbols.iter().enumerate()
+        {
+            // Public symbols don't have a size, they're assumed to extend up
+            // to the next public symbol and to the end of the section
+            // contribution containing them
+            let next_symbol_end = public_symbols
+                .get(public_symbol_index + 1)
+                .filter(|(next_symbol_offset, _)| {
+                    next_symbol_offset.section == symbol_offset.section
+                })
+                .map(|(next_symbol_offset, _)| next_symbol_offset.offset - symbol_offset.offset);
+            let contribution_end = symbol_rva(symbol_offset, &self.sections).and_then(|rva| {
+                let contribution = self.section_contributions.find_by_rva(rva)?;
+                Some(contribution.rva + contribution.size - rva)
+            });
+            let symbol_len = match (next_symbol_end, contribution_end) {
+                (Some(next_symbol_end), Some(contribution_end)) => {
+                    next_symbol_end.min(contribution_end)
+                }
+                (Some(symbol_len), None) | (None, Some(symbol_len)) => symbol_len,
+                // The symbol's extent is unknown
+                (None, None) => continue,
+            };
+
+            for (address_offset, address_index) in
+                address_offsets_in_range(*symbol_offset, symbol_len)
+            {
+                let symbolized_address = &mut symbolized_addresses[*address_index];
+                if !symbolized_address.frames.is_empty() {
+                    continue;
+                }
+                let function_name =
+                    msvc_demangler::demangle(symbol_name, msvc_demangler::DemangleFlags::NAME_ONLY)
+                        .unwrap_or_else(|_| symbol_name.clone());
+                symbolized_address.frames.push(SymbolizedFrame {
+                    function_name,
+                    function_offset: offset_in_range(address_offset, symbol_offset, symbol_len),
+                    source_location: None,
+                    is_inlined: false,
+                });
+            }
+        }
+
+        Ok(symbolized_addresses)
+    }
+
+    pub fn reconstruct_module_by_path(
+        &self,
+        module_path: &str,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        print_access_specifiers: AccessSpecifierReconstructionFlavor,
+    ) -> Result<String> {
+        // Find index for module
+        let mut modules = self.debug_information.modules()?;
+        let module_index = modules.position(|module| Ok(module.module_name() == module_path))?;
+
+        match module_index {
+            None => Err(ResymCoreError::ModuleNotFoundError(format!(
+                "Module '{}' not found",
+                module_path
+            ))),
+            Some(module_index) => self.reconstruct_module_by_index(
+                module_index,
+                primitives_flavor,
+                print_access_specifiers,
+            ),
+        }
+    }
+
+    pub fn reconstruct_module_by_index(
+        &self,
+        module_index: usize,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        print_access_specifiers: AccessSpecifierReconstructionFlavor,
+    ) -> Result<String> {
+        let mut modules = self.debug_information.modules()?;
+        let module = modules.nth(module_index)?.ok_or_else(|| {
+            ResymCoreError::ModuleInfoNotFoundError(format!("Module #{} not found", module_index))
+        })?;
+
+        let module_info = self
+            .pdb
+            .write()
+            .expect("lock shouldn't be poisoned")
+            .module_info(&module)?
+            .ok_or_else(|| {
+                ResymCoreError::ModuleInfoNotFoundError(format!(
+                    "No module information present for '{}'",
+                    module.object_file_name()
+                ))
+            })?;
+
+        // Populate our `TypeFinder`
+        let mut type_finder = self.type_information.finder();
+        {
+            let mut type_iter = self.type_information.iter();
+            while (type_iter.next()?).is_some() {
+                type_finder.update(&type_iter);
+            }
+        }
+
+        let mut result = String::default();
+        module_info.symbols()?.for_each(|symbol| {
+            let reconstructed_symbol = self.reconstruct_symbol(
+                &type_finder,
+                None,
+                Some(&module_info),
+                &symbol,
+                primitives_flavor,
+                print_access_specifiers,
+                false,
+                false,
+                false,
+            );
+            if let Some(reconstructed_symbol) = reconstructed_symbol {
+                result += &reconstructed_symbol;
+                result.push('\n');
+            }
+
+            Ok(())
+        })?;
+
+        Ok(result)
+    }
+
+    fn reconstruct_type_by_type_index_internal(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_index: TypeIndex,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
+        reconstruct_dependencies: bool,
+        ignore_std_types: bool,
     ) -> Result<ReconstructedType> {
-        let fmt_configuration = DataFormatConfiguration {
-            print_access_specifiers,
-            size_print_flavor,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
+        let mut type_data = pdb_types::Data::new(ignore_std_types);
+
+        // If dependencies aren't needed, only process the given type index and return
+        if !reconstruct_dependencies {
+            let mut needed_types = pdb_types::NeededTypeSet::new();
+            type_data.add(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                &self.virtual_table_records,
+                type_index.into(),
+                &fmt_configuration.primitive_flavor,
+                &mut needed_types,
+            )?;
+
+            if fmt_configuration.print_source_locations {
+                self.add_type_source_locations(&mut type_data);
+            }
+
+            let mut reconstruction_output = String::new();
+            self.reconstruct_type_data(
+                &type_data,
+                type_finder,
+                output_language,
+                fmt_configuration,
+                &Default::default(),
+                &mut reconstruction_output,
+            )?;
+            let needed_types: Vec<TypeIndex> = needed_types.into_iter().map(|e| e.0 .0).collect();
+            let xrefs_from = self.type_list_from_type_indices(&needed_types);
+
+            return Ok((reconstruction_output, xrefs_from));
+        }
+
+        // Add all the needed types iteratively until we're done
+        let (type_dependency_map, xrefs_from) = self.add_type_and_dependencies(
+            type_finder,
+            &mut type_data,
+            type_index,
+            fmt_configuration.primitive_flavor,
+        )?;
+
+        // Deduce type "depth" from the dependency map
+        let type_depth_map =
+            compute_type_depth_map(&type_data, &type_dependency_map, &[type_index]);
+
+        if fmt_configuration.print_source_locations {
+            self.add_type_source_locations(&mut type_data);
+        }
+
+        let mut reconstruction_output = String::new();
+        self.reconstruct_type_data(
+            &type_data,
+            type_finder,
+            output_language,
+            fmt_configuration,
+            &type_depth_map,
+            &mut reconstruction_output,
+        )?;
+
+        Ok((reconstruction_output, xrefs_from))
+    }
+
+    pub fn reconstruct_all_types(
+        &self,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
+        ignore_std_types: bool,
+    ) -> Result<String> {
+        let type_finder = self.complete_type_finder()?;
+        let (mut type_data, type_dependency_map, processed_types) = self.add_all_types(
+            &type_finder,
+            fmt_configuration.primitive_flavor,
+            ignore_std_types,
+        )?;
+
+        // Deduce type "depth" from the dependency map
+        let type_depth_map =
+            compute_type_depth_map(&type_data, &type_dependency_map, &processed_types);
+
+        if fmt_configuration.print_source_locations {
+            self.add_type_source_locations(&mut type_data);
+        }
+
+        let mut reconstruction_output = String::new();
+        self.reconstruct_type_data(
+            &type_data,
+            &type_finder,
+            output_language,
+            fmt_configuration,
+            &type_depth_map,
+            &mut reconstruction_output,
+        )?;
+
+        Ok(reconstruction_output)
+    }
+
+    /// Reconstruct all types into several headers, split according to
+    /// `header_split_flavor`. Headers include the headers defining the types
+    /// they depend on, and forward-declare the types they reference through
+    /// pointers. Headers which would include each other are merged.
+    pub fn reconstruct_all_types_into_headers(
+        &self,
+        header_split_flavor: HeaderSplitFlavor,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
+        ignore_std_types: bool,
+    ) -> Result<ReconstructedFiles> {
+        if !matches!(output_language, OutputLanguage::Cpp | OutputLanguage::C) {
+            return Err(ResymCoreError::InvalidParameterError(
+                "types can only be split into C or C++ headers".to_string(),
+            ));
+        }
+
+        let type_finder = self.complete_type_finder()?;
+        let (mut type_data, type_dependency_map, processed_types) = self.add_all_types(
+            &type_finder,
+            fmt_configuration.primitive_flavor,
+            ignore_std_types,
+        )?;
+
+        // Deduce type "depth" from the dependency map
+        let type_depth_map =
+            compute_type_depth_map(&type_data, &type_dependency_map, &processed_types);
+
+        if fmt_configuration.print_source_locations {
+            self.add_type_source_locations(&mut type_data);
+        }
+
+        let (mut type_header_paths, default_header_path) = match header_split_flavor {
+            HeaderSplitFlavor::SourceFile => (
+                self.source_file_header_paths(&type_data, &type_depth_map),
+                Path::new(UNKNOWN_SOURCE_HEADER_PATH),
+            ),
+            HeaderSplitFlavor::Namespace => (
+                namespace_header_paths(&type_data, &type_depth_map),
+                Path::new(GLOBAL_NAMESPACE_HEADER_PATH),
+            ),
         };
+
+        // Remaining types are declared along with the first type which
+        // depends on them, if any
+        for type_index in type_depth_map.values().flatten() {
+            let Some(header_path) = type_header_paths.get(type_index).cloned() else {
+                continue;
+            };
+            for (dependency_index, is_pointer) in
+                type_dependency_map.get(&type_index.0).into_iter().flatten()
+            {
+                let dependency_index = self.reconstructed_type_index(&type_data, *dependency_index);
+                if !*is_pointer && type_data.type_name_by_index(dependency_index).is_some() {
+                    type_header_paths
+                        .entry(dependency_index)
+                        .or_insert_with(|| header_path.clone());
+                }
+            }
+        }
+        for type_index in type_depth_map.values().flatten() {
+            type_header_paths
+                .entry(*type_index)
+                .or_insert_with(|| default_header_path.to_owned());
+        }
+
+        // Headers which include each other couldn't be compiled on their own,
+        // their types are declared in a single header instead
+        let merged_header_paths = merged_cyclic_header_paths(&self.header_includes(
+            &type_data,
+            &type_dependency_map,
+            &type_header_paths,
+        ));
+        for header_path in type_header_paths.values_mut() {
+            if let Some(merged_header_path) = merged_header_paths.get(header_path) {
+                *header_path = merged_header_path.clone();
+            }
+        }
+
+        self.reconstruct_type_files(
+            &type_data,
+            &type_finder,
+            output_language,
+            fmt_configuration,
+            &type_depth_map,
+            &type_dependency_map,
+            &type_header_paths,
+        )
+    }
+
+    /// Assign types to the header corresponding to the source file declaring
+    /// them, with paths mirroring the original directory layout. Types
+    /// without a known source file are declared along with their enclosing
+    /// type, if any.
+    fn source_file_header_paths(
+        &self,
+        type_data: &pdb_types::Data,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+    ) -> HashMap<pdb::TypeIndex, PathBuf> {
+        // Note: paths are compared case-insensitively, as they're usually
+        // Windows paths
+        let mut header_paths: HashMap<String, PathBuf> = HashMap::new();
+        let mut type_header_paths: HashMap<pdb::TypeIndex, PathBuf> = HashMap::new();
+        for type_index in type_depth_map.values().flatten() {
+            let Some(header_path) = self
+                .type_source_location(type_index.0)
+                .map(|source_location| source_relative_path(&source_location.file_path))
+                .filter(|header_path| header_path.components().next().is_some())
+            else {
+                continue;
+            };
+            let header_path = header_paths
+                .entry(header_path.to_string_lossy().to_lowercase())
+                .or_insert(header_path)
+                .clone();
+            type_header_paths.insert(*type_index, header_path);
+        }
+        for type_index in type_depth_map.values().flatten() {
+            if type_header_paths.contains_key(type_index) {
+                continue;
+            }
+            let mut enclosing_type_index = type_data.enclosing_type_index(*type_index);
+            while let Some(enclosing_index) = enclosing_type_index {
+                if let Some(header_path) = type_header_paths.get(&enclosing_index) {
+                    type_header_paths.insert(*type_index, header_path.clone());
+                    break;
+                }
+                enclosing_type_index = type_data.enclosing_type_index(enclosing_index);
+            }
+        }
+
+        type_header_paths
+    }
+
+    /// Return a `TypeFinder` which can find any type of the PDB.
+    fn complete_type_finder(&self) -> Result<pdb::TypeFinder<'_>> {
+        let mut type_finder = self.type_information.finder();
+        let mut type_iter = self.type_information.iter();
+        while (type_iter.next()?).is_some() {
+            type_finder.update(&type_iter);
+        }
+
+        Ok(type_finder)
+    }
+
+    /// Return the (complete) types the class at `type_index` directly derives
+    /// from. Return an empty list for other types.
+    fn base_class_type_indices(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_index: TypeIndex,
+    ) -> Result<Vec<TypeIndex>> {
+        let mut base_classes = vec![];
+        let mut fields = match type_finder.find(type_index.into())?.parse() {
+            Ok(pdb::TypeData::Class(data)) => data.fields,
+            _ => None,
+        };
+        while let Some(field_list_index) = fields {
+            let Ok(pdb::TypeData::FieldList(field_list)) =
+                type_finder.find(field_list_index)?.parse()
+            else {
+                break;
+            };
+            for field in &field_list.fields {
+                let base_class = match field {
+                    pdb::TypeData::BaseClass(data) => data.base_class,
+                    pdb::TypeData::VirtualBaseClass(data) => data.base_class,
+                    _ => continue,
+                };
+                base_classes.push(
+                    pdb_types::resolve_complete_type_index(
+                        &self.forwarder_to_complete_type,
+                        base_class,
+                    )
+                    .0,
+                );
+            }
+            fields = field_list.continuation;
+        }
+
+        Ok(base_classes)
+    }
+
+    /// Add the type at `type_index` to `type_data`, along with all the types
+    /// it depends on. Return the dependency map of the added types, along with
+    /// the types directly referenced by the requested type.
+    fn add_type_and_dependencies<'t>(
+        &self,
+        type_finder: &pdb::TypeFinder<'t>,
+        type_data: &mut pdb_types::Data<'t>,
+        type_index: TypeIndex,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+    ) -> Result<(TypeDependencyMap, TypeList)> {
+        let mut xrefs_from = vec![];
+        let mut type_dependency_map: TypeDependencyMap = HashMap::new();
+        let dep_start = Instant::now();
+
+        // Add the requested type first
+        let mut types_to_process: VecDeque<TypeIndex> = VecDeque::from([type_index]);
+        let mut processed_type_set = HashSet::new();
+        // Keep processing new types until there's nothing to process
+        while let Some(needed_type_index) = types_to_process.pop_front() {
+            if processed_type_set.contains(&needed_type_index) {
+                // Already processed, continue
+                continue;
+            }
+
+            // Add the type
+            let mut needed_types = pdb_types::NeededTypeSet::new();
+            type_data.add(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                &self.virtual_table_records,
+                needed_type_index.into(),
+                &primitives_flavor,
+                &mut needed_types,
+            )?;
+            // Initialize only once, the first time (i.e., for the requested type)
+            if xrefs_from.is_empty() {
+                let needed_types: Vec<TypeIndex> = needed_types.iter().map(|e| e.0 .0).collect();
+                xrefs_from = self.type_list_from_type_indices(&needed_types);
+            }
+
+            for (type_index, is_pointer) in &needed_types {
+                // Add forward declaration for types referenced by pointers
+                if *is_pointer {
+                    type_data.add_as_forward_declaration(type_finder, *type_index)?;
+                }
+
+                // Update type dependency map
+                if let Some(type_dependency) = type_dependency_map.get_mut(&needed_type_index) {
+                    type_dependency.push((type_index.0, *is_pointer));
+                } else {
+                    type_dependency_map
+                        .insert(needed_type_index, vec![(type_index.0, *is_pointer)]);
+                }
+            }
+            // Update the set of processed types
+            processed_type_set.insert(needed_type_index);
+            // Update the queue of type to process
+            types_to_process.extend(needed_types.into_iter().map(|pair| pair.0 .0));
+        }
+
+        log::debug!(
+            "Dependencies reconstruction took {} ms",
+            dep_start.elapsed().as_millis()
+        );
+
+        Ok((type_dependency_map, xrefs_from))
+    }
+
+    /// Add all the complete types of the PDB to a new `Data` object. Return
+    /// it along with the type dependency map and the list of added types.
+    fn add_all_types<'t>(
+        &self,
+        type_finder: &pdb::TypeFinder<'t>,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        ignore_std_types: bool,
+    ) -> Result<(pdb_types::Data<'t>, TypeDependencyMap, Vec<TypeIndex>)> {
         let mut type_data = pdb_types::Data::new(ignore_std_types);
+        let mut processed_types = Vec::new();
+        let mut type_dependency_map: TypeDependencyMap = HashMap::new();
 
-        // If dependencies aren't needed, only process the given type index and return
-        if !reconstruct_dependencies {
+        // Add the requested types
+        let mut type_iter = self.type_information.iter();
+        while let Some(item) = type_iter.next()? {
             let mut needed_types = pdb_types::NeededTypeSet::new();
-            type_data.add(
+            // Note(ergelet): try to get the complete type's index here.
+            // This avoids adding empty "forward reference" type index which
+            // usually have lower type indices
+            let complete_type_index = self
+                .forwarder_to_complete_type
+                .get(&item.index())
+                .map(|e| *e)
+                .unwrap_or_else(|| item.index());
+            let result = type_data.add(
                 type_finder,
                 &self.forwarder_to_complete_type,
-                type_index.into(),
+                &self.virtual_table_records,
+                complete_type_index,
                 &primitives_flavor,
                 &mut needed_types,
-            )?;
-
-            let mut reconstruction_output = String::new();
-            type_data.reconstruct(
-                &fmt_configuration,
-                &Default::default(),
-                &mut reconstruction_output,
-            )?;
-            let needed_types: Vec<TypeIndex> = needed_types.into_iter().map(|e| e.0 .0).collect();
-            let xrefs_from = self.type_list_from_type_indices(&needed_types);
-
-            return Ok((reconstruction_output, xrefs_from));
-        }
-
-        let mut xrefs_from = vec![];
-        // Add all the needed types iteratively until we're done
-        let mut type_dependency_map: HashMap<TypeIndex, Vec<(TypeIndex, bool)>> = HashMap::new();
-        {
-            let dep_start = Instant::now();
-
-            // Add the requested type first
-            let mut types_to_process: VecDeque<TypeIndex> = VecDeque::from([type_index]);
-            let mut processed_type_set = HashSet::new();
-            // Keep processing new types until there's nothing to process
-            while let Some(needed_type_index) = types_to_process.pop_front() {
-                if processed_type_set.contains(&needed_type_index) {
-                    // Already processed, continue
-                    continue;
-                }
+            );
 
-                // Add the type
-                let mut needed_types = pdb_types::NeededTypeSet::new();
-                type_data.add(
-                    type_finder,
-                    &self.forwarder_to_complete_type,
-                    needed_type_index.into(),
-                    &primitives_flavor,
-                    &mut needed_types,
-                )?;
-                // Initialize only once, the first time (i.e., for the requested type)
-                if xrefs_from.is_empty() {
-                    let needed_types: Vec<TypeIndex> =
-                        needed_types.iter().map(|e| e.0 .0).collect();
-                    xrefs_from = self.type_list_from_type_indices(&needed_types);
+            // Process result
+            if let Err(err) = result {
+                // Handle error
+                match err {
+                    ResymCoreError::PdbError(err) => {
+                        // Ignore this kind of error since some particular PDB features might not be supported.
+                        // This allows the recontruction to go through with the correctly reconstructed types.
+                        log::warn!(
+                            "Failed to reconstruct type with index {complete_type_index}: {err}"
+                        )
+                    }
+                    _ => return Err(err),
                 }
-
+            } else {
+                // Handle success
+                processed_types.push(complete_type_index.0);
                 for (type_index, is_pointer) in &needed_types {
                     // Add forward declaration for types referenced by pointers
                     if *is_pointer {
@@ -896,133 +2855,236 @@ where
                     }
 
                     // Update type dependency map
-                    if let Some(type_dependency) = type_dependency_map.get_mut(&needed_type_index) {
+                    if let Some(type_dependency) =
+                        type_dependency_map.get_mut(&complete_type_index.0)
+                    {
                         type_dependency.push((type_index.0, *is_pointer));
                     } else {
                         type_dependency_map
-                            .insert(needed_type_index, vec![(type_index.0, *is_pointer)]);
+                            .insert(complete_type_index.0, vec![(type_index.0, *is_pointer)]);
                     }
                 }
-                // Update the set of processed types
-                processed_type_set.insert(needed_type_index);
-                // Update the queue of type to process
-                types_to_process.extend(needed_types.into_iter().map(|pair| pair.0 .0));
             }
-
-            log::debug!(
-                "Dependencies reconstruction took {} ms",
-                dep_start.elapsed().as_millis()
-            );
         }
 
-        // Deduce type "depth" from the dependency map
-        let type_depth_map = compute_type_depth_map(&type_dependency_map, &[type_index]);
-
-        let mut reconstruction_output = String::new();
-        type_data.reconstruct(
-            &fmt_configuration,
-            &type_depth_map,
-            &mut reconstruction_output,
-        )?;
+        Ok((type_data, type_dependency_map, processed_types))
+    }
 
-        Ok((reconstruction_output, xrefs_from))
+    /// Return the index of the type reconstructed in `type_data` for the
+    /// given dependency, which may be a forward reference or a duplicate.
+    fn reconstructed_type_index(
+        &self,
+        type_data: &pdb_types::Data,
+        type_index: TypeIndex,
+    ) -> pdb::TypeIndex {
+        let type_index = type_index.into();
+        type_data.deduplicated_type_index(
+            self.forwarder_to_complete_type
+                .get(&type_index)
+                .map(|e| *e)
+                .unwrap_or(type_index),
+        )
     }
 
-    pub fn reconstruct_all_types(
+    /// Return the headers included by each header, given the headers types
+    /// are assigned to in `type_header_paths`. Types referenced through
+    /// pointers are forward-declared and don't require any include.
+    fn header_includes<'h>(
         &self,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
-        ignore_std_types: bool,
-    ) -> Result<String> {
-        let mut type_data = pdb_types::Data::new(ignore_std_types);
-        let mut processed_types = Vec::new();
-        let mut type_dependency_map: HashMap<TypeIndex, Vec<(TypeIndex, bool)>> = HashMap::new();
-        {
-            let mut type_finder = self.type_information.finder();
-            // Populate our `TypeFinder`
-            let mut type_iter = self.type_information.iter();
-            while (type_iter.next()?).is_some() {
-                type_finder.update(&type_iter);
+        type_data: &pdb_types::Data,
+        type_dependency_map: &TypeDependencyMap,
+        type_header_paths: &'h HashMap<pdb::TypeIndex, PathBuf>,
+    ) -> BTreeMap<&'h Path, BTreeSet<&'h Path>> {
+        let mut header_includes: BTreeMap<&Path, BTreeSet<&Path>> = BTreeMap::new();
+        for (type_index, header_path) in type_header_paths {
+            if is_ignored_std_type(type_data, *type_index) {
+                continue;
+            }
+            let included_header_paths = header_includes.entry(header_path).or_default();
+            for (dependency_index, _) in type_dependency_map
+                .get(&type_index.0)
+                .into_iter()
+                .flatten()
+                .filter(|(_, is_pointer)| !is_pointer)
+            {
+                let dependency_index = self.reconstructed_type_index(type_data, *dependency_index);
+                if is_ignored_std_type(type_data, dependency_index) {
+                    continue;
+                }
+                if let Some(dependency_header_path) = type_header_paths.get(&dependency_index) {
+                    if dependency_header_path != header_path {
+                        included_header_paths.insert(dependency_header_path);
+                    }
+                }
             }
+        }
 
-            // Add the requested types
-            let mut type_iter = self.type_information.iter();
-            while let Some(item) = type_iter.next()? {
-                let mut needed_types = pdb_types::NeededTypeSet::new();
-                // Note(ergelet): try to get the complete type's index here.
-                // This avoids adding empty "forward reference" type index which
-                // usually have lower type indices
-                let complete_type_index = self
-                    .forwarder_to_complete_type
-                    .get(&item.index())
-                    .map(|e| *e)
-                    .unwrap_or_else(|| item.index());
-                let result = type_data.add(
-                    &type_finder,
-                    &self.forwarder_to_complete_type,
-                    complete_type_index,
-                    &primitives_flavor,
-                    &mut needed_types,
-                );
+        header_includes
+    }
 
-                // Process result
-                if let Err(err) = result {
-                    // Handle error
-                    match err {
-                        ResymCoreError::PdbError(err) => {
-                            // Ignore this kind of error since some particular PDB features might not be supported.
-                            // This allows the recontruction to go through with the correctly reconstructed types.
-                            log::warn!("Failed to reconstruct type with index {complete_type_index}: {err}")
-                        }
-                        _ => return Err(err),
+    /// Reconstruct the types of `type_data` into the files they're assigned
+    /// to in `type_file_paths`. Files include the files defining the types
+    /// they depend on, and forward-declare the types they reference through
+    /// pointers.
+    fn reconstruct_type_files(
+        &self,
+        type_data: &pdb_types::Data,
+        type_finder: &pdb::TypeFinder,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+        type_dependency_map: &TypeDependencyMap,
+        type_file_paths: &HashMap<pdb::TypeIndex, PathBuf>,
+    ) -> Result<ReconstructedFiles> {
+        let is_ignored = |type_index: &pdb::TypeIndex| is_ignored_std_type(type_data, *type_index);
+
+        #[derive(Default)]
+        struct TypeFile<'a> {
+            type_depth_map: BTreeMap<usize, Vec<pdb::TypeIndex>>,
+            type_indices: BTreeSet<pdb::TypeIndex>,
+            forward_declaration_indices: BTreeSet<pdb::TypeIndex>,
+            included_file_paths: BTreeSet<&'a Path>,
+        }
+        let mut type_files: BTreeMap<&Path, TypeFile> = BTreeMap::new();
+        for (depth, type_indices) in type_depth_map {
+            for type_index in type_indices.iter().filter(|e| !is_ignored(e)) {
+                let Some(file_path) = type_file_paths.get(type_index) else {
+                    continue;
+                };
+                let type_file = type_files.entry(file_path).or_default();
+                type_file
+                    .type_depth_map
+                    .entry(*depth)
+                    .or_default()
+                    .push(*type_index);
+                type_file.type_indices.insert(*type_index);
+
+                for (dependency_index, is_pointer) in
+                    type_dependency_map.get(&type_index.0).into_iter().flatten()
+                {
+                    if *is_pointer {
+                        type_file
+                            .forward_declaration_indices
+                            .insert((*dependency_index).into());
+                        continue;
                     }
-                } else {
-                    // Handle success
-                    processed_types.push(complete_type_index.0);
-                    for (type_index, is_pointer) in &needed_types {
-                        // Add forward declaration for types referenced by pointers
-                        if *is_pointer {
-                            type_data.add_as_forward_declaration(&type_finder, *type_index)?;
-                        }
-
-                        // Update type dependency map
-                        if let Some(type_dependency) =
-                            type_dependency_map.get_mut(&complete_type_index.0)
-                        {
-                            type_dependency.push((type_index.0, *is_pointer));
-                        } else {
-                            type_dependency_map
-                                .insert(complete_type_index.0, vec![(type_index.0, *is_pointer)]);
+                    let dependency_index =
+                        self.reconstructed_type_index(type_data, *dependency_index);
+                    if is_ignored(&dependency_index) {
+                        continue;
+                    }
+                    if let Some(dependency_file_path) = type_file_paths.get(&dependency_index) {
+                        if dependency_file_path != file_path {
+                            type_file
+                                .included_file_paths
+                                .insert(dependency_file_path.as_path());
                         }
                     }
                 }
             }
         }
 
-        // Deduce type "depth" from the dependency map
-        let type_depth_map = compute_type_depth_map(&type_dependency_map, &processed_types);
+        let mut reconstructed_files = ReconstructedFiles::new();
+        for (file_path, type_file) in type_files {
+            let mut reconstruction_output = String::from("#pragma once\n");
+            if !type_file.included_file_paths.is_empty() {
+                writeln!(reconstruction_output)?;
+            }
+            for included_file_path in &type_file.included_file_paths {
+                writeln!(
+                    reconstruction_output,
+                    "#include \"{}\"",
+                    relative_include_path(file_path, included_file_path)
+                )?;
+            }
 
-        let mut reconstruction_output = String::new();
-        type_data.reconstruct(
-            &DataFormatConfiguration {
-                print_access_specifiers,
-                size_print_flavor,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
-            },
-            &type_depth_map,
-            &mut reconstruction_output,
-        )?;
+            self.reconstruct_type_data(
+                &type_data.subset(
+                    &type_file.type_indices,
+                    &type_file.forward_declaration_indices,
+                ),
+                type_finder,
+                output_language,
+                fmt_configuration,
+                &type_file.type_depth_map,
+                &mut reconstruction_output,
+            )?;
+            reconstructed_files.insert(file_path.to_owned(), reconstruction_output);
+        }
 
-        Ok(reconstruction_output)
+        Ok(reconstructed_files)
+    }
+
+    /// Attach the location of their declaration to the types of `type_data`.
+    fn add_type_source_locations(&self, type_data: &mut pdb_types::Data) {
+        let source_locations: Vec<(pdb::TypeIndex, SourceLocation)> = type_data
+            .type_indices()
+            .filter_map(|type_index| {
+                self.type_source_location(type_index.0)
+                    .map(|source_location| (type_index, source_location.clone()))
+            })
+            .collect();
+        for (type_index, source_location) in source_locations {
+            type_data.set_source_location(type_index, source_location);
+        }
+    }
+
+    fn reconstruct_type_data(
+        &self,
+        type_data: &pdb_types::Data,
+        type_finder: &pdb::TypeFinder,
+        output_language: OutputLanguage,
+        fmt_configuration: &DataFormatConfiguration,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+        output_writer: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        match output_language {
+            OutputLanguage::Cpp if fmt_configuration.print_namespaces => {
+                let class_names = self.class_names();
+                type_data.reconstruct_with_namespaces(
+                    fmt_configuration,
+                    type_depth_map,
+                    &class_names,
+                    output_writer,
+                )
+            }
+            OutputLanguage::Cpp => {
+                type_data.reconstruct(fmt_configuration, type_depth_map, output_writer)
+            }
+            OutputLanguage::C => type_data.reconstruct_c(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                self.machine_type,
+                fmt_configuration.primitive_flavor,
+                fmt_configuration,
+                type_depth_map,
+                output_writer,
+            ),
+            OutputLanguage::Rust => type_data.reconstruct_rust(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                fmt_configuration,
+                type_depth_map,
+                output_writer,
+            ),
+            OutputLanguage::Json => type_data.reconstruct_json(output_writer),
+        }
     }
 
     pub fn get_xrefs_for_type(&self, type_index: TypeIndex) -> Result<TypeList> {
+        let xref_list: Vec<TypeIndex> = self
+            .type_xrefs(type_index)?
+            .into_iter()
+            .map(|(xref_type_index, _)| xref_type_index)
+            .collect();
+
+        // Convert the xref list into a proper Name+TypeIndex tuple list
+        Ok(self.type_list_from_type_indices(&xref_list))
+    }
+
+    /// Return the types referencing the given type, with whether they
+    /// reference it through a pointer.
+    fn type_xrefs(&self, type_index: TypeIndex) -> Result<Vec<(TypeIndex, bool)>> {
         // Generate xref cache if empty
         if self
             .xref_to_map
@@ -1040,7 +3102,7 @@ where
             }
 
             // Iterate through all types
-            let xref_map: DashMap<TypeIndex, Vec<TypeIndex>> = DashMap::default();
+            let xref_map: DashMap<TypeIndex, Vec<(TypeIndex, bool)>> = DashMap::default();
             let mut type_iter = self.type_information.iter();
             while let Some(type_item) = type_iter.next()? {
                 let current_type_index = type_item.index();
@@ -1050,6 +3112,7 @@ where
                 let result = type_data.add(
                     &type_finder,
                     &self.forwarder_to_complete_type,
+                    &self.virtual_table_records,
                     current_type_index,
                     &PrimitiveReconstructionFlavor::Raw,
                     &mut needed_types,
@@ -1069,11 +3132,11 @@ where
                     }
                 }
 
-                par_iter_if_available!(needed_types).for_each(|(t, _)| {
+                par_iter_if_available!(needed_types).for_each(|(t, is_pointer)| {
                     if let Some(mut xref_list) = xref_map.get_mut(&t.0) {
-                        xref_list.push(current_type_index.0);
+                        xref_list.push((current_type_index.0, *is_pointer));
                     } else {
-                        xref_map.insert(t.0, vec![current_type_index.0]);
+                        xref_map.insert(t.0, vec![(current_type_index.0, *is_pointer)]);
                     }
                 });
             }
@@ -1085,20 +3148,142 @@ where
         }
 
         // Query xref cache
-        if let Some(xref_list) = self
+        Ok(self
             .xref_to_map
             .read()
             .expect("lock shouldn't be poisoned")
             .get(&type_index)
+            .map(|xref_list| xref_list.clone())
+            // No xrefs found for the given type
+            .unwrap_or_default())
+    }
+
+    /// Return the symbols using the type at `type_index`: variables of this
+    /// type, functions taking it as a parameter or returning it and functions
+    /// with local variables of this type. Symbols from both the global symbol
+    /// stream and the modules' symbol streams are returned.
+    pub fn get_symbol_xrefs_for_type(&self, type_index: TypeIndex) -> Result<Vec<SymbolXref>> {
+        let type_finder = self.complete_type_finder()?;
+        let uses_type = |used_type_index: pdb::TypeIndex| {
+            is_type_or_derived(
+                &type_finder,
+                &self.forwarder_to_complete_type,
+                used_type_index,
+                type_index,
+            )
+        };
+
+        let mut symbol_xrefs = vec![];
+        // Static variables may be declared in both the global and the module
+        // symbol streams, under different symbol indices but at the same offset
+        let mut known_variable_offsets = HashSet::new();
+        // Modules' private symbols
         {
-            // Convert the xref list into a proper Name+TypeIndex tuple list
-            let xref_type_list = self.type_list_from_type_indices(&xref_list);
+            let mut pdb = self.pdb.write().expect("lock shouldn't be poisoned");
+            let mut modules = self.debug_information.modules()?.enumerate();
+            while let Some((module_index, module)) = modules.next()? {
+                let Some(module_info) = pdb.module_info(&module)? else {
+                    continue;
+                };
+                let mut module_symbols = module_info.symbols()?;
+                while let Some(symbol) = module_symbols.next()? {
+                    let symbol_index = (module_index, symbol.index().0);
+                    let procedure = match symbol.parse() {
+                        Ok(pdb::SymbolData::Procedure(procedure)) => procedure,
+                        Ok(symbol_data) => {
+                            if let Some((symbol_xref, offset)) =
+                                variable_symbol_xref(&symbol_data, symbol_index, uses_type)
+                            {
+                                if known_variable_offsets.insert(offset) {
+                                    symbol_xrefs.push(symbol_xref);
+                                }
+                            }
+                            continue;
+                        }
+                        Err(_) => continue,
+                    };
 
-            Ok(xref_type_list)
-        } else {
-            // No xrefs found for the given type
-            Ok(vec![])
+                    let symbol_name = procedure.name.to_string().into_owned();
+                    let Some((return_type, argument_types, has_this_pointer)) =
+                        procedure_signature(&type_finder, procedure.type_index)
+                    else {
+                        continue;
+                    };
+                    if return_type.is_some_and(&uses_type) {
+                        symbol_xrefs.push(SymbolXref {
+                            symbol_name: symbol_name.clone(),
+                            symbol_index,
+                            kind: SymbolXrefKind::ReturnValue,
+                            variable_name: None,
+                        });
+                    }
+                    if argument_types.iter().copied().any(&uses_type) {
+                        symbol_xrefs.push(SymbolXref {
+                            symbol_name: symbol_name.clone(),
+                            symbol_index,
+                            kind: SymbolXrefKind::Parameter,
+                            variable_name: None,
+                        });
+                    }
+
+                    // Local variables
+                    // Note: variadic arguments are represented by untyped arguments
+                    let parameter_count = argument_types
+                        .iter()
+                        .filter(|type_index| type_index.0 != 0)
+                        .count()
+                        + usize::from(has_this_pointer);
+                    let Ok(procedure_scope) = ProcedureScope::parse(
+                        &module_info,
+                        symbol.index(),
+                        parameter_count,
+                        self.machine_type,
+                    ) else {
+                        continue;
+                    };
+                    let mut scopes_to_visit = vec![&procedure_scope];
+                    while let Some(scope) = scopes_to_visit.pop() {
+                        for variable in &scope.variables {
+                            if !variable.is_parameter && uses_type(variable.type_index) {
+                                symbol_xrefs.push(SymbolXref {
+                                    symbol_name: symbol_name.clone(),
+                                    symbol_index,
+                                    kind: SymbolXrefKind::LocalVariable,
+                                    variable_name: Some(variable.name.clone()),
+                                });
+                            }
+                        }
+                        scopes_to_visit.extend(scope.blocks.iter());
+                    }
+                }
+            }
+        }
+
+        // Global symbols
+        let mut global_symbols = self.global_symbols.iter();
+        while let Some(symbol) = global_symbols.next()? {
+            if let Ok(symbol_data) = symbol.parse() {
+                if let Some((symbol_xref, offset)) = variable_symbol_xref(
+                    &symbol_data,
+                    (GLOBAL_MODULE_INDEX, symbol.index().0),
+                    uses_type,
+                ) {
+                    if known_variable_offsets.insert(offset) {
+                        symbol_xrefs.push(symbol_xref);
+                    }
+                }
+            }
         }
+
+        symbol_xrefs.sort_by(|lhs, rhs| {
+            lhs.kind
+                .cmp(&rhs.kind)
+                .then_with(|| lhs.symbol_name.cmp(&rhs.symbol_name))
+                .then_with(|| lhs.variable_name.cmp(&rhs.variable_name))
+                .then_with(|| lhs.symbol_index.cmp(&rhs.symbol_index))
+        });
+
+        Ok(symbol_xrefs)
     }
 
     fn type_list_from_type_indices(&self, type_indices: &[TypeIndex]) -> TypeList {
@@ -1113,65 +3298,254 @@ where
             .collect()
     }
 
-    fn reconstruct_symbol(
+    /// Return the tree of functions inlined in `procedure`, in the order in
+    /// which they're declared.
+    fn procedure_inlined_calls_internal(
         &self,
         type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
-        symbol: &pdb::Symbol<'_>,
-        primitives_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    ) -> Option<String> {
-        let mut needed_types = pdb_types::NeededTypeSet::new();
-        match symbol.parse().ok()? {
-            pdb::SymbolData::UserDefinedType(udt) => {
-                if let Ok(type_name) = type_name(
-                    type_finder,
-                    &self.forwarder_to_complete_type,
-                    udt.type_index,
-                    &primitives_flavor,
-                    &mut needed_types,
-                ) {
-                    if type_name.0 == "..." {
-                        // No type
-                        Some(format!("char {}; // (missing type information)", udt.name))
-                    } else {
-                        Some(format!(
-                            "using {} = {}{};",
-                            udt.name, type_name.0, type_name.1
-                        ))
+        id_finder: Option<&pdb::ItemFinder<'_, pdb::IdIndex>>,
+        module_info: &pdb::ModuleInfo,
+        module_inlinees: &HashMap<pdb::IdIndex, pdb::Inlinee>,
+        symbol_index: pdb::SymbolIndex,
+        procedure: &pdb::ProcedureSymbol,
+    ) -> Result<Vec<InlinedCall>> {
+        let line_program = module_info.line_program()?;
+
+        let mut inlined_calls = vec![];
+        // Inlined calls whose `S_INLINESITE_END` record hasn't been reached yet
+        let mut call_stack: Vec<InlinedCall> = vec![];
+        let mut symbols = module_info.symbols_at(symbol_index)?;
+        // Skip the procedure's symbol
+        symbols.next()?;
+        while let Some(symbol) = symbols.next()? {
+            if symbol.index() >= procedure.end {
+                break;
+            }
+            match symbol.parse() {
+                Ok(pdb::SymbolData::InlineSite(inline_site)) => {
+                    let lines: Vec<pdb::LineInfo> = match module_inlinees.get(&inline_site.inlinee)
+                    {
+                        Some(inlinee) => inlinee
+                            .lines(procedure.offset, &inline_site)
+                            .collect()
+                            .unwrap_or_default(),
+                        None => vec![],
+                    };
+                    let source_location = lines
+                        .iter()
+                        .min_by_key(|line| line.offset.offset)
+                        .and_then(|line| self.line_source_location(&line_program, line));
+                    let code_ranges = merge_code_ranges(
+                        lines
+                            .iter()
+                            .filter_map(|line| {
+                                let start_rva = symbol_rva(&line.offset, &self.sections)?;
+                                Some((start_rva, start_rva + line.length.unwrap_or(0)))
+                            })
+                            .collect(),
+                    );
+                    call_stack.push(InlinedCall {
+                        function_name: inlinee_name(type_finder, id_finder, inline_site.inlinee),
+                        source_location,
+                        code_ranges,
+                        inlined_calls: vec![],
+                    });
+                }
+                Ok(pdb::SymbolData::InlineSiteEnd) => {
+                    if let Some(inlined_call) = call_stack.pop() {
+                        match call_stack.last_mut() {
+                            Some(caller) => caller.inlined_calls.push(inlined_call),
+                            None => inlined_calls.push(inlined_call),
+                        }
                     }
-                } else {
-                    None
                 }
+                _ => {}
+            }
+        }
+        // Close inline sites left open by truncated symbol streams
+        while let Some(inlined_call) = call_stack.pop() {
+            match call_stack.last_mut() {
+                Some(caller) => caller.inlined_calls.push(inlined_call),
+                None => inlined_calls.push(inlined_call),
             }
+        }
 
-            // Functions and methods
-            pdb::SymbolData::Procedure(procedure) => {
-                let symbol_rva = symbol_rva(&procedure.offset, &self.sections)
-                    .map(|offset| format!("RVA=0x{:x} ", offset))
-                    .unwrap_or_default();
+        Ok(inlined_calls)
+    }
+
+    /// Write the given inlined calls as comments, one per line.
+    fn fmt_inlined_calls(
+        inlined_calls: &[InlinedCall],
+        depth: usize,
+        f: &mut impl std::fmt::Write,
+    ) -> std::fmt::Result {
+        let indentation = "  ".repeat(depth);
+        for inlined_call in inlined_calls {
+            write!(f, "\n//{}{}", indentation, inlined_call.function_name)?;
+            if let Some(source_location) = &inlined_call.source_location {
+                write!(f, " at {}", source_location)?;
+            }
+            if !inlined_call.code_ranges.is_empty() {
+                let code_ranges: Vec<String> = inlined_call
+                    .code_ranges
+                    .iter()
+                    .map(|(start_rva, end_rva)| format!("0x{:x}-0x{:x}", start_rva, end_rva))
+                    .collect();
+                write!(f, " // RVA={}", code_ranges.join(","))?;
+            }
+            Self::fmt_inlined_calls(&inlined_call.inlined_calls, depth + 1, f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the source file and line of the given line record.
+    fn line_source_location(
+        &self,
+        line_program: &pdb::LineProgram,
+        line: &pdb::LineInfo,
+    ) -> Option<SourceLocation> {
+        let file_info = line_program.get_file_info(line.file_index).ok()?;
+        let file_path = file_info
+            .name
+            .to_string_lossy(self.string_table.as_ref()?)
+            .ok()?
+            .into_owned();
+
+        Some(SourceLocation {
+            file_path,
+            line: line.line_start,
+        })
+    }
+
+    /// Populate an `IdFinder` for the IPI stream, if it's present in the PDB.
+    fn id_finder(&self) -> Result<Option<pdb::ItemFinder<'_, pdb::IdIndex>>> {
+        let Some(id_information) = &self.id_information else {
+            return Ok(None);
+        };
+
+        let mut id_finder = id_information.finder();
+        let mut id_iter = id_information.iter();
+        while (id_iter.next()?).is_some() {
+            id_finder.update(&id_iter);
+        }
+
+        Ok(Some(id_finder))
+    }
+
+    /// Return the frames of the given address, located in `procedure`: the
+    /// functions inlined at this address (innermost first) and the procedure.
+    #[allow(clippy::too_many_arguments)]
+    fn procedure_frames(
+        &self,
+        type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
+        id_finder: Option<&pdb::ItemFinder<'_, pdb::IdIndex>>,
+        module_info: &pdb::ModuleInfo,
+        line_program: &pdb::LineProgram,
+        inlinees: &HashMap<pdb::IdIndex, pdb::Inlinee>,
+        symbol_index: pdb::SymbolIndex,
+        procedure: &pdb::ProcedureSymbol,
+        address_offset: &pdb::PdbInternalSectionOffset,
+    ) -> Result<Vec<SymbolizedFrame>> {
+        let source_location = |lines: Vec<pdb::LineInfo>| -> Option<SourceLocation> {
+            let line = closest_line(lines, address_offset)?;
+            self.line_source_location(line_program, &line)
+        };
+
+        // Functions inlined at this address, outermost first
+        let mut inlined_frames = vec![];
+        // Note: inline sites nested in sites which don't contain the address
+        // can't contain it either
+        let mut inline_site_stack: Vec<bool> = vec![];
+        let mut symbols = module_info.symbols_at(symbol_index)?;
+        // Skip the procedure's symbol
+        symbols.next()?;
+        while let Some(symbol) = symbols.next()? {
+            if symbol.index() >= procedure.end {
+                break;
+            }
+            match symbol.parse() {
+                Ok(pdb::SymbolData::InlineSite(inline_site)) => {
+                    let parent_contains_address = inline_site_stack.last().copied().unwrap_or(true);
+                    let lines: Vec<pdb::LineInfo> = match inlinees.get(&inline_site.inlinee) {
+                        Some(inlinee) if parent_contains_address => inlinee
+                            .lines(procedure.offset, &inline_site)
+                            .collect()
+                            .unwrap_or_default(),
+                        _ => vec![],
+                    };
+                    let contains_address = lines.iter().any(|line| {
+                        offset_in_range(address_offset, &line.offset, line.length.unwrap_or(0))
+                            .is_some()
+                    });
+                    if contains_address {
+                        inlined_frames.push(SymbolizedFrame {
+                            function_name: inlinee_name(
+                                type_finder,
+                                id_finder,
+                                inline_site.inlinee,
+                            ),
+                            function_offset: None,
+                            source_location: source_location(lines),
+                            is_inlined: true,
+                        });
+                    }
+                    inline_site_stack.push(contains_address);
+                }
+                Ok(pdb::SymbolData::InlineSiteEnd) => {
+                    inline_site_stack.pop();
+                }
+                _ => {}
+            }
+        }
+
+        let procedure_lines = line_program
+            .lines_for_symbol(procedure.offset)
+            .collect()
+            .unwrap_or_default();
+        let mut frames: Vec<SymbolizedFrame> = inlined_frames.into_iter().rev().collect();
+        frames.push(SymbolizedFrame {
+            function_name: procedure.name.to_string().into_owned(),
+            function_offset: offset_in_range(address_offset, &procedure.offset, procedure.len),
+            source_location: source_location(procedure_lines),
+            is_inlined: false,
+        });
+
+        Ok(frames)
+    }
+
+    /// Reconstruct the given symbol. `module_info` is the module containing
+    /// the symbol, if it's a module's private symbol.
+    #[allow(clippy::too_many_arguments)]
+    fn reconstruct_symbol(
+        &self,
+        type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
+        id_finder: Option<&pdb::ItemFinder<'_, pdb::IdIndex>>,
+        module_info: Option<&pdb::ModuleInfo>,
+        symbol: &pdb::Symbol<'_>,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        print_access_specifiers: AccessSpecifierReconstructionFlavor,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
+    ) -> Option<String> {
+        let mut needed_types = pdb_types::NeededTypeSet::new();
+        match symbol.parse().ok()? {
+            pdb::SymbolData::UserDefinedType(udt) => {
                 if let Ok(type_name) = type_name(
                     type_finder,
                     &self.forwarder_to_complete_type,
-                    procedure.type_index,
+                    udt.type_index,
                     &primitives_flavor,
                     &mut needed_types,
                 ) {
-                    let static_prefix = if procedure.global { "" } else { "static " };
                     if type_name.0 == "..." {
                         // No type
-                        Some(format!(
-                            "{}void {}(); // {}CodeSize=0x{:x} (missing type information)",
-                            static_prefix, procedure.name, symbol_rva, procedure.len,
-                        ))
+                        Some(format!("char {}; // (missing type information)", udt.name))
                     } else {
                         Some(format!(
-                            "{}{}{}{}; // {}CodeSize=0x{:x}",
-                            static_prefix,
-                            type_name.0,
-                            procedure.name,
-                            type_name.1,
-                            symbol_rva,
-                            procedure.len,
+                            "using {} = {}{};",
+                            udt.name, type_name.0, type_name.1
                         ))
                     }
                 } else {
@@ -1179,6 +3553,19 @@ where
                 }
             }
 
+            // Functions and methods
+            pdb::SymbolData::Procedure(procedure) => self.reconstruct_procedure(
+                type_finder,
+                id_finder,
+                module_info,
+                symbol.index(),
+                &procedure,
+                primitives_flavor,
+                print_locals,
+                print_locations,
+                print_inlinees,
+            ),
+
             // Global variables
             pdb::SymbolData::Data(data) => {
                 let symbol_rva = symbol_rva(&data.offset, &self.sections)
@@ -1268,9 +3655,327 @@ where
             }
         }
     }
+
+    /// Reconstruct the prototype of the given procedure, with the names of its
+    /// parameters if they're described in the module's symbols. The local
+    /// variables of each lexical block are listed if `print_locals` is set,
+    /// along with the address ranges in which they're live if
+    /// `print_locations` is set. The tree of inlined functions is listed
+    /// if `print_inlinees` is set.
+    #[allow(clippy::too_many_arguments)]
+    fn reconstruct_procedure(
+        &self,
+        type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
+        id_finder: Option<&pdb::ItemFinder<'_, pdb::IdIndex>>,
+        module_info: Option<&pdb::ModuleInfo>,
+        symbol_index: pdb::SymbolIndex,
+        procedure: &pdb::ProcedureSymbol,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
+    ) -> Option<String> {
+        let mut needed_types = pdb_types::NeededTypeSet::new();
+        let symbol_rva = symbol_rva(&procedure.offset, &self.sections)
+            .map(|offset| format!("RVA=0x{:x} ", offset))
+            .unwrap_or_default();
+        let static_prefix = if procedure.global { "" } else { "static " };
+
+        let (return_type, argument_list, has_this_pointer) = match type_finder
+            .find(procedure.type_index)
+            .and_then(|type_item| type_item.parse())
+        {
+            Ok(pdb::TypeData::Procedure(data)) => (data.return_type, data.argument_list, false),
+            Ok(pdb::TypeData::MemberFunction(data)) => (
+                Some(data.return_type),
+                data.argument_list,
+                data.this_pointer_type.is_some(),
+            ),
+            _ => {
+                // No type
+                return Some(format!(
+                    "{}void {}(); // {}CodeSize=0x{:x} (missing type information)",
+                    static_prefix, procedure.name, symbol_rva, procedure.len,
+                ));
+            }
+        };
+        let return_type_name = match return_type {
+            Some(return_type) => {
+                let (type_left, type_right) = type_name(
+                    type_finder,
+                    &self.forwarder_to_complete_type,
+                    pdb_types::resolve_complete_type_index(
+                        &self.forwarder_to_complete_type,
+                        return_type,
+                    ),
+                    &primitives_flavor,
+                    &mut needed_types,
+                )
+                .ok()?;
+                format!("{type_left}{type_right}")
+            }
+            None => "void".to_string(),
+        };
+        let argument_types = match type_finder.find(argument_list).ok()?.parse().ok()? {
+            pdb::TypeData::ArgumentList(data) => data.arguments,
+            _ => return None,
+        };
+
+        // Note: variadic arguments are represented by untyped arguments
+        let parameter_count = argument_types
+            .iter()
+            .filter(|type_index| type_index.0 != 0)
+            .count()
+            + usize::from(has_this_pointer);
+        let procedure_scope = module_info.and_then(|module_info| {
+            ProcedureScope::parse(
+                module_info,
+                symbol_index,
+                parameter_count,
+                self.machine_type,
+            )
+            .ok()
+        });
+        let parameter_names: Vec<&str> = procedure_scope
+            .iter()
+            .flat_map(|scope| scope.parameters())
+            // `this` is implicit
+            .filter(|parameter| !(has_this_pointer && parameter.name == "this"))
+            .map(|parameter| parameter.name.as_str())
+            .collect();
+
+        let mut arguments = vec![];
+        for (i, argument_type) in argument_types.into_iter().enumerate() {
+            let (type_left, type_right) = type_name(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                argument_type,
+                &primitives_flavor,
+                &mut needed_types,
+            )
+            .ok()?;
+            match parameter_names.get(i) {
+                Some(parameter_name) if argument_type.0 != 0 => {
+                    arguments.push(format!("{type_left} {parameter_name}{type_right}"))
+                }
+                _ => arguments.push(format!("{type_left}{type_right}")),
+            }
+        }
+        let prototype = format!(
+            "{}{} {}({})",
+            static_prefix,
+            return_type_name,
+            procedure.name,
+            arguments.join(", ")
+        );
+
+        let mut reconstruction_output = match procedure_scope {
+            Some(procedure_scope) if print_locals || print_locations => {
+                let mut reconstruction_output = format!(
+                    "{} {{ // {}CodeSize=0x{:x}\n",
+                    prototype, symbol_rva, procedure.len
+                );
+                self.fmt_procedure_scope(
+                    type_finder,
+                    &procedure_scope,
+                    primitives_flavor,
+                    print_locations,
+                    1,
+                    &mut reconstruction_output,
+                )
+                .ok()?;
+                reconstruction_output.push('}');
+                reconstruction_output
+            }
+            _ => format!(
+                "{}; // {}CodeSize=0x{:x}",
+                prototype, symbol_rva, procedure.len
+            ),
+        };
+
+        if let Some(module_info) = module_info.filter(|_| print_inlinees) {
+            // Note: the procedure is still reconstructed if its inlined calls
+            // cannot be read
+            let inlined_calls = module_inlinees(module_info)
+                .and_then(|module_inlinees| {
+                    self.procedure_inlined_calls_internal(
+                        type_finder,
+                        id_finder,
+                        module_info,
+                        &module_inlinees,
+                        symbol_index,
+                        procedure,
+                    )
+                })
+                .unwrap_or_else(|err| {
+                    log::warn!(
+                        "Failed to read the calls inlined in '{}': {err}",
+                        procedure.name
+                    );
+                    vec![]
+                });
+            if !inlined_calls.is_empty() {
+                reconstruction_output.push_str("\n// Inlined calls:");
+                Self::fmt_inlined_calls(&inlined_calls, 1, &mut reconstruction_output).ok()?;
+            }
+        }
+
+        Some(reconstruction_output)
+    }
+
+    /// Write the variables and nested blocks of the given procedure scope,
+    /// with their location. The address ranges in which variables are live
+    /// are listed if `print_locations` is set.
+    fn fmt_procedure_scope(
+        &self,
+        type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
+        scope: &ProcedureScope,
+        primitives_flavor: PrimitiveReconstructionFlavor,
+        print_locations: bool,
+        depth: usize,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let indentation = "  ".repeat(depth);
+        let mut needed_types = pdb_types::NeededTypeSet::new();
+        for variable in &scope.variables {
+            let (type_left, type_right) = type_name(
+                type_finder,
+                &self.forwarder_to_complete_type,
+                variable.type_index,
+                &primitives_flavor,
+                &mut needed_types,
+            )?;
+            let mut comments = vec![];
+            if variable.is_parameter {
+                comments.push("Parameter".to_string());
+            }
+            if let Some(location) = variable.location {
+                comments.push(format!("Location={}", self.fmt_variable_location(location)));
+            }
+            write!(
+                f,
+                "{}{} {}{};",
+                indentation, type_left, variable.name, type_right
+            )?;
+            if comments.is_empty() {
+                writeln!(f)?;
+            } else {
+                writeln!(f, " // {}", comments.join(" "))?;
+            }
+            if print_locations && !variable.live_ranges.is_empty() {
+                for live_range in self.variable_live_ranges(scope, variable) {
+                    writeln!(
+                        f,
+                        "{}//   RVA=0x{:x}-0x{:x} Location={}",
+                        indentation, live_range.start_rva, live_range.end_rva, live_range.location
+                    )?;
+                }
+            }
+        }
+
+        for block in &scope.blocks {
+            let block_rva = block
+                .offset
+                .and_then(|offset| symbol_rva(&offset, &self.sections))
+                .map(|offset| format!("RVA=0x{:x} ", offset))
+                .unwrap_or_default();
+            writeln!(
+                f,
+                "{}{{ // {}Size=0x{:x}",
+                indentation, block_rva, block.len
+            )?;
+            self.fmt_procedure_scope(
+                type_finder,
+                block,
+                primitives_flavor,
+                print_locations,
+                depth + 1,
+                f,
+            )?;
+            writeln!(f, "{}}}", indentation)?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the address ranges in which the given variable, declared in
+    /// `scope`, is live. Variables whose location doesn't change are live in
+    /// the whole scope.
+    fn variable_live_ranges(
+        &self,
+        scope: &ProcedureScope,
+        variable: &ScopeVariable,
+    ) -> Vec<VariableLiveRange> {
+        if variable.live_ranges.is_empty() {
+            let start_rva = scope
+                .offset
+                .and_then(|offset| symbol_rva(&offset, &self.sections));
+            return match (start_rva, variable.location) {
+                (Some(start_rva), Some(location)) => vec![VariableLiveRange {
+                    start_rva,
+                    end_rva: start_rva + scope.len,
+                    location: self.fmt_variable_location(location),
+                }],
+                _ => vec![],
+            };
+        }
+
+        let mut variable_live_ranges = vec![];
+        for live_range in &variable.live_ranges {
+            let Some(range_rva) = symbol_rva(&live_range.offset, &self.sections) else {
+                continue;
+            };
+            let location = match live_range.member_offset {
+                Some(member_offset) => format!(
+                    "{} (member at +0x{:x})",
+                    self.fmt_variable_location(live_range.location),
+                    member_offset
+                ),
+                None => self.fmt_variable_location(live_range.location),
+            };
+            for (start, end) in live_range.live_parts() {
+                variable_live_ranges.push(VariableLiveRange {
+                    start_rva: range_rva + start,
+                    end_rva: range_rva + end,
+                    location: location.clone(),
+                });
+            }
+        }
+        variable_live_ranges.sort_by_key(|live_range| live_range.start_rva);
+
+        variable_live_ranges
+    }
+
+    fn fmt_variable_location(&self, location: VariableLocation) -> String {
+        let fmt_offset = |offset: i32| -> String {
+            if offset < 0 {
+                format!("-0x{:x}", offset.unsigned_abs())
+            } else {
+                format!("+0x{:x}", offset)
+            }
+        };
+
+        match location {
+            VariableLocation::Register(register) => register_name(self.machine_type, register),
+            VariableLocation::RegisterRelative(register, offset) => format!(
+                "[{}{}]",
+                register_name(self.machine_type, register),
+                fmt_offset(offset)
+            ),
+            VariableLocation::FramePointerRelative(offset) => {
+                let frame_pointer = match self.machine_type {
+                    pdb::MachineType::X86 => "ebp",
+                    pdb::MachineType::Amd64 => "rbp",
+                    _ => "fp",
+                };
+                format!("[{}{}]", frame_pointer, fmt_offset(offset))
+            }
+        }
+    }
 }
 
 fn compute_type_depth_map(
+    type_data: &pdb_types::Data,
     type_dependency_map: &HashMap<TypeIndex, Vec<(TypeIndex, bool)>>,
     root_types: &[TypeIndex],
 ) -> BTreeMap<usize, Vec<pdb::TypeIndex>> {
@@ -1284,22 +3989,26 @@ fn compute_type_depth_map(
     while let Some((current_type_depth, current_type_index)) = types_to_visit.pop_back() {
         if let Some(type_dependencies) = type_dependency_map.get(&current_type_index) {
             for (child_type_index, child_is_pointer) in type_dependencies {
+                // Depend on the type actually reconstructed, in case of duplicates
+                let child_type_index = type_data
+                    .deduplicated_type_index((*child_type_index).into())
+                    .0;
                 // Visit child only if it's directly referenced, to avoid infinite loops
-                if !child_is_pointer && *child_type_index != current_type_index {
+                if !child_is_pointer && child_type_index != current_type_index {
                     let current_child_depth = current_type_depth + 1;
-                    if let Some(child_type_depth) = type_depth_map.get_mut(child_type_index) {
+                    if let Some(child_type_depth) = type_depth_map.get_mut(&child_type_index) {
                         *child_type_depth = std::cmp::max(*child_type_depth, current_child_depth);
                     } else {
-                        type_depth_map.insert(*child_type_index, current_child_depth);
+                        type_depth_map.insert(child_type_index, current_child_depth);
                     }
-                    types_to_visit.push_back((current_child_depth, *child_type_index));
+                    types_to_visit.push_back((current_child_depth, child_type_index));
                 }
             }
         }
     }
 
     // Invert type depth map
-    let inverted_type_depth_map: BTreeMap<usize, Vec<pdb::TypeIndex>> = type_depth_map
+    let mut inverted_type_depth_map: BTreeMap<usize, Vec<pdb::TypeIndex>> = type_depth_map
         .into_iter()
         .fold(BTreeMap::new(), |mut acc, (type_index, type_depth)| {
             if let Some(type_indices) = acc.get_mut(&type_depth) {
@@ -1310,6 +4019,10 @@ fn compute_type_depth_map(
 
             acc
         });
+    // Types of the same depth follow type index order, to get a stable output
+    for type_indices in inverted_type_depth_map.values_mut() {
+        type_indices.sort();
+    }
 
     log::debug!(
         "Depth calculation took {} ms",
@@ -1319,6 +4032,323 @@ fn compute_type_depth_map(
     inverted_type_depth_map
 }
 
+/// Convert the path of a source file, as recorded in the PDB, into a relative
+/// path mirroring its location (e.g., `C:\src\a.h` becomes `C/src/a.h`).
+fn source_relative_path(source_path: &str) -> PathBuf {
+    source_path
+        .split(['\\', '/'])
+        .filter(|component| !matches!(*component, "" | "." | ".."))
+        // Drive letters become directories
+        .map(|component| component.trim_end_matches(':'))
+        .collect()
+}
+
+/// Return the namespace symbols are attributed to in size reports. Members of
+/// the given classes are attributed to the namespace of their class.
+pub(crate) fn symbol_size_namespace<'s>(
+    symbol_name: &'s str,
+    class_names: &HashSet<&str>,
+) -> &'s str {
+    pdb_types::symbol_namespace(symbol_name, |scope| class_names.contains(scope))
+        .unwrap_or(GLOBAL_NAMESPACE_NAME)
+}
+
+/// Add a symbol or contribution of the given size to the group named `name`.
+fn add_to_size_group(size_groups: &mut HashMap<String, (u64, usize)>, name: &str, size: u64) {
+    let (group_size, group_count) = size_groups.entry(name.to_string()).or_default();
+    *group_size += size;
+    *group_count += 1;
+}
+
+/// Convert size groups into report entries, sorted by decreasing size.
+fn sorted_size_report_entries(size_groups: HashMap<String, (u64, usize)>) -> Vec<SizeReportEntry> {
+    let mut entries: Vec<SizeReportEntry> = size_groups
+        .into_iter()
+        .map(|(name, (size, count))| SizeReportEntry { name, size, count })
+        .collect();
+    entries.sort_by(|lhs, rhs| {
+        rhs.size
+            .cmp(&lhs.size)
+            .then_with(|| lhs.name.cmp(&rhs.name))
+    });
+
+    entries
+}
+
+/// Check whether `file_path` designates the source file at `source_path`,
+/// either entirely or by its trailing components. Paths are compared
+/// case-insensitively, like on Windows.
+fn source_path_matches(source_path: &str, file_path: &str) -> bool {
+    let normalize_path = |path: &str| path.replace('/', "\\").to_lowercase();
+    let source_path = normalize_path(source_path);
+    let file_path = normalize_path(file_path);
+
+    source_path == file_path || source_path.ends_with(&format!("\\{file_path}"))
+}
+
+/// Assign named types to the header corresponding to their top-level
+/// namespace. Anonymous types of the global namespace are left unassigned.
+fn namespace_header_paths(
+    type_data: &pdb_types::Data,
+    type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+) -> HashMap<pdb::TypeIndex, PathBuf> {
+    // Note: header names are compared case-insensitively, to avoid clashes on
+    // case-insensitive file systems
+    let mut header_paths: HashMap<String, PathBuf> = HashMap::new();
+    let mut type_header_paths: HashMap<pdb::TypeIndex, PathBuf> = HashMap::new();
+    for type_index in type_depth_map.values().flatten() {
+        let header_path = match type_data.top_level_namespace(*type_index) {
+            Some(namespace) => PathBuf::from(format!("{}.h", sanitize_file_name(namespace))),
+            None => {
+                if type_data
+                    .type_name_by_index(*type_index)
+                    .map_or(true, is_unnamed_type)
+                {
+                    continue;
+                }
+                PathBuf::from(GLOBAL_NAMESPACE_HEADER_PATH)
+            }
+        };
+        let header_path = header_paths
+            .entry(header_path.to_string_lossy().to_lowercase())
+            .or_insert(header_path)
+            .clone();
+        type_header_paths.insert(*type_index, header_path);
+    }
+
+    type_header_paths
+}
+
+/// Replace the characters of `name` which aren't safe to use in file names.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string()
+}
+
+/// Return the path to use to include `included_file_path` from
+/// `file_path`, both being relative to the same directory.
+fn relative_include_path(file_path: &Path, included_file_path: &Path) -> String {
+    let directory: Vec<Component> = file_path
+        .parent()
+        .map(|parent| parent.components().collect())
+        .unwrap_or_default();
+    let included_components: Vec<Component> = included_file_path.components().collect();
+    let common_prefix_len = directory
+        .iter()
+        .zip(&included_components)
+        .take_while(|(lhs, rhs)| lhs == rhs)
+        .count();
+
+    std::iter::repeat("..".into())
+        .take(directory.len() - common_prefix_len)
+        .chain(
+            included_components[common_prefix_len..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy()),
+        )
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Return whether the type at `type_index` is a type from the standard library
+/// which isn't reconstructed.
+fn is_ignored_std_type(type_data: &pdb_types::Data, type_index: pdb::TypeIndex) -> bool {
+    type_data.ignores_std_types()
+        && type_data
+            .type_name_by_index(type_index)
+            .is_some_and(|name| name.starts_with("std::"))
+}
+
+/// Return the header into which each header taking part in an include cycle
+/// should be merged, given the headers included by each header. Headers of a
+/// cycle are merged into the first one, in lexicographic order.
+fn merged_cyclic_header_paths(
+    header_includes: &BTreeMap<&Path, BTreeSet<&Path>>,
+) -> HashMap<PathBuf, PathBuf> {
+    let mut merged_header_paths = HashMap::new();
+    for component in strongly_connected_components(header_includes) {
+        let Some(merged_header_path) = component.iter().min() else {
+            continue;
+        };
+        if component.len() > 1 {
+            log::info!(
+                "Headers {:?} include each other, merging them into '{}'",
+                component,
+                merged_header_path.display()
+            );
+        }
+        for header_path in &component {
+            if header_path != merged_header_path {
+                merged_header_paths
+                    .insert(header_path.to_path_buf(), merged_header_path.to_path_buf());
+            }
+        }
+    }
+
+    merged_header_paths
+}
+
+/// Return the strongly connected components of the directed graph described
+/// by `edges`, using Tarjan's algorithm (iteratively, as graphs can be deep).
+fn strongly_connected_components<N: Copy + Ord>(edges: &BTreeMap<N, BTreeSet<N>>) -> Vec<Vec<N>> {
+    struct NodeState {
+        index: usize,
+        low_link: usize,
+        is_on_stack: bool,
+    }
+
+    let mut node_states: BTreeMap<N, NodeState> = BTreeMap::new();
+    let mut node_stack: Vec<N> = vec![];
+    let mut components = vec![];
+    for root in edges.keys() {
+        if node_states.contains_key(root) {
+            continue;
+        }
+
+        // Nodes being visited, along with their successors left to visit
+        let mut visit_stack = vec![];
+        let visit = |node: N, node_states: &mut BTreeMap<N, NodeState>, node_stack: &mut Vec<N>| {
+            let index = node_states.len();
+            node_states.insert(
+                node,
+                NodeState {
+                    index,
+                    low_link: index,
+                    is_on_stack: true,
+                },
+            );
+            node_stack.push(node);
+            (node, edges.get(&node).into_iter().flatten())
+        };
+        visit_stack.push(visit(*root, &mut node_states, &mut node_stack));
+        while let Some((node, successors)) = visit_stack.last_mut() {
+            let node = *node;
+            if let Some(successor) = successors.next() {
+                match node_states.get(successor) {
+                    None => visit_stack.push(visit(*successor, &mut node_states, &mut node_stack)),
+                    Some(successor_state) if successor_state.is_on_stack => {
+                        let successor_index = successor_state.index;
+                        let node_state = node_states.get_mut(&node).expect("visited node");
+                        node_state.low_link = node_state.low_link.min(successor_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            visit_stack.pop();
+            let NodeState {
+                index, low_link, ..
+            } = node_states[&node];
+            if let Some((parent, _)) = visit_stack.last() {
+                let parent_state = node_states.get_mut(parent).expect("visited node");
+                parent_state.low_link = parent_state.low_link.min(low_link);
+            }
+            if low_link == index {
+                let mut component = vec![];
+                while let Some(member) = node_stack.pop() {
+                    if let Some(member_state) = node_states.get_mut(&member) {
+                        member_state.is_on_stack = false;
+                    }
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Return whether the type at `type_index` is the type at `target_type_index`,
+/// or a pointer to, an array of or a modified version of it.
+fn is_type_or_derived(
+    type_finder: &pdb::TypeFinder,
+    forwarder_to_complete_type: &pdb_types::TypeForwarder,
+    type_index: pdb::TypeIndex,
+    target_type_index: TypeIndex,
+) -> bool {
+    let target_type_index = pdb_types::resolve_complete_type_index(
+        forwarder_to_complete_type,
+        pdb::TypeIndex(target_type_index),
+    );
+    let mut type_index = type_index;
+    loop {
+        type_index = pdb_types::resolve_complete_type_index(forwarder_to_complete_type, type_index);
+        if type_index == target_type_index {
+            return true;
+        }
+        type_index = match type_finder
+            .find(type_index)
+            .and_then(|type_item| type_item.parse())
+        {
+            Ok(pdb::TypeData::Pointer(data)) => data.underlying_type,
+            Ok(pdb::TypeData::Modifier(data)) => data.underlying_type,
+            Ok(pdb::TypeData::Array(data)) => data.element_type,
+            _ => return false,
+        };
+    }
+}
+
+/// Return the return type, the argument types and whether the procedure of
+/// type `type_index` has a `this` pointer.
+fn procedure_signature(
+    type_finder: &pdb::TypeFinder,
+    type_index: pdb::TypeIndex,
+) -> Option<(Option<pdb::TypeIndex>, Vec<pdb::TypeIndex>, bool)> {
+    let (return_type, argument_list, has_this_pointer) =
+        match type_finder.find(type_index).ok()?.parse().ok()? {
+            pdb::TypeData::Procedure(data) => (data.return_type, data.argument_list, false),
+            pdb::TypeData::MemberFunction(data) => (
+                Some(data.return_type),
+                data.argument_list,
+                data.this_pointer_type.is_some(),
+            ),
+            _ => return None,
+        };
+    match type_finder.find(argument_list).ok()?.parse().ok()? {
+        pdb::TypeData::ArgumentList(data) => Some((return_type, data.arguments, has_this_pointer)),
+        _ => None,
+    }
+}
+
+/// Return a cross-reference to the given symbol, along with the variable's
+/// offset, if it's a variable whose type matches `uses_type`.
+fn variable_symbol_xref(
+    symbol_data: &pdb::SymbolData,
+    symbol_index: SymbolIndex,
+    uses_type: impl Fn(pdb::TypeIndex) -> bool,
+) -> Option<(SymbolXref, pdb::PdbInternalSectionOffset)> {
+    let (name, type_index, offset) = match symbol_data {
+        pdb::SymbolData::Data(data) => (data.name, data.type_index, data.offset),
+        pdb::SymbolData::ThreadStorage(data) => (data.name, data.type_index, data.offset),
+        _ => return None,
+    };
+    uses_type(type_index).then(|| {
+        (
+            SymbolXref {
+                symbol_name: name.to_string().into_owned(),
+                symbol_index,
+                kind: SymbolXrefKind::Variable,
+                variable_name: None,
+            },
+            offset,
+        )
+    })
+}
+
 fn get_symbol_name(symbol_data: &pdb::SymbolData) -> Option<String> {
     const UNNAMED_CONSTANT_PREFIXES: [&str; 5] = ["`", "??_", "__@@_PchSym_", "__real@", "__xmm@"];
     const UNNAMED_CONSTANT_SUFFIXES: [&str; 1] = ["@@9@9"];
@@ -1407,6 +4437,129 @@ fn symbol_rva(
     }
 }
 
+/// Convert an RVA into an offset relative to the section containing it.
+fn rva_to_section_offset(
+    rva: u32,
+    sections: &[pdb::ImageSectionHeader],
+) -> Option<pdb::PdbInternalSectionOffset> {
+    sections
+        .iter()
+        .enumerate()
+        .find_map(|(section_index, section_header)| {
+            let section_size = section_header
+                .virtual_size
+                .max(section_header.size_of_raw_data);
+            let offset = rva.checked_sub(section_header.virtual_address)?;
+            (offset < section_size).then_some(pdb::PdbInternalSectionOffset {
+                offset,
+                section: u16::try_from(section_index + 1).ok()?,
+            })
+        })
+}
+
+/// Return the key used to sort section offsets.
+fn section_offset_key(offset: &pdb::PdbInternalSectionOffset) -> (u16, u32) {
+    (offset.section, offset.offset)
+}
+
+/// Return the offset of `offset` in the range starting at `range_start` and of
+/// length `range_len`, if it's in the range.
+fn offset_in_range(
+    offset: &pdb::PdbInternalSectionOffset,
+    range_start: &pdb::PdbInternalSectionOffset,
+    range_len: u32,
+) -> Option<u32> {
+    if offset.section != range_start.section {
+        return None;
+    }
+    let offset_in_range = offset.offset.checked_sub(range_start.offset)?;
+    (offset_in_range < range_len).then_some(offset_in_range)
+}
+
+/// Return the line containing `offset` or, if there's none (e.g., because
+/// `offset` is in a function inlined by the one described by `lines`), the
+/// closest line preceding it.
+fn closest_line(
+    lines: Vec<pdb::LineInfo>,
+    offset: &pdb::PdbInternalSectionOffset,
+) -> Option<pdb::LineInfo> {
+    lines
+        .into_iter()
+        .filter(|line| offset_in_range(offset, &line.offset, u32::MAX).is_some())
+        .max_by_key(|line| {
+            let contains_offset =
+                offset_in_range(offset, &line.offset, line.length.unwrap_or(0)).is_some();
+            (contains_offset, line.offset.offset)
+        })
+}
+
+/// Return the inlined functions described by the given module, mapped to
+/// their index in the IPI stream.
+fn module_inlinees<'a>(
+    module_info: &'a pdb::ModuleInfo,
+) -> Result<HashMap<pdb::IdIndex, pdb::Inlinee<'a>>> {
+    let mut inlinees = HashMap::new();
+    let mut inlinee_iter = module_info.inlinees()?;
+    while let Some(inlinee) = inlinee_iter.next()? {
+        inlinees.insert(inlinee.index(), inlinee);
+    }
+
+    Ok(inlinees)
+}
+
+/// Sort the given ranges and merge those that overlap or are contiguous.
+fn merge_code_ranges(mut code_ranges: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    code_ranges.sort_unstable();
+
+    let mut merged_code_ranges: Vec<(u32, u32)> = vec![];
+    for (start, end) in code_ranges {
+        match merged_code_ranges.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => merged_code_ranges.push((start, end)),
+        }
+    }
+
+    merged_code_ranges
+}
+
+/// Return the name of the given inlined function.
+fn inlinee_name(
+    type_finder: &pdb::ItemFinder<'_, pdb::TypeIndex>,
+    id_finder: Option<&pdb::ItemFinder<'_, pdb::IdIndex>>,
+    inlinee: pdb::IdIndex,
+) -> String {
+    let inlinee_data = id_finder.and_then(|id_finder| id_finder.find(inlinee).ok()?.parse().ok());
+    match inlinee_data {
+        Some(pdb::IdData::Function(data)) => {
+            let scope =
+                data.scope
+                    .and_then(|scope| match id_finder?.find(scope).ok()?.parse().ok()? {
+                        pdb::IdData::String(scope) => Some(scope.name.to_string().into_owned()),
+                        _ => None,
+                    });
+            match scope {
+                Some(scope) => format!("{}::{}", scope, data.name),
+                None => data.name.to_string().into_owned(),
+            }
+        }
+        Some(pdb::IdData::MemberFunction(data)) => {
+            let parent_name = match type_finder
+                .find(data.parent)
+                .and_then(|type_item| type_item.parse())
+            {
+                Ok(pdb::TypeData::Class(parent)) => Some(parent.name),
+                Ok(pdb::TypeData::Union(parent)) => Some(parent.name),
+                _ => None,
+            };
+            match parent_name {
+                Some(parent_name) => format!("{}::{}", parent_name, data.name),
+                None => data.name.to_string().into_owned(),
+            }
+        }
+        _ => format!("inlinee_{:x}", inlinee.0),
+    }
+}
+
 fn demangle_symbol_name(
     symbol_name: impl AsRef<str>,
     print_access_specifiers: AccessSpecifierReconstructionFlavor,
@@ -1442,3 +4595,128 @@ fn symbol_priority(symbol_data: &pdb::SymbolData) -> u16 {
         _ => 10,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_code_ranges() {
+        assert_eq!(merge_code_ranges(vec![]), []);
+        // Overlapping and contiguous ranges are merged, in any order
+        assert_eq!(
+            merge_code_ranges(vec![(0x20, 0x30), (0x10, 0x18), (0x18, 0x20), (0x12, 0x14)]),
+            [(0x10, 0x30)]
+        );
+        // Disjoint ranges are kept apart
+        assert_eq!(
+            merge_code_ranges(vec![(0x40, 0x50), (0x10, 0x20), (0x15, 0x25)]),
+            [(0x10, 0x25), (0x40, 0x50)]
+        );
+    }
+
+    #[test]
+    fn test_source_relative_path() {
+        // Drive letters become directories
+        assert_eq!(
+            source_relative_path(r"C:\src\include\a.h"),
+            Path::new("C/src/include/a.h")
+        );
+        // Relative components can't escape the output directory
+        assert_eq!(source_relative_path(r"..\src\.\a.h"), Path::new("src/a.h"));
+        assert_eq!(
+            source_relative_path("/usr/include//a.h"),
+            Path::new("usr/include/a.h")
+        );
+        assert_eq!(source_relative_path(""), Path::new(""));
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("resym_test"), "resym_test");
+        assert_eq!(sanitize_file_name("outer::inner"), "outer__inner");
+        assert_eq!(
+            sanitize_file_name("std::vector<int, std::allocator<int> >"),
+            "std__vector_int__std__allocator_int"
+        );
+        assert_eq!(
+            sanitize_file_name("`anonymous namespace'"),
+            "anonymous_namespace"
+        );
+    }
+
+    #[test]
+    fn test_relative_include_path() {
+        // Headers in the same directory
+        assert_eq!(
+            relative_include_path(Path::new("a.h"), Path::new("b.h")),
+            "b.h"
+        );
+        assert_eq!(
+            relative_include_path(Path::new("C/src/a.h"), Path::new("C/src/b.h")),
+            "b.h"
+        );
+        // Headers in nested and sibling directories
+        assert_eq!(
+            relative_include_path(Path::new("C/src/a.h"), Path::new("C/src/include/b.h")),
+            "include/b.h"
+        );
+        assert_eq!(
+            relative_include_path(Path::new("C/src/include/a.h"), Path::new("C/lib/b.h")),
+            "../../lib/b.h"
+        );
+        assert_eq!(
+            relative_include_path(Path::new("C/src/a.h"), Path::new("D/b.h")),
+            "../../D/b.h"
+        );
+    }
+
+    #[test]
+    fn test_merged_cyclic_header_paths() {
+        // a.h <-> b.h -> c.h, d.h -> e.h -> f.h -> d.h, g.h -> a.h
+        let header_includes: BTreeMap<&Path, BTreeSet<&Path>> = [
+            ("a.h", vec!["b.h"]),
+            ("b.h", vec!["a.h", "c.h"]),
+            ("c.h", vec![]),
+            ("d.h", vec!["e.h"]),
+            ("e.h", vec!["f.h"]),
+            ("f.h", vec!["d.h"]),
+            ("g.h", vec!["a.h"]),
+        ]
+        .into_iter()
+        .map(|(header_path, included_header_paths)| {
+            (
+                Path::new(header_path),
+                included_header_paths.into_iter().map(Path::new).collect(),
+            )
+        })
+        .collect();
+
+        // Headers of a cycle are merged into the first one, others are kept
+        assert_eq!(
+            merged_cyclic_header_paths(&header_includes),
+            HashMap::from([
+                (PathBuf::from("b.h"), PathBuf::from("a.h")),
+                (PathBuf::from("e.h"), PathBuf::from("d.h")),
+                (PathBuf::from("f.h"), PathBuf::from("d.h")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let edges: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::from([
+            (0, BTreeSet::from([1])),
+            (1, BTreeSet::from([2, 3])),
+            (2, BTreeSet::from([0])),
+            (3, BTreeSet::from([3])),
+            (4, BTreeSet::from([3, 5])),
+        ]);
+        let mut components = strongly_connected_components(&edges);
+        components.iter_mut().for_each(|component| component.sort());
+        components.sort();
+
+        // Nodes without outgoing edges form components of their own
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3], vec![4], vec![5]]);
+    }
+}
@@ -0,0 +1,921 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
+
+use super::{
+    class::Class,
+    enum_underlying_type,
+    enumeration::Enum,
+    field::{Field, FieldAccess},
+    fmt_struct_fields, fmt_union_fields_recursive,
+    forward_declaration::ForwardDeclarationKind,
+    is_unnamed_type,
+    primitive_types::{primitive_kind_as_str, PrimitiveReconstructionFlavor},
+    resolve_complete_type_index, type_alignment, type_size,
+    union::Union,
+    vftable::{unknown_slot_name, vftable_type_name, VirtualFunctionTable, VFPTR_MEMBER_NAME},
+    AccessSpecifierReconstructionFlavor, AggregateLayout, Data, DataFormatConfiguration,
+    SizePrintFlavor, TypeAlignmentMap, TypeForwarder,
+};
+use crate::error::Result;
+
+/// Name of the member holding the virtual base table pointer.
+const VBPTR_MEMBER_NAME: &str = "__vbptr";
+/// Name of the member covering the storage of virtual base classes.
+const VBASES_MEMBER_NAME: &str = "__vbases";
+
+/// C keywords which are valid identifiers in C++.
+const C_ONLY_KEYWORDS: &[&str] = &[
+    "restrict",
+    "_Alignas",
+    "_Alignof",
+    "_Atomic",
+    "_Bool",
+    "_Complex",
+    "_Generic",
+    "_Imaginary",
+    "_Noreturn",
+    "_Static_assert",
+    "_Thread_local",
+];
+
+impl Data<'_> {
+    /// Reconstruct types as C declarations. C++-specific constructs are
+    /// lowered to their C equivalent (e.g., base classes become members,
+    /// references become pointers) and methods are dropped.
+    pub fn reconstruct_c(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_forwarder: &TypeForwarder,
+        machine_type: pdb::MachineType,
+        primitive_flavor: PrimitiveReconstructionFlavor,
+        fmt_configuration: &DataFormatConfiguration,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+        output_writer: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let is_ignored =
+            |name: &str| -> bool { self.ignore_std_types && name.starts_with("std::") };
+        let mut resolver = CTypeResolver {
+            type_finder,
+            type_forwarder,
+            pointer_size: pointer_size(machine_type),
+            primitive_flavor,
+            declared_types: HashSet::new(),
+            type_alignments: RefCell::new(TypeAlignmentMap::new()),
+        };
+
+        // Declare type names upfront, so that types can reference each
+        // other regardless of the order of their definitions
+        let mut type_declarations = String::new();
+        for e in self.forward_declarations.values() {
+            if !self.type_names.contains_key(&e.name) && !is_ignored(&e.name) {
+                let keyword = match e.kind {
+                    ForwardDeclarationKind::Union => "union",
+                    _ => "struct",
+                };
+                resolver.declare_aggregate_name(keyword, &e.name, &mut type_declarations)?;
+            }
+        }
+        for e in self.enums.values().filter(|e| !is_ignored(&e.name)) {
+            resolver.declare_enum_name(e, &mut type_declarations)?;
+        }
+        for c in self.classes.values().filter(|c| !is_ignored(&c.name)) {
+            resolver.declare_class_names(c, &mut type_declarations)?;
+        }
+        for u in self.unions.values().filter(|u| !is_ignored(&u.name)) {
+            resolver.declare_union_names(u, &mut type_declarations)?;
+        }
+        if !type_declarations.is_empty() {
+            writeln!(output_writer)?;
+            write!(output_writer, "{type_declarations}")?;
+        }
+
+        let type_indices: Vec<pdb::TypeIndex> = if !type_depth_map.is_empty() {
+            // Follow type depth map order
+            type_depth_map.values().rev().flatten().copied().collect()
+        } else {
+            // Follow type index order
+            self.enums
+                .keys()
+                .chain(self.classes.keys())
+                .chain(self.unions.keys())
+                .copied()
+                .collect()
+        };
+        for type_index in type_indices {
+            let mut declaration = String::new();
+            let (name, result) = if let Some(e) = self.enums.get(&type_index) {
+                if is_ignored(&e.name) {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (
+                    &e.name,
+                    resolver.declare_enum(e, fmt_configuration, &mut declaration),
+                )
+            } else if let Some(c) = self.classes.get(&type_index) {
+                if is_ignored(&c.name) {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (
+                    &c.name,
+                    resolver.declare_class(c, fmt_configuration, &mut declaration),
+                )
+            } else if let Some(u) = self.unions.get(&type_index) {
+                if is_ignored(&u.name) {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (
+                    &u.name,
+                    resolver.declare_union(u, fmt_configuration, &mut declaration),
+                )
+            } else {
+                continue;
+            };
+
+            writeln!(output_writer)?;
+            self.fmt_source_location(type_index, output_writer)?;
+            match result {
+                Ok(()) => write!(output_writer, "{declaration}")?,
+                Err(err) => {
+                    // Note: Do not propagate the error, this allows the
+                    // reconstruction of other types to go through
+                    log::error!("Error encountered while reconstructing '{}': {}", name, err);
+                    writeln!(output_writer, "// Failed to reconstruct '{name}': {err}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Member which doesn't exist in C++ declarations (e.g., base class storage).
+struct SyntheticMember {
+    offset: u64,
+    size: u64,
+    alignment: u64,
+    name: String,
+    type_name: (String, String),
+}
+
+struct CTypeResolver<'a, 't> {
+    type_finder: &'a pdb::TypeFinder<'t>,
+    type_forwarder: &'a TypeForwarder,
+    pointer_size: u64,
+    primitive_flavor: PrimitiveReconstructionFlavor,
+    /// Names of the types declared with a `typedef`, as C identifiers.
+    declared_types: HashSet<String>,
+    type_alignments: RefCell<TypeAlignmentMap>,
+}
+
+impl CTypeResolver<'_, '_> {
+    fn declare_aggregate_name(
+        &mut self,
+        keyword: &str,
+        name: &str,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let name = c_identifier(name);
+        if self.declared_types.insert(name.clone()) {
+            writeln!(f, "typedef {keyword} {name} {name};")?;
+        }
+
+        Ok(())
+    }
+
+    /// Enums are declared as their underlying type, to preserve their size.
+    fn declare_enum_name(&mut self, e: &Enum, f: &mut impl std::fmt::Write) -> Result<()> {
+        let name = c_identifier(&e.name);
+        if !self.declared_types.contains(&name) {
+            let underlying_type_name = match self.type_finder.find(e.index)?.parse()? {
+                pdb::TypeData::Enumeration(data) => self.type_name(enum_underlying_type(&data))?.0,
+                _ => e.underlying_type_name.clone(),
+            };
+            writeln!(f, "typedef {underlying_type_name} {name};")?;
+            self.declared_types.insert(name);
+        }
+
+        Ok(())
+    }
+
+    fn declare_class_names(&mut self, class: &Class, f: &mut impl std::fmt::Write) -> Result<()> {
+        self.declare_nested_type_names(
+            &class.nested_enums,
+            &class.nested_classes,
+            &class.nested_unions,
+            f,
+        )?;
+        self.declare_aggregate_name("struct", &class.name, f)
+    }
+
+    fn declare_union_names(&mut self, u: &Union, f: &mut impl std::fmt::Write) -> Result<()> {
+        self.declare_nested_type_names(&u.nested_enums, &u.nested_classes, &u.nested_unions, f)?;
+        self.declare_aggregate_name("union", &u.name, f)
+    }
+
+    fn declare_nested_type_names(
+        &mut self,
+        nested_enums: &[Enum],
+        nested_classes: &[Class],
+        nested_unions: &[Union],
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        for e in nested_enums {
+            self.declare_enum_name(e, f)?;
+        }
+        for class in nested_classes {
+            self.declare_class_names(class, f)?;
+        }
+        for u in nested_unions {
+            self.declare_union_names(u, f)?;
+        }
+
+        Ok(())
+    }
+
+    fn declare_enum(
+        &self,
+        e: &Enum,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let name = c_identifier(&e.name);
+        if e.values.is_empty() {
+            // Empty enums aren't allowed in C, the type name has already been
+            // declared anyway
+            writeln!(f, "// enum {name} has no enumerators")?;
+            return Ok(());
+        }
+        writeln!(
+            f,
+            "enum {}{}{{",
+            name,
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            }
+        )?;
+        for value in &e.values {
+            // Enumerators share a single scope in C, prefix them with the
+            // name of their enum to avoid collisions
+            writeln!(
+                f,
+                "  {}_{} = {},",
+                name,
+                c_identifier(&value.name.to_string()),
+                value.format_value(fmt_configuration)
+            )?;
+        }
+        writeln!(f, "}};")?;
+
+        Ok(())
+    }
+
+    fn declare_class(
+        &self,
+        class: &Class,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        self.declare_nested_types(
+            &class.nested_enums,
+            &class.nested_classes,
+            &class.nested_unions,
+            fmt_configuration,
+            f,
+        )?;
+
+        let name = c_identifier(&class.name);
+        if fmt_configuration.print_vftables {
+            for vftable in class.vftable.iter().chain(&class.secondary_vftables) {
+                self.declare_vftable(&class.name, vftable, fmt_configuration, f)?;
+                writeln!(f)?;
+            }
+        }
+
+        let mut members: Vec<SyntheticMember> = vec![];
+        for (i, base) in class.base_classes.iter().enumerate() {
+            // Virtual base classes aren't located at a fixed offset, their
+            // storage is covered by a byte array
+            if base.is_virtual {
+                continue;
+            }
+
+            let offset = u64::from(base.offset);
+            let size = type_size(self.type_finder, base.type_index)? as u64;
+            // Empty base classes may share their storage with other members
+            let is_overlapped = class
+                .fields
+                .iter()
+                .map(|field| field.offset)
+                .chain(
+                    class
+                        .base_classes
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, other)| *j != i && !other.is_virtual)
+                        .map(|(_, other)| u64::from(other.offset)),
+                )
+                .any(|other_offset| (offset..offset + size).contains(&other_offset));
+            if is_overlapped && size <= 1 {
+                continue;
+            }
+
+            members.push(SyntheticMember {
+                offset,
+                size,
+                alignment: type_alignment(
+                    self.type_finder,
+                    self.type_forwarder,
+                    base.type_index,
+                    &mut self.type_alignments.borrow_mut(),
+                )?,
+                name: format!("base_{i}"),
+                type_name: self.type_name(base.type_index)?,
+            });
+        }
+        if let Some(vftable) = class.vftable.as_ref().filter(|vftable| vftable.owns_vfptr) {
+            members.push(SyntheticMember {
+                offset: vftable.vfptr_offset,
+                size: vftable.slot_size,
+                alignment: vftable.slot_size,
+                name: VFPTR_MEMBER_NAME.to_string(),
+                type_name: if fmt_configuration.print_vftables {
+                    (
+                        format!("struct {}*", c_identifier(&vftable_type_name(&class.name))),
+                        String::default(),
+                    )
+                } else {
+                    ("void**".to_string(), String::default())
+                },
+            });
+        }
+        for base in class.base_classes.iter().filter(|base| base.is_virtual) {
+            let offset = u64::from(base.offset);
+            // Virtual base classes may share the same virtual base table
+            // pointer, which may also be part of a base class
+            let is_covered = members
+                .iter()
+                .any(|member| (member.offset..member.offset + member.size).contains(&offset))
+                || class.fields.iter().any(|field| field.offset == offset);
+            if !is_covered {
+                members.push(SyntheticMember {
+                    offset,
+                    size: self.pointer_size,
+                    alignment: self.pointer_size,
+                    name: VBPTR_MEMBER_NAME.to_string(),
+                    type_name: ("void*".to_string(), String::default()),
+                });
+            }
+        }
+        let mut field_types = Vec::with_capacity(class.fields.len());
+        for field in &class.fields {
+            field_types.push(self.type_name(field.type_index)?);
+        }
+        let field_names: Vec<String> = class
+            .fields
+            .iter()
+            .map(|field| c_identifier(&field.name.to_string()))
+            .collect();
+
+        // Synthetic members are located before the fields, whose declaration
+        // order must be preserved to reconstruct nested anonymous types
+        members.sort_by_key(|member| member.offset);
+        let mut fields: Vec<Field> = members
+            .iter()
+            .map(|member| Field {
+                type_left: member.type_name.0.clone(),
+                type_right: member.type_name.1.clone(),
+                type_index: pdb::TypeIndex(0),
+                name: member.name.as_str().into(),
+                offset: member.offset,
+                size: member.size as usize,
+                alignment: member.alignment,
+                bitfield_info: None,
+                access: FieldAccess::None,
+                is_implicit: false,
+            })
+            .collect();
+        for ((field, (type_left, type_right)), name) in
+            class.fields.iter().zip(field_types).zip(&field_names)
+        {
+            fields.push(Field {
+                type_left,
+                type_right,
+                name: name.as_str().into(),
+                ..field.clone()
+            });
+        }
+
+        // Storage for virtual base classes is located at the end
+        let fields_end = fields
+            .iter()
+            .map(|field| field.offset + field.size as u64)
+            .max()
+            .unwrap_or_default();
+        if class.base_classes.iter().any(|base| base.is_virtual) && fields_end < class.size {
+            fields.push(byte_array_field(
+                VBASES_MEMBER_NAME,
+                fields_end,
+                class.size - fields_end,
+            ));
+        }
+        if fields.is_empty() && class.size > 0 {
+            // Empty structs aren't allowed in C
+            fields.push(byte_array_field("__empty", 0, class.size));
+        }
+
+        self.write_aggregate("struct", &name, class.size, &fields, fmt_configuration, f)
+    }
+
+    fn declare_union(
+        &self,
+        u: &Union,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        self.declare_nested_types(
+            &u.nested_enums,
+            &u.nested_classes,
+            &u.nested_unions,
+            fmt_configuration,
+            f,
+        )?;
+
+        let mut field_types = Vec::with_capacity(u.fields.len());
+        for field in &u.fields {
+            field_types.push(self.type_name(field.type_index)?);
+        }
+        let field_names: Vec<String> = u
+            .fields
+            .iter()
+            .map(|field| c_identifier(&field.name.to_string()))
+            .collect();
+        let mut fields: Vec<Field> = u
+            .fields
+            .iter()
+            .zip(field_types)
+            .zip(&field_names)
+            .map(|((field, (type_left, type_right)), name)| Field {
+                type_left,
+                type_right,
+                name: name.as_str().into(),
+                ..field.clone()
+            })
+            .collect();
+        if fields.is_empty() && u.size > 0 {
+            // Empty unions aren't allowed in C
+            fields.push(byte_array_field("__empty", 0, u.size));
+        }
+
+        self.write_aggregate(
+            "union",
+            &c_identifier(&u.name),
+            u.size,
+            &fields,
+            fmt_configuration,
+            f,
+        )
+    }
+
+    /// Nested types are declared before the type that contains them.
+    fn declare_nested_types(
+        &self,
+        nested_enums: &[Enum],
+        nested_classes: &[Class],
+        nested_unions: &[Union],
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        for e in nested_enums {
+            self.declare_enum(e, fmt_configuration, f)?;
+            writeln!(f)?;
+        }
+        for class in nested_classes {
+            self.declare_class(class, fmt_configuration, f)?;
+            writeln!(f)?;
+        }
+        for u in nested_unions {
+            self.declare_union(u, fmt_configuration, f)?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn declare_vftable(
+        &self,
+        class_name: &str,
+        vftable: &VirtualFunctionTable,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let this_type_name = c_identifier(vftable.this_type_name(class_name));
+        let mut known_slots = vftable.slots.iter().peekable();
+        let slots: Vec<_> = (0..vftable.slot_count)
+            .map(|index| known_slots.next_if(|slot| slot.index == index))
+            .collect();
+        let names: Vec<String> = slots
+            .iter()
+            .enumerate()
+            .map(|(index, slot)| {
+                c_identifier(&match slot {
+                    Some(slot) if slot.method_name.starts_with('~') => {
+                        // Destructors cannot be used as identifiers
+                        format!("__vecDelDtor_{}", slot.method_name.trim_start_matches('~'))
+                    }
+                    Some(slot) => slot.method_name.clone(),
+                    // No method is known for this slot
+                    None => unknown_slot_name(index),
+                })
+            })
+            .collect();
+        let mut fields = vec![];
+        for (index, (slot, name)) in slots.iter().zip(&names).enumerate() {
+            let (type_left, type_right, type_index) = match slot {
+                Some(slot) => {
+                    let (type_left, type_right) =
+                        self.method_pointer_type_name(slot.type_index, &this_type_name)?;
+                    (type_left, type_right, slot.type_index)
+                }
+                None => ("void*".to_string(), String::default(), pdb::TypeIndex(0)),
+            };
+            fields.push(Field {
+                type_left,
+                type_right,
+                type_index,
+                name: name.as_str().into(),
+                offset: index as u64 * vftable.slot_size,
+                size: vftable.slot_size as usize,
+                alignment: vftable.slot_size,
+                bitfield_info: None,
+                access: FieldAccess::None,
+                is_implicit: false,
+            });
+        }
+        let vftable_size = vftable.slot_count as u64 * vftable.slot_size;
+
+        // Note: the vftable type is only referred to with the `struct` keyword
+        self.write_aggregate(
+            "struct",
+            &c_identifier(&vftable.type_name(class_name)),
+            vftable_size,
+            &fields,
+            fmt_configuration,
+            f,
+        )
+    }
+
+    fn write_aggregate(
+        &self,
+        keyword: &str,
+        name: &str,
+        size: u64,
+        fields: &[Field],
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let layout = AggregateLayout::new(fmt_configuration, fields, size);
+        if let Some(packing) = layout.packing {
+            writeln!(f, "#pragma pack(push, {packing})")?;
+        }
+
+        writeln!(
+            f,
+            "{} {}{}{{{}",
+            keyword,
+            name,
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            },
+            if fmt_configuration.size_print_flavor == SizePrintFlavor::Comment {
+                format!(" /* Size={size:#x} */")
+            } else {
+                String::default()
+            }
+        )?;
+
+        // Access specifiers don't exist in C
+        let fmt_configuration = DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+            ..fmt_configuration.clone()
+        };
+        if keyword == "union" {
+            fmt_union_fields_recursive(&fmt_configuration, &layout, fields, 1, f)?;
+        } else {
+            fmt_struct_fields(&fmt_configuration, &layout, fields, 0..size, f)?;
+        }
+        writeln!(f, "}};")?;
+        if layout.packing.is_some() {
+            writeln!(f, "#pragma pack(pop)")?;
+        }
+
+        if fmt_configuration.size_print_flavor == SizePrintFlavor::StaticAssert {
+            // Note: the message is mandatory before C23
+            if fmt_configuration.integers_as_hexadecimal {
+                writeln!(
+                    f,
+                    "_Static_assert(sizeof({keyword} {name}) == {size:#x}, \"{name}\"); // {size}",
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "_Static_assert(sizeof({keyword} {name}) == {size}, \"{name}\"); // {size:#x}",
+                )?;
+            }
+        }
+        if fmt_configuration.print_offset_asserts {
+            // Note: `offsetof` cannot be used on bitfields
+            for field in fields.iter().filter(|field| field.bitfield_info.is_none()) {
+                let field_name = &field.name;
+                let offset = field.offset;
+                if fmt_configuration.integers_as_hexadecimal {
+                    writeln!(
+                        f,
+                        "_Static_assert(offsetof({keyword} {name}, {field_name}) == {offset:#x}, \"{name}::{field_name}\"); // {offset}",
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "_Static_assert(offsetof({keyword} {name}, {field_name}) == {offset}, \"{name}::{field_name}\"); // {offset:#x}",
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return a pair of strings representing the given `type_index` in C.
+    fn type_name(&self, type_index: pdb::TypeIndex) -> Result<(String, String)> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        let type_name = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Primitive(data) if data.kind == pdb::PrimitiveKind::Char8 => (
+                // `char8_t` is only available since C23
+                format!(
+                    "unsigned char{}",
+                    if data.indirection.is_some() { "*" } else { "" }
+                ),
+                String::default(),
+            ),
+            pdb::TypeData::Primitive(data) => (
+                primitive_kind_as_str(
+                    &self.primitive_flavor,
+                    data.kind,
+                    data.indirection.is_some(),
+                )?,
+                String::default(),
+            ),
+
+            pdb::TypeData::Class(data) => (
+                self.aggregate_name("struct", &data.name.to_string(), type_index),
+                String::default(),
+            ),
+
+            pdb::TypeData::Union(data) => (
+                self.aggregate_name("union", &data.name.to_string(), type_index),
+                String::default(),
+            ),
+
+            pdb::TypeData::Enumeration(data) => {
+                let name = c_identifier(&data.name.to_string());
+                if self.declared_types.contains(&name) {
+                    (name, String::default())
+                } else {
+                    // Undeclared enum, use its underlying type
+                    self.type_name(enum_underlying_type(&data))?
+                }
+            }
+
+            pdb::TypeData::Pointer(data) => {
+                if matches!(
+                    data.attributes.pointer_mode(),
+                    pdb::PointerMode::Member | pdb::PointerMode::MemberFunction
+                ) {
+                    // Pointers to members are opaque in C
+                    return Ok(integer_type_name(data.attributes.size().into()));
+                }
+
+                let (type_left, type_right) = self.type_name(data.underlying_type)?;
+                // References are turned into pointers
+                let mut suffix = String::from("*");
+                if data.attributes.is_restrict() {
+                    suffix.push_str(" restrict");
+                }
+                (format!("{type_left}{suffix}"), type_right)
+            }
+
+            pdb::TypeData::Modifier(data) => {
+                let (type_left, type_right) = self.type_name(data.underlying_type)?;
+                let mut prefix = String::new();
+                if data.constant {
+                    prefix.push_str("const ");
+                }
+                if data.volatile {
+                    prefix.push_str("volatile ");
+                }
+                (format!("{prefix}{type_left}"), type_right)
+            }
+
+            pdb::TypeData::Array(_) => {
+                let ((type_left, type_right), dimensions) = self.array_base_name(type_index)?;
+                let mut dimensions_str = String::default();
+                // Note: Dimensions are collected in reverse order so we have to use
+                // a reverse iterator
+                for dim in dimensions.iter().rev() {
+                    dimensions_str = format!("{dimensions_str}[{dim}]");
+                }
+
+                (type_left, format!("{dimensions_str}{type_right}"))
+            }
+
+            pdb::TypeData::Bitfield(data) => {
+                let (type_left, type_right) = self.type_name(data.underlying_type)?;
+                (type_left, format!("{} : {}", type_right, data.length))
+            }
+
+            pdb::TypeData::Procedure(data) => {
+                let (return_type_left, return_type_right) = match data.return_type {
+                    Some(return_type) => self.type_name(return_type)?,
+                    None => ("void".to_string(), String::default()),
+                };
+                let arguments = self.argument_list(data.argument_list, None)?;
+                // Note: the return type's declarator wraps the function's
+                // declarator (e.g., `int (*f(void))(char)` for a function
+                // returning a function pointer)
+                (
+                    format!("{return_type_left} ("),
+                    format!("){arguments}{return_type_right}"),
+                )
+            }
+
+            pdb::TypeData::MemberFunction(_) => {
+                self.method_pointer_type_name(type_index, "void")?
+            }
+
+            type_data => {
+                log::warn!(
+                    "FIXME: figure out how to name it: TypeIndex={}, TypeData={:?}",
+                    type_index,
+                    type_data
+                );
+                ("FIXME_UNKNOWN_TYPE".to_string(), String::default())
+            }
+        };
+
+        Ok(type_name)
+    }
+
+    /// Return a pair of strings representing a pointer to the given method
+    /// type, with an explicit `this` argument.
+    fn method_pointer_type_name(
+        &self,
+        type_index: pdb::TypeIndex,
+        this_type_name: &str,
+    ) -> Result<(String, String)> {
+        match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::MemberFunction(data) => {
+                let (return_type_left, return_type_right) = self.type_name(data.return_type)?;
+                let arguments = self
+                    .argument_list(data.argument_list, Some(&format!("{this_type_name}* this")))?;
+                Ok((
+                    format!("{return_type_left} (*"),
+                    format!("){arguments}{return_type_right}"),
+                ))
+            }
+            _ => Ok(("void*".to_string(), String::default())),
+        }
+    }
+
+    fn argument_list(
+        &self,
+        type_index: pdb::TypeIndex,
+        this_argument: Option<&str>,
+    ) -> Result<String> {
+        let mut arguments = vec![];
+        if let Some(this_argument) = this_argument {
+            arguments.push(this_argument.to_string());
+        }
+        if let pdb::TypeData::ArgumentList(data) = self.type_finder.find(type_index)?.parse()? {
+            for argument_type in data.arguments {
+                let (type_left, type_right) = self.type_name(argument_type)?;
+                arguments.push(format!("{type_left}{type_right}"));
+            }
+        }
+        if arguments.is_empty() {
+            // Empty parentheses don't mean "no arguments" in C
+            arguments.push("void".to_string());
+        }
+
+        Ok(format!("({})", arguments.join(", ")))
+    }
+
+    fn array_base_name(
+        &self,
+        type_index: pdb::TypeIndex,
+    ) -> Result<((String, String), Vec<usize>)> {
+        match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Array(data) => {
+                let element_type_index =
+                    resolve_complete_type_index(self.type_forwarder, data.element_type);
+                let (base_name, mut dimensions) = self.array_base_name(element_type_index)?;
+                let element_size = type_size(self.type_finder, element_type_index)?;
+                let mut divider = if element_size == 0 {
+                    log::warn!(
+                        "'{}{}' has invalid size (0), array dimensions might be incorrect",
+                        base_name.0,
+                        base_name.1,
+                    );
+                    1
+                } else {
+                    element_size
+                };
+                for dim_size in data.dimensions {
+                    dimensions.push(dim_size as usize / divider);
+                    divider = dim_size as usize;
+                }
+
+                Ok((base_name, dimensions))
+            }
+            _ => Ok((self.type_name(type_index)?, vec![])),
+        }
+    }
+
+    fn aggregate_name(&self, keyword: &str, name: &str, type_index: pdb::TypeIndex) -> String {
+        let name = if is_unnamed_type(name) {
+            format!("_unnamed_{type_index}")
+        } else {
+            c_identifier(name)
+        };
+        if self.declared_types.contains(&name) {
+            name
+        } else {
+            format!("{keyword} {name}")
+        }
+    }
+}
+
+/// Member made of `size` bytes, used to cover storage which cannot be
+/// represented in C.
+fn byte_array_field(name: &'static str, offset: u64, size: u64) -> Field<'static> {
+    Field {
+        type_left: "uint8_t".to_string(),
+        type_right: format!("[{size}]"),
+        type_index: pdb::TypeIndex(0),
+        name: name.into(),
+        offset,
+        size: size as usize,
+        alignment: 1,
+        bitfield_info: None,
+        access: FieldAccess::None,
+        is_implicit: false,
+    }
+}
+
+fn pointer_size(machine_type: pdb::MachineType) -> u64 {
+    match machine_type {
+        pdb::MachineType::X86
+        | pdb::MachineType::Arm
+        | pdb::MachineType::ArmNT
+        | pdb::MachineType::Thumb
+        | pdb::MachineType::RiscV32 => 4,
+        _ => 8,
+    }
+}
+
+fn integer_type_name(size: u64) -> (String, String) {
+    match size {
+        1 => ("uint8_t".to_string(), String::default()),
+        2 => ("uint16_t".to_string(), String::default()),
+        4 => ("uint32_t".to_string(), String::default()),
+        8 => ("uint64_t".to_string(), String::default()),
+        _ => ("uint8_t".to_string(), format!("[{size}]")),
+    }
+}
+
+/// Turn a C++ name (e.g., `ns::Type<int>`) into a valid C identifier.
+fn c_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if C_ONLY_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+
+    identifier
+}
@@ -1,17 +1,23 @@
 use std::fmt;
 
+use serde::Serialize;
+
 use super::{
     enumeration::Enum,
     field::{FieldAccess, StaticField},
-    fmt_struct_fields_recursive, is_unnamed_type,
+    fmt_offset_static_asserts, fmt_struct_fields, is_unnamed_type,
+    json::{serialize_class_kind, serialize_type_index},
     primitive_types::PrimitiveReconstructionFlavor,
-    resolve_complete_type_index, type_bitfield_info, type_name, type_size,
+    resolve_complete_type_index, type_alignment, type_bitfield_info, type_name, type_size,
     union::Union,
-    AccessSpecifierReconstructionFlavor, DataFormatConfiguration, Field, Method, NeededTypeSet,
-    ReconstructibleTypeData, Result, ResymCoreError, SizePrintFlavor, TypeForwarder,
+    vftable::{vftable_type_name, VirtualFunctionTable, VirtualTableRecords, VFPTR_MEMBER_NAME},
+    AccessSpecifierReconstructionFlavor, AggregateLayout, DataFormatConfiguration, Field, Method,
+    NeededTypeSet, ReconstructibleTypeData, Result, ResymCoreError, SizePrintFlavor,
+    TypeAlignmentMap, TypeForwarder,
 };
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ClassAccess {
     None,
     Private,
@@ -44,16 +50,24 @@ impl fmt::Display for ClassAccess {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct BaseClass {
-    type_name: String,
-    offset: u32,
-    access: ClassAccess,
+    pub type_name: String,
+    /// Index of the base class' (complete) type.
+    #[serde(serialize_with = "serialize_type_index")]
+    pub type_index: pdb::TypeIndex,
+    pub offset: u32,
+    pub access: ClassAccess,
+    /// Virtual base classes are not laid out at a fixed offset, `offset` is
+    /// the offset of the virtual base pointer instead.
+    pub is_virtual: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Class<'p> {
+    #[serde(serialize_with = "serialize_type_index")]
     pub index: pdb::TypeIndex,
+    #[serde(serialize_with = "serialize_class_kind")]
     pub kind: pdb::ClassKind,
     pub name: String,
     pub size: u64,
@@ -65,6 +79,11 @@ pub struct Class<'p> {
     pub nested_classes: Vec<Class<'p>>,
     pub nested_unions: Vec<Union<'p>>,
     pub nested_enums: Vec<Enum<'p>>,
+    /// Primary virtual function table, present for polymorphic classes only.
+    pub vftable: Option<VirtualFunctionTable>,
+    /// Virtual function tables used through the subobjects of secondary
+    /// polymorphic base classes.
+    pub secondary_vftables: Vec<VirtualFunctionTable>,
 }
 
 impl<'p> Class<'p> {
@@ -79,6 +98,86 @@ impl<'p> Class<'p> {
         Ok(())
     }
 
+    /// Reconstruct the virtual function tables of the class and of the
+    /// classes nested in it.
+    pub fn add_vftables(
+        &mut self,
+        type_finder: &pdb::TypeFinder<'p>,
+        type_forwarder: &TypeForwarder,
+        vtable_records: &VirtualTableRecords,
+        primitive_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<()> {
+        let mut vftables = VirtualFunctionTable::find(
+            type_finder,
+            type_forwarder,
+            vtable_records,
+            self.index,
+            primitive_flavor,
+        )?
+        .into_iter();
+        self.vftable = vftables.next();
+        self.secondary_vftables = vftables.collect();
+
+        for class in &mut self.nested_classes {
+            class.add_vftables(
+                type_finder,
+                type_forwarder,
+                vtable_records,
+                primitive_flavor,
+            )?;
+        }
+        for u in &mut self.nested_unions {
+            u.add_vftables(
+                type_finder,
+                type_forwarder,
+                vtable_records,
+                primitive_flavor,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the class' fields, along with the virtual function table
+    /// pointer if the class declares one.
+    fn fields_with_vfptr(&self) -> Vec<Field<'p>> {
+        let mut fields = self.fields.clone();
+        if let Some(vftable) = self.vftable.as_ref().filter(|vftable| vftable.owns_vfptr) {
+            let vfptr = Field {
+                type_left: format!("{}*", vftable_type_name(&self.name)),
+                type_right: String::default(),
+                type_index: pdb::TypeIndex(0),
+                name: VFPTR_MEMBER_NAME.into(),
+                offset: vftable.vfptr_offset,
+                size: vftable.slot_size as usize,
+                alignment: vftable.slot_size,
+                bitfield_info: None,
+                access: FieldAccess::None,
+                is_implicit: true,
+            };
+            let position = fields.partition_point(|field| field.offset < vfptr.offset);
+            fields.insert(position, vfptr);
+        }
+
+        fields
+    }
+
+    /// Indicate if `field` is public in the reconstructed declaration.
+    fn is_field_public(&self, field: &Field, fmt_configuration: &DataFormatConfiguration) -> bool {
+        let has_default_access = match fmt_configuration.print_access_specifiers {
+            AccessSpecifierReconstructionFlavor::Disabled => true,
+            AccessSpecifierReconstructionFlavor::Always => field.access == FieldAccess::None,
+            AccessSpecifierReconstructionFlavor::Automatic => {
+                field.access == FieldAccess::None || field.access == FieldAccess::Public
+            }
+        };
+        if has_default_access {
+            self.kind != pdb::ClassKind::Class
+        } else {
+            field.access == FieldAccess::Public
+        }
+    }
+
     pub fn add_fields(
         &mut self,
         type_finder: &pdb::TypeFinder<'p>,
@@ -86,6 +185,7 @@ impl<'p> Class<'p> {
         type_index: pdb::TypeIndex,
         primitive_flavor: &PrimitiveReconstructionFlavor,
         needed_types: &mut NeededTypeSet,
+        type_alignments: &mut TypeAlignmentMap,
     ) -> Result<()> {
         // Resolve the complete type's index, if present in the PDB
         let complete_type_index = resolve_complete_type_index(type_forwarder, type_index);
@@ -98,6 +198,7 @@ impl<'p> Class<'p> {
                         field,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
 
@@ -109,6 +210,7 @@ impl<'p> Class<'p> {
                         continuation,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
             }
@@ -136,6 +238,8 @@ impl<'p> Class<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vftable: None,
+                    secondary_vftables: Vec::new(),
                 };
 
                 if let Some(derived_from) = data.derived_from {
@@ -149,9 +253,9 @@ impl<'p> Class<'p> {
                         fields,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
-
                 self.nested_classes.insert(0, class);
             }
 
@@ -183,6 +287,7 @@ impl<'p> Class<'p> {
                     data.fields,
                     primitive_flavor,
                     needed_types,
+                    type_alignments,
                 )?;
 
                 self.nested_unions.insert(0, u);
@@ -243,6 +348,7 @@ impl<'p> Class<'p> {
         field: &pdb::TypeData<'p>,
         primitive_flavor: &PrimitiveReconstructionFlavor,
         needed_types: &mut NeededTypeSet,
+        type_alignments: &mut TypeAlignmentMap,
     ) -> Result<()> {
         match *field {
             pdb::TypeData::Member(ref data) => {
@@ -258,16 +364,25 @@ impl<'p> Class<'p> {
                 )?;
                 let type_bitfield_info = type_bitfield_info(type_finder, complete_type_index)?;
                 let type_size = type_size(type_finder, complete_type_index)?;
+                let type_alignment = type_alignment(
+                    type_finder,
+                    type_forwarder,
+                    complete_type_index,
+                    type_alignments,
+                )?;
                 let access = FieldAccess::from_field_attribute(data.attributes.access());
 
                 self.fields.push(Field {
                     type_left,
                     type_right,
+                    type_index: complete_type_index,
                     name: data.name,
                     offset: data.offset,
                     size: type_size,
+                    alignment: type_alignment,
                     bitfield_info: type_bitfield_info,
                     access,
+                    is_implicit: false,
                 });
             }
 
@@ -287,6 +402,7 @@ impl<'p> Class<'p> {
                 self.static_fields.push(StaticField {
                     type_left,
                     type_right,
+                    type_index: complete_type_index,
                     name: data.name,
                     access,
                 });
@@ -364,8 +480,10 @@ impl<'p> Class<'p> {
                         needed_types,
                     )?
                     .0,
+                    type_index: complete_base_class_type_index,
                     offset: data.offset,
                     access: ClassAccess::from_field_attribute(data.attributes.access()),
+                    is_virtual: false,
                 })
             }
 
@@ -382,8 +500,10 @@ impl<'p> Class<'p> {
                         needed_types,
                     )?
                     .0,
+                    type_index: complete_base_class_type_index,
                     offset: data.base_pointer_offset,
                     access: ClassAccess::from_field_attribute(data.attributes.access()),
+                    is_virtual: true,
                 })
             }
 
@@ -423,6 +543,19 @@ impl ReconstructibleTypeData for Class<'_> {
         fmt_configuration: &DataFormatConfiguration,
         f: &mut impl std::fmt::Write,
     ) -> fmt::Result {
+        if fmt_configuration.print_vftables {
+            for vftable in self.vftable.iter().chain(&self.secondary_vftables) {
+                vftable.reconstruct(&self.name, fmt_configuration, f)?;
+                writeln!(f)?;
+            }
+        }
+
+        let fields = self.fields_with_vfptr();
+        let layout = AggregateLayout::new(fmt_configuration, &fields, self.size);
+        if let Some(packing) = layout.packing {
+            writeln!(f, "#pragma pack(push, {packing})")?;
+        }
+
         write!(
             f,
             "{} {}",
@@ -468,7 +601,14 @@ impl ReconstructibleTypeData for Class<'_> {
             )?;
         }
 
-        // Nested declarations
+        // Nested declarations (enums first, as they may be used by
+        // the other types)
+        if !self.nested_enums.is_empty() {
+            writeln!(f, "  ")?;
+            for e in &self.nested_enums {
+                e.reconstruct(fmt_configuration, f)?;
+            }
+        }
         if !self.nested_classes.is_empty() {
             writeln!(f, "  ")?;
             for class in &self.nested_classes {
@@ -481,15 +621,30 @@ impl ReconstructibleTypeData for Class<'_> {
                 u.reconstruct(fmt_configuration, f)?;
             }
         }
-        if !self.nested_enums.is_empty() {
-            writeln!(f, "  ")?;
-            for e in &self.nested_enums {
-                e.reconstruct(fmt_configuration, f)?;
-            }
-        }
 
-        // Dump fields while detecting unnamed structs and unions
-        fmt_struct_fields_recursive(fmt_configuration, &self.fields, 1, f)?;
+        // Dump fields while detecting unnamed structs and unions. Base classes
+        // are located before the fields and virtual base classes after them.
+        let storage_start = if self.base_classes.is_empty() {
+            0
+        } else {
+            fields.first().map(|field| field.offset).unwrap_or_default()
+        };
+        let storage_end = if self.base_classes.iter().any(|base| base.is_virtual) {
+            fields
+                .iter()
+                .map(|field| field.offset + field.size as u64)
+                .max()
+                .unwrap_or_default()
+        } else {
+            self.size
+        };
+        fmt_struct_fields(
+            fmt_configuration,
+            &layout,
+            &fields,
+            storage_start..storage_end,
+            f,
+        )?;
 
         // Static fields
         for field in &self.static_fields {
@@ -518,7 +673,12 @@ impl ReconstructibleTypeData for Class<'_> {
             let class_name = self.name.as_str().into();
             writeln!(f, "  ")?;
             for method in &self.instance_methods {
-                let method_has_class_name = method.name == class_name;
+                // Note: the return type of constructors, destructors and
+                // conversion functions isn't declared
+                let has_return_type = !(method.is_ctor
+                    || method.is_dtor
+                    || method.name == class_name
+                    || method.is_conversion_operator());
                 writeln!(
                     f,
                     "  {}{}{}{}{}({}){}{}{}{};",
@@ -536,16 +696,12 @@ impl ReconstructibleTypeData for Class<'_> {
                         }
                     },
                     if method.is_virtual { "virtual " } else { "" },
-                    if method.is_ctor || method.is_dtor || method_has_class_name {
-                        ""
-                    } else {
+                    if has_return_type {
                         &method.return_type_name.0
+                    } else {
+                        ""
                     },
-                    if !method.is_ctor
-                        && !method.is_dtor
-                        && !method_has_class_name
-                        && method.return_type_name.1.is_empty()
-                    {
+                    if has_return_type && method.return_type_name.1.is_empty() {
                         " "
                     } else {
                         ""
@@ -605,6 +761,9 @@ impl ReconstructibleTypeData for Class<'_> {
         }
 
         writeln!(f, "}};")?;
+        if layout.packing.is_some() {
+            writeln!(f, "#pragma pack(pop)")?;
+        }
         if fmt_configuration.size_print_flavor == SizePrintFlavor::StaticAssert {
             if fmt_configuration.integers_as_hexadecimal {
                 writeln!(
@@ -620,6 +779,15 @@ impl ReconstructibleTypeData for Class<'_> {
                 )?;
             }
         }
+        if fmt_configuration.print_offset_asserts {
+            fmt_offset_static_asserts(
+                fmt_configuration,
+                &self.name,
+                &self.fields,
+                |field| self.is_field_public(field, fmt_configuration),
+                f,
+            )?;
+        }
 
         Ok(())
     }
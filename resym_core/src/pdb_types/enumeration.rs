@@ -1,10 +1,16 @@
 use std::fmt;
 
-use super::{DataFormatConfiguration, NeededTypeSet, ReconstructibleTypeData};
+use serde::Serialize;
+
+use super::{
+    json::{serialize_raw_string, serialize_type_index, serialize_variant},
+    DataFormatConfiguration, NeededTypeSet, ReconstructibleTypeData,
+};
 use crate::error::Result;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Enum<'p> {
+    #[serde(serialize_with = "serialize_type_index")]
     pub index: pdb::TypeIndex,
     pub name: String,
     pub underlying_type_name: String,
@@ -80,40 +86,7 @@ impl ReconstructibleTypeData for Enum<'_> {
                 f,
                 "  {} = {},",
                 value.name.to_string(),
-                match value.value {
-                    pdb::Variant::U8(v) => {
-                        if fmt_configuration.integers_as_hexadecimal {
-                            format!("0x{v:02x}")
-                        } else {
-                            format!("{v}")
-                        }
-                    }
-                    pdb::Variant::U16(v) => {
-                        if fmt_configuration.integers_as_hexadecimal {
-                            format!("0x{v:04x}")
-                        } else {
-                            format!("{v}")
-                        }
-                    }
-                    pdb::Variant::U32(v) => {
-                        if fmt_configuration.integers_as_hexadecimal {
-                            format!("0x{v:08x}")
-                        } else {
-                            format!("{v}")
-                        }
-                    }
-                    pdb::Variant::U64(v) => {
-                        if fmt_configuration.integers_as_hexadecimal {
-                            format!("0x{v:16x}")
-                        } else {
-                            format!("{v}")
-                        }
-                    }
-                    pdb::Variant::I8(v) => format!("{v}"),
-                    pdb::Variant::I16(v) => format!("{v}"),
-                    pdb::Variant::I32(v) => format!("{v}"),
-                    pdb::Variant::I64(v) => format!("{v}"),
-                }
+                value.format_value(fmt_configuration),
             )?;
         }
         writeln!(f, "}};")?;
@@ -122,8 +95,50 @@ impl ReconstructibleTypeData for Enum<'_> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct EnumValue<'p> {
-    name: pdb::RawString<'p>,
-    value: pdb::Variant,
+    #[serde(serialize_with = "serialize_raw_string")]
+    pub name: pdb::RawString<'p>,
+    #[serde(serialize_with = "serialize_variant")]
+    pub value: pdb::Variant,
+}
+
+impl EnumValue<'_> {
+    /// Format the value as it should appear in an enum declaration.
+    pub fn format_value(&self, fmt_configuration: &DataFormatConfiguration) -> String {
+        match self.value {
+            pdb::Variant::U8(v) => {
+                if fmt_configuration.integers_as_hexadecimal {
+                    format!("0x{v:02x}")
+                } else {
+                    format!("{v}")
+                }
+            }
+            pdb::Variant::U16(v) => {
+                if fmt_configuration.integers_as_hexadecimal {
+                    format!("0x{v:04x}")
+                } else {
+                    format!("{v}")
+                }
+            }
+            pdb::Variant::U32(v) => {
+                if fmt_configuration.integers_as_hexadecimal {
+                    format!("0x{v:08x}")
+                } else {
+                    format!("{v}")
+                }
+            }
+            pdb::Variant::U64(v) => {
+                if fmt_configuration.integers_as_hexadecimal {
+                    format!("0x{v:16x}")
+                } else {
+                    format!("{v}")
+                }
+            }
+            pdb::Variant::I8(v) => format!("{v}"),
+            pdb::Variant::I16(v) => format!("{v}"),
+            pdb::Variant::I32(v) => format!("{v}"),
+            pdb::Variant::I64(v) => format!("{v}"),
+        }
+    }
 }
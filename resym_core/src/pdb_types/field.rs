@@ -1,29 +1,50 @@
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use serde::Serialize;
+
+use super::json::{serialize_bitfield_info, serialize_raw_string, serialize_type_index};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Field<'p> {
     pub type_left: String,
     pub type_right: String,
+    /// Index of the field's (complete) type.
+    #[serde(serialize_with = "serialize_type_index")]
+    pub type_index: pdb::TypeIndex,
+    #[serde(serialize_with = "serialize_raw_string")]
     pub name: pdb::RawString<'p>,
     /// Offset of the field in bytes within the current struct/union.
     pub offset: u64,
     /// Size of the field in bytes.
     pub size: usize,
+    /// Natural alignment of the field's type in bytes.
+    pub alignment: u64,
     /// Offset and size of the field in bits, within the current type's size (0 means LSB).
     /// Present only for bitfield members.
+    #[serde(serialize_with = "serialize_bitfield_info")]
     pub bitfield_info: Option<(u8, u8)>,
     pub access: FieldAccess,
+    /// Whether the member is implicitly declared by the compiler (e.g., the
+    /// virtual function table pointer of a C++ class). Such members are only
+    /// shown in comments.
+    #[serde(skip)]
+    pub is_implicit: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct StaticField<'p> {
     pub type_left: String,
     pub type_right: String,
+    /// Index of the field's (complete) type.
+    #[serde(serialize_with = "serialize_type_index")]
+    pub type_index: pdb::TypeIndex,
+    #[serde(serialize_with = "serialize_raw_string")]
     pub name: pdb::RawString<'p>,
     pub access: FieldAccess,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FieldAccess {
     None,
     Private,
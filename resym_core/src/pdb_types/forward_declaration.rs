@@ -1,9 +1,12 @@
 use std::fmt;
 
-use super::{DataFormatConfiguration, ReconstructibleTypeData};
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+use super::{json::serialize_type_index, DataFormatConfiguration, ReconstructibleTypeData};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ForwardDeclaration {
+    #[serde(serialize_with = "serialize_type_index")]
     pub index: pdb::TypeIndex,
     pub kind: ForwardDeclarationKind,
     pub name: String,
@@ -29,7 +32,8 @@ impl ReconstructibleTypeData for ForwardDeclaration {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ForwardDeclarationKind {
     Class,
     Struct,
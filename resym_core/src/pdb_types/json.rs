@@ -0,0 +1,142 @@
+use serde::{Serialize, Serializer};
+
+use super::{
+    class::Class, enumeration::Enum, forward_declaration::ForwardDeclaration, union::Union, Data,
+    SourceLocation,
+};
+use crate::error::Result;
+
+/// Serializable representation of the content of a `Data` object.
+#[derive(Serialize)]
+struct JsonTypeData<'a, 'p> {
+    forward_declarations: Vec<&'a ForwardDeclaration>,
+    enums: Vec<JsonType<'a, Enum<'p>>>,
+    classes: Vec<JsonType<'a, Class<'p>>>,
+    unions: Vec<JsonType<'a, Union<'p>>>,
+}
+
+/// Top-level type, along with the indices of the types it references.
+#[derive(Serialize)]
+struct JsonType<'a, T> {
+    #[serde(flatten)]
+    data: &'a T,
+    referenced_type_indices: Vec<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_location: Option<&'a SourceLocation>,
+}
+
+impl<'p> Data<'p> {
+    /// Write a JSON representation of the types contained in this object.
+    pub fn reconstruct_json(&self, output_writer: &mut impl std::fmt::Write) -> Result<()> {
+        let is_ignored =
+            |name: &str| -> bool { self.ignore_std_types && name.starts_with("std::") };
+        let referenced_type_indices = |type_index: &pdb::TypeIndex| -> Vec<u32> {
+            self.referenced_types
+                .get(type_index)
+                .map(|type_indices| type_indices.iter().map(|e| e.0).collect())
+                .unwrap_or_default()
+        };
+
+        let json_type_data = JsonTypeData {
+            forward_declarations: self
+                .forward_declarations
+                .values()
+                .filter(|e| !is_ignored(&e.name))
+                .collect(),
+            enums: self
+                .enums
+                .iter()
+                .filter(|(_, e)| !is_ignored(&e.name))
+                .map(|(type_index, e)| JsonType {
+                    data: e,
+                    referenced_type_indices: referenced_type_indices(type_index),
+                    source_location: self.source_locations.get(type_index),
+                })
+                .collect(),
+            classes: self
+                .classes
+                .iter()
+                .filter(|(_, c)| !is_ignored(&c.name))
+                .map(|(type_index, c)| JsonType {
+                    data: c,
+                    referenced_type_indices: referenced_type_indices(type_index),
+                    source_location: self.source_locations.get(type_index),
+                })
+                .collect(),
+            unions: self
+                .unions
+                .iter()
+                .filter(|(_, u)| !is_ignored(&u.name))
+                .map(|(type_index, u)| JsonType {
+                    data: u,
+                    referenced_type_indices: referenced_type_indices(type_index),
+                    source_location: self.source_locations.get(type_index),
+                })
+                .collect(),
+        };
+        writeln!(
+            output_writer,
+            "{}",
+            serde_json::to_string_pretty(&json_type_data)?
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Bit-field information, as stored in `Field::bitfield_info`.
+#[derive(Serialize)]
+struct BitfieldInfo {
+    position: u8,
+    length: u8,
+}
+
+pub(crate) fn serialize_type_index<S: Serializer>(
+    type_index: &pdb::TypeIndex,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_u32(type_index.0)
+}
+
+pub(crate) fn serialize_raw_string<S: Serializer>(
+    raw_string: &pdb::RawString,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&raw_string.to_string())
+}
+
+pub(crate) fn serialize_variant<S: Serializer>(
+    variant: &pdb::Variant,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match *variant {
+        pdb::Variant::U8(value) => serializer.serialize_u8(value),
+        pdb::Variant::U16(value) => serializer.serialize_u16(value),
+        pdb::Variant::U32(value) => serializer.serialize_u32(value),
+        pdb::Variant::U64(value) => serializer.serialize_u64(value),
+        pdb::Variant::I8(value) => serializer.serialize_i8(value),
+        pdb::Variant::I16(value) => serializer.serialize_i16(value),
+        pdb::Variant::I32(value) => serializer.serialize_i32(value),
+        pdb::Variant::I64(value) => serializer.serialize_i64(value),
+    }
+}
+
+pub(crate) fn serialize_class_kind<S: Serializer>(
+    class_kind: &pdb::ClassKind,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(match class_kind {
+        pdb::ClassKind::Class => "class",
+        pdb::ClassKind::Struct => "struct",
+        pdb::ClassKind::Interface => "interface",
+    })
+}
+
+pub(crate) fn serialize_bitfield_info<S: Serializer>(
+    bitfield_info: &Option<(u8, u8)>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    bitfield_info
+        .map(|(position, length)| BitfieldInfo { position, length })
+        .serialize(serializer)
+}
@@ -1,12 +1,21 @@
+use serde::Serialize;
+
 use super::{
-    argument_list, field::FieldAccess, primitive_types::PrimitiveReconstructionFlavor, type_name,
-    NeededTypeSet, TypeForwarder,
+    argument_list,
+    field::FieldAccess,
+    json::{serialize_raw_string, serialize_type_index},
+    primitive_types::PrimitiveReconstructionFlavor,
+    type_name, NeededTypeSet, TypeForwarder,
 };
 use crate::error::{Result, ResymCoreError};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Method<'p> {
+    #[serde(serialize_with = "serialize_raw_string")]
     pub name: pdb::RawString<'p>,
+    /// Index of the method's `LF_MFUNCTION` type.
+    #[serde(serialize_with = "serialize_type_index")]
+    pub type_index: pdb::TypeIndex,
     pub return_type_name: (String, String),
     pub arguments: Vec<(String, String)>,
     pub is_virtual: bool,
@@ -31,6 +40,7 @@ impl<'p> Method<'p> {
         match type_finder.find(type_index)?.parse()? {
             pdb::TypeData::MemberFunction(data) => Ok(Method {
                 name,
+                type_index,
                 return_type_name: type_name(
                     type_finder,
                     type_forwarder,
@@ -79,6 +89,17 @@ impl<'p> Method<'p> {
         }
     }
 
+    /// Indicate if the method is a conversion function (e.g., `operator bool`),
+    /// whose return type is implied by its name.
+    pub fn is_conversion_operator(&self) -> bool {
+        self.name
+            .to_string()
+            .strip_prefix("operator ")
+            .is_some_and(|target| {
+                !matches!(target, "new" | "new[]" | "delete" | "delete[]" | "co_await")
+            })
+    }
+
     pub fn find_func_modifier(
         member_func_type: &pdb::MemberFunctionType,
         type_finder: &pdb::TypeFinder<'p>,
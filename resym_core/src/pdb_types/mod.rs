@@ -1,15 +1,21 @@
+mod c;
 mod class;
 mod enumeration;
 mod field;
 mod forward_declaration;
+mod json;
 mod method;
 mod primitive_types;
+mod rust;
 mod union;
+mod vftable;
 
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::ops::Range;
 
+use serde::Serialize;
+
 use crate::error::{Result, ResymCoreError};
 use class::Class;
 use enumeration::Enum;
@@ -19,9 +25,12 @@ use primitive_types::primitive_kind_as_str;
 use union::Union;
 
 pub use primitive_types::{
-    include_headers_for_flavor, AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor,
+    include_c_headers_for_flavor, include_headers_for_flavor, AccessSpecifierReconstructionFlavor,
+    GraphFormat, HeaderSplitFlavor, OutputFormat, OutputLanguage, PrimitiveReconstructionFlavor,
     SizePrintFlavor,
 };
+pub use rust::RUST_FILE_ATTRIBUTES;
+pub use vftable::VirtualTableRecords;
 
 use self::forward_declaration::{ForwardDeclaration, ForwardDeclarationKind};
 
@@ -31,8 +40,25 @@ use self::forward_declaration::{ForwardDeclaration, ForwardDeclarationKind};
 /// or a C++ reference.
 pub type NeededTypeSet = HashSet<(pdb::TypeIndex, bool)>;
 
+/// Alignment in bytes of the (complete) types encountered so far, which saves
+/// walking the members of aggregates several times.
+pub type TypeAlignmentMap = HashMap<pdb::TypeIndex, u64>;
+
 pub type TypeForwarder = dashmap::DashMap<pdb::TypeIndex, pdb::TypeIndex>;
 
+/// Location of a declaration in the source code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SourceLocation {
+    pub file_path: String,
+    pub line: u32,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file_path, self.line)
+    }
+}
+
 /// Return a pair of strings representing the given `type_index`.
 pub fn type_name(
     type_finder: &pdb::TypeFinder,
@@ -75,7 +101,13 @@ pub fn type_name(
 
         pdb::TypeData::Enumeration(data) => {
             needed_types.insert((type_index, false));
-            (data.name.to_string().into_owned(), String::default())
+            // Rename unnamed anonymous tags to something unique
+            let name = data.name.to_string();
+            if is_unnamed_type(&name) {
+                (unnamed_enum_name(&name, type_index), String::default())
+            } else {
+                (name.into_owned(), String::default())
+            }
         }
 
         pdb::TypeData::Pointer(data) => {
@@ -230,10 +262,12 @@ pub fn type_name(
                 needed_types,
             )?;
 
+            // Note: the return type's declarator (e.g., a function pointer's
+            // argument list) wraps the procedure's
             (
-                format!("{ret_type_left}{ret_type_right} ("),
+                format!("{ret_type_left} ("),
                 format!(
-                    ")({})",
+                    ")({}){ret_type_right}",
                     arg_list
                         .into_iter()
                         .map(|(type_left, type_right)| format!("{type_left}{type_right}"))
@@ -273,9 +307,9 @@ pub fn type_name(
             )?;
 
             (
-                format!("{ret_type_left}{ret_type_right} ({class_type_left}::"),
+                format!("{ret_type_left} ({class_type_left}::"),
                 format!(
-                    ")({})",
+                    ")({}){ret_type_right}",
                     arg_list
                         .into_iter()
                         .map(|(type_left, type_right)| format!("{type_left}{type_right}"))
@@ -405,6 +439,7 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
                 pdb::PrimitiveKind::Char
                 | pdb::PrimitiveKind::RChar
                 | pdb::PrimitiveKind::UChar
+                | pdb::PrimitiveKind::Char8
                 | pdb::PrimitiveKind::I8
                 | pdb::PrimitiveKind::U8
                 | pdb::PrimitiveKind::Bool8 => 1,
@@ -415,6 +450,7 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
                 | pdb::PrimitiveKind::Short
                 | pdb::PrimitiveKind::U16
                 | pdb::PrimitiveKind::UShort
+                | pdb::PrimitiveKind::F16
                 | pdb::PrimitiveKind::Bool16 => 2,
 
                 pdb::PrimitiveKind::RChar32
@@ -423,6 +459,7 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
                 | pdb::PrimitiveKind::U32
                 | pdb::PrimitiveKind::ULong
                 | pdb::PrimitiveKind::F32
+                | pdb::PrimitiveKind::HRESULT
                 | pdb::PrimitiveKind::Bool32 => 4,
 
                 pdb::PrimitiveKind::I64
@@ -432,6 +469,11 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
                 | pdb::PrimitiveKind::F64
                 | pdb::PrimitiveKind::Bool64 => 8,
 
+                pdb::PrimitiveKind::Octa
+                | pdb::PrimitiveKind::UOcta
+                | pdb::PrimitiveKind::I128
+                | pdb::PrimitiveKind::U128 => 16,
+
                 _ => 0,
             };
 
@@ -451,7 +493,7 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
 
         pdb::TypeData::Class(data) => data.size as usize,
 
-        pdb::TypeData::Enumeration(data) => type_size(type_finder, data.underlying_type)?,
+        pdb::TypeData::Enumeration(data) => type_size(type_finder, enum_underlying_type(&data))?,
 
         pdb::TypeData::Union(data) => data.size as usize,
 
@@ -473,6 +515,8 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
 
         pdb::TypeData::Modifier(data) => type_size(type_finder, data.underlying_type)?,
 
+        pdb::TypeData::Bitfield(data) => type_size(type_finder, data.underlying_type)?,
+
         pdb::TypeData::Array(data) => *data.dimensions.iter().last().unwrap_or(&0) as usize,
 
         _ => 0,
@@ -481,6 +525,134 @@ pub fn type_size(type_finder: &pdb::TypeFinder, type_index: pdb::TypeIndex) -> R
     Ok(size)
 }
 
+/// Return the underlying type of the given enumeration. Forward declared
+/// enumerations have no underlying type, they're assumed to be `int`-based.
+pub fn enum_underlying_type(data: &pdb::EnumerationType) -> pdb::TypeIndex {
+    if data.underlying_type == pdb::TypeIndex(0) {
+        // T_INT4
+        pdb::TypeIndex(0x74)
+    } else {
+        data.underlying_type
+    }
+}
+
+/// Return the type's natural alignment in bytes.
+pub fn type_alignment(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+    type_alignments: &mut TypeAlignmentMap,
+) -> Result<u64> {
+    let type_index = resolve_complete_type_index(type_forwarder, type_index);
+    if let Some(alignment) = type_alignments.get(&type_index) {
+        return Ok(*alignment);
+    }
+
+    let alignment = match type_finder.find(type_index)?.parse()? {
+        pdb::TypeData::Primitive(_) => type_size(type_finder, type_index)? as u64,
+
+        pdb::TypeData::Pointer(data) => {
+            if data.containing_class.is_some() {
+                // The layout of pointers to members is implementation-defined
+                1
+            } else {
+                type_size(type_finder, type_index)? as u64
+            }
+        }
+
+        pdb::TypeData::Modifier(data) => type_alignment(
+            type_finder,
+            type_forwarder,
+            data.underlying_type,
+            type_alignments,
+        )?,
+
+        pdb::TypeData::Bitfield(data) => type_alignment(
+            type_finder,
+            type_forwarder,
+            data.underlying_type,
+            type_alignments,
+        )?,
+
+        pdb::TypeData::Enumeration(data) => type_alignment(
+            type_finder,
+            type_forwarder,
+            enum_underlying_type(&data),
+            type_alignments,
+        )?,
+
+        pdb::TypeData::Array(data) => type_alignment(
+            type_finder,
+            type_forwarder,
+            data.element_type,
+            type_alignments,
+        )?,
+
+        pdb::TypeData::Class(data) => match data.fields {
+            Some(fields) if !data.properties.forward_reference() => {
+                field_list_alignment(type_finder, type_forwarder, fields, type_alignments)?
+            }
+            _ => 1,
+        },
+
+        pdb::TypeData::Union(data) if !data.properties.forward_reference() => {
+            field_list_alignment(type_finder, type_forwarder, data.fields, type_alignments)?
+        }
+
+        _ => 1,
+    }
+    .max(1);
+    type_alignments.insert(type_index, alignment);
+
+    Ok(alignment)
+}
+
+fn field_list_alignment(
+    type_finder: &pdb::TypeFinder,
+    type_forwarder: &TypeForwarder,
+    type_index: pdb::TypeIndex,
+    type_alignments: &mut TypeAlignmentMap,
+) -> Result<u64> {
+    let mut alignment = 1;
+    let mut field_list_index = Some(type_index);
+    while let Some(current_field_list_index) = field_list_index {
+        let field_list = match type_finder.find(current_field_list_index)?.parse()? {
+            pdb::TypeData::FieldList(data) => data,
+            _ => break,
+        };
+        for field in &field_list.fields {
+            let field_alignment = match field {
+                pdb::TypeData::Member(data) => type_alignment(
+                    type_finder,
+                    type_forwarder,
+                    data.field_type,
+                    type_alignments,
+                )?,
+                pdb::TypeData::BaseClass(data) => type_alignment(
+                    type_finder,
+                    type_forwarder,
+                    data.base_class,
+                    type_alignments,
+                )?,
+                pdb::TypeData::VirtualBaseClass(data) => type_alignment(
+                    type_finder,
+                    type_forwarder,
+                    data.base_pointer,
+                    type_alignments,
+                )?,
+                pdb::TypeData::VirtualFunctionTablePointer(data) => {
+                    type_alignment(type_finder, type_forwarder, data.table, type_alignments)?
+                }
+                _ => 1,
+            };
+            alignment = alignment.max(field_alignment);
+        }
+        field_list_index = field_list.continuation;
+    }
+
+    Ok(alignment)
+}
+
 /// Indicate if the given `type_name` is the name of an anonymous type.
 pub fn is_unnamed_type(type_name: &str) -> bool {
     type_name.contains("<anonymous-")
@@ -488,6 +660,191 @@ pub fn is_unnamed_type(type_name: &str) -> bool {
         || type_name.contains("__unnamed")
 }
 
+/// Return the byte offsets of the scope separators (i.e., `::`) of the given
+/// qualified type name, ignoring those located in template argument lists.
+fn scope_separator_offsets(type_name: &str) -> Vec<usize> {
+    let mut offsets = vec![];
+    let mut depth = 0_usize;
+    let mut chars = type_name.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 && chars.peek().is_some_and(|(_, next)| *next == ':') => {
+                offsets.push(offset);
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+
+    offsets
+}
+
+/// Return a unique name for the given anonymous enumeration. Its scope is
+/// kept, as its enumerators are declared in the enclosing scope and would
+/// conflict with those of other anonymous enumerations otherwise.
+fn unnamed_enum_name(type_name: &str, type_index: pdb::TypeIndex) -> String {
+    match scope_separator_offsets(type_name).last() {
+        Some(offset) => format!("{}::_unnamed_{type_index}", &type_name[..*offset]),
+        None => format!("_unnamed_{type_index}"),
+    }
+}
+
+/// Return the name of the given type without its scopes.
+fn unqualified_type_name(type_name: &str) -> &str {
+    match scope_separator_offsets(type_name).last() {
+        Some(offset) => &type_name[offset + 2..],
+        None => type_name,
+    }
+}
+
+/// Split the given qualified type name into the namespaces it's declared in
+/// and its name relative to them. Scopes located after the first class scope
+/// are kept in the relative name.
+fn split_namespace(type_name: &str, is_class: impl Fn(&str) -> bool) -> (Vec<&str>, &str) {
+    let mut namespace = vec![];
+    let mut scope_start = 0;
+    for offset in scope_separator_offsets(type_name) {
+        if is_class(&type_name[..offset]) {
+            break;
+        }
+        namespace.push(&type_name[scope_start..offset]);
+        scope_start = offset + 2;
+    }
+
+    (namespace, &type_name[scope_start..])
+}
+
+/// Return the namespaces in which the given symbol is declared, up to its
+/// first class or templated scope, or `None` if it's declared in the global
+/// namespace.
+pub(crate) fn symbol_namespace(symbol_name: &str, is_class: impl Fn(&str) -> bool) -> Option<&str> {
+    let mut namespace_end = None;
+    let mut scope_start = 0;
+    for offset in scope_separator_offsets(symbol_name) {
+        if symbol_name[scope_start..offset].contains('<') || is_class(&symbol_name[..offset]) {
+            break;
+        }
+        namespace_end = Some(offset);
+        scope_start = offset + 2;
+    }
+
+    namespace_end.map(|namespace_end| &symbol_name[..namespace_end])
+}
+
+/// Return the template family of the given symbol (i.e., its name up to its
+/// first template argument list, with the arguments removed), or `None` if
+/// it's not templated.
+pub(crate) fn template_family(symbol_name: &str) -> Option<String> {
+    symbol_name
+        .match_indices('<')
+        .map(|(offset, _)| &symbol_name[..offset])
+        .find(|prefix| {
+            // Ignore comparison and shift operators, as well as MSVC's
+            // special names (e.g., `<lambda_1>`)
+            !(prefix.is_empty()
+                || prefix.ends_with("::")
+                || prefix.ends_with("operator")
+                || prefix.ends_with("operator<"))
+        })
+        .map(|prefix| format!("{prefix}<>"))
+}
+
+/// Return the template family of the given unqualified class name and a
+/// parameter list for its primary template, or `None` if it's not a template
+/// specialization. Parameter kinds are deduced from the arguments.
+fn template_specialization_parameters(type_name: &str) -> Option<(&str, String)> {
+    if !scope_separator_offsets(type_name).is_empty() {
+        return None;
+    }
+    let family = template_family(type_name)?;
+    let family = &type_name[..family.len() - "<>".len()];
+    let arguments = type_name.get(family.len() + 1..)?.strip_suffix('>')?.trim();
+    if arguments.is_empty() {
+        return Some((family, "typename...".to_string()));
+    }
+
+    let mut parameters = vec![];
+    let mut depth = 0_usize;
+    let mut argument_start = 0;
+    for (offset, c) in arguments.char_indices().chain([(arguments.len(), ',')]) {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                let argument = arguments[argument_start..offset].trim();
+                let is_value = argument == "true"
+                    || argument == "false"
+                    || argument.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+                parameters.push(if is_value { "auto" } else { "typename" });
+                argument_start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+
+    Some((family, parameters.join(", ")))
+}
+
+/// Anonymous namespaces are named "`anonymous namespace'" by MSVC.
+fn is_anonymous_namespace(namespace: &str) -> bool {
+    namespace.starts_with("`anonymous")
+}
+
+fn fmt_namespace_begin(namespace: &[&str], f: &mut impl std::fmt::Write) -> fmt::Result {
+    if namespace.is_empty() {
+        return Ok(());
+    }
+
+    // Note: consecutive named namespaces are merged, anonymous namespaces
+    // have to be declared on their own
+    let mut named_namespaces: Vec<&str> = vec![];
+    let mut declarations: Vec<String> = vec![];
+    for scope in namespace {
+        if is_anonymous_namespace(scope) {
+            if !named_namespaces.is_empty() {
+                declarations.push(format!("namespace {} {{", named_namespaces.join("::")));
+                named_namespaces.clear();
+            }
+            declarations.push("namespace {".to_string());
+        } else {
+            named_namespaces.push(scope);
+        }
+    }
+    if !named_namespaces.is_empty() {
+        declarations.push(format!("namespace {} {{", named_namespaces.join("::")));
+    }
+    writeln!(f, "{}", declarations.join(" "))
+}
+
+fn fmt_namespace_end(namespace: &[&str], f: &mut impl std::fmt::Write) -> fmt::Result {
+    if namespace.is_empty() {
+        return Ok(());
+    }
+
+    // One closing bracket per declaration (see `fmt_namespace_begin`)
+    let mut bracket_count = 0;
+    let mut previous_is_named = false;
+    for scope in namespace {
+        if is_anonymous_namespace(scope) {
+            bracket_count += 1;
+            previous_is_named = false;
+        } else {
+            if !previous_is_named {
+                bracket_count += 1;
+            }
+            previous_is_named = true;
+        }
+    }
+    writeln!(
+        f,
+        "{} // namespace {}",
+        "}".repeat(bracket_count),
+        namespace.join("::")
+    )
+}
+
 /// Trait for type data that can be reconstructed to C++
 pub trait ReconstructibleTypeData {
     fn reconstruct(
@@ -511,11 +868,20 @@ pub struct Data<'p> {
     classes: BTreeMap<pdb::TypeIndex, Class<'p>>,
     /// Union types
     unions: BTreeMap<pdb::TypeIndex, Union<'p>>,
-    /// Unique type names
-    type_names: HashSet<String>,
+    /// Unique type names, mapped to the index of the type reconstructed
+    type_names: HashMap<String, pdb::TypeIndex>,
+    /// Types skipped because their name was already taken, mapped to the
+    /// index of the type reconstructed in their place
+    duplicate_types: HashMap<pdb::TypeIndex, pdb::TypeIndex>,
+    /// Types referenced by each of the enums, classes/structs and unions
+    referenced_types: BTreeMap<pdb::TypeIndex, BTreeSet<pdb::TypeIndex>>,
+    /// Declaration site of the enums, classes/structs and unions, if known
+    source_locations: HashMap<pdb::TypeIndex, SourceLocation>,
+    /// Alignment of the types of the members encountered so far
+    type_alignments: TypeAlignmentMap,
 }
 
-impl Data<'_> {
+impl<'p> Data<'p> {
     pub fn reconstruct(
         &self,
         fmt_configuration: &DataFormatConfiguration,
@@ -545,6 +911,7 @@ impl Data<'_> {
                             continue;
                         }
                         writeln!(output_writer)?;
+                        self.fmt_source_location(*type_index, output_writer)?;
                         e.reconstruct(fmt_configuration, output_writer)?;
                     }
                     // Class definitions
@@ -554,6 +921,7 @@ impl Data<'_> {
                             continue;
                         }
                         writeln!(output_writer)?;
+                        self.fmt_source_location(*type_index, output_writer)?;
                         c.reconstruct(fmt_configuration, output_writer)?;
                     }
                     // Union definitions
@@ -563,6 +931,7 @@ impl Data<'_> {
                             continue;
                         }
                         writeln!(output_writer)?;
+                        self.fmt_source_location(*type_index, output_writer)?;
                         u.reconstruct(fmt_configuration, output_writer)?;
                     }
                 }
@@ -571,38 +940,316 @@ impl Data<'_> {
             // Follow type index order
             //
             // Enum definitions
-            for e in self.enums.values() {
+            for (type_index, e) in self.enums.iter() {
                 if self.ignore_std_types && e.name.starts_with("std::") {
                     // Type is in the `std` namespace and should be ignored
                     continue;
                 }
                 writeln!(output_writer)?;
+                self.fmt_source_location(*type_index, output_writer)?;
                 e.reconstruct(fmt_configuration, output_writer)?;
             }
 
             // Class/struct definitions
-            for class in self.classes.values() {
+            for (type_index, class) in self.classes.iter() {
                 if self.ignore_std_types && class.name.starts_with("std::") {
                     // Type is in the `std` namespace and should be ignored
                     continue;
                 }
                 writeln!(output_writer)?;
+                self.fmt_source_location(*type_index, output_writer)?;
                 class.reconstruct(fmt_configuration, output_writer)?;
             }
 
             // Union definitions
-            for u in self.unions.values() {
+            for (type_index, u) in self.unions.iter() {
                 if self.ignore_std_types && u.name.starts_with("std::") {
                     // Type is in the `std` namespace and should be ignored
                     continue;
                 }
                 writeln!(output_writer)?;
+                self.fmt_source_location(*type_index, output_writer)?;
                 u.reconstruct(fmt_configuration, output_writer)?;
             }
         }
 
         Ok(())
     }
+
+    /// Reconstruct types in C++ with their namespaces declared as blocks,
+    /// instead of using qualified type names. Nested types are defined in
+    /// their enclosing class if it's reconstructed as well.
+    ///
+    /// `class_names` contains the names of the known classes and unions, which
+    /// is used to tell nested types apart from namespace members.
+    pub fn reconstruct_with_namespaces(
+        &self,
+        fmt_configuration: &DataFormatConfiguration,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+        class_names: &HashSet<&str>,
+        output_writer: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let is_ignored =
+            |name: &str| -> bool { self.ignore_std_types && name.starts_with("std::") };
+        let forward_declaration_names: HashSet<&str> = self
+            .forward_declarations
+            .values()
+            .map(|e| e.name.as_str())
+            .collect();
+        let is_class = |name: &str| -> bool {
+            // Note: namespaces cannot be templated
+            name.ends_with('>')
+                || class_names.contains(name)
+                || forward_declaration_names.contains(name)
+                || self.classes_and_unions_index(name).is_some()
+        };
+
+        // Forward declarations, grouped by namespace
+        let mut forward_declarations: Vec<(Vec<&str>, ForwardDeclaration)> = vec![];
+        for e in self.forward_declarations.values() {
+            if is_ignored(&e.name) {
+                // Type is in the `std` namespace and should be ignored
+                continue;
+            }
+            let (namespace, name) = split_namespace(&e.name, is_class);
+            if name.contains("::") {
+                // Nested types cannot be forward-declared outside of their
+                // enclosing class
+                continue;
+            }
+            forward_declarations.push((
+                namespace,
+                ForwardDeclaration {
+                    name: name.to_string(),
+                    ..e.clone()
+                },
+            ));
+        }
+        forward_declarations.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        if !forward_declarations.is_empty() {
+            writeln!(output_writer)?;
+        }
+        let mut current_namespace: Vec<&str> = vec![];
+        for (i, (namespace, e)) in forward_declarations.iter().enumerate() {
+            if *namespace != current_namespace {
+                fmt_namespace_end(&current_namespace, output_writer)?;
+                if i > 0 {
+                    writeln!(output_writer)?;
+                }
+                fmt_namespace_begin(namespace, output_writer)?;
+                current_namespace = namespace.clone();
+            }
+            e.reconstruct(fmt_configuration, output_writer)?;
+        }
+        fmt_namespace_end(&current_namespace, output_writer)?;
+
+        let type_indices: Vec<pdb::TypeIndex> = if !type_depth_map.is_empty() {
+            // Follow type depth map order
+            type_depth_map.values().rev().flatten().copied().collect()
+        } else {
+            // Follow type index order
+            self.enums
+                .keys()
+                .chain(self.classes.keys())
+                .chain(self.unions.keys())
+                .copied()
+                .collect()
+        };
+
+        // Types nested in a reconstructed class are defined in it
+        let reconstructed_types: HashSet<pdb::TypeIndex> = type_indices.iter().copied().collect();
+        let mut nested_types: HashMap<&str, Vec<pdb::TypeIndex>> = HashMap::new();
+        for type_index in &type_indices {
+            let Some(name) = self.type_name_by_index(*type_index) else {
+                continue;
+            };
+            if let Some(separator_offset) = scope_separator_offsets(name).last() {
+                let enclosing_type_name = &name[..*separator_offset];
+                if self
+                    .classes_and_unions_index(enclosing_type_name)
+                    .is_some_and(|index| reconstructed_types.contains(&index))
+                {
+                    nested_types
+                        .entry(enclosing_type_name)
+                        .or_default()
+                        .push(*type_index);
+                }
+            }
+        }
+        let nested_type_indices: HashSet<pdb::TypeIndex> =
+            nested_types.values().flatten().copied().collect();
+
+        // Template families whose primary template has been declared
+        let mut declared_templates: HashSet<(Vec<&str>, String)> = HashSet::new();
+        let mut current_namespace: Vec<&str> = vec![];
+        for type_index in type_indices {
+            if nested_type_indices.contains(&type_index) {
+                // Type is defined in its enclosing class
+                continue;
+            }
+            let Some(name) = self.type_name_by_index(type_index) else {
+                continue;
+            };
+            if is_ignored(name) {
+                // Type is in the `std` namespace and should be ignored
+                continue;
+            }
+
+            let (namespace, name) = split_namespace(name, is_class);
+            if namespace != current_namespace {
+                if !current_namespace.is_empty() {
+                    writeln!(output_writer)?;
+                    fmt_namespace_end(&current_namespace, output_writer)?;
+                }
+                if !namespace.is_empty() {
+                    writeln!(output_writer)?;
+                    fmt_namespace_begin(&namespace, output_writer)?;
+                }
+                current_namespace = namespace;
+            }
+            writeln!(output_writer)?;
+            // Template specializations can only be defined after their
+            // primary template has been declared
+            let template_parameters = template_specialization_parameters(name);
+            if let Some((family, parameters)) = &template_parameters {
+                if declared_templates.insert((current_namespace.clone(), family.to_string())) {
+                    let class_key = if self.unions.contains_key(&type_index) {
+                        "union"
+                    } else {
+                        "struct"
+                    };
+                    writeln!(
+                        output_writer,
+                        "template <{parameters}> {class_key} {family};"
+                    )?;
+                }
+            }
+            self.fmt_source_location(type_index, output_writer)?;
+            if template_parameters.is_some() {
+                writeln!(output_writer, "template <>")?;
+            }
+            if let Some(e) = self.enums.get(&type_index) {
+                Enum {
+                    name: name.to_string(),
+                    ..e.clone()
+                }
+                .reconstruct(fmt_configuration, output_writer)?;
+            } else if let Some(class) = self.classes.get(&type_index) {
+                self.scoped_class(class, name, &nested_types)
+                    .reconstruct(fmt_configuration, output_writer)?;
+            } else if let Some(u) = self.unions.get(&type_index) {
+                self.scoped_union(u, name, &nested_types)
+                    .reconstruct(fmt_configuration, output_writer)?;
+            }
+        }
+        if !current_namespace.is_empty() {
+            writeln!(output_writer)?;
+            fmt_namespace_end(&current_namespace, output_writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Return a copy of `class` named `name`, with its nested types.
+    fn scoped_class(
+        &self,
+        class: &Class<'p>,
+        name: &str,
+        nested_types: &HashMap<&str, Vec<pdb::TypeIndex>>,
+    ) -> Class<'p> {
+        let mut scoped_class = Class {
+            name: name.to_string(),
+            ..class.clone()
+        };
+        for type_index in nested_types.get(class.name.as_str()).into_iter().flatten() {
+            if let Some(e) = self.enums.get(type_index) {
+                scoped_class.nested_enums.push(Enum {
+                    name: unqualified_type_name(&e.name).to_string(),
+                    ..e.clone()
+                });
+            } else if let Some(nested_class) = self.classes.get(type_index) {
+                scoped_class.nested_classes.push(self.scoped_class(
+                    nested_class,
+                    unqualified_type_name(&nested_class.name),
+                    nested_types,
+                ));
+            } else if let Some(u) = self.unions.get(type_index) {
+                scoped_class.nested_unions.push(self.scoped_union(
+                    u,
+                    unqualified_type_name(&u.name),
+                    nested_types,
+                ));
+            }
+        }
+
+        scoped_class
+    }
+
+    /// Return a copy of `u` named `name`, with its nested types.
+    fn scoped_union(
+        &self,
+        u: &Union<'p>,
+        name: &str,
+        nested_types: &HashMap<&str, Vec<pdb::TypeIndex>>,
+    ) -> Union<'p> {
+        let mut scoped_union = Union {
+            name: name.to_string(),
+            ..u.clone()
+        };
+        for type_index in nested_types.get(u.name.as_str()).into_iter().flatten() {
+            if let Some(e) = self.enums.get(type_index) {
+                scoped_union.nested_enums.push(Enum {
+                    name: unqualified_type_name(&e.name).to_string(),
+                    ..e.clone()
+                });
+            } else if let Some(class) = self.classes.get(type_index) {
+                scoped_union.nested_classes.push(self.scoped_class(
+                    class,
+                    unqualified_type_name(&class.name),
+                    nested_types,
+                ));
+            } else if let Some(nested_union) = self.unions.get(type_index) {
+                scoped_union.nested_unions.push(self.scoped_union(
+                    nested_union,
+                    unqualified_type_name(&nested_union.name),
+                    nested_types,
+                ));
+            }
+        }
+
+        scoped_union
+    }
+
+    pub(crate) fn type_name_by_index(&self, type_index: pdb::TypeIndex) -> Option<&str> {
+        if let Some(e) = self.enums.get(&type_index) {
+            Some(&e.name)
+        } else if let Some(class) = self.classes.get(&type_index) {
+            Some(&class.name)
+        } else {
+            self.unions.get(&type_index).map(|u| u.name.as_str())
+        }
+    }
+
+    /// Return the index of the class or union named `name`, if reconstructed.
+    fn classes_and_unions_index(&self, name: &str) -> Option<pdb::TypeIndex> {
+        self.type_names
+            .get(name)
+            .copied()
+            .filter(|index| self.classes.contains_key(index) || self.unions.contains_key(index))
+    }
+
+    /// Write the location of the declaration of the type at `type_index` as a
+    /// comment, if known.
+    fn fmt_source_location(
+        &self,
+        type_index: pdb::TypeIndex,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        if let Some(source_location) = self.source_locations.get(&type_index) {
+            writeln!(f, "// Declared in {source_location}")?;
+        }
+        Ok(())
+    }
 }
 
 impl<'p> Data<'p> {
@@ -613,7 +1260,11 @@ impl<'p> Data<'p> {
             classes: BTreeMap::new(),
             enums: BTreeMap::new(),
             unions: BTreeMap::new(),
-            type_names: HashSet::new(),
+            type_names: HashMap::new(),
+            duplicate_types: HashMap::new(),
+            referenced_types: BTreeMap::new(),
+            source_locations: HashMap::new(),
+            type_alignments: TypeAlignmentMap::new(),
         }
     }
 
@@ -621,6 +1272,7 @@ impl<'p> Data<'p> {
         &mut self,
         type_finder: &pdb::TypeFinder<'p>,
         type_forwarder: &TypeForwarder,
+        vtable_records: &VirtualTableRecords,
         type_index: pdb::TypeIndex,
         primitive_flavor: &PrimitiveReconstructionFlavor,
         needed_types: &mut NeededTypeSet,
@@ -635,8 +1287,11 @@ impl<'p> Data<'p> {
                     name_str.into_owned()
                 };
 
-                if self.type_names.contains(&name) {
+                if let Some(added_type_index) = self.type_names.get(&name) {
                     // Type has already been added, return
+                    if *added_type_index != type_index {
+                        self.duplicate_types.insert(type_index, *added_type_index);
+                    }
                     return Ok(());
                 }
                 if data.properties.forward_reference() {
@@ -657,6 +1312,8 @@ impl<'p> Data<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vftable: None,
+                    secondary_vftables: Vec::new(),
                 };
 
                 if let Some(derived_from) = data.derived_from {
@@ -674,6 +1331,7 @@ impl<'p> Data<'p> {
                         fields,
                         primitive_flavor,
                         needed_types,
+                        &mut self.type_alignments,
                     ) {
                         log::error!(
                             "Error encountered while reconstructing '{}': {}",
@@ -682,9 +1340,22 @@ impl<'p> Data<'p> {
                         );
                     }
                 }
+                if let Err(err) = class.add_vftables(
+                    type_finder,
+                    type_forwarder,
+                    vtable_records,
+                    primitive_flavor,
+                ) {
+                    log::error!(
+                        "Error encountered while reconstructing '{}''s vftable: {}",
+                        class.name,
+                        err
+                    );
+                }
 
-                self.type_names.insert(name);
+                self.type_names.insert(name, type_index);
                 self.classes.insert(type_index, class);
+                self.add_referenced_types(type_index, needed_types);
             }
 
             pdb::TypeData::Union(data) => {
@@ -696,8 +1367,11 @@ impl<'p> Data<'p> {
                     name_str.into_owned()
                 };
 
-                if self.type_names.contains(&name) {
+                if let Some(added_type_index) = self.type_names.get(&name) {
                     // Type has already been added, return
+                    if *added_type_index != type_index {
+                        self.duplicate_types.insert(type_index, *added_type_index);
+                    }
                     return Ok(());
                 }
 
@@ -720,6 +1394,7 @@ impl<'p> Data<'p> {
                     data.fields,
                     primitive_flavor,
                     needed_types,
+                    &mut self.type_alignments,
                 ) {
                     log::error!(
                         "Error encountered while reconstructing '{}': {}",
@@ -727,22 +1402,38 @@ impl<'p> Data<'p> {
                         err
                     );
                 }
+                if let Err(err) = u.add_vftables(
+                    type_finder,
+                    type_forwarder,
+                    vtable_records,
+                    primitive_flavor,
+                ) {
+                    log::error!(
+                        "Error encountered while reconstructing '{}''s vftables: {}",
+                        u.name,
+                        err
+                    );
+                }
 
-                self.type_names.insert(name);
+                self.type_names.insert(name, type_index);
                 self.unions.insert(type_index, u);
+                self.add_referenced_types(type_index, needed_types);
             }
 
             pdb::TypeData::Enumeration(data) => {
                 let name_str = data.name.to_string();
                 // Rename unnamed anonymous tags to something unique
                 let name = if is_unnamed_type(&name_str) {
-                    format!("_unnamed_{type_index}")
+                    unnamed_enum_name(&name_str, type_index)
                 } else {
                     name_str.into_owned()
                 };
 
-                if self.type_names.contains(&name) {
+                if let Some(added_type_index) = self.type_names.get(&name) {
                     // Type has already been added, return
+                    if *added_type_index != type_index {
+                        self.duplicate_types.insert(type_index, *added_type_index);
+                    }
                     return Ok(());
                 }
 
@@ -752,7 +1443,7 @@ impl<'p> Data<'p> {
                     underlying_type_name: type_name(
                         type_finder,
                         type_forwarder,
-                        data.underlying_type,
+                        enum_underlying_type(&data),
                         primitive_flavor,
                         needed_types,
                     )?
@@ -768,8 +1459,9 @@ impl<'p> Data<'p> {
                     );
                 }
 
-                self.type_names.insert(name.clone());
+                self.type_names.insert(name.clone(), type_index);
                 self.enums.insert(type_index, e);
+                self.add_referenced_types(type_index, needed_types);
             }
 
             // ignore
@@ -779,6 +1471,122 @@ impl<'p> Data<'p> {
         Ok(())
     }
 
+    /// Return the index of the type reconstructed in place of `type_index`,
+    /// types sharing their name with an already-added type being skipped.
+    pub fn deduplicated_type_index(&self, type_index: pdb::TypeIndex) -> pdb::TypeIndex {
+        self.duplicate_types
+            .get(&type_index)
+            .copied()
+            .unwrap_or(type_index)
+    }
+
+    /// Indicate whether types in the `std` namespace are reconstructed.
+    pub(crate) fn ignores_std_types(&self) -> bool {
+        self.ignore_std_types
+    }
+
+    /// Return the indices of the enums, classes/structs and unions added.
+    pub fn type_indices(&self) -> impl Iterator<Item = pdb::TypeIndex> + '_ {
+        self.enums
+            .keys()
+            .chain(self.classes.keys())
+            .chain(self.unions.keys())
+            .copied()
+    }
+
+    /// Set the location of the declaration of the type at `type_index`, which
+    /// is printed above its definition.
+    pub fn set_source_location(
+        &mut self,
+        type_index: pdb::TypeIndex,
+        source_location: SourceLocation,
+    ) {
+        self.source_locations.insert(type_index, source_location);
+    }
+
+    /// Return the index of the class or union in which the type at
+    /// `type_index` is nested, if reconstructed.
+    pub(crate) fn enclosing_type_index(
+        &self,
+        type_index: pdb::TypeIndex,
+    ) -> Option<pdb::TypeIndex> {
+        let name = self.type_name_by_index(type_index)?;
+        let separator_offset = scope_separator_offsets(name).pop()?;
+        self.classes_and_unions_index(&name[..separator_offset])
+    }
+
+    /// Return the outermost namespace in which the type at `type_index` is
+    /// declared, or `None` if it's declared in the global namespace.
+    pub(crate) fn top_level_namespace(&self, type_index: pdb::TypeIndex) -> Option<&str> {
+        let name = self.type_name_by_index(type_index)?;
+        let separator_offset = *scope_separator_offsets(name).first()?;
+        let scope = &name[..separator_offset];
+        // Note: namespaces cannot be templated
+        if scope.ends_with('>') || self.classes_and_unions_index(scope).is_some() {
+            None
+        } else {
+            Some(scope)
+        }
+    }
+
+    /// Return a copy of this object restricted to the types at
+    /// `type_indices` and the forward declarations at
+    /// `forward_declaration_indices`, meant to be reconstructed on its own.
+    ///
+    /// Note: duplicate types aren't tracked in the copy, as they're only
+    /// needed to compute type depth maps.
+    pub(crate) fn subset(
+        &self,
+        type_indices: &BTreeSet<pdb::TypeIndex>,
+        forward_declaration_indices: &BTreeSet<pdb::TypeIndex>,
+    ) -> Self {
+        fn filter_map<K: Ord + Copy, V: Clone>(
+            map: &BTreeMap<K, V>,
+            keys: &BTreeSet<K>,
+        ) -> BTreeMap<K, V> {
+            keys.iter()
+                .filter_map(|key| Some((*key, map.get(key)?.clone())))
+                .collect()
+        }
+
+        Self {
+            ignore_std_types: self.ignore_std_types,
+            forward_declarations: filter_map(
+                &self.forward_declarations,
+                forward_declaration_indices,
+            ),
+            enums: filter_map(&self.enums, type_indices),
+            classes: filter_map(&self.classes, type_indices),
+            unions: filter_map(&self.unions, type_indices),
+            type_names: type_indices
+                .iter()
+                .filter_map(|type_index| {
+                    Some((
+                        self.type_name_by_index(*type_index)?.to_string(),
+                        *type_index,
+                    ))
+                })
+                .collect(),
+            duplicate_types: HashMap::new(),
+            referenced_types: filter_map(&self.referenced_types, type_indices),
+            source_locations: type_indices
+                .iter()
+                .filter_map(|type_index| {
+                    Some((*type_index, self.source_locations.get(type_index)?.clone()))
+                })
+                .collect(),
+            type_alignments: TypeAlignmentMap::new(),
+        }
+    }
+
+    fn add_referenced_types(&mut self, type_index: pdb::TypeIndex, needed_types: &NeededTypeSet) {
+        self.referenced_types.entry(type_index).or_default().extend(
+            needed_types
+                .iter()
+                .map(|(needed_type_index, _)| *needed_type_index),
+        );
+    }
+
     pub fn add_as_forward_declaration(
         &mut self,
         type_finder: &pdb::TypeFinder<'p>,
@@ -840,8 +1648,207 @@ pub fn resolve_complete_type_index(
     }
 }
 
+/// Layout properties of a struct or union, used to reproduce its layout with
+/// explicit padding members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AggregateLayout {
+    /// Size of the aggregate in bytes.
+    size: u64,
+    /// Maximum alignment of the aggregate's members (as in `#pragma pack`),
+    /// present only if it's lower than their natural alignment.
+    packing: Option<u64>,
+}
+
+impl AggregateLayout {
+    fn new(fmt_configuration: &DataFormatConfiguration, fields: &[Field], size: u64) -> Self {
+        Self {
+            size,
+            packing: if fmt_configuration.reproduces_layout() {
+                infer_packing(fields, size)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Alignment of `field` in this aggregate, taking packing into account.
+    fn field_alignment(&self, field: &Field) -> u64 {
+        match self.packing {
+            Some(packing) => field.alignment.min(packing),
+            None => field.alignment,
+        }
+    }
+
+    /// Return the alignment (as in `alignas`) explaining why `field` isn't
+    /// located right after the previous member ending at `previous_end`, if
+    /// any.
+    fn infer_field_alignment(&self, field: &Field, previous_end: u64) -> Option<u64> {
+        let alignment = self.field_alignment(field);
+        if previous_end.next_multiple_of(alignment) >= field.offset {
+            return None;
+        }
+
+        // Look for the lowest alignment which explains the field's offset
+        let mut candidate = alignment * 2;
+        while candidate <= field.offset && self.size % candidate == 0 {
+            if previous_end.next_multiple_of(candidate) == field.offset {
+                return Some(candidate);
+            }
+            candidate *= 2;
+        }
+
+        None
+    }
+}
+
+/// Return the packing value (as in `#pragma pack`) needed to explain the
+/// offsets of the given `fields` and the aggregate's `size`, or `None` if the
+/// fields are naturally aligned.
+fn infer_packing(fields: &[Field], size: u64) -> Option<u64> {
+    let natural_alignment = fields
+        .iter()
+        .map(|field| field.alignment)
+        .max()
+        .unwrap_or(1);
+    let is_explained_by = |packing: u64| {
+        size % natural_alignment.min(packing) == 0
+            && fields
+                .iter()
+                .all(|field| field.offset % field.alignment.min(packing) == 0)
+    };
+    if is_explained_by(natural_alignment) {
+        return None;
+    }
+
+    let mut packing = natural_alignment;
+    while packing > 1 && !is_explained_by(packing) {
+        packing /= 2;
+    }
+
+    Some(packing)
+}
+
+/// Write the given struct `fields`, along with padding members covering the
+/// bytes of `storage` located before the first field and after the last one
+/// if the layout is reproduced.
+fn fmt_struct_fields(
+    fmt_configuration: &DataFormatConfiguration,
+    layout: &AggregateLayout,
+    fields: &[Field],
+    storage: Range<u64>,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    if fields.is_empty() {
+        return Ok(());
+    }
+
+    let fields_start = fields
+        .iter()
+        .map(|field| field.offset)
+        .min()
+        .unwrap_or_default();
+    let fields_end = fields
+        .iter()
+        .map(|field| field.offset + field.size as u64)
+        .max()
+        .unwrap_or_default();
+    fmt_hole(fmt_configuration, Some(storage.start), fields_start, 1, f)?;
+    fmt_struct_fields_recursive(fmt_configuration, layout, fields, 1, f)?;
+    fmt_hole(fmt_configuration, Some(fields_end), storage.end, 1, f)?;
+
+    Ok(())
+}
+
+/// Write an explicit padding member covering the hole between the end of the
+/// previous member (`last_end`) and `offset`, if the layout is reproduced.
+fn fmt_hole(
+    fmt_configuration: &DataFormatConfiguration,
+    last_end: Option<u64>,
+    offset: u64,
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    match last_end {
+        Some(last_end) if fmt_configuration.reproduces_layout() && offset > last_end => {
+            fmt_padding(fmt_configuration, last_end, offset - last_end, depth, f)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Write an explicit padding member covering `size` bytes at `offset`.
+fn fmt_padding(
+    fmt_configuration: &DataFormatConfiguration,
+    offset: u64,
+    size: u64,
+    depth: usize,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let padding_type_name = primitive_kind_as_str(
+        &fmt_configuration.primitive_flavor,
+        pdb::PrimitiveKind::U8,
+        false,
+    )
+    .map_err(|_| fmt::Error)?;
+    write!(f, "{}", "  ".repeat(depth))?;
+    if fmt_configuration.print_offset_info {
+        write!(f, "/* {offset:#06x} */ ")?;
+    }
+    writeln!(f, "{padding_type_name} _pad_{offset:#x}[{size}];")
+}
+
+/// Write `static_assert`s checking the offset of each of the given `fields`
+/// within the type named `type_name`. Bit-field members are skipped since
+/// `offsetof` cannot be applied to them, and so are the members for which
+/// `is_accessible` returns false since `offsetof` can only be used on members
+/// accessible from outside the type. Skipped inaccessible members are listed
+/// in a comment.
+fn fmt_offset_static_asserts<'a, 'p: 'a>(
+    fmt_configuration: &DataFormatConfiguration,
+    type_name: &str,
+    fields: impl IntoIterator<Item = &'a Field<'p>>,
+    is_accessible: impl Fn(&Field) -> bool,
+    f: &mut impl std::fmt::Write,
+) -> fmt::Result {
+    let mut inaccessible_fields = vec![];
+    for field in fields {
+        if field.bitfield_info.is_some() {
+            continue;
+        }
+        if !is_accessible(field) {
+            inaccessible_fields.push(field.name.to_string());
+            continue;
+        }
+
+        if fmt_configuration.integers_as_hexadecimal {
+            writeln!(
+                f,
+                "static_assert(offsetof({}, {}) == {:#x}); // {}",
+                type_name, field.name, field.offset, field.offset
+            )?;
+        } else {
+            writeln!(
+                f,
+                "static_assert(offsetof({}, {}) == {}); // {:#x}",
+                type_name, field.name, field.offset, field.offset
+            )?;
+        }
+    }
+    if !inaccessible_fields.is_empty() {
+        writeln!(
+            f,
+            "// Note: offsetof cannot be used on non-public members of {}: {}",
+            type_name,
+            inaccessible_fields.join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
 fn fmt_struct_fields_recursive(
     fmt_configuration: &DataFormatConfiguration,
+    layout: &AggregateLayout,
     fields: &[Field],
     depth: usize,
     f: &mut impl std::fmt::Write,
@@ -854,7 +1861,16 @@ fn fmt_struct_fields_recursive(
     // Write fields into the `Formatter`
     let indentation = "  ".repeat(depth);
     let mut last_field: Option<&Field> = None;
+    // Offset of the end of the last field or union
+    let mut last_end: Option<u64> = None;
     for union_range in unions_found {
+        let range_start = fields[union_range.start].offset;
+        let range_end = fields[union_range.start..union_range.end.max(union_range.start + 1)]
+            .iter()
+            .map(|field| field.offset + field.size as u64)
+            .max()
+            .unwrap_or(range_start);
+
         // Fields out of unnamed unions are represented by "empty" unions
         if union_range.is_empty() {
             let field = &fields[union_range.start];
@@ -896,41 +1912,60 @@ fn fmt_struct_fields_recursive(
                     }
                 }
             }
-
-            write!(f, "{}", &indentation)?;
-            if fmt_configuration.print_offset_info {
-                write!(f, "/* {:#06x} */ ", field.offset)?;
-            }
-            write!(
-                f,
-                "{}",
-                match fmt_configuration.print_access_specifiers {
-                    AccessSpecifierReconstructionFlavor::Disabled => &FieldAccess::None,
-                    AccessSpecifierReconstructionFlavor::Always => &field.access,
-                    AccessSpecifierReconstructionFlavor::Automatic => {
-                        if field.access != FieldAccess::Public {
-                            &field.access
-                        } else {
-                            &FieldAccess::None
+            fmt_hole(fmt_configuration, last_end, range_start, depth, f)?;
+
+            // Implicit members (e.g., virtual function table pointers) are
+            // only taken into account for the layout, unless requested
+            if !field.is_implicit || fmt_configuration.print_vftables {
+                write!(f, "{}", &indentation)?;
+                if fmt_configuration.print_offset_info {
+                    write!(f, "/* {:#06x} */ ", field.offset)?;
+                }
+                if field.is_implicit {
+                    write!(f, "// ")?;
+                }
+                write!(
+                    f,
+                    "{}",
+                    match fmt_configuration.print_access_specifiers {
+                        AccessSpecifierReconstructionFlavor::Disabled => &FieldAccess::None,
+                        AccessSpecifierReconstructionFlavor::Always => &field.access,
+                        AccessSpecifierReconstructionFlavor::Automatic => {
+                            if field.access != FieldAccess::Public {
+                                &field.access
+                            } else {
+                                &FieldAccess::None
+                            }
                         }
                     }
+                )?;
+                // Members located further than their natural alignment allows
+                // are probably over-aligned
+                if let Some(alignment) = last_end
+                    .filter(|_| {
+                        fmt_configuration.reproduces_layout() && field.bitfield_info.is_none()
+                    })
+                    .and_then(|last_end| layout.infer_field_alignment(field, last_end))
+                {
+                    write!(f, "alignas({alignment}) ")?;
                 }
-            )?;
-            write!(
-                f,
-                "{} {}{};",
-                field.type_left,
-                field.name.to_string(),
-                field.type_right
-            )?;
-            if fmt_configuration.print_offset_info {
-                if let Some((bit_position, _)) = field.bitfield_info {
-                    write!(f, " /* BitPos={bit_position} */")?;
+                write!(
+                    f,
+                    "{} {}{};",
+                    field.type_left,
+                    field.name.to_string(),
+                    field.type_right
+                )?;
+                if fmt_configuration.print_offset_info {
+                    if let Some((bit_position, _)) = field.bitfield_info {
+                        write!(f, " /* BitPos={bit_position} */")?;
+                    }
                 }
+                writeln!(f)?;
             }
-            writeln!(f)?;
             last_field = Some(field);
         } else {
+            fmt_hole(fmt_configuration, last_end, range_start, depth, f)?;
             writeln!(
                 f,
                 "{}union{}{{",
@@ -941,10 +1976,17 @@ fn fmt_struct_fields_recursive(
                     String::from(" ")
                 }
             )?;
-            fmt_union_fields_recursive(fmt_configuration, &fields[union_range], depth + 1, f)?;
+            fmt_union_fields_recursive(
+                fmt_configuration,
+                layout,
+                &fields[union_range],
+                depth + 1,
+                f,
+            )?;
             writeln!(f, "{}}};", &indentation)?;
             last_field = None;
         }
+        last_end = Some(last_end.map_or(range_end, |last_end| last_end.max(range_end)));
     }
 
     Ok(())
@@ -1058,6 +2100,7 @@ fn find_unnamed_unions_in_struct(fields: &[Field]) -> Vec<Range<usize>> {
 
 fn fmt_union_fields_recursive(
     fmt_configuration: &DataFormatConfiguration,
+    layout: &AggregateLayout,
     fields: &[Field],
     depth: usize,
     f: &mut impl std::fmt::Write,
@@ -1115,7 +2158,13 @@ fn fmt_union_fields_recursive(
                     String::from(" ")
                 }
             )?;
-            fmt_struct_fields_recursive(fmt_configuration, &fields[struct_range], depth + 1, f)?;
+            fmt_struct_fields_recursive(
+                fmt_configuration,
+                layout,
+                &fields[struct_range],
+                depth + 1,
+                f,
+            )?;
             writeln!(f, "{}}};", &indentation)?;
         }
     }
@@ -1183,6 +2232,14 @@ pub struct DataFormatConfiguration {
     pub integers_as_hexadecimal: bool,
     pub print_offset_info: bool,
     pub print_brackets_new_line: bool,
+    pub print_vftables: bool,
+    pub print_padding: bool,
+    pub print_offset_asserts: bool,
+    pub print_namespaces: bool,
+    pub print_source_locations: bool,
+    /// Flavor used to name primitive types, including the type of padding
+    /// members
+    pub primitive_flavor: PrimitiveReconstructionFlavor,
 }
 
 impl Default for DataFormatConfiguration {
@@ -1193,6 +2250,143 @@ impl Default for DataFormatConfiguration {
             integers_as_hexadecimal: true,
             print_offset_info: true,
             print_brackets_new_line: false,
+            print_vftables: false,
+            print_padding: false,
+            print_offset_asserts: false,
+            print_namespaces: false,
+            print_source_locations: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+        }
+    }
+}
+
+impl DataFormatConfiguration {
+    /// Whether the layout of aggregates is reproduced with explicit padding
+    /// members, packing and alignment. This is implied by layout assertions,
+    /// which couldn't hold otherwise.
+    fn reproduces_layout(&self) -> bool {
+        self.print_padding
+            || self.print_offset_asserts
+            || self.size_print_flavor == SizePrintFlavor::StaticAssert
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(offset: u64, size: usize, alignment: u64) -> Field<'static> {
+        Field {
+            type_left: String::default(),
+            type_right: String::default(),
+            type_index: pdb::TypeIndex(0),
+            name: "field".into(),
+            offset,
+            size,
+            alignment,
+            bitfield_info: None,
+            access: FieldAccess::None,
+            is_implicit: false,
         }
     }
+
+    fn aggregate_layout(fields: &[Field], size: u64) -> AggregateLayout {
+        let fmt_configuration = DataFormatConfiguration {
+            print_padding: true,
+            ..Default::default()
+        };
+        AggregateLayout::new(&fmt_configuration, fields, size)
+    }
+
+    #[test]
+    fn test_infer_packing_natural_alignment() {
+        // struct { char a; int b; long long c; }
+        let fields = [field(0, 1, 1), field(4, 4, 4), field(8, 8, 8)];
+        assert_eq!(infer_packing(&fields, 16), None);
+        // Padding at the end of the struct doesn't imply any packing
+        let fields = [field(0, 8, 8), field(8, 1, 1)];
+        assert_eq!(infer_packing(&fields, 16), None);
+    }
+
+    #[test]
+    fn test_infer_packing_pragma_pack() {
+        // #pragma pack(1): struct { char a; int b; }
+        let fields = [field(0, 1, 1), field(1, 4, 4)];
+        assert_eq!(infer_packing(&fields, 5), Some(1));
+        // #pragma pack(2): struct { char a; int b; }
+        let fields = [field(0, 1, 1), field(2, 4, 4)];
+        assert_eq!(infer_packing(&fields, 6), Some(2));
+        // #pragma pack(4): struct { int a; long long b; }
+        let fields = [field(0, 4, 4), field(4, 8, 8)];
+        assert_eq!(infer_packing(&fields, 12), Some(4));
+        // #pragma pack(4): struct { long long a; int b; }, only the size
+        // reveals the packing
+        let fields = [field(0, 8, 8), field(8, 4, 4)];
+        assert_eq!(infer_packing(&fields, 12), Some(4));
+    }
+
+    #[test]
+    fn test_infer_field_alignment() {
+        // struct { int a; alignas(16) int b; }
+        let fields = [field(0, 4, 4), field(16, 4, 4)];
+        let layout = aggregate_layout(&fields, 32);
+        assert_eq!(layout.packing, None);
+        assert_eq!(layout.infer_field_alignment(&fields[1], 4), Some(16));
+
+        // struct { char a; int b; }, the padding is explained by the natural
+        // alignment of `b`
+        let fields = [field(0, 1, 1), field(4, 4, 4)];
+        let layout = aggregate_layout(&fields, 8);
+        assert_eq!(layout.infer_field_alignment(&fields[1], 1), None);
+
+        // Fields located right after the previous one
+        let fields = [field(0, 4, 4), field(4, 4, 4)];
+        let layout = aggregate_layout(&fields, 8);
+        assert_eq!(layout.infer_field_alignment(&fields[1], 4), None);
+
+        // #pragma pack(1): struct { char a; int b; }, the lack of padding is
+        // explained by the packing
+        let fields = [field(0, 1, 1), field(1, 4, 4)];
+        let layout = aggregate_layout(&fields, 5);
+        assert_eq!(layout.packing, Some(1));
+        assert_eq!(layout.infer_field_alignment(&fields[1], 1), None);
+    }
+
+    #[test]
+    fn test_template_specialization_parameters() {
+        assert_eq!(template_specialization_parameters("StructTest"), None);
+        assert_eq!(template_specialization_parameters("<lambda_1>"), None);
+        assert_eq!(
+            template_specialization_parameters("Outer<int>::Inner<char>"),
+            None
+        );
+        assert_eq!(
+            template_specialization_parameters("Array<Pair<int, char>, 16, false>"),
+            Some(("Array", "typename, auto, auto".to_string()))
+        );
+        assert_eq!(
+            template_specialization_parameters("Tuple<>"),
+            Some(("Tuple", "typename...".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_symbol_namespace() {
+        let is_class = |scope: &str| scope == "ns::Class";
+        assert_eq!(symbol_namespace("main", is_class), None);
+        assert_eq!(
+            symbol_namespace("ns::inner::function", is_class),
+            Some("ns::inner")
+        );
+        // Methods are declared in the namespace of their class
+        assert_eq!(symbol_namespace("ns::Class::method", is_class), Some("ns"));
+        assert_eq!(
+            symbol_namespace("ns::Class::Nested::method", is_class),
+            Some("ns")
+        );
+        assert_eq!(
+            symbol_namespace("ns::Template<int>::method", is_class),
+            Some("ns")
+        );
+    }
 }
@@ -64,9 +64,85 @@ impl FromStr for SizePrintFlavor {
     }
 }
 
+/// Language in which types are reconstructed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputLanguage {
+    Cpp,
+    /// C++ constructs lowered to C
+    C,
+    Rust,
+    /// Serialized type model, meant to be consumed by scripts
+    Json,
+}
+
+impl FromStr for OutputLanguage {
+    type Err = ResymCoreError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "c++" | "cpp" | "cxx" => Ok(OutputLanguage::Cpp),
+            "c" => Ok(OutputLanguage::C),
+            "rust" | "rs" => Ok(OutputLanguage::Rust),
+            "json" => Ok(OutputLanguage::Json),
+            _ => Err(ResymCoreError::ParsePrimitiveFlavorError(s.to_owned())),
+        }
+    }
+}
+
+/// How reconstructed types are split into header files.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HeaderSplitFlavor {
+    /// One header per source file declaring types
+    SourceFile,
+    /// One header per top-level namespace
+    Namespace,
+}
+
+/// Format of the data produced by commands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ResymCoreError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(ResymCoreError::ParsePrimitiveFlavorError(s.to_owned())),
+        }
+    }
+}
+
+/// Format of exported graphs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GraphFormat {
+    /// Graphviz's DOT language
+    Dot,
+    GraphMl,
+    Json,
+}
+
+impl FromStr for GraphFormat {
+    type Err = ResymCoreError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" | "gv" => Ok(GraphFormat::Dot),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "json" => Ok(GraphFormat::Json),
+            _ => Err(ResymCoreError::ParsePrimitiveFlavorError(s.to_owned())),
+        }
+    }
+}
+
 pub fn include_headers_for_flavor(
     flavor: PrimitiveReconstructionFlavor,
     ignore_std_types: bool,
+    print_offset_asserts: bool,
 ) -> String {
     let flavor_header = match flavor {
         PrimitiveReconstructionFlavor::Portable => "#include <cstdint>\n",
@@ -74,6 +150,13 @@ pub fn include_headers_for_flavor(
         PrimitiveReconstructionFlavor::Raw => "",
         PrimitiveReconstructionFlavor::Msvc => "",
     };
+    // `offsetof` is defined in <cstddef>
+    let cstddef_header =
+        if flavor == PrimitiveReconstructionFlavor::Portable || print_offset_asserts {
+            "#include <cstddef>\n"
+        } else {
+            ""
+        };
 
     let common_std_headers = if ignore_std_types {
         concat!(
@@ -90,7 +173,27 @@ pub fn include_headers_for_flavor(
         ""
     };
 
-    format!("{flavor_header}{common_std_headers}")
+    format!("{cstddef_header}{flavor_header}{common_std_headers}")
+}
+
+pub fn include_c_headers_for_flavor(flavor: PrimitiveReconstructionFlavor) -> String {
+    let flavor_headers = match flavor {
+        PrimitiveReconstructionFlavor::Microsoft => "#include <Windows.h>\n",
+        // Note: fixed-width integers are also used by the other flavors for
+        // members which cannot be represented in C
+        PrimitiveReconstructionFlavor::Portable
+        | PrimitiveReconstructionFlavor::Raw
+        | PrimitiveReconstructionFlavor::Msvc => "#include <stdint.h>\n#include <uchar.h>\n",
+    };
+    // `alignas` and `bool` are macros before C23, `offsetof` and `wchar_t`
+    // are defined in <stddef.h>
+    let common_std_headers = concat!(
+        "#include <stdalign.h>\n",
+        "#include <stdbool.h>\n",
+        "#include <stddef.h>\n",
+    );
+
+    format!("{flavor_headers}{common_std_headers}")
 }
 
 pub fn primitive_kind_as_str(
@@ -0,0 +1,1317 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+use super::{
+    class::Class, enum_underlying_type, enumeration::Enum, field::Field,
+    find_unnamed_structs_in_unions, find_unnamed_unions_in_struct, is_unnamed_type,
+    resolve_complete_type_index, type_size, union::Union, vftable::VFPTR_MEMBER_NAME, Data,
+    DataFormatConfiguration, SizePrintFlavor, TypeForwarder,
+};
+use crate::error::Result;
+
+/// Inner attributes put at the top of reconstructed Rust files, C++ type and
+/// field names are kept (mostly) untouched.
+pub const RUST_FILE_ATTRIBUTES: &str =
+    "#![allow(non_camel_case_types, non_snake_case, non_upper_case_globals, dead_code)]\n";
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+impl Data<'_> {
+    /// Reconstruct types as `#[repr(C)]` Rust declarations.
+    pub fn reconstruct_rust(
+        &self,
+        type_finder: &pdb::TypeFinder,
+        type_forwarder: &TypeForwarder,
+        fmt_configuration: &DataFormatConfiguration,
+        type_depth_map: &BTreeMap<usize, Vec<pdb::TypeIndex>>,
+        output_writer: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        let resolver = RustTypeResolver::new(type_finder, type_forwarder);
+
+        // Opaque declarations for types which are referenced but not defined
+        let opaque_declarations = self
+            .forward_declarations
+            .values()
+            .filter(|e| !self.type_names.contains_key(&e.name))
+            .filter(|e| !(self.ignore_std_types && e.name.starts_with("std::")))
+            .collect::<Vec<_>>();
+        if !opaque_declarations.is_empty() {
+            writeln!(output_writer)?;
+        }
+        for e in opaque_declarations {
+            resolver.write_opaque_struct(&e.name, fmt_configuration, output_writer)?;
+        }
+
+        let type_indices: Vec<pdb::TypeIndex> = if !type_depth_map.is_empty() {
+            // Follow type depth map order
+            type_depth_map.values().rev().flatten().copied().collect()
+        } else {
+            // Follow type index order
+            self.enums
+                .keys()
+                .chain(self.classes.keys())
+                .chain(self.unions.keys())
+                .copied()
+                .collect()
+        };
+        for type_index in type_indices {
+            let (name, declaration) = if let Some(e) = self.enums.get(&type_index) {
+                if self.ignore_std_types && e.name.starts_with("std::") {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (&e.name, resolver.declare_enum(e, fmt_configuration))
+            } else if let Some(c) = self.classes.get(&type_index) {
+                if self.ignore_std_types && c.name.starts_with("std::") {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (&c.name, resolver.declare_class(c, fmt_configuration))
+            } else if let Some(u) = self.unions.get(&type_index) {
+                if self.ignore_std_types && u.name.starts_with("std::") {
+                    // Type is in the `std` namespace and should be ignored
+                    continue;
+                }
+                (&u.name, resolver.declare_union(u, fmt_configuration))
+            } else {
+                continue;
+            };
+
+            writeln!(output_writer)?;
+            self.fmt_source_location(type_index, output_writer)?;
+            match declaration {
+                Ok(declaration) => write!(output_writer, "{declaration}")?,
+                Err(err) => {
+                    // Note: Do not propagate the error, this allows the
+                    // reconstruction of other types to go through
+                    log::error!("Error encountered while reconstructing '{}': {}", name, err);
+                    writeln!(output_writer, "// Failed to reconstruct '{name}': {err}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Member of a Rust struct or union.
+struct RustMember {
+    /// Offset in bytes, relative to the beginning of the parent type.
+    offset: u64,
+    size: u64,
+    alignment: u64,
+    name: String,
+    type_name: String,
+}
+
+/// Getter and setter generated for a bit-field member, which is stored into
+/// an integer member shared with adjacent bit-fields.
+struct RustBitfieldAccessor {
+    name: String,
+    storage_name: String,
+    storage_type: &'static str,
+    storage_size: u64,
+    value_type: &'static str,
+    position: u8,
+    length: u8,
+}
+
+/// Rust struct or union declaration.
+struct RustAggregate {
+    is_union: bool,
+    name: String,
+    size: u64,
+    members: Vec<RustMember>,
+    bitfield_accessors: Vec<RustBitfieldAccessor>,
+}
+
+/// Types declared for the unnamed unions and structs of a given type.
+struct RustAnonymousTypes {
+    owner_name: String,
+    count: usize,
+    declarations: String,
+}
+
+struct RustTypeResolver<'a, 't> {
+    type_finder: &'a pdb::TypeFinder<'t>,
+    type_forwarder: &'a TypeForwarder,
+    alignment_cache: RefCell<HashMap<pdb::TypeIndex, u64>>,
+}
+
+impl<'a, 't> RustTypeResolver<'a, 't> {
+    fn new(type_finder: &'a pdb::TypeFinder<'t>, type_forwarder: &'a TypeForwarder) -> Self {
+        Self {
+            type_finder,
+            type_forwarder,
+            alignment_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn declare_enum(
+        &self,
+        e: &Enum,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let mut f = String::new();
+        let name = rust_identifier(&e.name);
+        let repr = match self.type_finder.find(e.index)?.parse()? {
+            pdb::TypeData::Enumeration(data) => self.integer_type(enum_underlying_type(&data))?,
+            _ => "i32",
+        };
+        if e.values.is_empty() {
+            writeln!(f, "pub type {name} = {repr};")?;
+            return Ok(f);
+        }
+
+        // C++ enumerations are open (any value of the underlying type is valid)
+        // and may have several enumerators sharing the same value, so they're
+        // declared as transparent newtypes with associated constants, which is
+        // sound for values coming from foreign code
+        writeln!(f, "#[repr(transparent)]")?;
+        writeln!(f, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]")?;
+        writeln!(f, "pub struct {name}(pub {repr});")?;
+        writeln!(
+            f,
+            "impl {}{}{{",
+            name,
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            }
+        )?;
+        for value in &e.values {
+            let value_name = rust_identifier(&value.name.to_string());
+            let discriminant = normalize_discriminant(variant_as_i128(&value.value), repr);
+            writeln!(
+                f,
+                "    pub const {}: {} = {}({});",
+                value_name,
+                name,
+                name,
+                if fmt_configuration.integers_as_hexadecimal && discriminant >= 0 {
+                    format!("{discriminant:#x}")
+                } else {
+                    format!("{discriminant}")
+                }
+            )?;
+        }
+        writeln!(f, "}}")?;
+
+        Ok(f)
+    }
+
+    fn declare_class(
+        &self,
+        class: &Class,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let mut f = self.declare_nested_types(
+            &class.nested_enums,
+            &class.nested_classes,
+            &class.nested_unions,
+            fmt_configuration,
+        )?;
+        let name = rust_identifier(&class.name);
+        let mut anonymous_types = RustAnonymousTypes {
+            owner_name: name.clone(),
+            count: 0,
+            declarations: String::new(),
+        };
+
+        let mut members = vec![];
+        for (i, base) in class.base_classes.iter().enumerate() {
+            // Virtual base classes aren't located at a fixed offset, their
+            // storage is covered by padding
+            if base.is_virtual {
+                continue;
+            }
+
+            let offset = u64::from(base.offset);
+            let size = self.type_size(base.type_index)?;
+            // Empty base classes may share their storage with other members
+            let is_overlapped = class
+                .fields
+                .iter()
+                .map(|field| field.offset)
+                .chain(
+                    class
+                        .base_classes
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, other)| *j != i && !other.is_virtual)
+                        .map(|(_, other)| u64::from(other.offset)),
+                )
+                .any(|other_offset| (offset..offset + size).contains(&other_offset));
+            if is_overlapped && self.is_empty_type(base.type_index)? {
+                continue;
+            }
+
+            members.push(RustMember {
+                offset,
+                size,
+                alignment: self.type_alignment(base.type_index)?,
+                name: format!("base_{i}"),
+                type_name: self.type_name(base.type_index)?,
+            });
+        }
+
+        if let Some(vftable) = class.vftable.as_ref().filter(|vftable| vftable.owns_vfptr) {
+            members.push(RustMember {
+                offset: vftable.vfptr_offset,
+                size: vftable.slot_size,
+                alignment: vftable.slot_size,
+                name: VFPTR_MEMBER_NAME.to_string(),
+                type_name: "*const *const core::ffi::c_void".to_string(),
+            });
+        }
+
+        let (field_members, bitfield_accessors) = self.struct_members(
+            &class.fields,
+            0,
+            class.size,
+            fmt_configuration,
+            &mut anonymous_types,
+        )?;
+        members.extend(field_members);
+
+        f.push_str(&anonymous_types.declarations);
+        self.write_aggregate(
+            &RustAggregate {
+                is_union: false,
+                name,
+                size: class.size,
+                members,
+                bitfield_accessors,
+            },
+            fmt_configuration,
+            &mut f,
+        )?;
+
+        Ok(f)
+    }
+
+    fn declare_union(
+        &self,
+        u: &Union,
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let mut f = self.declare_nested_types(
+            &u.nested_enums,
+            &u.nested_classes,
+            &u.nested_unions,
+            fmt_configuration,
+        )?;
+        let name = rust_identifier(&u.name);
+        let mut anonymous_types = RustAnonymousTypes {
+            owner_name: name.clone(),
+            count: 0,
+            declarations: String::new(),
+        };
+
+        let (members, bitfield_accessors) = self.union_members(
+            &u.fields,
+            0,
+            u.size,
+            fmt_configuration,
+            &mut anonymous_types,
+        )?;
+
+        f.push_str(&anonymous_types.declarations);
+        self.write_aggregate(
+            &RustAggregate {
+                is_union: true,
+                name,
+                size: u.size,
+                members,
+                bitfield_accessors,
+            },
+            fmt_configuration,
+            &mut f,
+        )?;
+
+        Ok(f)
+    }
+
+    fn declare_nested_types(
+        &self,
+        nested_enums: &[Enum],
+        nested_classes: &[Class],
+        nested_unions: &[Union],
+        fmt_configuration: &DataFormatConfiguration,
+    ) -> Result<String> {
+        let mut f = String::new();
+        for e in nested_enums {
+            writeln!(f, "{}", self.declare_enum(e, fmt_configuration)?)?;
+        }
+        for class in nested_classes {
+            writeln!(f, "{}", self.declare_class(class, fmt_configuration)?)?;
+        }
+        for u in nested_unions {
+            writeln!(f, "{}", self.declare_union(u, fmt_configuration)?)?;
+        }
+
+        Ok(f)
+    }
+
+    /// Convert struct fields into Rust members, with offsets relative to
+    /// `base_offset`. Unnamed unions are declared as separate types.
+    fn struct_members(
+        &self,
+        fields: &[Field],
+        base_offset: u64,
+        end_offset: u64,
+        fmt_configuration: &DataFormatConfiguration,
+        anonymous_types: &mut RustAnonymousTypes,
+    ) -> Result<(Vec<RustMember>, Vec<RustBitfieldAccessor>)> {
+        let mut members = vec![];
+        let mut bitfield_accessors = vec![];
+        let mut bitfield_count = 0;
+
+        let unions_found = find_unnamed_unions_in_struct(fields);
+        let mut i = 0;
+        while i < unions_found.len() {
+            let union_range = unions_found[i].clone();
+            // Fields out of unnamed unions are represented by "empty" unions
+            if union_range.is_empty() {
+                let field = &fields[union_range.start];
+                if field.bitfield_info.is_some() {
+                    // Group bit-fields which share the same allocation unit
+                    let mut bitfields = vec![field];
+                    while let Some(next_range) = unions_found.get(i + 1) {
+                        let next_field = &fields[next_range.start];
+                        if !next_range.is_empty()
+                            || next_field.bitfield_info.is_none()
+                            || next_field.offset != field.offset
+                        {
+                            break;
+                        }
+                        bitfields.push(next_field);
+                        i += 1;
+                    }
+
+                    bitfield_count += 1;
+                    let (member, mut accessors) =
+                        self.bitfield_member(&bitfields, base_offset, bitfield_count)?;
+                    members.push(member);
+                    bitfield_accessors.append(&mut accessors);
+                } else {
+                    members.push(self.field_member(field, base_offset)?);
+                }
+            } else {
+                let union_end_offset = unions_found
+                    .get(i + 1)
+                    .map(|next_range| fields[next_range.start].offset)
+                    .unwrap_or(end_offset);
+                members.push(self.anonymous_union_member(
+                    &fields[union_range],
+                    base_offset,
+                    union_end_offset,
+                    fmt_configuration,
+                    anonymous_types,
+                )?);
+            }
+            i += 1;
+        }
+
+        Ok((members, bitfield_accessors))
+    }
+
+    /// Convert union fields into Rust members, with offsets relative to
+    /// `base_offset`. Unnamed structs are declared as separate types.
+    fn union_members(
+        &self,
+        fields: &[Field],
+        base_offset: u64,
+        end_offset: u64,
+        fmt_configuration: &DataFormatConfiguration,
+        anonymous_types: &mut RustAnonymousTypes,
+    ) -> Result<(Vec<RustMember>, Vec<RustBitfieldAccessor>)> {
+        let mut members = vec![];
+        let mut bitfield_accessors = vec![];
+        let mut bitfield_count = 0;
+        if fields.is_empty() {
+            return Ok((members, bitfield_accessors));
+        }
+
+        for struct_range in find_unnamed_structs_in_unions(fields) {
+            // Fields out of unnamed structs are represented by "empty" structs
+            if struct_range.is_empty() && fields[struct_range.start].offset == base_offset {
+                let field = &fields[struct_range.start];
+                if field.bitfield_info.is_some() {
+                    bitfield_count += 1;
+                    let (member, mut accessors) =
+                        self.bitfield_member(&[field], base_offset, bitfield_count)?;
+                    members.push(member);
+                    bitfield_accessors.append(&mut accessors);
+                } else {
+                    members.push(self.field_member(field, base_offset)?);
+                }
+            } else {
+                // Union members must start at the beginning of the union, wrap
+                // misplaced fields into a struct
+                let struct_range = if struct_range.is_empty() {
+                    struct_range.start..struct_range.start + 1
+                } else {
+                    struct_range
+                };
+                members.push(self.anonymous_struct_member(
+                    &fields[struct_range],
+                    base_offset,
+                    end_offset,
+                    fmt_configuration,
+                    anonymous_types,
+                )?);
+            }
+        }
+
+        Ok((members, bitfield_accessors))
+    }
+
+    fn anonymous_union_member(
+        &self,
+        fields: &[Field],
+        base_offset: u64,
+        end_offset: u64,
+        fmt_configuration: &DataFormatConfiguration,
+        anonymous_types: &mut RustAnonymousTypes,
+    ) -> Result<RustMember> {
+        let union_offset = fields[0].offset;
+        let (members, bitfield_accessors) = self.union_members(
+            fields,
+            union_offset,
+            end_offset,
+            fmt_configuration,
+            anonymous_types,
+        )?;
+        let size = self.anonymous_type_size(fields, &members, union_offset, end_offset)?;
+
+        anonymous_types.count += 1;
+        let index = anonymous_types.count;
+        let aggregate = RustAggregate {
+            is_union: true,
+            name: format!("{}__anon_union_{index}", anonymous_types.owner_name),
+            size,
+            members,
+            bitfield_accessors,
+        };
+        let alignment = self.write_aggregate(
+            &aggregate,
+            fmt_configuration,
+            &mut anonymous_types.declarations,
+        )?;
+        writeln!(anonymous_types.declarations)?;
+
+        Ok(RustMember {
+            offset: union_offset.saturating_sub(base_offset),
+            size,
+            alignment,
+            name: format!("anon_union_{index}"),
+            type_name: aggregate.name,
+        })
+    }
+
+    fn anonymous_struct_member(
+        &self,
+        fields: &[Field],
+        base_offset: u64,
+        end_offset: u64,
+        fmt_configuration: &DataFormatConfiguration,
+        anonymous_types: &mut RustAnonymousTypes,
+    ) -> Result<RustMember> {
+        let (members, bitfield_accessors) = self.struct_members(
+            fields,
+            base_offset,
+            end_offset,
+            fmt_configuration,
+            anonymous_types,
+        )?;
+        let size = self.anonymous_type_size(fields, &members, base_offset, end_offset)?;
+
+        anonymous_types.count += 1;
+        let index = anonymous_types.count;
+        let aggregate = RustAggregate {
+            is_union: false,
+            name: format!("{}__anon_struct_{index}", anonymous_types.owner_name),
+            size,
+            members,
+            bitfield_accessors,
+        };
+        let alignment = self.write_aggregate(
+            &aggregate,
+            fmt_configuration,
+            &mut anonymous_types.declarations,
+        )?;
+        writeln!(anonymous_types.declarations)?;
+
+        Ok(RustMember {
+            offset: 0,
+            size,
+            alignment,
+            name: format!("anon_struct_{index}"),
+            type_name: aggregate.name,
+        })
+    }
+
+    /// Compute the size of an unnamed struct or union. The size is rounded up
+    /// to the type's alignment, as long as it doesn't overlap with what
+    /// follows the type in its parent.
+    fn anonymous_type_size(
+        &self,
+        fields: &[Field],
+        members: &[RustMember],
+        base_offset: u64,
+        end_offset: u64,
+    ) -> Result<u64> {
+        let mut fields_end = 0;
+        for field in fields {
+            fields_end = fields_end
+                .max(field.offset.saturating_sub(base_offset) + self.type_size(field.type_index)?);
+        }
+        let alignment = members.iter().map(|m| m.alignment).max().unwrap_or(1);
+        let aligned_size = fields_end.div_ceil(alignment) * alignment;
+
+        Ok(aligned_size
+            .min(end_offset.saturating_sub(base_offset))
+            .max(fields_end))
+    }
+
+    fn field_member(&self, field: &Field, base_offset: u64) -> Result<RustMember> {
+        let name = field.name.to_string();
+        Ok(RustMember {
+            offset: field.offset.saturating_sub(base_offset),
+            size: self.type_size(field.type_index)?,
+            alignment: self.type_alignment(field.type_index)?,
+            name: if name.is_empty() {
+                format!("_unnamed_{:#x}", field.offset)
+            } else {
+                rust_identifier(&name)
+            },
+            type_name: self.type_name(field.type_index)?,
+        })
+    }
+
+    /// Declare an integer member holding the given bit-fields, which must
+    /// share the same allocation unit.
+    fn bitfield_member(
+        &self,
+        bitfields: &[&Field],
+        base_offset: u64,
+        bitfield_index: usize,
+    ) -> Result<(RustMember, Vec<RustBitfieldAccessor>)> {
+        let first_field = bitfields[0];
+        let storage_size = self.type_size(first_field.type_index)?;
+        let storage_type = unsigned_integer_type(storage_size);
+        let storage_name = format!("_bitfield_{bitfield_index}");
+
+        let mut accessors = vec![];
+        for field in bitfields {
+            let name = field.name.to_string();
+            if name.is_empty() {
+                continue;
+            }
+            let (position, length) = field.bitfield_info.unwrap_or_default();
+            accessors.push(RustBitfieldAccessor {
+                name: rust_identifier(&name),
+                storage_name: storage_name.clone(),
+                storage_type,
+                storage_size,
+                value_type: self.bitfield_value_type(field.type_index)?,
+                position,
+                length,
+            });
+        }
+
+        Ok((
+            RustMember {
+                offset: first_field.offset.saturating_sub(base_offset),
+                size: storage_size,
+                alignment: storage_size,
+                name: storage_name,
+                type_name: storage_type.to_string(),
+            },
+            accessors,
+        ))
+    }
+
+    /// Write the declaration of a struct or union, followed by its bit-field
+    /// accessors. Gaps between members are filled with explicit padding.
+    ///
+    /// Return the alignment of the declared type.
+    fn write_aggregate(
+        &self,
+        aggregate: &RustAggregate,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<u64> {
+        let natural_alignment = aggregate
+            .members
+            .iter()
+            .map(|m| m.alignment)
+            .max()
+            .unwrap_or(1);
+        let is_packed = aggregate.size % natural_alignment != 0
+            || aggregate
+                .members
+                .iter()
+                .any(|m| m.offset % m.alignment != 0);
+
+        // (offset, name, type name) tuples
+        let mut lines: Vec<(u64, String, String)> = vec![];
+        // Members actually declared, padding excluded
+        let mut declared_members: Vec<&RustMember> = vec![];
+        let padding = |offset: u64, size: u64| {
+            (
+                offset,
+                format!("_pad_{offset:#x}"),
+                if fmt_configuration.integers_as_hexadecimal {
+                    format!("[u8; {size:#x}]")
+                } else {
+                    format!("[u8; {size}]")
+                },
+            )
+        };
+        if aggregate.is_union {
+            let mut members_size = 0;
+            for member in &aggregate.members {
+                lines.push((member.offset, member.name.clone(), member.type_name.clone()));
+                declared_members.push(member);
+                members_size = members_size.max(member.size);
+            }
+            if aggregate.members.is_empty() || members_size < aggregate.size {
+                lines.push(padding(0, aggregate.size));
+            }
+        } else {
+            let mut members: Vec<&RustMember> = aggregate.members.iter().collect();
+            members.sort_by_key(|m| m.offset);
+            let mut current_offset = 0;
+            for member in members {
+                if member.offset < current_offset {
+                    log::warn!(
+                        "'{}': member '{}' overlaps with the previous member, ignoring it",
+                        aggregate.name,
+                        member.name
+                    );
+                    continue;
+                }
+                if member.offset > current_offset {
+                    lines.push(padding(current_offset, member.offset - current_offset));
+                }
+                lines.push((member.offset, member.name.clone(), member.type_name.clone()));
+                declared_members.push(member);
+                current_offset = member.offset + member.size;
+            }
+            if aggregate.size > current_offset {
+                lines.push(padding(current_offset, aggregate.size - current_offset));
+            }
+        }
+
+        writeln!(f, "#[repr(C{})]", if is_packed { ", packed" } else { "" })?;
+        writeln!(f, "#[derive(Clone, Copy)]")?;
+        writeln!(
+            f,
+            "pub {} {}{}{{{}",
+            if aggregate.is_union {
+                "union"
+            } else {
+                "struct"
+            },
+            aggregate.name,
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            },
+            if fmt_configuration.size_print_flavor == SizePrintFlavor::Comment {
+                format!(" /* Size={:#x} */", aggregate.size)
+            } else {
+                String::default()
+            }
+        )?;
+        for (offset, name, type_name) in lines {
+            write!(f, "    ")?;
+            if fmt_configuration.print_offset_info {
+                write!(f, "/* {offset:#06x} */ ")?;
+            }
+            writeln!(f, "pub {name}: {type_name},")?;
+        }
+        writeln!(f, "}}")?;
+
+        self.write_bitfield_accessors(aggregate, f)?;
+
+        if fmt_configuration.size_print_flavor == SizePrintFlavor::StaticAssert {
+            if fmt_configuration.integers_as_hexadecimal {
+                writeln!(
+                    f,
+                    "const _: () = assert!(core::mem::size_of::<{}>() == {:#x}); // {}",
+                    aggregate.name, aggregate.size, aggregate.size
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "const _: () = assert!(core::mem::size_of::<{}>() == {}); // {:#x}",
+                    aggregate.name, aggregate.size, aggregate.size
+                )?;
+            }
+        }
+        if fmt_configuration.print_offset_asserts {
+            for member in declared_members {
+                if fmt_configuration.integers_as_hexadecimal {
+                    writeln!(
+                        f,
+                        "const _: () = assert!(core::mem::offset_of!({}, {}) == {:#x}); // {}",
+                        aggregate.name, member.name, member.offset, member.offset
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "const _: () = assert!(core::mem::offset_of!({}, {}) == {}); // {:#x}",
+                        aggregate.name, member.name, member.offset, member.offset
+                    )?;
+                }
+            }
+        }
+
+        Ok(if is_packed { 1 } else { natural_alignment })
+    }
+
+    fn write_bitfield_accessors(
+        &self,
+        aggregate: &RustAggregate,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        if aggregate.bitfield_accessors.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "impl {} {{", aggregate.name)?;
+        for (i, accessor) in aggregate.bitfield_accessors.iter().enumerate() {
+            let storage = format!("self.{}", accessor.storage_name);
+            let storage_bits = accessor.storage_size * 8;
+            let position = u64::from(accessor.position);
+            let length = u64::from(accessor.length);
+            let mask = format!("{:#x}{}", (1u128 << length) - 1, accessor.storage_type);
+
+            let getter = match accessor.value_type {
+                "bool" => format!("(({storage} >> {position}) & {mask}) != 0"),
+                value_type if value_type.starts_with('i') => {
+                    // Shift the value to the left then back to the right to
+                    // extend its sign
+                    format!(
+                        "(({storage} << {}) as i{storage_bits} >> {}) as {value_type}",
+                        storage_bits - position - length,
+                        storage_bits - length,
+                    )
+                }
+                value_type => {
+                    format!("(({storage} >> {position}) & {mask}) as {value_type}")
+                }
+            };
+            let setter = format!(
+                "{storage} = ({storage} & !({mask} << {position})) | (((value as {}) & {mask}) << {position});",
+                accessor.storage_type
+            );
+            let (getter, setter) = if aggregate.is_union {
+                (
+                    format!("unsafe {{ {getter} }}"),
+                    format!("unsafe {{ {setter} }}"),
+                )
+            } else {
+                (getter, setter)
+            };
+
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "    #[inline]")?;
+            writeln!(
+                f,
+                "    pub fn {}(&self) -> {} {{",
+                accessor.name, accessor.value_type
+            )?;
+            writeln!(f, "        {getter}")?;
+            writeln!(f, "    }}")?;
+            writeln!(f)?;
+            writeln!(f, "    #[inline]")?;
+            writeln!(
+                f,
+                "    pub fn set_{}(&mut self, value: {}) {{",
+                accessor.name.trim_start_matches("r#"),
+                accessor.value_type
+            )?;
+            writeln!(f, "        {setter}")?;
+            writeln!(f, "    }}")?;
+        }
+        writeln!(f, "}}")?;
+
+        Ok(())
+    }
+
+    fn write_opaque_struct(
+        &self,
+        name: &str,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> Result<()> {
+        writeln!(f, "#[repr(C)]")?;
+        writeln!(
+            f,
+            "pub struct {}{}{{",
+            rust_identifier(name),
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            }
+        )?;
+        writeln!(f, "    _opaque: [u8; 0],")?;
+        writeln!(f, "}}")?;
+
+        Ok(())
+    }
+
+    /// Return the Rust type corresponding to the given `type_index`.
+    fn type_name(&self, type_index: pdb::TypeIndex) -> Result<String> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        let type_name = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Primitive(data) => {
+                let (name, _, _) = primitive_kind_as_rust_type(data.kind);
+                if data.indirection.is_some() {
+                    format!("*mut {name}")
+                } else {
+                    name.to_string()
+                }
+            }
+
+            pdb::TypeData::Class(data) => tag_name(type_index, &data.name.to_string()),
+            pdb::TypeData::Union(data) => tag_name(type_index, &data.name.to_string()),
+            pdb::TypeData::Enumeration(data) => tag_name(type_index, &data.name.to_string()),
+
+            pdb::TypeData::Pointer(data) => {
+                if data.containing_class.is_some() {
+                    // Pointers to members have no Rust equivalent, keep the
+                    // storage only
+                    format!("[u8; {}]", type_size(self.type_finder, type_index)?)
+                } else {
+                    let underlying_type_index =
+                        resolve_complete_type_index(self.type_forwarder, data.underlying_type);
+                    match self.type_finder.find(underlying_type_index)?.parse()? {
+                        pdb::TypeData::Procedure(_) | pdb::TypeData::MemberFunction(_) => {
+                            // Function pointers are nullable
+                            self.type_name(underlying_type_index)?
+                        }
+                        pdb::TypeData::Modifier(data) if data.constant => {
+                            format!("*const {}", self.type_name(data.underlying_type)?)
+                        }
+                        _ => format!("*mut {}", self.type_name(underlying_type_index)?),
+                    }
+                }
+            }
+
+            pdb::TypeData::Modifier(data) => self.type_name(data.underlying_type)?,
+
+            pdb::TypeData::Bitfield(data) => self.type_name(data.underlying_type)?,
+
+            pdb::TypeData::Array(data) => {
+                let element_type_index =
+                    resolve_complete_type_index(self.type_forwarder, data.element_type);
+                let element_size = self.type_size(element_type_index)?;
+                if element_size == 0 {
+                    let size = data.dimensions.last().copied().unwrap_or_default();
+                    log::warn!(
+                        "array element type {} has invalid size (0), using a byte array",
+                        element_type_index
+                    );
+                    format!("[u8; {size}]")
+                } else {
+                    let mut type_name = self.type_name(element_type_index)?;
+                    let mut divider = element_size;
+                    for dimension_size in data.dimensions {
+                        let dimension_size = u64::from(dimension_size);
+                        type_name = format!("[{}; {}]", type_name, dimension_size / divider);
+                        divider = dimension_size.max(1);
+                    }
+                    type_name
+                }
+            }
+
+            pdb::TypeData::Procedure(data) => {
+                self.function_pointer_type_name(data.return_type, None, data.argument_list)?
+            }
+
+            pdb::TypeData::MemberFunction(data) => self.function_pointer_type_name(
+                Some(data.return_type),
+                data.this_pointer_type,
+                data.argument_list,
+            )?,
+
+            type_data => {
+                log::warn!(
+                    "FIXME: figure out how to name it: TypeIndex={}, TypeData={:?}",
+                    type_index,
+                    type_data
+                );
+                format!("[u8; {}]", type_size(self.type_finder, type_index)?)
+            }
+        };
+
+        Ok(type_name)
+    }
+
+    fn function_pointer_type_name(
+        &self,
+        return_type: Option<pdb::TypeIndex>,
+        this_pointer_type: Option<pdb::TypeIndex>,
+        argument_list: pdb::TypeIndex,
+    ) -> Result<String> {
+        let mut arguments = vec![];
+        if let Some(this_pointer_type) = this_pointer_type {
+            arguments.push(self.type_name(this_pointer_type)?);
+        }
+        if let pdb::TypeData::ArgumentList(data) = self.type_finder.find(argument_list)?.parse()? {
+            for argument_type in data.arguments {
+                match self.type_finder.find(argument_type)?.parse()? {
+                    pdb::TypeData::Primitive(pdb::PrimitiveType {
+                        kind: pdb::PrimitiveKind::NoType,
+                        indirection: None,
+                    }) => {
+                        // Variadic arguments must follow a named argument
+                        if !arguments.is_empty() {
+                            arguments.push("...".to_string());
+                        }
+                    }
+                    pdb::TypeData::Primitive(pdb::PrimitiveType {
+                        kind: pdb::PrimitiveKind::Void,
+                        indirection: None,
+                    }) => {}
+                    _ => arguments.push(self.type_name(argument_type)?),
+                }
+            }
+        }
+
+        let return_type = match return_type {
+            Some(return_type) => {
+                let return_type_name = self.type_name(return_type)?;
+                if return_type_name == "core::ffi::c_void" {
+                    String::default()
+                } else {
+                    format!(" -> {return_type_name}")
+                }
+            }
+            None => String::default(),
+        };
+
+        Ok(format!(
+            "Option<unsafe extern \"C\" fn({}){}>",
+            arguments.join(", "),
+            return_type
+        ))
+    }
+
+    /// Return the size in bytes of the Rust type corresponding to the given
+    /// `type_index`.
+    fn type_size(&self, type_index: pdb::TypeIndex) -> Result<u64> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        let size = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Primitive(data) if data.indirection.is_none() => {
+                primitive_kind_as_rust_type(data.kind).1
+            }
+            pdb::TypeData::Bitfield(data) => self.type_size(data.underlying_type)?,
+            pdb::TypeData::Modifier(data) => self.type_size(data.underlying_type)?,
+            pdb::TypeData::Enumeration(data) => self.type_size(enum_underlying_type(&data))?,
+            _ => type_size(self.type_finder, type_index)? as u64,
+        };
+
+        Ok(size)
+    }
+
+    /// Return the alignment in bytes of the given `type_index`.
+    fn type_alignment(&self, type_index: pdb::TypeIndex) -> Result<u64> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        if let Some(alignment) = self.alignment_cache.borrow().get(&type_index) {
+            return Ok(*alignment);
+        }
+
+        let alignment = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Primitive(data) => {
+                if data.indirection.is_some() {
+                    type_size(self.type_finder, type_index)? as u64
+                } else {
+                    primitive_kind_as_rust_type(data.kind).2
+                }
+            }
+            pdb::TypeData::Pointer(data) => {
+                if data.containing_class.is_some() {
+                    1
+                } else {
+                    type_size(self.type_finder, type_index)? as u64
+                }
+            }
+            pdb::TypeData::Modifier(data) => self.type_alignment(data.underlying_type)?,
+            pdb::TypeData::Bitfield(data) => self.type_alignment(data.underlying_type)?,
+            pdb::TypeData::Enumeration(data) => self.type_alignment(enum_underlying_type(&data))?,
+            pdb::TypeData::Array(data) => {
+                if self.type_size(data.element_type)? == 0 {
+                    1
+                } else {
+                    self.type_alignment(data.element_type)?
+                }
+            }
+            pdb::TypeData::Class(data) => match data.fields {
+                Some(fields) if !data.properties.forward_reference() => {
+                    self.field_list_alignment(fields)?
+                }
+                _ => 1,
+            },
+            pdb::TypeData::Union(data) if !data.properties.forward_reference() => {
+                self.field_list_alignment(data.fields)?
+            }
+            _ => 1,
+        };
+        let alignment = alignment.max(1);
+        self.alignment_cache
+            .borrow_mut()
+            .insert(type_index, alignment);
+
+        Ok(alignment)
+    }
+
+    fn field_list_alignment(&self, type_index: pdb::TypeIndex) -> Result<u64> {
+        let mut alignment = 1;
+        if let pdb::TypeData::FieldList(data) = self.type_finder.find(type_index)?.parse()? {
+            for field in &data.fields {
+                let field_alignment = match field {
+                    pdb::TypeData::Member(data) => self.type_alignment(data.field_type)?,
+                    pdb::TypeData::BaseClass(data) => self.type_alignment(data.base_class)?,
+                    pdb::TypeData::VirtualBaseClass(data) => {
+                        self.type_alignment(data.base_pointer)?
+                    }
+                    pdb::TypeData::VirtualFunctionTablePointer(data) => {
+                        self.type_alignment(data.table)?
+                    }
+                    _ => 1,
+                };
+                alignment = alignment.max(field_alignment);
+            }
+
+            if let Some(continuation) = data.continuation {
+                alignment = alignment.max(self.field_list_alignment(continuation)?);
+            }
+        }
+
+        Ok(alignment)
+    }
+
+    /// Indicate if the given class has no data members (i.e., is subject to
+    /// the empty base optimization).
+    fn is_empty_type(&self, type_index: pdb::TypeIndex) -> Result<bool> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Class(data) => match data.fields {
+                Some(fields) => self.is_empty_field_list(fields),
+                None => Ok(true),
+            },
+            _ => Ok(false),
+        }
+    }
+
+    fn is_empty_field_list(&self, type_index: pdb::TypeIndex) -> Result<bool> {
+        if let pdb::TypeData::FieldList(data) = self.type_finder.find(type_index)?.parse()? {
+            for field in &data.fields {
+                let is_empty = match field {
+                    pdb::TypeData::Member(_)
+                    | pdb::TypeData::VirtualBaseClass(_)
+                    | pdb::TypeData::VirtualFunctionTablePointer(_) => false,
+                    pdb::TypeData::BaseClass(data) => self.is_empty_type(data.base_class)?,
+                    _ => true,
+                };
+                if !is_empty {
+                    return Ok(false);
+                }
+            }
+
+            if let Some(continuation) = data.continuation {
+                return self.is_empty_field_list(continuation);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Return the integer type corresponding to the given `type_index`, used
+    /// as the representation of enums.
+    fn integer_type(&self, type_index: pdb::TypeIndex) -> Result<&'static str> {
+        let type_index = resolve_complete_type_index(self.type_forwarder, type_index);
+        let integer_type = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Primitive(data) if data.indirection.is_none() => {
+                let (name, size, _) = primitive_kind_as_rust_type(data.kind);
+                if is_integer_type(name) {
+                    name
+                } else {
+                    unsigned_integer_type(size)
+                }
+            }
+            pdb::TypeData::Modifier(data) => self.integer_type(data.underlying_type)?,
+            pdb::TypeData::Enumeration(data) => self.integer_type(enum_underlying_type(&data))?,
+            _ => unsigned_integer_type(self.type_size(type_index)?),
+        };
+
+        Ok(integer_type)
+    }
+
+    /// Return the type of the values held by the given bit-field type.
+    fn bitfield_value_type(&self, type_index: pdb::TypeIndex) -> Result<&'static str> {
+        let underlying_type_index = match self.type_finder.find(type_index)?.parse()? {
+            pdb::TypeData::Bitfield(data) => data.underlying_type,
+            _ => type_index,
+        };
+        if let pdb::TypeData::Primitive(data) =
+            self.type_finder.find(underlying_type_index)?.parse()?
+        {
+            if data.indirection.is_none() && primitive_kind_as_rust_type(data.kind).0 == "bool" {
+                return Ok("bool");
+            }
+        }
+
+        self.integer_type(underlying_type_index)
+    }
+}
+
+/// Return the Rust type corresponding to the given primitive kind, along with
+/// its size and alignment in bytes.
+fn primitive_kind_as_rust_type(kind: pdb::PrimitiveKind) -> (&'static str, u64, u64) {
+    match kind {
+        pdb::PrimitiveKind::NoType | pdb::PrimitiveKind::Void => ("core::ffi::c_void", 0, 1),
+        pdb::PrimitiveKind::Char | pdb::PrimitiveKind::RChar | pdb::PrimitiveKind::I8 => {
+            ("i8", 1, 1)
+        }
+        pdb::PrimitiveKind::UChar | pdb::PrimitiveKind::U8 | pdb::PrimitiveKind::Char8 => {
+            ("u8", 1, 1)
+        }
+        pdb::PrimitiveKind::Short | pdb::PrimitiveKind::I16 => ("i16", 2, 2),
+        pdb::PrimitiveKind::WChar
+        | pdb::PrimitiveKind::RChar16
+        | pdb::PrimitiveKind::UShort
+        | pdb::PrimitiveKind::U16 => ("u16", 2, 2),
+        pdb::PrimitiveKind::Long | pdb::PrimitiveKind::I32 | pdb::PrimitiveKind::HRESULT => {
+            ("i32", 4, 4)
+        }
+        pdb::PrimitiveKind::RChar32 | pdb::PrimitiveKind::ULong | pdb::PrimitiveKind::U32 => {
+            ("u32", 4, 4)
+        }
+        pdb::PrimitiveKind::Quad | pdb::PrimitiveKind::I64 => ("i64", 8, 8),
+        pdb::PrimitiveKind::UQuad | pdb::PrimitiveKind::U64 => ("u64", 8, 8),
+        pdb::PrimitiveKind::Octa | pdb::PrimitiveKind::I128 => ("i128", 16, 16),
+        pdb::PrimitiveKind::UOcta | pdb::PrimitiveKind::U128 => ("u128", 16, 16),
+        pdb::PrimitiveKind::F32 | pdb::PrimitiveKind::F32PP => ("f32", 4, 4),
+        pdb::PrimitiveKind::F64 => ("f64", 8, 8),
+        pdb::PrimitiveKind::Bool8 => ("bool", 1, 1),
+        pdb::PrimitiveKind::Bool16 => ("u16", 2, 2),
+        pdb::PrimitiveKind::Bool32 => ("u32", 4, 4),
+        pdb::PrimitiveKind::Bool64 => ("u64", 8, 8),
+        pdb::PrimitiveKind::F16 => ("[u8; 2]", 2, 1),
+        pdb::PrimitiveKind::F48 => ("[u8; 6]", 6, 1),
+        pdb::PrimitiveKind::F80 => ("[u8; 10]", 10, 1),
+        pdb::PrimitiveKind::F128 => ("[u8; 16]", 16, 1),
+        pdb::PrimitiveKind::Complex32 => ("[f32; 2]", 8, 4),
+        pdb::PrimitiveKind::Complex64 => ("[f64; 2]", 16, 8),
+        pdb::PrimitiveKind::Complex80 => ("[u8; 20]", 20, 1),
+        pdb::PrimitiveKind::Complex128 => ("[u8; 32]", 32, 1),
+        _ => {
+            log::warn!("FIXME: unsupported primitive kind: {:?}", kind);
+            ("core::ffi::c_void", 0, 1)
+        }
+    }
+}
+
+fn is_integer_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64" | "i128" | "u128"
+    )
+}
+
+fn unsigned_integer_type(size: u64) -> &'static str {
+    match size {
+        1 => "u8",
+        2 => "u16",
+        8 => "u64",
+        16 => "u128",
+        _ => "u32",
+    }
+}
+
+/// Name of a class, union or enum type, anonymous tags are renamed to
+/// something unique.
+fn tag_name(type_index: pdb::TypeIndex, name: &str) -> String {
+    if is_unnamed_type(name) {
+        format!("_unnamed_{type_index}")
+    } else {
+        rust_identifier(name)
+    }
+}
+
+/// Turn a C++ name into a valid Rust identifier.
+fn rust_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+
+    if matches!(
+        identifier.as_str(),
+        "_" | "crate" | "self" | "Self" | "super"
+    ) {
+        // These cannot be raw identifiers
+        identifier.push('_');
+    } else if RUST_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.insert_str(0, "r#");
+    }
+
+    identifier
+}
+
+fn variant_as_i128(value: &pdb::Variant) -> i128 {
+    match *value {
+        pdb::Variant::U8(v) => v.into(),
+        pdb::Variant::U16(v) => v.into(),
+        pdb::Variant::U32(v) => v.into(),
+        pdb::Variant::U64(v) => v.into(),
+        pdb::Variant::I8(v) => v.into(),
+        pdb::Variant::I16(v) => v.into(),
+        pdb::Variant::I32(v) => v.into(),
+        pdb::Variant::I64(v) => v.into(),
+    }
+}
+
+/// Wrap the given enum value into the range of the given integer type.
+fn normalize_discriminant(value: i128, integer_type: &str) -> i128 {
+    let bits: u32 = integer_type[1..].parse().unwrap_or(32);
+    if bits >= 128 {
+        return value;
+    }
+
+    let modulus = 1i128 << bits;
+    let value = value.rem_euclid(modulus);
+    if integer_type.starts_with('i') && value >= modulus / 2 {
+        value - modulus
+    } else {
+        value
+    }
+}
@@ -1,20 +1,25 @@
 use std::{char::from_digit, fmt};
 
+use serde::Serialize;
+
 use super::{
     class::Class,
     enumeration::Enum,
     field::{FieldAccess, StaticField},
-    fmt_union_fields_recursive, is_unnamed_type,
+    fmt_offset_static_asserts, fmt_union_fields_recursive, is_unnamed_type,
+    json::serialize_type_index,
     primitive_types::AccessSpecifierReconstructionFlavor,
     primitive_types::PrimitiveReconstructionFlavor,
     primitive_types::SizePrintFlavor,
-    resolve_complete_type_index, type_bitfield_info, type_name, type_size, DataFormatConfiguration,
-    Field, Method, NeededTypeSet, ReconstructibleTypeData, TypeForwarder,
+    resolve_complete_type_index, type_alignment, type_bitfield_info, type_name, type_size,
+    AggregateLayout, DataFormatConfiguration, Field, Method, NeededTypeSet,
+    ReconstructibleTypeData, TypeAlignmentMap, TypeForwarder, VirtualTableRecords,
 };
 use crate::error::{Result, ResymCoreError};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Union<'p> {
+    #[serde(serialize_with = "serialize_type_index")]
     pub index: pdb::TypeIndex,
     pub name: String,
     pub size: u64,
@@ -28,6 +33,35 @@ pub struct Union<'p> {
 }
 
 impl<'p> Union<'p> {
+    /// Reconstruct the virtual function tables of the classes nested in the
+    /// union.
+    pub fn add_vftables(
+        &mut self,
+        type_finder: &pdb::TypeFinder<'p>,
+        type_forwarder: &TypeForwarder,
+        vtable_records: &VirtualTableRecords,
+        primitive_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<()> {
+        for class in &mut self.nested_classes {
+            class.add_vftables(
+                type_finder,
+                type_forwarder,
+                vtable_records,
+                primitive_flavor,
+            )?;
+        }
+        for u in &mut self.nested_unions {
+            u.add_vftables(
+                type_finder,
+                type_forwarder,
+                vtable_records,
+                primitive_flavor,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn add_fields(
         &mut self,
         type_finder: &pdb::TypeFinder<'p>,
@@ -35,6 +69,7 @@ impl<'p> Union<'p> {
         type_index: pdb::TypeIndex,
         primitive_flavor: &PrimitiveReconstructionFlavor,
         needed_types: &mut NeededTypeSet,
+        type_alignments: &mut TypeAlignmentMap,
     ) -> Result<()> {
         // Resolve the complete type's index, if present in the PDB
         let complete_type_index = resolve_complete_type_index(type_forwarder, type_index);
@@ -47,6 +82,7 @@ impl<'p> Union<'p> {
                         field,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
 
@@ -58,6 +94,7 @@ impl<'p> Union<'p> {
                         continuation,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
             }
@@ -85,6 +122,8 @@ impl<'p> Union<'p> {
                     nested_classes: Vec::new(),
                     nested_unions: Vec::new(),
                     nested_enums: Vec::new(),
+                    vftable: None,
+                    secondary_vftables: Vec::new(),
                 };
 
                 if let Some(derived_from) = data.derived_from {
@@ -98,9 +137,9 @@ impl<'p> Union<'p> {
                         fields,
                         primitive_flavor,
                         needed_types,
+                        type_alignments,
                     )?;
                 }
-
                 self.nested_classes.insert(0, class);
             }
 
@@ -132,6 +171,7 @@ impl<'p> Union<'p> {
                     data.fields,
                     primitive_flavor,
                     needed_types,
+                    type_alignments,
                 )?;
 
                 self.nested_unions.insert(0, u);
@@ -188,6 +228,7 @@ impl<'p> Union<'p> {
         field: &pdb::TypeData<'p>,
         primitive_flavor: &PrimitiveReconstructionFlavor,
         needed_types: &mut NeededTypeSet,
+        type_alignments: &mut TypeAlignmentMap,
     ) -> Result<()> {
         match *field {
             pdb::TypeData::Member(ref data) => {
@@ -203,17 +244,26 @@ impl<'p> Union<'p> {
                 )?;
                 let type_bitfield_info = type_bitfield_info(type_finder, complete_type_index)?;
                 let type_size = type_size(type_finder, complete_type_index)?;
+                let type_alignment = type_alignment(
+                    type_finder,
+                    type_forwarder,
+                    complete_type_index,
+                    type_alignments,
+                )?;
                 let access = FieldAccess::from_field_attribute(data.attributes.access());
 
                 // TODO: attributes (static, virtual, etc.)
                 self.fields.push(Field {
                     type_left,
                     type_right,
+                    type_index: complete_type_index,
                     name: data.name,
                     offset: data.offset,
                     size: type_size,
+                    alignment: type_alignment,
                     bitfield_info: type_bitfield_info,
                     access,
+                    is_implicit: false,
                 });
             }
 
@@ -232,6 +282,7 @@ impl<'p> Union<'p> {
                 self.static_fields.push(StaticField {
                     type_left,
                     type_right,
+                    type_index: complete_type_index,
                     name: data.name,
                     access,
                 });
@@ -327,6 +378,11 @@ impl ReconstructibleTypeData for Union<'_> {
         fmt_configuration: &DataFormatConfiguration,
         f: &mut impl std::fmt::Write,
     ) -> fmt::Result {
+        let layout = AggregateLayout::new(fmt_configuration, &self.fields, self.size);
+        if let Some(packing) = layout.packing {
+            writeln!(f, "#pragma pack(push, {packing})")?;
+        }
+
         writeln!(
             f,
             "union {}{}{{{}",
@@ -343,7 +399,14 @@ impl ReconstructibleTypeData for Union<'_> {
             }
         )?;
 
-        // Nested delcarations
+        // Nested delcarations (enums first, as they may be used by
+        // the other types)
+        if !self.nested_enums.is_empty() {
+            writeln!(f, "  ")?;
+            for e in &self.nested_enums {
+                e.reconstruct(fmt_configuration, f)?;
+            }
+        }
         if !self.nested_classes.is_empty() {
             writeln!(f, "  ")?;
             for class in &self.nested_classes {
@@ -356,15 +419,9 @@ impl ReconstructibleTypeData for Union<'_> {
                 u.reconstruct(fmt_configuration, f)?;
             }
         }
-        if !self.nested_enums.is_empty() {
-            writeln!(f, "  ")?;
-            for e in &self.nested_enums {
-                e.reconstruct(fmt_configuration, f)?;
-            }
-        }
 
         // Dump fields while detecting unnamed structs and unions
-        fmt_union_fields_recursive(fmt_configuration, &self.fields, 1, f)?;
+        fmt_union_fields_recursive(fmt_configuration, &layout, &self.fields, 1, f)?;
 
         // Static fields
         for field in &self.static_fields {
@@ -391,6 +448,10 @@ impl ReconstructibleTypeData for Union<'_> {
         if !self.instance_methods.is_empty() {
             writeln!(f, "  ")?;
             for method in &self.instance_methods {
+                // Note: the return type of constructors, destructors and
+                // conversion functions isn't declared
+                let has_return_type =
+                    !(method.is_ctor || method.is_dtor || method.is_conversion_operator());
                 writeln!(
                     f,
                     "  {}{}{}{}{}({}){}{}{}{};",
@@ -406,12 +467,12 @@ impl ReconstructibleTypeData for Union<'_> {
                         }
                     },
                     if method.is_virtual { "virtual " } else { "" },
-                    if method.is_ctor || method.is_dtor {
-                        ""
-                    } else {
+                    if has_return_type {
                         &method.return_type_name.0
+                    } else {
+                        ""
                     },
-                    if !method.is_ctor && !method.is_dtor && method.return_type_name.1.is_empty() {
+                    if has_return_type && method.return_type_name.1.is_empty() {
                         " "
                     } else {
                         ""
@@ -470,6 +531,9 @@ impl ReconstructibleTypeData for Union<'_> {
         }
 
         writeln!(f, "}};")?;
+        if layout.packing.is_some() {
+            writeln!(f, "#pragma pack(pop)")?;
+        }
         if fmt_configuration.size_print_flavor == SizePrintFlavor::StaticAssert {
             if fmt_configuration.integers_as_hexadecimal {
                 writeln!(
@@ -485,6 +549,20 @@ impl ReconstructibleTypeData for Union<'_> {
                 )?;
             }
         }
+        if fmt_configuration.print_offset_asserts {
+            fmt_offset_static_asserts(
+                fmt_configuration,
+                &self.name,
+                &self.fields,
+                |field| {
+                    fmt_configuration.print_access_specifiers
+                        == AccessSpecifierReconstructionFlavor::Disabled
+                        || field.access == FieldAccess::None
+                        || field.access == FieldAccess::Public
+                },
+                f,
+            )?;
+        }
 
         Ok(())
     }
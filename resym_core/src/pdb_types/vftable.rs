@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{
+    json::serialize_type_index, method::Method, primitive_types::PrimitiveReconstructionFlavor,
+    resolve_complete_type_index, type_name, type_size, DataFormatConfiguration, NeededTypeSet,
+    SizePrintFlavor, TypeForwarder,
+};
+use crate::error::Result;
+
+/// Name of the hidden member holding the virtual function table pointer.
+pub const VFPTR_MEMBER_NAME: &str = "__vfptr";
+/// Maximum number of base classes followed when looking for a primary
+/// polymorphic base class, to avoid looping forever on malformed PDBs.
+const MAX_BASE_CLASS_DEPTH: usize = 64;
+/// Leaf kinds of the type records describing virtual function tables, which
+/// aren't parsed by `pdb`.
+const LF_VTSHAPE: u16 = 0x000a;
+const LF_VFTABLE: u16 = 0x151d;
+
+/// Return the name of the struct representing the virtual function table of
+/// the class named `class_name`.
+pub fn vftable_type_name(class_name: &str) -> String {
+    format!("{class_name}_vftable")
+}
+
+/// Return the name of the member representing the slot at `index` of a
+/// virtual function table, for slots no method is known for.
+pub fn unknown_slot_name(index: usize) -> String {
+    format!("__slot{index}")
+}
+
+/// Information from the `LF_VTSHAPE` and `LF_VFTABLE` records of the TPI
+/// stream.
+#[derive(Debug, Default)]
+pub struct VirtualTableRecords {
+    /// Number of slots of the virtual function table described by each
+    /// `LF_VTSHAPE` record
+    shape_slot_counts: HashMap<pdb::TypeIndex, usize>,
+    /// Names of the methods in the slots of the virtual function tables
+    /// described by `LF_VFTABLE` records, by (complete) class and offset of
+    /// the virtual function table pointer
+    slot_names: HashMap<(pdb::TypeIndex, u64), Vec<String>>,
+}
+
+impl VirtualTableRecords {
+    /// Read the virtual function table records of the raw TPI stream
+    /// `type_stream`. Parsing stops at the first malformed record.
+    pub fn parse(type_stream: &[u8], type_forwarder: &TypeForwarder) -> Self {
+        let mut records = Self::default();
+        let (Some(header_size), Some(first_type_index)) =
+            (read_u32(type_stream, 4), read_u32(type_stream, 8))
+        else {
+            return records;
+        };
+
+        let mut offset = header_size as usize;
+        let mut type_index = pdb::TypeIndex(first_type_index);
+        while let Some(record_size) = read_u16(type_stream, offset) {
+            let record_start = offset + 2;
+            let record_end = record_start + record_size as usize;
+            let Some(record) = type_stream.get(record_start..record_end) else {
+                log::warn!("Type record {type_index} is truncated");
+                break;
+            };
+            match read_u16(record, 0) {
+                Some(LF_VTSHAPE) => {
+                    if let Some(slot_count) = read_u16(record, 2) {
+                        records
+                            .shape_slot_counts
+                            .insert(type_index, slot_count as usize);
+                    }
+                }
+                Some(LF_VFTABLE) => {
+                    if let Some((class_index, vfptr_offset, slot_names)) =
+                        parse_vftable_record(record)
+                    {
+                        let class_index = resolve_complete_type_index(type_forwarder, class_index);
+                        records
+                            .slot_names
+                            .insert((class_index, vfptr_offset), slot_names);
+                    }
+                }
+                _ => {}
+            }
+            offset = record_end;
+            type_index = pdb::TypeIndex(type_index.0 + 1);
+        }
+
+        records
+    }
+
+    /// Return the number of slots described by the `LF_VTSHAPE` record at
+    /// `shape_index`, if any.
+    fn shape_slot_count(&self, shape_index: pdb::TypeIndex) -> Option<usize> {
+        self.shape_slot_counts.get(&shape_index).copied()
+    }
+
+    /// Return the names of the methods in the slots of the virtual function
+    /// table of the (complete) class at `class_index`, whose pointer is
+    /// located at `vfptr_offset`, if the PDB describes it.
+    fn slot_names(&self, class_index: pdb::TypeIndex, vfptr_offset: u64) -> Option<&Vec<String>> {
+        self.slot_names.get(&(class_index, vfptr_offset))
+    }
+}
+
+/// Parse the owning class, the offset of the table's pointer and the
+/// names of the slots of an `LF_VFTABLE` record.
+fn parse_vftable_record(record: &[u8]) -> Option<(pdb::TypeIndex, u64, Vec<String>)> {
+    let class_index = pdb::TypeIndex(read_u32(record, 2)?);
+    let vfptr_offset = read_u32(record, 10)? as u64;
+    let names_size = read_u32(record, 14)? as usize;
+    let names = record.get(18..18usize.checked_add(names_size)?)?;
+    // The first name is the name of the table itself
+    let slot_names = names
+        .split(|c| *c == 0)
+        .filter(|name| !name.is_empty())
+        .skip(1)
+        .map(|name| String::from_utf8_lossy(name).into_owned())
+        .collect();
+
+    Some((class_index, vfptr_offset, slot_names))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VirtualFunctionOrigin {
+    /// Virtual function introduced by the class
+    Introduced,
+    /// Virtual function overridden by the class
+    Overridden,
+    /// Virtual function inherited from a base class, as is
+    Inherited,
+}
+
+impl fmt::Display for VirtualFunctionOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                VirtualFunctionOrigin::Introduced => "introduced",
+                VirtualFunctionOrigin::Overridden => "overridden",
+                VirtualFunctionOrigin::Inherited => "inherited",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VirtualFunctionTableSlot {
+    pub index: usize,
+    pub method_name: String,
+    /// Index of the method's `MemberFunction` type.
+    #[serde(serialize_with = "serialize_type_index")]
+    pub type_index: pdb::TypeIndex,
+    pub return_type_name: (String, String),
+    pub arguments: Vec<(String, String)>,
+    pub is_pure_virtual: bool,
+    pub is_const: bool,
+    pub is_volatile: bool,
+    /// Name of the class providing the implementation used in this slot.
+    pub defining_class: String,
+    pub origin: VirtualFunctionOrigin,
+}
+
+impl VirtualFunctionTableSlot {
+    fn from_method(
+        index: usize,
+        method: &Method,
+        type_index: pdb::TypeIndex,
+        defining_class: &str,
+        origin: VirtualFunctionOrigin,
+    ) -> Self {
+        Self {
+            index,
+            method_name: method.name.to_string().into_owned(),
+            type_index,
+            return_type_name: method.return_type_name.clone(),
+            arguments: method.arguments.clone(),
+            is_pure_virtual: method.is_pure_virtual,
+            is_const: method.is_const,
+            is_volatile: method.is_volatile,
+            defining_class: defining_class.to_owned(),
+            origin,
+        }
+    }
+
+    /// Return `true` if `method` overrides the virtual function in this slot.
+    fn is_overridden_by(&self, method: &Method) -> bool {
+        let method_name = method.name.to_string();
+        let same_name = if method.is_dtor {
+            // Destructors have different names in derived classes
+            self.method_name.starts_with('~')
+        } else {
+            self.method_name == method_name
+        };
+
+        same_name
+            && self.arguments == method.arguments
+            && self.is_const == method.is_const
+            && self.is_volatile == method.is_volatile
+    }
+}
+
+/// Virtual function table used through one of a class' virtual function
+/// table pointers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VirtualFunctionTable {
+    /// `true` if the virtual function table pointer is declared by the class
+    /// itself, rather than being inherited from one of its base classes.
+    pub owns_vfptr: bool,
+    /// Offset of the virtual function table pointer in the class.
+    pub vfptr_offset: u64,
+    /// Base class in which the virtual function table pointer is declared,
+    /// for tables which aren't shared with the class' primary base class.
+    pub base_class: Option<String>,
+    /// Size of a slot (i.e., of a pointer) in bytes.
+    pub slot_size: u64,
+    /// Number of slots, including the ones no method is known for.
+    pub slot_count: usize,
+    /// Names of the methods in each slot, if the PDB has an `LF_VFTABLE`
+    /// record for this table.
+    pub slot_names: Vec<String>,
+    pub slots: Vec<VirtualFunctionTableSlot>,
+}
+
+impl VirtualFunctionTable {
+    /// Reconstruct the virtual function tables of the class with the given
+    /// `type_index`. The table used through the class' primary virtual
+    /// function table pointer comes first, followed by the ones of its
+    /// secondary polymorphic base classes. Returns an empty list if the
+    /// class isn't polymorphic.
+    pub fn find(
+        type_finder: &pdb::TypeFinder,
+        type_forwarder: &TypeForwarder,
+        vtable_records: &VirtualTableRecords,
+        type_index: pdb::TypeIndex,
+        primitive_flavor: &PrimitiveReconstructionFlavor,
+    ) -> Result<Vec<Self>> {
+        Self::find_recursive(
+            type_finder,
+            type_forwarder,
+            vtable_records,
+            type_index,
+            primitive_flavor,
+            0,
+        )
+    }
+
+    fn find_recursive(
+        type_finder: &pdb::TypeFinder,
+        type_forwarder: &TypeForwarder,
+        vtable_records: &VirtualTableRecords,
+        type_index: pdb::TypeIndex,
+        primitive_flavor: &PrimitiveReconstructionFlavor,
+        depth: usize,
+    ) -> Result<Vec<Self>> {
+        if depth > MAX_BASE_CLASS_DEPTH {
+            log::warn!("Base class hierarchy of type {type_index} is too deep, giving up");
+            return Ok(vec![]);
+        }
+
+        let complete_type_index = resolve_complete_type_index(type_forwarder, type_index);
+        let (class_name, fields, vtable_shape) =
+            match type_finder.find(complete_type_index)?.parse()? {
+                pdb::TypeData::Class(data) => match data.fields {
+                    Some(fields) if !data.properties.forward_reference() => (
+                        data.name.to_string().into_owned(),
+                        fields,
+                        data.vtable_shape,
+                    ),
+                    _ => return Ok(vec![]),
+                },
+                _ => return Ok(vec![]),
+            };
+
+        // Note: types referenced by the virtual function table's slots are
+        // already referenced by the class' methods or by its base classes.
+        let mut needed_types = NeededTypeSet::new();
+        let mut vfptr_size = None;
+        let mut base_classes = vec![];
+        let mut virtual_methods = vec![];
+        let mut field_list_index = Some(fields);
+        while let Some(current_field_list_index) = field_list_index {
+            let field_list = match type_finder.find(current_field_list_index)?.parse()? {
+                pdb::TypeData::FieldList(data) => data,
+                _ => break,
+            };
+            for field in &field_list.fields {
+                match field {
+                    pdb::TypeData::VirtualFunctionTablePointer(data) => {
+                        vfptr_size = Some(type_size(type_finder, data.table)? as u64);
+                    }
+                    pdb::TypeData::BaseClass(data) => {
+                        base_classes.push((data.base_class, data.offset));
+                    }
+                    pdb::TypeData::Method(data) if is_virtual_method(data.attributes) => {
+                        virtual_methods.push((
+                            Method::find(
+                                data.name,
+                                data.attributes,
+                                type_finder,
+                                type_forwarder,
+                                data.method_type,
+                                primitive_flavor,
+                                &mut needed_types,
+                            )?,
+                            data.method_type,
+                            data.vtable_offset,
+                        ));
+                    }
+                    pdb::TypeData::OverloadedMethod(data) => {
+                        if let pdb::TypeData::MethodList(method_list) =
+                            type_finder.find(data.method_list)?.parse()?
+                        {
+                            for entry in method_list.methods {
+                                if is_virtual_method(entry.attributes) {
+                                    virtual_methods.push((
+                                        Method::find(
+                                            data.name,
+                                            entry.attributes,
+                                            type_finder,
+                                            type_forwarder,
+                                            entry.method_type,
+                                            primitive_flavor,
+                                            &mut needed_types,
+                                        )?,
+                                        entry.method_type,
+                                        entry.vtable_offset,
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            field_list_index = field_list.continuation;
+        }
+
+        let mut vftables = vec![];
+        if let Some(slot_size) = vfptr_size {
+            // The class declares its own virtual function table pointer
+            vftables.push(VirtualFunctionTable {
+                owns_vfptr: true,
+                vfptr_offset: 0,
+                base_class: None,
+                slot_size,
+                slot_count: 0,
+                slot_names: vec![],
+                slots: vec![],
+            });
+        }
+        // The table of the first polymorphic base class is shared with the
+        // class (unless it declares its own pointer), the others are used
+        // through the base class subobjects.
+        // Note: virtual base classes aren't supported
+        for (base_class_index, base_class_offset) in base_classes {
+            let base_vftables = Self::find_recursive(
+                type_finder,
+                type_forwarder,
+                vtable_records,
+                base_class_index,
+                primitive_flavor,
+                depth + 1,
+            )?;
+            if base_vftables.is_empty() {
+                continue;
+            }
+
+            let base_class_name = type_name(
+                type_finder,
+                type_forwarder,
+                base_class_index,
+                primitive_flavor,
+                &mut needed_types,
+            )?
+            .0;
+            for mut base_vftable in base_vftables {
+                base_vftable.owns_vfptr = false;
+                base_vftable.vfptr_offset += base_class_offset as u64;
+                if !vftables.is_empty() && base_vftable.base_class.is_none() {
+                    base_vftable.base_class = Some(base_class_name.clone());
+                }
+                for slot in &mut base_vftable.slots {
+                    slot.origin = VirtualFunctionOrigin::Inherited;
+                }
+                vftables.push(base_vftable);
+            }
+        }
+        if vftables.is_empty() {
+            return Ok(vftables);
+        }
+
+        for (method, method_type, vtable_offset) in virtual_methods {
+            if let Some(vtable_offset) = vtable_offset {
+                // Introducing virtual function, its slot in the primary table
+                // is known
+                let primary_vftable = &mut vftables[0];
+                let index = (vtable_offset as u64 / primary_vftable.slot_size) as usize;
+                let slot = VirtualFunctionTableSlot::from_method(
+                    index,
+                    &method,
+                    method_type,
+                    &class_name,
+                    VirtualFunctionOrigin::Introduced,
+                );
+                match primary_vftable
+                    .slots
+                    .binary_search_by_key(&index, |slot| slot.index)
+                {
+                    Ok(position) => primary_vftable.slots[position] = slot,
+                    Err(position) => primary_vftable.slots.insert(position, slot),
+                }
+                continue;
+            }
+
+            // Overriding virtual function, which may override functions of
+            // several base classes
+            let mut is_override = false;
+            for vftable in &mut vftables {
+                for slot in vftable
+                    .slots
+                    .iter_mut()
+                    .filter(|slot| slot.is_overridden_by(&method))
+                {
+                    *slot = VirtualFunctionTableSlot::from_method(
+                        slot.index,
+                        &method,
+                        method_type,
+                        &class_name,
+                        VirtualFunctionOrigin::Overridden,
+                    );
+                    is_override = true;
+                }
+            }
+            if !is_override {
+                log::debug!(
+                    "'{}::{}' doesn't override any known virtual function",
+                    class_name,
+                    method.name
+                );
+            }
+        }
+
+        // Slots which aren't associated with a method (e.g., slots of
+        // compiler-generated functions) are accounted for by the shape of the
+        // class' primary table
+        if let Some(slot_count) =
+            vtable_shape.and_then(|shape_index| vtable_records.shape_slot_count(shape_index))
+        {
+            vftables[0].slot_count = vftables[0].slot_count.max(slot_count);
+        }
+        for vftable in &mut vftables {
+            if let Some(last_slot) = vftable.slots.last() {
+                vftable.slot_count = vftable.slot_count.max(last_slot.index + 1);
+            }
+            if let Some(slot_names) =
+                vtable_records.slot_names(complete_type_index, vftable.vfptr_offset)
+            {
+                vftable.slot_names = slot_names.clone();
+            }
+        }
+
+        Ok(vftables)
+    }
+
+    /// Return the name of the struct representing this virtual function
+    /// table, for the class named `class_name`.
+    pub fn type_name(&self, class_name: &str) -> String {
+        if self.base_class.is_some() {
+            format!(
+                "{}_at_{:#x}",
+                vftable_type_name(class_name),
+                self.vfptr_offset
+            )
+        } else {
+            vftable_type_name(class_name)
+        }
+    }
+
+    /// Return the name of the type pointed to by the `this` parameter of
+    /// the functions in this table, for the class named `class_name`.
+    pub fn this_type_name<'a>(&'a self, class_name: &'a str) -> &'a str {
+        self.base_class.as_deref().unwrap_or(class_name)
+    }
+
+    /// Write the declaration of a struct representing this virtual function
+    /// table.
+    pub fn reconstruct(
+        &self,
+        class_name: &str,
+        fmt_configuration: &DataFormatConfiguration,
+        f: &mut impl std::fmt::Write,
+    ) -> fmt::Result {
+        let vftable_name = self.type_name(class_name);
+        let vftable_size = self.slot_count as u64 * self.slot_size;
+        writeln!(
+            f,
+            "struct {}{}{{{}",
+            vftable_name,
+            if fmt_configuration.print_brackets_new_line {
+                "\n"
+            } else {
+                " "
+            },
+            if fmt_configuration.size_print_flavor == SizePrintFlavor::Comment {
+                format!(" /* Size={vftable_size:#x} */")
+            } else {
+                String::default()
+            }
+        )?;
+
+        // Note: `this` is a keyword in C++, the argument is left unnamed like
+        // the others
+        let this_argument = format!("{}*", self.this_type_name(class_name));
+        let mut slots = self.slots.iter().peekable();
+        for index in 0..self.slot_count {
+            write!(f, "  ")?;
+            if fmt_configuration.print_offset_info {
+                write!(f, "/* {:#06x} */ ", index as u64 * self.slot_size)?;
+            }
+            let Some(slot) = slots.next_if(|slot| slot.index == index) else {
+                // No method is known for this slot
+                write!(f, "void* {}; // [{index}]", unknown_slot_name(index))?;
+                match self.slot_names.get(index) {
+                    Some(method_name) => writeln!(f, " {method_name}")?,
+                    None => writeln!(f, " unknown")?,
+                }
+                continue;
+            };
+
+            let method_name = if slot.method_name.starts_with('~') {
+                // Destructors cannot be used as identifiers
+                format!("__vecDelDtor_{}", slot.method_name.trim_start_matches('~'))
+            } else {
+                slot.method_name.clone()
+            };
+            writeln!(
+                f,
+                "{}{} (*{})({}){}; // [{}] {} {} {}",
+                slot.return_type_name.0,
+                slot.return_type_name.1,
+                method_name,
+                std::iter::once(this_argument.clone())
+                    .chain(
+                        slot.arguments
+                            .iter()
+                            .map(|(type_left, type_right)| format!("{type_left}{type_right}"))
+                    )
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                if slot.is_pure_virtual {
+                    " /* = 0 */"
+                } else {
+                    ""
+                },
+                slot.index,
+                slot.origin,
+                match slot.origin {
+                    VirtualFunctionOrigin::Introduced => "in",
+                    VirtualFunctionOrigin::Overridden | VirtualFunctionOrigin::Inherited => "by",
+                },
+                slot.defining_class,
+            )?;
+        }
+
+        writeln!(f, "}};")?;
+        if fmt_configuration.size_print_flavor == SizePrintFlavor::StaticAssert {
+            if fmt_configuration.integers_as_hexadecimal {
+                writeln!(
+                    f,
+                    "static_assert(sizeof({vftable_name}) == {vftable_size:#x}); // {vftable_size}",
+                )?;
+            } else {
+                writeln!(
+                    f,
+                    "static_assert(sizeof({vftable_name}) == {vftable_size}); // {vftable_size:#x}",
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_virtual_method(attributes: pdb::FieldAttributes) -> bool {
+    !attributes.is_static()
+        && (attributes.is_virtual()
+            || attributes.is_pure_virtual()
+            || attributes.is_intro_virtual())
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
@@ -0,0 +1,486 @@
+use pdb::FallibleIterator;
+
+use crate::error::Result;
+
+// Symbol kinds of records which aren't parsed by `pdb`
+const S_BPREL32: u16 = 0x110b;
+const S_FRAMEPROC: u16 = 0x1012;
+const S_DEFRANGE_REGISTER: u16 = 0x1141;
+const S_DEFRANGE_FRAMEPOINTER_REL: u16 = 0x1142;
+const S_DEFRANGE_SUBFIELD_REGISTER: u16 = 0x1143;
+const S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE: u16 = 0x1144;
+const S_DEFRANGE_REGISTER_REL: u16 = 0x1145;
+
+/// Location of a variable declared in a procedure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VariableLocation {
+    /// Variable stored in a register for its whole lifetime
+    Register(pdb::Register),
+    /// Variable stored at an offset relative to a register
+    RegisterRelative(pdb::Register, i32),
+    /// Variable stored at an offset relative to the frame pointer
+    FramePointerRelative(i32),
+}
+
+/// Parameter or local variable declared in a procedure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScopeVariable {
+    pub name: String,
+    pub type_index: pdb::TypeIndex,
+    /// `None` if the location isn't described by the variable's record
+    pub location: Option<VariableLocation>,
+    pub is_parameter: bool,
+    /// Address ranges in which the variable is live, for variables whose
+    /// location changes in the procedure (i.e., in optimized code)
+    pub live_ranges: Vec<LiveRange>,
+}
+
+/// Address range in which a variable is stored at a given location, as
+/// described by an `S_DEFRANGE_*` record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LiveRange {
+    pub location: VariableLocation,
+    /// Offset of the described member in the variable, for variables whose
+    /// members are stored at different locations
+    pub member_offset: Option<u32>,
+    pub offset: pdb::PdbInternalSectionOffset,
+    pub len: u32,
+    /// Parts of the range in which the location isn't valid, as (offset
+    /// relative to the range's start, length) pairs
+    pub gaps: Vec<(u32, u32)>,
+}
+
+impl LiveRange {
+    /// Return the parts of the range in which the location is valid, as
+    /// (start, end) offsets relative to the range's start.
+    pub fn live_parts(&self) -> Vec<(u32, u32)> {
+        let mut gaps = self.gaps.clone();
+        gaps.sort_unstable();
+
+        let mut live_parts = vec![];
+        let mut start = 0;
+        for (gap_start, gap_len) in gaps {
+            let end = gap_start.min(self.len);
+            if end > start {
+                live_parts.push((start, end));
+            }
+            start = start.max(gap_start.saturating_add(gap_len));
+        }
+        if start < self.len {
+            live_parts.push((start, self.len));
+        }
+
+        live_parts
+    }
+}
+
+/// Lexical scope of a procedure, with the variables declared in it and its
+/// nested scopes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProcedureScope {
+    /// Offset of the scope's code
+    pub offset: Option<pdb::PdbInternalSectionOffset>,
+    pub len: u32,
+    pub variables: Vec<ScopeVariable>,
+    pub blocks: Vec<ProcedureScope>,
+}
+
+impl ProcedureScope {
+    /// Parse the scopes of the procedure whose symbol is located at
+    /// `procedure_index` in the given module.
+    ///
+    /// `parameter_count` is the number of parameters declared by the
+    /// procedure's type (including `this`), which is used to tell parameters
+    /// apart from local variables for records which don't flag them.
+    pub fn parse(
+        module_info: &pdb::ModuleInfo,
+        procedure_index: pdb::SymbolIndex,
+        parameter_count: usize,
+        machine_type: pdb::MachineType,
+    ) -> Result<Self> {
+        let mut symbols = module_info.symbols_at(procedure_index)?;
+        let mut root_scope = ProcedureScope::default();
+        if let Some(pdb::SymbolData::Procedure(procedure)) =
+            symbols.next()?.and_then(|symbol| symbol.parse().ok())
+        {
+            root_scope.offset = Some(procedure.offset);
+            root_scope.len = procedure.len;
+        }
+
+        // Note: the outermost scope is the first element of the stack
+        let mut scope_stack = vec![root_scope];
+        // Variables of inlined procedures aren't declared in this procedure
+        let mut inline_site_depth = 0_usize;
+        let mut root_variable_count = 0_usize;
+        // Registers used to address locals and parameters, as described by
+        // the procedure's `S_FRAMEPROC` record (which usually comes after the
+        // variables' records)
+        let mut frame_registers = None;
+        // `S_DEFRANGE_*` records describe the preceding `S_LOCAL` record
+        let mut describing_local = false;
+        while let Some(symbol) = symbols.next()? {
+            if inline_site_depth == 0 {
+                match symbol.raw_kind() {
+                    S_FRAMEPROC => {
+                        frame_registers = parse_frame_proc_symbol(symbol.raw_bytes())
+                            .map(|flags| frame_proc_registers(machine_type, flags));
+                        continue;
+                    }
+                    S_DEFRANGE_REGISTER
+                    | S_DEFRANGE_FRAMEPOINTER_REL
+                    | S_DEFRANGE_SUBFIELD_REGISTER
+                    | S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE
+                    | S_DEFRANGE_REGISTER_REL => {
+                        if let Some(scope) = scope_stack.last_mut() {
+                            if let Some(live_range) =
+                                parse_def_range_symbol(symbol.raw_kind(), symbol.raw_bytes(), scope)
+                            {
+                                if let Some(variable) =
+                                    scope.variables.last_mut().filter(|_| describing_local)
+                                {
+                                    variable.live_ranges.push(live_range);
+                                }
+                            }
+                        }
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+
+            let variable = if symbol.raw_kind() == S_BPREL32 {
+                parse_bp_relative_symbol(symbol.raw_bytes()).map(|(offset, type_index, name)| {
+                    (
+                        name,
+                        type_index,
+                        Some(VariableLocation::FramePointerRelative(offset)),
+                        None,
+                    )
+                })
+            } else {
+                match symbol.parse() {
+                    Ok(pdb::SymbolData::RegisterRelative(data)) => Some((
+                        data.name.to_string().into_owned(),
+                        data.type_index,
+                        Some(VariableLocation::RegisterRelative(
+                            data.register,
+                            data.offset,
+                        )),
+                        None,
+                    )),
+                    Ok(pdb::SymbolData::RegisterVariable(data)) => Some((
+                        data.name.to_string().into_owned(),
+                        data.type_index,
+                        Some(VariableLocation::Register(data.register)),
+                        None,
+                    )),
+                    Ok(pdb::SymbolData::Local(data)) => Some((
+                        data.name.to_string().into_owned(),
+                        data.type_index,
+                        None,
+                        Some(data.flags.isparam),
+                    )),
+                    Ok(pdb::SymbolData::Block(data)) => {
+                        if inline_site_depth == 0 {
+                            scope_stack.push(ProcedureScope {
+                                offset: Some(data.offset),
+                                len: data.len,
+                                ..Default::default()
+                            });
+                        }
+                        None
+                    }
+                    Ok(pdb::SymbolData::InlineSite(_)) => {
+                        inline_site_depth += 1;
+                        None
+                    }
+                    Ok(pdb::SymbolData::InlineSiteEnd) => {
+                        inline_site_depth = inline_site_depth.saturating_sub(1);
+                        None
+                    }
+                    Ok(pdb::SymbolData::ScopeEnd) if inline_site_depth == 0 => {
+                        if scope_stack.len() == 1 {
+                            // End of the procedure
+                            break;
+                        }
+                        if let Some(block) = scope_stack.pop() {
+                            if let Some(parent) = scope_stack.last_mut() {
+                                parent.blocks.push(block);
+                            }
+                        }
+                        None
+                    }
+                    Ok(pdb::SymbolData::ProcedureEnd) => break,
+                    _ => None,
+                }
+            };
+
+            if inline_site_depth > 0 {
+                continue;
+            }
+            describing_local = false;
+            if let Some((name, type_index, location, is_parameter)) = variable {
+                // Note: variables described by `S_LOCAL` records may be
+                // described again by records giving their location
+                if let Some(described_variable) = scope_stack.last_mut().and_then(|scope| {
+                    scope
+                        .variables
+                        .iter_mut()
+                        .find(|variable| variable.location.is_none() && variable.name == name)
+                }) {
+                    described_variable.location = location;
+                    continue;
+                }
+                let is_root_scope = scope_stack.len() == 1;
+                let is_parameter =
+                    is_root_scope && is_parameter.unwrap_or(root_variable_count < parameter_count);
+                if is_root_scope {
+                    root_variable_count += 1;
+                }
+                if let Some(scope) = scope_stack.last_mut() {
+                    scope.variables.push(ScopeVariable {
+                        name,
+                        type_index,
+                        location,
+                        is_parameter,
+                        live_ranges: vec![],
+                    });
+                    // Only `S_LOCAL` records flag parameters
+                    describing_local = location.is_none();
+                }
+            }
+        }
+
+        // Close scopes left open by truncated symbol streams
+        while scope_stack.len() > 1 {
+            if let Some(block) = scope_stack.pop() {
+                if let Some(parent) = scope_stack.last_mut() {
+                    parent.blocks.push(block);
+                }
+            }
+        }
+
+        let mut procedure_scope = scope_stack.pop().unwrap_or_default();
+        if let Some(frame_registers) = frame_registers {
+            procedure_scope.resolve_frame_pointer(frame_registers);
+        }
+
+        Ok(procedure_scope)
+    }
+
+    /// Make the frame-relative live ranges of the scope's variables relative
+    /// to the registers used to address locals and parameters, when known.
+    fn resolve_frame_pointer(
+        &mut self,
+        frame_registers: (Option<pdb::Register>, Option<pdb::Register>),
+    ) {
+        let (local_register, parameter_register) = frame_registers;
+        for variable in &mut self.variables {
+            let frame_register = if variable.is_parameter {
+                parameter_register
+            } else {
+                local_register
+            };
+            let Some(frame_register) = frame_register else {
+                continue;
+            };
+            for live_range in &mut variable.live_ranges {
+                if let VariableLocation::FramePointerRelative(offset) = live_range.location {
+                    live_range.location =
+                        VariableLocation::RegisterRelative(frame_register, offset);
+                }
+            }
+        }
+        for block in &mut self.blocks {
+            block.resolve_frame_pointer(frame_registers);
+        }
+    }
+
+    /// Return the parameters of the procedure, in declaration order.
+    pub fn parameters(&self) -> impl Iterator<Item = &ScopeVariable> {
+        self.variables
+            .iter()
+            .filter(|variable| variable.is_parameter)
+    }
+}
+
+/// Parse the offset, type and name of an `S_BPREL32` record.
+fn parse_bp_relative_symbol(raw_bytes: &[u8]) -> Option<(i32, pdb::TypeIndex, String)> {
+    // Skip the record's kind
+    let data = raw_bytes.get(2..)?;
+    let offset = i32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+    let type_index = u32::from_le_bytes(data.get(4..8)?.try_into().ok()?);
+    let name = data.get(8..)?;
+    let name_len = name.iter().position(|c| *c == 0).unwrap_or(name.len());
+
+    Some((
+        offset,
+        pdb::TypeIndex(type_index),
+        String::from_utf8_lossy(&name[..name_len]).into_owned(),
+    ))
+}
+
+/// Parse the flags of an `S_FRAMEPROC` record.
+fn parse_frame_proc_symbol(raw_bytes: &[u8]) -> Option<u32> {
+    // Skip the record's kind, the frame's description and the exception
+    // handler's offset
+    let flags = raw_bytes.get(24..28)?;
+    Some(u32::from_le_bytes(flags.try_into().ok()?))
+}
+
+/// Return the registers used to address local variables and parameters,
+/// given the flags of an `S_FRAMEPROC` record. `None` means that variables
+/// are addressed relative to a virtual frame pointer.
+fn frame_proc_registers(
+    machine_type: pdb::MachineType,
+    flags: u32,
+) -> (Option<pdb::Register>, Option<pdb::Register>) {
+    let decode_register = |encoded_register: u32| -> Option<pdb::Register> {
+        let register = match (machine_type, encoded_register) {
+            // esp-based frames are addressed with a virtual frame pointer
+            (pdb::MachineType::X86, 2) => 22,    // ebp
+            (pdb::MachineType::X86, 3) => 20,    // ebx
+            (pdb::MachineType::Amd64, 1) => 335, // rsp
+            (pdb::MachineType::Amd64, 2) => 334, // rbp
+            (pdb::MachineType::Amd64, 3) => 341, // r13
+            (pdb::MachineType::Arm64, 1) => 81,  // sp
+            (pdb::MachineType::Arm64, 2) => 79,  // fp
+            (pdb::MachineType::Arm64, 3) => 69,  // x19
+            _ => return None,
+        };
+        Some(pdb::Register(register))
+    };
+
+    (
+        decode_register((flags >> 14) & 0b11),
+        decode_register((flags >> 16) & 0b11),
+    )
+}
+
+/// Parse an `S_DEFRANGE_*` record describing a variable declared in `scope`.
+fn parse_def_range_symbol(
+    kind: u16,
+    raw_bytes: &[u8],
+    scope: &ProcedureScope,
+) -> Option<LiveRange> {
+    let read_u16 = |offset: usize| -> Option<u16> {
+        Some(u16::from_le_bytes(
+            raw_bytes.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_le_bytes(
+            raw_bytes.get(offset..offset + 4)?.try_into().ok()?,
+        ))
+    };
+
+    // Note: offsets include the record's kind
+    let (location, member_offset, range_offset) = match kind {
+        S_DEFRANGE_REGISTER => (
+            VariableLocation::Register(pdb::Register(read_u16(2)?)),
+            None,
+            6,
+        ),
+        S_DEFRANGE_FRAMEPOINTER_REL => (
+            VariableLocation::FramePointerRelative(read_u32(2)? as i32),
+            None,
+            6,
+        ),
+        S_DEFRANGE_SUBFIELD_REGISTER => (
+            VariableLocation::Register(pdb::Register(read_u16(2)?)),
+            Some(read_u32(6)? & 0xfff),
+            10,
+        ),
+        S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE => {
+            // Valid in the whole scope
+            return Some(LiveRange {
+                location: VariableLocation::FramePointerRelative(read_u32(2)? as i32),
+                member_offset: None,
+                offset: scope.offset?,
+                len: scope.len,
+                gaps: vec![],
+            });
+        }
+        S_DEFRANGE_REGISTER_REL => {
+            let flags = read_u16(4)?;
+            let is_spilled_member = flags & 1 != 0;
+            (
+                VariableLocation::RegisterRelative(
+                    pdb::Register(read_u16(2)?),
+                    read_u32(6)? as i32,
+                ),
+                is_spilled_member.then_some(u32::from(flags >> 4)),
+                10,
+            )
+        }
+        _ => return None,
+    };
+
+    let offset = pdb::PdbInternalSectionOffset {
+        offset: read_u32(range_offset)?,
+        section: read_u16(range_offset + 4)?,
+    };
+    let len = u32::from(read_u16(range_offset + 6)?);
+    let gaps = raw_bytes
+        .get(range_offset + 8..)?
+        .chunks_exact(4)
+        .map(|gap| {
+            (
+                u32::from(u16::from_le_bytes([gap[0], gap[1]])),
+                u32::from(u16::from_le_bytes([gap[2], gap[3]])),
+            )
+        })
+        .collect();
+
+    Some(LiveRange {
+        location,
+        member_offset,
+        offset,
+        len,
+        gaps,
+    })
+}
+
+/// Return the name of the given CodeView register, for the given
+/// architecture.
+pub fn register_name(machine_type: pdb::MachineType, register: pdb::Register) -> String {
+    const X86_REGISTERS: [&str; 34] = [
+        "al", "cl", "dl", "bl", "ah", "ch", "dh", "bh", "ax", "cx", "dx", "bx", "sp", "bp", "si",
+        "di", "eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi", "es", "cs", "ss", "ds", "fs",
+        "gs", "ip", "flags", "eip", "eflags",
+    ];
+    const AMD64_REGISTERS: [&str; 16] = [
+        "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp", "r8", "r9", "r10", "r11", "r12",
+        "r13", "r14", "r15",
+    ];
+
+    let register = register.0;
+    let name = match machine_type {
+        pdb::MachineType::X86 | pdb::MachineType::Amd64 => match register {
+            1..=34 => Some(X86_REGISTERS[usize::from(register) - 1].to_string()),
+            154..=161 => Some(format!("xmm{}", register - 154)),
+            252..=259 => Some(format!("xmm{}", register - 244)),
+            324 => Some("sil".to_string()),
+            325 => Some("dil".to_string()),
+            326 => Some("bpl".to_string()),
+            327 => Some("spl".to_string()),
+            328..=343 => Some(AMD64_REGISTERS[usize::from(register) - 328].to_string()),
+            344..=351 => Some(format!("r{}b", register - 336)),
+            352..=359 => Some(format!("r{}w", register - 344)),
+            360..=367 => Some(format!("r{}d", register - 352)),
+            _ => None,
+        },
+        pdb::MachineType::Arm64 => match register {
+            10..=40 => Some(format!("w{}", register - 10)),
+            50..=78 => Some(format!("x{}", register - 50)),
+            79 => Some("fp".to_string()),
+            80 => Some("lr".to_string()),
+            81 => Some("sp".to_string()),
+            82 => Some("xzr".to_string()),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    name.unwrap_or_else(|| format!("reg{register}"))
+}
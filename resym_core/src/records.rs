@@ -0,0 +1,760 @@
+//! Textual representation of raw CodeView records (types, ids and symbols),
+//! in a format close to the one of Microsoft's `cvdump`.
+use std::fmt::Write;
+
+use crate::procedure::register_name;
+
+/// Append the type record `item` to `output`: its index, length and kind,
+/// followed by its fields and the indices of the records it references.
+pub(crate) fn fmt_type_record(output: &mut String, item: &pdb::Item<'_, pdb::TypeIndex>) {
+    fmt_record_header(
+        output,
+        item.index().0,
+        item.len(),
+        item.raw_kind(),
+        type_leaf_name(item.raw_kind()),
+    );
+    match item.parse() {
+        Ok(type_data) => fmt_type_data(output, &type_data),
+        Err(err) => {
+            let _ = writeln!(output, "\tUnsupported record: {err}");
+        }
+    }
+    output.push('\n');
+}
+
+/// Append the id record `item` to `output`, in the same format as type
+/// records.
+pub(crate) fn fmt_id_record(output: &mut String, item: &pdb::Item<'_, pdb::IdIndex>) {
+    fmt_record_header(
+        output,
+        item.index().0,
+        item.len(),
+        item.raw_kind(),
+        type_leaf_name(item.raw_kind()),
+    );
+    match item.parse() {
+        Ok(id_data) => fmt_id_data(output, &id_data),
+        Err(err) => {
+            let _ = writeln!(output, "\tUnsupported record: {err}");
+        }
+    }
+    output.push('\n');
+}
+
+/// Append the symbol record `symbol` to `output`: its offset in the symbol
+/// stream and its kind, followed by its fields.
+pub(crate) fn fmt_symbol_record(
+    output: &mut String,
+    symbol: &pdb::Symbol<'_>,
+    machine_type: pdb::MachineType,
+) {
+    let kind = symbol.raw_kind();
+    let _ = write!(output, "({:06X}) ", symbol.index().0);
+    match symbol_kind_name(kind) {
+        Some(kind_name) => output.push_str(kind_name),
+        None => {
+            let _ = write!(output, "S_0x{kind:04x}");
+        }
+    }
+    match symbol.parse() {
+        Ok(symbol_data) => fmt_symbol_data(output, &symbol_data, machine_type),
+        Err(_) => {
+            let _ = writeln!(
+                output,
+                ": Unsupported record ({} bytes)",
+                symbol.raw_bytes().len()
+            );
+        }
+    }
+}
+
+/// Return the indices of the type records directly referenced by
+/// `type_data`.
+pub(crate) fn type_data_references(type_data: &pdb::TypeData) -> Vec<pdb::TypeIndex> {
+    let references = match type_data {
+        pdb::TypeData::Class(data) => vec![data.fields, data.derived_from, data.vtable_shape],
+        pdb::TypeData::Union(data) => vec![Some(data.fields)],
+        pdb::TypeData::Enumeration(data) => {
+            vec![Some(data.underlying_type), Some(data.fields)]
+        }
+        pdb::TypeData::Pointer(data) => vec![Some(data.underlying_type), data.containing_class],
+        pdb::TypeData::Modifier(data) => vec![Some(data.underlying_type)],
+        pdb::TypeData::Procedure(data) => vec![data.return_type, Some(data.argument_list)],
+        pdb::TypeData::MemberFunction(data) => vec![
+            Some(data.return_type),
+            Some(data.class_type),
+            data.this_pointer_type,
+            Some(data.argument_list),
+        ],
+        pdb::TypeData::Array(data) => vec![Some(data.element_type), Some(data.indexing_type)],
+        pdb::TypeData::Bitfield(data) => vec![Some(data.underlying_type)],
+        pdb::TypeData::ArgumentList(data) => data.arguments.iter().copied().map(Some).collect(),
+        pdb::TypeData::MethodList(data) => data
+            .methods
+            .iter()
+            .map(|method| Some(method.method_type))
+            .collect(),
+        pdb::TypeData::FieldList(data) => data
+            .fields
+            .iter()
+            .flat_map(type_data_references)
+            .map(Some)
+            .chain(std::iter::once(data.continuation))
+            .collect(),
+        pdb::TypeData::Member(data) => vec![Some(data.field_type)],
+        pdb::TypeData::StaticMember(data) => vec![Some(data.field_type)],
+        pdb::TypeData::Method(data) => vec![Some(data.method_type)],
+        pdb::TypeData::OverloadedMethod(data) => vec![Some(data.method_list)],
+        pdb::TypeData::Nested(data) => vec![Some(data.nested_type)],
+        pdb::TypeData::BaseClass(data) => vec![Some(data.base_class)],
+        pdb::TypeData::VirtualBaseClass(data) => {
+            vec![Some(data.base_class), Some(data.base_pointer)]
+        }
+        pdb::TypeData::VirtualFunctionTablePointer(data) => vec![Some(data.table)],
+        _ => vec![],
+    };
+
+    references.into_iter().flatten().collect()
+}
+
+fn fmt_record_header(output: &mut String, index: u32, len: usize, kind: u16, kind_name: &str) {
+    let _ = writeln!(
+        output,
+        "0x{index:04x} : Length = {len}, Leaf = 0x{kind:04x} {kind_name}"
+    );
+}
+
+fn fmt_type_data(output: &mut String, type_data: &pdb::TypeData) {
+    let _ = match type_data {
+        pdb::TypeData::Class(data) => writeln!(
+            output,
+            "\t# members = {}, field list type {}, derivation list type {}, VT shape type {}\n\
+             \tSize = {}, class name = {}{}{}",
+            data.count,
+            fmt_index(data.fields),
+            fmt_index(data.derived_from),
+            fmt_index(data.vtable_shape),
+            data.size,
+            data.name,
+            fmt_unique_name(data.unique_name),
+            fmt_forward_reference(data.properties),
+        ),
+        pdb::TypeData::Union(data) => writeln!(
+            output,
+            "\t# members = {}, field list type {}\n\tSize = {}, class name = {}{}{}",
+            data.count,
+            data.fields,
+            data.size,
+            data.name,
+            fmt_unique_name(data.unique_name),
+            fmt_forward_reference(data.properties),
+        ),
+        pdb::TypeData::Enumeration(data) => writeln!(
+            output,
+            "\t# members = {}, type = {}, field list type {}\n\tenum name = {}{}{}",
+            data.count,
+            data.underlying_type,
+            data.fields,
+            data.name,
+            fmt_unique_name(data.unique_name),
+            fmt_forward_reference(data.properties),
+        ),
+        pdb::TypeData::Pointer(data) => writeln!(
+            output,
+            "\tType = {}, mode = {:?}, kind = {:?}, size = {}{}{}{}",
+            data.underlying_type,
+            data.attributes.pointer_mode(),
+            data.attributes.pointer_kind(),
+            data.attributes.size(),
+            if data.attributes.is_const() {
+                ", const"
+            } else {
+                ""
+            },
+            if data.attributes.is_volatile() {
+                ", volatile"
+            } else {
+                ""
+            },
+            data.containing_class
+                .map(|containing_class| format!(", containing class = {containing_class}"))
+                .unwrap_or_default(),
+        ),
+        pdb::TypeData::Modifier(data) => writeln!(
+            output,
+            "\tModifies type {}{}{}{}",
+            data.underlying_type,
+            if data.constant { ", const" } else { "" },
+            if data.volatile { ", volatile" } else { "" },
+            if data.unaligned { ", unaligned" } else { "" },
+        ),
+        pdb::TypeData::Procedure(data) => writeln!(
+            output,
+            "\tReturn type = {}, Call type = {}, # Parms = {}, Arg list type = {}",
+            fmt_index(data.return_type),
+            calling_convention_name(data.attributes.calling_convention()),
+            data.parameter_count,
+            data.argument_list,
+        ),
+        pdb::TypeData::MemberFunction(data) => writeln!(
+            output,
+            "\tReturn type = {}, Class type = {}, This type = {}\n\
+             \tCall type = {}, # Parms = {}, Arg list type = {}, This adjust = {}",
+            data.return_type,
+            data.class_type,
+            fmt_index(data.this_pointer_type),
+            calling_convention_name(data.attributes.calling_convention()),
+            data.parameter_count,
+            data.argument_list,
+            data.this_adjustment,
+        ),
+        pdb::TypeData::Array(data) => writeln!(
+            output,
+            "\tElement type = {}, Index type = {}, length = {:?}",
+            data.element_type, data.indexing_type, data.dimensions,
+        ),
+        pdb::TypeData::Bitfield(data) => writeln!(
+            output,
+            "\tbits = {}, starting position = {}, Type = {}",
+            data.length, data.position, data.underlying_type,
+        ),
+        pdb::TypeData::ArgumentList(data) => {
+            let _ = writeln!(output, "\targument count = {}", data.arguments.len());
+            for (i, argument) in data.arguments.iter().enumerate() {
+                let _ = writeln!(output, "\tlist[{i}] = {argument}");
+            }
+            Ok(())
+        }
+        pdb::TypeData::MethodList(data) => {
+            for (i, method) in data.methods.iter().enumerate() {
+                let _ = writeln!(
+                    output,
+                    "\tlist[{i}] = {}, {}, {}{}",
+                    access_name(method.attributes),
+                    method_property_name(method.attributes),
+                    method.method_type,
+                    fmt_vtable_offset(method.vtable_offset),
+                );
+            }
+            Ok(())
+        }
+        pdb::TypeData::FieldList(data) => {
+            for (i, field) in data.fields.iter().enumerate() {
+                let _ = write!(output, "\tlist[{i}] = {}, ", field_leaf_name(field));
+                fmt_field(output, field);
+            }
+            if let Some(continuation) = data.continuation {
+                let _ = writeln!(output, "\tcontinuation = {continuation}");
+            }
+            Ok(())
+        }
+        field => {
+            output.push('\t');
+            fmt_field(output, field);
+            Ok(())
+        }
+    };
+}
+
+/// Append the fields of a member of a field list to `output`
+fn fmt_field(output: &mut String, field: &pdb::TypeData) {
+    let _ = match field {
+        pdb::TypeData::Member(data) => writeln!(
+            output,
+            "{}, type = {}, offset = {}, member name = '{}'",
+            access_name(data.attributes),
+            data.field_type,
+            data.offset,
+            data.name,
+        ),
+        pdb::TypeData::StaticMember(data) => writeln!(
+            output,
+            "{}, type = {}, member name = '{}'",
+            access_name(data.attributes),
+            data.field_type,
+            data.name,
+        ),
+        pdb::TypeData::Method(data) => writeln!(
+            output,
+            "{}, {}, index = {}{}, name = '{}'",
+            access_name(data.attributes),
+            method_property_name(data.attributes),
+            data.method_type,
+            fmt_vtable_offset(data.vtable_offset),
+            data.name,
+        ),
+        pdb::TypeData::OverloadedMethod(data) => writeln!(
+            output,
+            "count = {}, list = {}, name = '{}'",
+            data.count, data.method_list, data.name,
+        ),
+        pdb::TypeData::Nested(data) => writeln!(
+            output,
+            "type = {}, name = '{}'",
+            data.nested_type, data.name
+        ),
+        pdb::TypeData::BaseClass(data) => writeln!(
+            output,
+            "{}, type = {}, offset = {}",
+            access_name(data.attributes),
+            data.base_class,
+            data.offset,
+        ),
+        pdb::TypeData::VirtualBaseClass(data) => writeln!(
+            output,
+            "{}, direct base type = {}, virtual base ptr = {}, vbpoff = {}, vbind = {}",
+            access_name(data.attributes),
+            data.base_class,
+            data.base_pointer,
+            data.base_pointer_offset,
+            data.virtual_base_offset,
+        ),
+        pdb::TypeData::VirtualFunctionTablePointer(data) => {
+            writeln!(output, "type = {}", data.table)
+        }
+        pdb::TypeData::Enumerate(data) => writeln!(
+            output,
+            "{}, value = {}, name = '{}'",
+            access_name(data.attributes),
+            data.value,
+            data.name,
+        ),
+        field => writeln!(output, "{field:?}"),
+    };
+}
+
+fn fmt_id_data(output: &mut String, id_data: &pdb::IdData) {
+    let _ = match id_data {
+        pdb::IdData::Function(data) => writeln!(
+            output,
+            "\tType = {}, Scope = {}, Name = {}",
+            data.function_type,
+            data.scope
+                .map_or("global".to_string(), |scope| scope.to_string()),
+            data.name,
+        ),
+        pdb::IdData::MemberFunction(data) => writeln!(
+            output,
+            "\tType = {}, Parent type = {}, Name = {}",
+            data.function_type, data.parent, data.name,
+        ),
+        pdb::IdData::BuildInfo(data) => {
+            let _ = writeln!(output, "\targument count = {}", data.arguments.len());
+            for (i, argument) in data.arguments.iter().enumerate() {
+                let _ = writeln!(output, "\tlist[{i}] = {argument}");
+            }
+            Ok(())
+        }
+        pdb::IdData::StringList(data) => {
+            let _ = writeln!(output, "\tstring count = {}", data.substrings.len());
+            for (i, substring) in data.substrings.iter().enumerate() {
+                let _ = writeln!(output, "\tlist[{i}] = {substring}");
+            }
+            Ok(())
+        }
+        pdb::IdData::String(data) => writeln!(
+            output,
+            "\tSubstring list = {}, String = {}",
+            data.substrings
+                .map_or("0x0".to_string(), |substrings| substrings.to_string()),
+            data.name,
+        ),
+        pdb::IdData::UserDefinedTypeSource(data) => writeln!(
+            output,
+            "\tUDT = {}, Source file = {}, Line = {}",
+            data.udt,
+            match data.source_file {
+                pdb::UserDefinedTypeSourceFileRef::Local(source_file) => source_file.to_string(),
+                pdb::UserDefinedTypeSourceFileRef::Remote(module, source_file) => {
+                    format!("{source_file} (module {module})")
+                }
+            },
+            data.line,
+        ),
+        id_data => writeln!(output, "\t{id_data:?}"),
+    };
+}
+
+fn fmt_symbol_data(
+    output: &mut String,
+    symbol_data: &pdb::SymbolData,
+    machine_type: pdb::MachineType,
+) {
+    let _ = match symbol_data {
+        pdb::SymbolData::ObjName(data) => {
+            writeln!(output, ": Signature: {:08X}, {}", data.signature, data.name)
+        }
+        pdb::SymbolData::CompileFlags(data) => writeln!(
+            output,
+            ": Language: {:?}, Machine: {:?}\n\tFrontend version: {}, Backend version: {}, Version string: {}",
+            data.language,
+            data.cpu_type,
+            fmt_compiler_version(&data.frontend_version),
+            fmt_compiler_version(&data.backend_version),
+            data.version_string,
+        ),
+        pdb::SymbolData::Procedure(data) => writeln!(
+            output,
+            ": {}, Cb: {:08X}, Type: {}, {}\n\
+             \tParent: {:08X}, End: {:08X}, Next: {:08X}\n\
+             \tDebug start: {:08X}, Debug end: {:08X}",
+            fmt_section_offset(&data.offset),
+            data.len,
+            data.type_index,
+            data.name,
+            data.parent.map_or(0, |parent| parent.0),
+            data.end.0,
+            data.next.map_or(0, |next| next.0),
+            data.dbg_start_offset,
+            data.dbg_end_offset,
+        ),
+        pdb::SymbolData::Thunk(data) => writeln!(
+            output,
+            ": {}, Cb: {:08X}, {}\n\tParent: {:08X}, End: {:08X}, Next: {:08X}",
+            fmt_section_offset(&data.offset),
+            data.len,
+            data.name,
+            data.parent.map_or(0, |parent| parent.0),
+            data.end.0,
+            data.next.map_or(0, |next| next.0),
+        ),
+        pdb::SymbolData::Block(data) => writeln!(
+            output,
+            ": {}, Cb: {:08X}, {}\n\tParent: {:08X}, End: {:08X}",
+            fmt_section_offset(&data.offset),
+            data.len,
+            data.name,
+            data.parent.0,
+            data.end.0,
+        ),
+        pdb::SymbolData::Data(data) => writeln!(
+            output,
+            ": {}, Type: {}, {}",
+            fmt_section_offset(&data.offset),
+            data.type_index,
+            data.name,
+        ),
+        pdb::SymbolData::ThreadStorage(data) => writeln!(
+            output,
+            ": {}, Type: {}, {}",
+            fmt_section_offset(&data.offset),
+            data.type_index,
+            data.name,
+        ),
+        pdb::SymbolData::Public(data) => writeln!(
+            output,
+            ": {}, Flags: {:08X}, {}",
+            fmt_section_offset(&data.offset),
+            u32::from(data.code)
+                | (u32::from(data.function) << 1)
+                | (u32::from(data.managed) << 2)
+                | (u32::from(data.msil) << 3),
+            data.name,
+        ),
+        pdb::SymbolData::Label(data) => writeln!(
+            output,
+            ": {}, {}",
+            fmt_section_offset(&data.offset),
+            data.name
+        ),
+        pdb::SymbolData::Constant(data) => writeln!(
+            output,
+            ": Type: {}, Value: {}, {}",
+            data.type_index, data.value, data.name,
+        ),
+        pdb::SymbolData::UserDefinedType(data) => {
+            writeln!(output, ": {}, {}", data.type_index, data.name)
+        }
+        pdb::SymbolData::Local(data) => writeln!(
+            output,
+            ": {}Type: {}, {}",
+            if data.flags.isparam { "Param: " } else { "" },
+            data.type_index,
+            data.name,
+        ),
+        pdb::SymbolData::RegisterRelative(data) => writeln!(
+            output,
+            ": {}+{:08X}, Type: {}, {}",
+            register_name(machine_type, data.register),
+            data.offset,
+            data.type_index,
+            data.name,
+        ),
+        pdb::SymbolData::RegisterVariable(data) => writeln!(
+            output,
+            ": {}, Type: {}, {}",
+            register_name(machine_type, data.register),
+            data.type_index,
+            data.name,
+        ),
+        pdb::SymbolData::InlineSite(data) => writeln!(
+            output,
+            ": Parent: {:08X}, End: {:08X}, Inlinee: {}",
+            data.parent.map_or(0, |parent| parent.0),
+            data.end.0,
+            data.inlinee,
+        ),
+        pdb::SymbolData::BuildInfo(data) => writeln!(output, ": {}", data.id),
+        pdb::SymbolData::UsingNamespace(data) => writeln!(output, ": {}", data.name),
+        pdb::SymbolData::ProcedureReference(data) => writeln!(
+            output,
+            ": {:08X}: ({}, {:08X}) {}",
+            data.sum_name,
+            data.module.map_or(0, |module| module + 1),
+            data.symbol_index.0,
+            data.name.unwrap_or_default(),
+        ),
+        pdb::SymbolData::DataReference(data) => writeln!(
+            output,
+            ": {:08X}: ({}, {:08X}) {}",
+            data.sum_name,
+            data.module.map_or(0, |module| module + 1),
+            data.symbol_index.0,
+            data.name.unwrap_or_default(),
+        ),
+        pdb::SymbolData::Export(data) => {
+            writeln!(output, ": Ordinal = {}, {}", data.ordinal, data.name)
+        }
+        pdb::SymbolData::ScopeEnd
+        | pdb::SymbolData::ProcedureEnd
+        | pdb::SymbolData::InlineSiteEnd => {
+            output.push('\n');
+            Ok(())
+        }
+        symbol_data => writeln!(output, ": {symbol_data:?}"),
+    };
+}
+
+fn fmt_index<I: std::fmt::Display>(index: Option<I>) -> String {
+    index.map_or("0x0".to_string(), |index| index.to_string())
+}
+
+fn fmt_unique_name(unique_name: Option<pdb::RawString>) -> String {
+    unique_name
+        .map(|unique_name| format!(", unique name = {unique_name}"))
+        .unwrap_or_default()
+}
+
+fn fmt_forward_reference(properties: pdb::TypeProperties) -> &'static str {
+    if properties.forward_reference() {
+        ", FORWARD REF"
+    } else {
+        ""
+    }
+}
+
+fn fmt_vtable_offset(vtable_offset: Option<u32>) -> String {
+    vtable_offset
+        .map(|vtable_offset| format!(", vfptr offset = {vtable_offset}"))
+        .unwrap_or_default()
+}
+
+fn fmt_section_offset(offset: &pdb::PdbInternalSectionOffset) -> String {
+    format!("[{:04X}:{:08X}]", offset.section, offset.offset)
+}
+
+fn fmt_compiler_version(version: &pdb::CompilerVersion) -> String {
+    match version.qfe {
+        Some(qfe) => format!(
+            "{}.{}.{}.{}",
+            version.major, version.minor, version.build, qfe
+        ),
+        None => format!("{}.{}.{}", version.major, version.minor, version.build),
+    }
+}
+
+fn access_name(attributes: pdb::FieldAttributes) -> &'static str {
+    match attributes.access() {
+        1 => "private",
+        2 => "protected",
+        3 => "public",
+        _ => "none",
+    }
+}
+
+fn method_property_name(attributes: pdb::FieldAttributes) -> &'static str {
+    if attributes.is_pure_virtual() {
+        if attributes.is_intro_virtual() {
+            "PURE INTRO"
+        } else {
+            "PURE VIRTUAL"
+        }
+    } else if attributes.is_intro_virtual() {
+        "INTRODUCING VIRTUAL"
+    } else if attributes.is_virtual() {
+        "VIRTUAL"
+    } else if attributes.is_static() {
+        "STATIC"
+    } else {
+        "VANILLA"
+    }
+}
+
+fn calling_convention_name(calling_convention: u8) -> String {
+    match calling_convention {
+        0x00 => "C Near".to_string(),
+        0x01 => "C Far".to_string(),
+        0x04 => "Fast Near".to_string(),
+        0x07 => "STD Near".to_string(),
+        0x0b => "ThisCall".to_string(),
+        0x16 => "CLR Call".to_string(),
+        0x18 => "Vector Near".to_string(),
+        calling_convention => format!("0x{calling_convention:02x}"),
+    }
+}
+
+/// Return the name of the leaf of a member of a field list
+fn field_leaf_name(field: &pdb::TypeData) -> &'static str {
+    match field {
+        pdb::TypeData::Member(_) => "LF_MEMBER",
+        pdb::TypeData::StaticMember(_) => "LF_STMEMBER",
+        pdb::TypeData::Method(_) => "LF_ONEMETHOD",
+        pdb::TypeData::OverloadedMethod(_) => "LF_METHOD",
+        pdb::TypeData::Nested(_) => "LF_NESTTYPE",
+        pdb::TypeData::BaseClass(_) => "LF_BCLASS",
+        pdb::TypeData::VirtualBaseClass(data) if data.direct => "LF_VBCLASS",
+        pdb::TypeData::VirtualBaseClass(_) => "LF_IVBCLASS",
+        pdb::TypeData::VirtualFunctionTablePointer(_) => "LF_VFUNCTAB",
+        pdb::TypeData::Enumerate(_) => "LF_ENUMERATE",
+        _ => "LF_UNKNOWN",
+    }
+}
+
+/// Return the name of the given type or id leaf kind
+fn type_leaf_name(kind: u16) -> &'static str {
+    match kind {
+        0x000a => "LF_VTSHAPE",
+        0x1001 => "LF_MODIFIER",
+        0x1002 => "LF_POINTER",
+        0x1008 => "LF_PROCEDURE",
+        0x1009 => "LF_MFUNCTION",
+        0x100b => "LF_BARRAY",
+        0x100d => "LF_VFTPATH",
+        0x1201 => "LF_ARGLIST",
+        0x1203 => "LF_FIELDLIST",
+        0x1204 => "LF_DERIVED",
+        0x1205 => "LF_BITFIELD",
+        0x1206 => "LF_METHODLIST",
+        0x1400 => "LF_BCLASS",
+        0x1401 => "LF_VBCLASS",
+        0x1402 => "LF_IVBCLASS",
+        0x1404 => "LF_INDEX",
+        0x1409 => "LF_VFUNCTAB",
+        0x140a => "LF_FRIENDCLS",
+        0x140c => "LF_VFUNCOFF",
+        0x1501 => "LF_TYPESERVER",
+        0x1502 => "LF_ENUMERATE",
+        0x1503 => "LF_ARRAY",
+        0x1504 => "LF_CLASS",
+        0x1505 => "LF_STRUCTURE",
+        0x1506 => "LF_UNION",
+        0x1507 => "LF_ENUM",
+        0x1508 => "LF_DIMARRAY",
+        0x1509 => "LF_PRECOMP",
+        0x150a => "LF_ALIAS",
+        0x150b => "LF_DEFARG",
+        0x150c => "LF_FRIENDFCN",
+        0x150d => "LF_MEMBER",
+        0x150e => "LF_STMEMBER",
+        0x150f => "LF_METHOD",
+        0x1510 => "LF_NESTTYPE",
+        0x1511 => "LF_ONEMETHOD",
+        0x1512 => "LF_NESTTYPEEX",
+        0x1513 => "LF_MEMBERMODIFY",
+        0x1514 => "LF_MANAGED",
+        0x1515 => "LF_TYPESERVER2",
+        0x1516 => "LF_STRIDED_ARRAY",
+        0x1517 => "LF_HLSL",
+        0x1518 => "LF_MODIFIER_EX",
+        0x1519 => "LF_INTERFACE",
+        0x151a => "LF_BINTERFACE",
+        0x151b => "LF_VECTOR",
+        0x151c => "LF_MATRIX",
+        0x151d => "LF_VFTABLE",
+        0x1601 => "LF_FUNC_ID",
+        0x1602 => "LF_MFUNC_ID",
+        0x1603 => "LF_BUILDINFO",
+        0x1604 => "LF_SUBSTR_LIST",
+        0x1605 => "LF_STRING_ID",
+        0x1606 => "LF_UDT_SRC_LINE",
+        0x1607 => "LF_UDT_MOD_SRC_LINE",
+        0x1609 => "LF_STRUCTURE19",
+        _ => "LF_UNKNOWN",
+    }
+}
+
+/// Return the name of the given symbol kind, if known
+fn symbol_kind_name(kind: u16) -> Option<&'static str> {
+    Some(match kind {
+        0x0001 => "S_COMPILE",
+        0x0006 => "S_END",
+        0x0007 => "S_SKIP",
+        0x1012 => "S_FRAMEPROC",
+        0x1019 => "S_ANNOTATION",
+        0x1101 => "S_OBJNAME",
+        0x1102 => "S_THUNK32",
+        0x1103 => "S_BLOCK32",
+        0x1104 => "S_WITH32",
+        0x1105 => "S_LABEL32",
+        0x1106 => "S_REGISTER",
+        0x1107 => "S_CONSTANT",
+        0x1108 => "S_UDT",
+        0x110a => "S_MANYREG",
+        0x110b => "S_BPREL32",
+        0x110c => "S_LDATA32",
+        0x110d => "S_GDATA32",
+        0x110e => "S_PUB32",
+        0x110f => "S_LPROC32",
+        0x1110 => "S_GPROC32",
+        0x1111 => "S_REGREL32",
+        0x1112 => "S_LTHREAD32",
+        0x1113 => "S_GTHREAD32",
+        0x1116 => "S_COMPILE2",
+        0x1117 => "S_MANYREG2",
+        0x1124 => "S_UNAMESPACE",
+        0x1125 => "S_PROCREF",
+        0x1126 => "S_DATAREF",
+        0x1127 => "S_LPROCREF",
+        0x1128 => "S_ANNOTATIONREF",
+        0x112c => "S_TRAMPOLINE",
+        0x112d => "S_MANCONSTANT",
+        0x1132 => "S_SEPCODE",
+        0x1136 => "S_SECTION",
+        0x1137 => "S_COFFGROUP",
+        0x1138 => "S_EXPORT",
+        0x1139 => "S_CALLSITEINFO",
+        0x113a => "S_FRAMECOOKIE",
+        0x113c => "S_COMPILE3",
+        0x113d => "S_ENVBLOCK",
+        0x113e => "S_LOCAL",
+        0x113f => "S_DEFRANGE",
+        0x1140 => "S_DEFRANGE_SUBFIELD",
+        0x1141 => "S_DEFRANGE_REGISTER",
+        0x1142 => "S_DEFRANGE_FRAMEPOINTER_REL",
+        0x1143 => "S_DEFRANGE_SUBFIELD_REGISTER",
+        0x1144 => "S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE",
+        0x1145 => "S_DEFRANGE_REGISTER_REL",
+        0x1146 => "S_LPROC32_ID",
+        0x1147 => "S_GPROC32_ID",
+        0x114c => "S_BUILDINFO",
+        0x114d => "S_INLINESITE",
+        0x114e => "S_INLINESITE_END",
+        0x114f => "S_PROC_ID_END",
+        0x1153 => "S_FILESTATIC",
+        0x1155 => "S_LPROC32_DPC",
+        0x1156 => "S_LPROC32_DPC_ID",
+        0x1159 => "S_ARMSWITCHTABLE",
+        0x115a => "S_CALLEES",
+        0x115b => "S_CALLERS",
+        0x115c => "S_POGODATA",
+        0x115d => "S_INLINESITE2",
+        0x115e => "S_HEAPALLOCSITE",
+        0x115f => "S_MOD_TYPEREF",
+        0x1160 => "S_REF_MINIPDB",
+        0x1161 => "S_PDBMAP",
+        0x1167 => "S_FASTLINK",
+        0x1168 => "S_INLINEES",
+        _ => return None,
+    })
+}
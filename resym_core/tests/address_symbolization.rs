@@ -0,0 +1,121 @@
+use std::path::Path;
+
+use resym_core::{
+    pdb_file::{PdbFile, SymbolizedAddress, SymbolizedFrame},
+    pdb_types::SourceLocation,
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_INLINE_PDB_FILE_PATH: &str = "tests/data/inline_test.pdb";
+const TEST_INLINE_SOURCE_FILE_PATH: &str = "/tmp/inl/inline_test.rs";
+
+#[test]
+fn test_address_symbolization() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let symbolized_addresses = pdb_file
+        .symbolize_addresses(&[0x12f40, 0x11bc0, 0x1])
+        .expect("address symbolization");
+
+    assert_eq!(
+        symbolized_addresses,
+        [
+            SymbolizedAddress {
+                rva: 0x12f40,
+                frames: vec![SymbolizedFrame {
+                    function_name: "failwithmessage".to_string(),
+                    function_offset: Some(0x10),
+                    source_location: Some(SourceLocation {
+                        file_path: r"D:\a\_work\1\s\src\vctools\crt\vcstartup\src\rtc\error.cpp"
+                            .to_string(),
+                        line: 133,
+                    }),
+                    is_inlined: false,
+                }],
+            },
+            SymbolizedAddress {
+                rva: 0x11bc0,
+                frames: vec![SymbolizedFrame {
+                    function_name: "resym_test::UnionTest::~UnionTest".to_string(),
+                    function_offset: Some(0),
+                    source_location: Some(SourceLocation {
+                        file_path: r"C:\Users\Henry\source\repos\symbol_zoo\symbol_zoo.cpp"
+                            .to_string(),
+                        line: 112,
+                    }),
+                    is_inlined: false,
+                }],
+            },
+            // Not mapped in any section
+            SymbolizedAddress {
+                rva: 0x1,
+                frames: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_address_symbolization_public_symbols() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let symbolized_addresses = pdb_file
+        .symbolize_addresses(&[0x11860, 0x11910])
+        .expect("address symbolization");
+
+    assert_eq!(
+        symbolized_addresses,
+        [
+            // Not described by any procedure, only by a public symbol
+            SymbolizedAddress {
+                rva: 0x11860,
+                frames: vec![SymbolizedFrame {
+                    function_name:
+                        "resym_test::ClassWithRefsAndStaticsTest::ClassWithRefsAndStaticsTest"
+                            .to_string(),
+                    function_offset: Some(0x10),
+                    source_location: None,
+                    is_inlined: false,
+                }],
+            },
+            // Padding following the public symbol's section contribution
+            SymbolizedAddress {
+                rva: 0x11910,
+                frames: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_address_symbolization_inlined_frames() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_INLINE_PDB_FILE_PATH))
+        .expect("load inline_test.pdb");
+
+    let symbolized_addresses = pdb_file
+        .symbolize_addresses(&[0x1050])
+        .expect("address symbolization");
+
+    let frame = |function_name: &str, function_offset, line, is_inlined| SymbolizedFrame {
+        function_name: function_name.to_string(),
+        function_offset,
+        source_location: Some(SourceLocation {
+            file_path: TEST_INLINE_SOURCE_FILE_PATH.to_string(),
+            line,
+        }),
+        is_inlined,
+    };
+    // Inlined frames come first, innermost first
+    assert_eq!(
+        symbolized_addresses,
+        [SymbolizedAddress {
+            rva: 0x1050,
+            frames: vec![
+                frame("inline_test::record_twice", None, 36, true),
+                frame("inline_test::record_pair", None, 43, true),
+                frame("inline_test::compute", None, 50, true),
+                frame("inline_test::main", Some(0x10), 57, false),
+            ],
+        }]
+    );
+}
@@ -0,0 +1,59 @@
+// Source of inline_test.pdb, built with a nightly toolchain:
+//   rustc --edition 2021 --target x86_64-pc-windows-msvc -C debuginfo=2 -C opt-level=1 -C panic=abort --emit obj inline_test.rs
+//   rustc --edition 2021 --target x86_64-pc-windows-msvc -C debuginfo=2 -C panic=abort --cfg record --emit obj -o record.o inline_test.rs
+//   rust-lld -flavor link /debug:full /entry:main /subsystem:console /nodefaultlib /out:inline_test.exe inline_test.o record.o
+#![feature(no_core, lang_items)]
+#![allow(internal_features)]
+#![no_core]
+#![crate_type = "lib"]
+
+#[lang = "pointee_sized"]
+pub trait PointeeSized {}
+#[lang = "meta_sized"]
+pub trait MetaSized: PointeeSized {}
+#[lang = "sized"]
+pub trait Sized: MetaSized {}
+#[lang = "copy"]
+pub trait Copy {}
+impl Copy for i32 {}
+
+// Note: `record` is defined in its own object file, so that its calls
+// aren't optimized out
+#[cfg(record)]
+#[no_mangle]
+pub extern "C" fn record(_value: i32) {}
+
+#[cfg(not(record))]
+extern "C" {
+    fn record(value: i32);
+}
+
+#[cfg(not(record))]
+#[inline(always)]
+fn record_twice(value: i32) {
+    unsafe {
+        record(value);
+        record(value);
+    }
+}
+
+#[cfg(not(record))]
+#[inline(always)]
+fn record_pair(first: i32, second: i32) {
+    record_twice(first);
+    record_twice(second);
+}
+
+#[cfg(not(record))]
+#[no_mangle]
+pub extern "C" fn compute(first: i32, second: i32) {
+    record_pair(first, second);
+    unsafe { record(first) }
+}
+
+#[cfg(not(record))]
+#[no_mangle]
+pub extern "C" fn main() -> i32 {
+    compute(3, 4);
+    0
+}
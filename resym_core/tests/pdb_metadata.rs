@@ -0,0 +1,35 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{KnownStreamInfo, PdbFile};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_pdb_metadata() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let pdb_metadata = pdb_file.metadata().expect("pdb metadata");
+    assert_eq!(pdb_metadata.guid, "24F10FEF-8588-4660-A5C7-BF4F2CCBADCE");
+    assert_eq!(pdb_metadata.age, 1);
+    assert_eq!(pdb_metadata.dbi_age, Some(1));
+    assert_eq!(pdb_metadata.signature, 0x65ed20f7);
+    assert_eq!(pdb_metadata.machine_type, "Amd64");
+    assert_eq!(pdb_metadata.pdb_version, 20000404);
+    assert_eq!(pdb_metadata.dbi_version, 19990903);
+    assert_eq!(pdb_metadata.type_count, 2533);
+    assert_eq!(pdb_metadata.id_count, 507);
+    assert_eq!(pdb_metadata.module_count, 54);
+    assert_eq!(pdb_metadata.stream_count, Some(116));
+
+    assert!(pdb_metadata.streams.contains(&KnownStreamInfo {
+        name: "Section headers".to_string(),
+        index: Some(12),
+        size: Some(400),
+    }));
+    // Note: the test PDB isn't indexed
+    assert!(pdb_metadata.streams.contains(&KnownStreamInfo {
+        name: "Source server".to_string(),
+        index: None,
+        size: Some(0),
+    }));
+}
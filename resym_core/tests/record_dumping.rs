@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_type_record_dumping() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let type_records = pdb_file.dump_type_records().expect("type records");
+    assert!(type_records.starts_with(
+        "0x1000 : Length = 42, Leaf = 0x1203 LF_FIELDLIST\n\
+         \tlist[0] = LF_MEMBER, public, type = 0x77, offset = 0, member name = 'SavedR10'\n\
+         \tlist[1] = LF_MEMBER, public, type = 0x77, offset = 8, member name = 'SavedR11'\n\
+         \n\
+         0x1001 : Length = 30, Leaf = 0x1505 LF_STRUCTURE\n\
+         \t# members = 2, field list type 0x1000, derivation list type 0x0, VT shape type 0x0\n\
+         \tSize = 16, class name = CsFrame\n\
+         \n"
+    ));
+    assert!(type_records.contains(
+        "0x1015 : Length = 10, Leaf = 0x1002 LF_POINTER\n\
+         \tType = 0x1013, mode = Pointer, kind = Ptr64, size = 8, const\n"
+    ));
+}
+
+#[test]
+fn test_type_records_by_index_dumping() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // The field list of the structure should be dumped after it
+    let type_records = pdb_file
+        .dump_type_records_by_index(0x1001)
+        .expect("type records");
+    assert_eq!(
+        type_records,
+        "0x1001 : Length = 30, Leaf = 0x1505 LF_STRUCTURE\n\
+         \t# members = 2, field list type 0x1000, derivation list type 0x0, VT shape type 0x0\n\
+         \tSize = 16, class name = CsFrame\n\
+         \n\
+         0x1000 : Length = 42, Leaf = 0x1203 LF_FIELDLIST\n\
+         \tlist[0] = LF_MEMBER, public, type = 0x77, offset = 0, member name = 'SavedR10'\n\
+         \tlist[1] = LF_MEMBER, public, type = 0x77, offset = 8, member name = 'SavedR11'\n\
+         \n"
+    );
+}
+
+#[test]
+fn test_id_record_dumping() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let id_records = pdb_file.dump_id_records().expect("id records");
+    assert!(id_records.starts_with(
+        "0x1000 : Length = 16, Leaf = 0x1607 LF_UDT_MOD_SRC_LINE\n\
+         \tUDT = 0x1005, Source file = 0x1 (module 3), Line = 20620\n\
+         \n"
+    ));
+}
+
+#[test]
+fn test_symbol_record_dumping() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let symbol_records = pdb_file.dump_symbol_records().expect("symbol records");
+    assert!(symbol_records.starts_with(
+        "** Module: \"C:\\Users\\Henry\\source\\repos\\symbol_zoo\\x64\\Debug\\symbol_zoo.obj\"\n\
+         \n\
+         (000004) S_OBJNAME: Signature: 00000000, C:\\Users\\Henry\\source\\repos\\symbol_zoo\\x64\\Debug\\symbol_zoo.obj\n"
+    ));
+    assert!(symbol_records.contains(
+        "(0023D4) S_GPROC32: [0002:00000850], Cb: 000000A5, Type: 0x1743, resym_test::ClassWithRefsAndStaticsTest::ClassWithRefsAndStaticsTest\n\
+         \tParent: 00000000, End: 00002474, Next: 00000000\n\
+         \tDebug start: 00000011, Debug end: 0000009B\n"
+    ));
+    assert!(symbol_records.contains("(002460) S_REGREL32: rbp+000000E0, Type: 0x1741, this\n"));
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use resym_core::pdb_file::PdbFile;
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_MODULE_PATH: &str = r"D:\a\_work\1\s\Intermediate\crt\vcstartup\build\xmd\msvcrt_kernel32\msvcrt_kernel32.nativeproj\objd\amd64\error.obj";
+const TEST_LIBRARY_PATH: &str = r"C:\Program Files\Microsoft Visual Studio\2022\Professional\VC\Tools\MSVC\14.35.32215\lib\x64\MSVCRTD.lib";
+
+#[test]
+fn test_section_contribution_lookup() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // `failwithmessage`
+    let section_contribution = pdb_file
+        .section_contributions
+        .find_by_rva(0x12f40)
+        .expect("section contribution not found");
+    assert_eq!(section_contribution.section_name, ".text");
+    assert_eq!(section_contribution.rva, 0x12f30);
+    assert_eq!(section_contribution.size, 0x260);
+    assert_eq!(section_contribution.module_index, 10);
+    assert_eq!(section_contribution.module_name, TEST_MODULE_PATH);
+    assert_eq!(section_contribution.object_file_name, TEST_LIBRARY_PATH);
+
+    // Last byte of the contribution
+    assert_eq!(
+        pdb_file.section_contributions.find_by_rva(0x1318f),
+        Some(section_contribution)
+    );
+    // Padding between contributions
+    assert!(pdb_file
+        .section_contributions
+        .find_by_rva(0x13190)
+        .is_none());
+    // Not mapped in any section
+    assert!(pdb_file.section_contributions.find_by_rva(0x1).is_none());
+}
+
+#[test]
+fn test_section_contribution_listing_by_module() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let module_contributions: Vec<_> = pdb_file
+        .section_contributions
+        .find_by_module_path(TEST_MODULE_PATH)
+        .collect();
+    assert!(!module_contributions.is_empty());
+    assert!(module_contributions
+        .iter()
+        .all(|section_contribution| section_contribution.module_index == 10));
+
+    // Static libraries contain several modules
+    let library_contributions: Vec<_> = pdb_file
+        .section_contributions
+        .find_by_module_path(TEST_LIBRARY_PATH)
+        .collect();
+    assert!(library_contributions.len() > module_contributions.len());
+    assert!(module_contributions
+        .iter()
+        .all(|section_contribution| library_contributions.contains(section_contribution)));
+}
@@ -0,0 +1,74 @@
+use std::path::Path;
+
+use resym_core::{
+    diffing::{diff_symbol_sizes, SizeChange, SymbolSizeDiff},
+    pdb_file::{PdbFile, SymbolKind},
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_PDB_FROM_FILE_PATH: &str = "tests/data/test_diff_from.pdb";
+const TEST_PDB_TO_FILE_PATH: &str = "tests/data/test_diff_to.pdb";
+
+#[test]
+fn test_symbol_size_diffing() {
+    let pdb_file_from = PdbFile::load_from_file(Path::new(TEST_PDB_FROM_FILE_PATH))
+        .expect("load test_diff_from.pdb");
+    let pdb_file_to =
+        PdbFile::load_from_file(Path::new(TEST_PDB_TO_FILE_PATH)).expect("load test_diff_to.pdb");
+
+    let size_diff = diff_symbol_sizes(&pdb_file_from, &pdb_file_to).expect("size diff");
+    assert_eq!(size_diff.total_from, 23122);
+    assert_eq!(size_diff.total_to, 23120);
+    assert_eq!(size_diff.total_delta(), -2);
+    assert!(size_diff.symbols.contains(&SymbolSizeDiff {
+        name: "__B346B13A_test_diff_from@cpp".to_string(),
+        kind: SymbolKind::Variable,
+        module_name: Some(
+            r"C:\Users\Henry\source\repos\test_diff_from\x64\Debug\test_diff_from.obj".to_string()
+        ),
+        size_from: 1,
+        size_to: 0,
+        change: SizeChange::Removed,
+    }));
+    // Largest changes come first
+    assert!(size_diff
+        .symbols
+        .windows(2)
+        .all(|symbols| symbols[0].delta().abs() >= symbols[1].delta().abs()));
+    assert!(size_diff
+        .modules
+        .iter()
+        .all(|module| module.size_from != module.size_to));
+}
+
+#[test]
+fn test_symbol_size_diffing_namespaces() {
+    let pdb_file_from = PdbFile::load_from_file(Path::new(TEST_PDB_FROM_FILE_PATH))
+        .expect("load test_diff_from.pdb");
+    let pdb_file_to =
+        PdbFile::load_from_file(Path::new(TEST_PDB_TO_FILE_PATH)).expect("load test_diff_to.pdb");
+
+    let size_diff = diff_symbol_sizes(&pdb_file_from, &pdb_file_to).expect("size diff");
+    assert!(!size_diff.namespaces.is_empty());
+    // Namespaces are compared as a whole, unchanged symbols included
+    let size_report = pdb_file_to.size_report().expect("size report");
+    for namespace in &size_diff.namespaces {
+        let namespace_size = size_report
+            .namespaces
+            .iter()
+            .find(|report_entry| report_entry.name == namespace.name)
+            .map_or(0, |report_entry| report_entry.size);
+        assert_eq!(namespace.size_to, namespace_size, "{}", namespace.name);
+    }
+}
+
+#[test]
+fn test_symbol_size_diffing_identical() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let size_diff = diff_symbol_sizes(&pdb_file, &pdb_file).expect("size diff");
+    assert!(size_diff.symbols.is_empty());
+    assert!(size_diff.modules.is_empty());
+    assert!(size_diff.namespaces.is_empty());
+    assert_eq!(size_diff.total_from, size_diff.total_to);
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{PdbFile, SizeReportEntry};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_size_report() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let size_report = pdb_file.size_report().expect("size report");
+    assert_eq!(
+        size_report.modules[0],
+        SizeReportEntry {
+            name: r"C:\Users\Henry\source\repos\symbol_zoo\x64\Debug\symbol_zoo.obj".to_string(),
+            size: 5330,
+            count: 69,
+        }
+    );
+    assert_eq!(
+        size_report.object_files[0],
+        SizeReportEntry {
+            name: r"C:\Program Files\Microsoft Visual Studio\2022\Professional\VC\Tools\MSVC\14.35.32215\lib\x64\MSVCRTD.lib".to_string(),
+            size: 18429,
+            count: 341,
+        }
+    );
+    // Methods are counted under the namespace of their class
+    assert!(size_report
+        .namespaces
+        .iter()
+        .all(|entry| entry.name != "resym_test::StructTest"));
+    let namespace_entry = size_report
+        .namespaces
+        .iter()
+        .find(|entry| entry.name == "resym_test")
+        .expect("resym_test namespace");
+    assert!(namespace_entry.size >= 238);
+    assert!(namespace_entry.count >= 4);
+
+    // Entries are sorted by decreasing size
+    for entries in [
+        &size_report.modules,
+        &size_report.object_files,
+        &size_report.namespaces,
+        &size_report.template_families,
+    ] {
+        assert!(entries.windows(2).all(|pair| pair[0].size >= pair[1].size));
+    }
+
+    // Modules and object files both cover all the contributions
+    let contributions_size: u64 = pdb_file
+        .section_contributions
+        .iter()
+        .map(|section_contribution| section_contribution.size as u64)
+        .sum();
+    let modules_size: u64 = size_report.modules.iter().map(|entry| entry.size).sum();
+    let object_files_size: u64 = size_report
+        .object_files
+        .iter()
+        .map(|entry| entry.size)
+        .sum();
+    assert_eq!(modules_size, contributions_size);
+    assert_eq!(object_files_size, contributions_size);
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{ChecksumKind, PdbFile};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_SOURCE_FILE_PATH: &str = r"D:\a\_work\1\s\src\vctools\crt\vcstartup\src\rtc\error.cpp";
+const TEST_SOURCE_FILE_CHECKSUM: &str =
+    "10b3cf85f7060e8d0600678095a650826d1c4b281bd54562b822252c3700ed3c";
+
+#[test]
+fn test_source_file_listing() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let source_file_list = pdb_file.source_file_list().expect("source file list");
+    let source_file = source_file_list
+        .iter()
+        .find(|source_file| source_file.file_path == TEST_SOURCE_FILE_PATH)
+        .expect("source file not found");
+    assert_eq!(source_file.checksum_kind, ChecksumKind::Sha256);
+    assert_eq!(source_file.checksum, TEST_SOURCE_FILE_CHECKSUM);
+    assert_eq!(source_file.modules.len(), 1);
+    assert_eq!(source_file.modules[0].1, 10);
+}
+
+#[test]
+fn test_source_file_functions() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let function_list = pdb_file
+        .source_file_functions(TEST_SOURCE_FILE_PATH)
+        .expect("source file functions");
+    assert!(function_list
+        .iter()
+        .any(|(function_name, _)| function_name == "failwithmessage"));
+
+    // Files can be designated by their name, regardless of case
+    let function_list_by_name = pdb_file
+        .source_file_functions("RTC/Error.cpp")
+        .expect("source file functions");
+    assert_eq!(function_list, function_list_by_name);
+
+    // Partial file names don't match
+    let function_list = pdb_file
+        .source_file_functions("rror.cpp")
+        .expect("source file functions");
+    assert!(function_list.is_empty());
+}
+
+#[test]
+fn test_source_file_listing_corrupted_file_name() {
+    let pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test.pdb");
+    let source_file_count =
+        PdbFile::load_from_bytes_as_vec("test.pdb".to_string(), pdb_data.clone())
+            .expect("load test.pdb")
+            .source_file_list()
+            .expect("source file list")
+            .len();
+
+    // Make the name of the test source file point outside of the string table.
+    // File checksum entries start with the offset of the file's name, followed
+    // by the checksum's size and kind.
+    let mut pdb_data = pdb_data;
+    let checksum: Vec<u8> = (0..TEST_SOURCE_FILE_CHECKSUM.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&TEST_SOURCE_FILE_CHECKSUM[i..i + 2], 16).expect("hex digit"))
+        .collect();
+    let checksum_offset = pdb_data
+        .windows(checksum.len())
+        .position(|window| window == checksum)
+        .expect("checksum not found");
+    pdb_data[checksum_offset - 6..checksum_offset - 2].fill(0xFF);
+    let pdb_file =
+        PdbFile::load_from_bytes_as_vec("test.pdb".to_string(), pdb_data).expect("load test.pdb");
+
+    // Only the corrupted file is skipped, other files of the module are listed
+    let source_file_list = pdb_file.source_file_list().expect("source file list");
+    assert_eq!(source_file_list.len(), source_file_count - 1);
+    assert!(!source_file_list
+        .iter()
+        .any(|source_file| source_file.file_path == TEST_SOURCE_FILE_PATH));
+    assert!(source_file_list
+        .iter()
+        .filter(|source_file| source_file.modules.iter().any(|(_, index)| *index == 10))
+        .any(|source_file| source_file.file_path.ends_with(r"\ucrt\stdio.h")));
+
+    let function_list = pdb_file
+        .source_file_functions(TEST_SOURCE_FILE_PATH)
+        .expect("source file functions");
+    assert!(function_list.is_empty());
+}
@@ -0,0 +1,105 @@
+use std::{fs::File, io::Cursor};
+
+use resym_core::{
+    hexdump::hexdump,
+    msf::{read_stream, stream_list},
+    pdb_file::{PdbFile, StreamInfo},
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+/// Offset of the TPI stream's header in test.pdb
+const TEST_PDB_TPI_HEADER_OFFSET: usize = 280 * 4096;
+
+#[test]
+fn test_stream_listing() {
+    let mut pdb_file = File::open(TEST_PDB_FILE_PATH).expect("open test.pdb");
+
+    let stream_list = stream_list(&mut pdb_file).expect("stream list");
+    assert_eq!(stream_list.len(), 116);
+    assert_eq!(
+        stream_list[7],
+        StreamInfo {
+            index: 7,
+            size: Some(37531),
+            name: Some("/names".to_string()),
+        }
+    );
+    assert_eq!(
+        stream_list[111],
+        StreamInfo {
+            index: 111,
+            size: Some(10284),
+            name: Some("TPI hash".to_string()),
+        }
+    );
+    assert_eq!(
+        stream_list[114],
+        StreamInfo {
+            index: 114,
+            size: None,
+            name: None,
+        }
+    );
+    assert_eq!(
+        stream_list[16].name.as_deref(),
+        Some("Module 'Import:KERNEL32.dll'")
+    );
+}
+
+#[test]
+fn test_stream_data() {
+    let mut pdb_file = File::open(TEST_PDB_FILE_PATH).expect("open test.pdb");
+
+    // Section headers
+    let stream_data = read_stream(&mut pdb_file, 12, None).expect("stream data");
+    assert_eq!(stream_data.len(), 400);
+    assert_eq!(&stream_data[..8], b".textbss");
+    let stream_data = read_stream(&mut pdb_file, 12, Some(16)).expect("stream data");
+    assert_eq!(stream_data.len(), 16);
+
+    assert!(read_stream(&mut pdb_file, 114, None).is_err());
+    assert!(read_stream(&mut pdb_file, 1000, None).is_err());
+}
+
+#[test]
+fn test_stream_listing_corrupted_pdb() {
+    let mut pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test.pdb");
+    pdb_data[TEST_PDB_TPI_HEADER_OFFSET..TEST_PDB_TPI_HEADER_OFFSET + 56].fill(0xFF);
+    // The PDB cannot be loaded anymore
+    assert!(PdbFile::load_from_bytes_as_vec("test.pdb".to_string(), pdb_data.clone()).is_err());
+
+    // But its streams can still be listed and read
+    let mut pdb_data = Cursor::new(pdb_data);
+    let stream_list = stream_list(&mut pdb_data).expect("stream list");
+    assert_eq!(stream_list.len(), 116);
+    assert_eq!(stream_list[2].name.as_deref(), Some("TPI"));
+    // The TPI stream's hash streams aren't referenced anymore
+    assert_eq!(stream_list[111].name, None);
+    assert_eq!(
+        stream_list[16].name.as_deref(),
+        Some("Module 'Import:KERNEL32.dll'")
+    );
+    let stream_data = read_stream(&mut pdb_data, 12, None).expect("stream data");
+    assert_eq!(&stream_data[..8], b".textbss");
+}
+
+#[test]
+fn test_stream_listing_truncated_pdb() {
+    let mut pdb_data = std::fs::read(TEST_PDB_FILE_PATH).expect("read test.pdb");
+
+    // The stream directory is stored at the end of test.pdb
+    pdb_data.truncate(pdb_data.len() / 2);
+    let mut pdb_data = Cursor::new(pdb_data);
+    assert!(stream_list(&mut pdb_data).is_err());
+    assert!(read_stream(&mut pdb_data, 12, None).is_err());
+}
+
+#[test]
+fn test_hexdump() {
+    assert_eq!(hexdump(&[]), "");
+    assert_eq!(
+        hexdump(b"Microsoft C/C++ MSF 7.00\r\n"),
+        "00000000: 4d 69 63 72 6f 73 6f 66  74 20 43 2f 43 2b 2b 20  Microsoft C/C++ \n\
+         00000010: 4d 53 46 20 37 2e 30 30  0d 0a                    MSF 7.00..\n"
+    );
+}
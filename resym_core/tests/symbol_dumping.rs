@@ -0,0 +1,244 @@
+use std::path::Path;
+
+use resym_core::{
+    pdb_file::{InlinedCall, PdbFile, VariableLiveRange},
+    pdb_types::{
+        AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor, SourceLocation,
+    },
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+const TEST_PROCEDURE_NAME: &str = "find_pe_section";
+const TEST_OPTIMIZED_PROCEDURE_NAME: &str = "failwithmessage";
+const TEST_INLINE_PDB_FILE_PATH: &str = "tests/data/inline_test.pdb";
+const TEST_INLINE_SOURCE_FILE_PATH: &str = "/tmp/inl/inline_test.rs";
+
+#[test]
+fn test_symbol_dumping_procedure() {
+    test_symbol_dumping_internal(
+        "symbol_dumping_procedure",
+        TEST_PROCEDURE_NAME,
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_symbol_dumping_procedure_locals() {
+    test_symbol_dumping_internal(
+        "symbol_dumping_procedure_locals",
+        TEST_PROCEDURE_NAME,
+        true,
+        false,
+    );
+}
+
+#[test]
+fn test_symbol_dumping_procedure_locations() {
+    test_symbol_dumping_internal(
+        "symbol_dumping_procedure_locations",
+        TEST_OPTIMIZED_PROCEDURE_NAME,
+        true,
+        true,
+    );
+}
+
+#[test]
+fn test_procedure_variables() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    // Note: procedures are declared in modules, the global symbol is a reference
+    let symbol_index = pdb_file
+        .symbol_list()
+        .expect("symbol list")
+        .into_iter()
+        .find(|(symbol_name, symbol_index, _)| {
+            symbol_name.contains(TEST_OPTIMIZED_PROCEDURE_NAME) && symbol_index.0 != usize::MAX
+        })
+        .map(|(_, symbol_index, _)| *symbol_index)
+        .expect("procedure not found");
+
+    let procedure_variables = pdb_file
+        .procedure_variables(symbol_index, PrimitiveReconstructionFlavor::Portable)
+        .expect("procedure variables");
+    let parameter_names: Vec<&str> = procedure_variables
+        .iter()
+        .filter(|variable| variable.is_parameter)
+        .map(|variable| variable.name.as_str())
+        .collect();
+    assert_eq!(parameter_names, ["retaddr", "crttype", "errnum", "msg"]);
+
+    let errnum = procedure_variables
+        .iter()
+        .find(|variable| variable.name == "errnum")
+        .expect("errnum not found");
+    assert_eq!(errnum.type_name, "int32_t");
+    assert_eq!(
+        errnum.live_ranges,
+        [
+            VariableLiveRange {
+                start_rva: 0x12f30,
+                end_rva: 0x12f5b,
+                location: "r8d".to_string(),
+            },
+            VariableLiveRange {
+                start_rva: 0x12f5b,
+                end_rva: 0x1318e,
+                location: "rbp".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_procedure_inlined_calls() {
+    let mut pdb_file =
+        PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    let symbol_index = pdb_file
+        .symbol_list()
+        .expect("symbol list")
+        .into_iter()
+        .find(|(symbol_name, symbol_index, _)| {
+            symbol_name.contains(TEST_OPTIMIZED_PROCEDURE_NAME) && symbol_index.0 != usize::MAX
+        })
+        .map(|(_, symbol_index, _)| *symbol_index)
+        .expect("procedure not found");
+
+    // Note: the test PDB doesn't contain any inline site
+    let inlined_calls = pdb_file
+        .procedure_inlined_calls(symbol_index)
+        .expect("inlined calls");
+    assert!(inlined_calls.is_empty());
+    let inline_call_sites = pdb_file
+        .find_inline_call_sites(TEST_PROCEDURE_NAME)
+        .expect("inline call sites");
+    assert!(inline_call_sites.is_empty());
+}
+
+#[test]
+fn test_procedure_inlined_calls_tree() {
+    let mut pdb_file = PdbFile::load_from_file(Path::new(TEST_INLINE_PDB_FILE_PATH))
+        .expect("load inline_test.pdb");
+    let symbol_index = pdb_file
+        .symbol_list()
+        .expect("symbol list")
+        .into_iter()
+        .find(|(symbol_name, symbol_index, _)| {
+            symbol_name == "inline_test::compute" && symbol_index.0 != usize::MAX
+        })
+        .map(|(_, symbol_index, _)| *symbol_index)
+        .expect("procedure not found");
+
+    let inlined_call = |function_name: &str, line, code_ranges, inlined_calls| InlinedCall {
+        function_name: function_name.to_string(),
+        source_location: Some(SourceLocation {
+            file_path: TEST_INLINE_SOURCE_FILE_PATH.to_string(),
+            line,
+        }),
+        code_ranges,
+        inlined_calls,
+    };
+    let inlined_calls = pdb_file
+        .procedure_inlined_calls(symbol_index)
+        .expect("inlined calls");
+    // Note: the contiguous ranges of `record_pair` are merged
+    assert_eq!(
+        inlined_calls,
+        [inlined_call(
+            "inline_test::record_pair",
+            43,
+            vec![(0x100a, 0x1024)],
+            vec![
+                inlined_call(
+                    "inline_test::record_twice",
+                    35,
+                    vec![(0x100a, 0x1016)],
+                    vec![]
+                ),
+                inlined_call(
+                    "inline_test::record_twice",
+                    35,
+                    vec![(0x1016, 0x1024)],
+                    vec![]
+                ),
+            ]
+        )]
+    );
+}
+
+#[test]
+fn test_find_inline_call_sites_nested() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_INLINE_PDB_FILE_PATH))
+        .expect("load inline_test.pdb");
+
+    // `record_pair` is inlined in `compute`, which is itself inlined in `main`
+    let inline_call_sites = pdb_file
+        .find_inline_call_sites("inline_test::record_pair")
+        .expect("inline call sites");
+    assert_eq!(inline_call_sites.len(), 2);
+    assert_eq!(inline_call_sites[0].caller_name, "inline_test::compute");
+    assert_eq!(
+        inline_call_sites[0].inlined_call.code_ranges,
+        [(0x100a, 0x1024)]
+    );
+    assert_eq!(inline_call_sites[1].caller_name, "inline_test::main");
+    assert_eq!(
+        inline_call_sites[1].inlined_call.code_ranges,
+        [(0x1044, 0x106c)]
+    );
+    assert_eq!(inline_call_sites[1].inlined_call.inlined_calls.len(), 2);
+
+    // Functions inlined in inlined functions are found too
+    let inline_call_sites = pdb_file
+        .find_inline_call_sites("inline_test::record_twice")
+        .expect("inline call sites");
+    assert_eq!(inline_call_sites.len(), 4);
+}
+
+#[test]
+fn test_symbol_dumping_inlined_calls() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_INLINE_PDB_FILE_PATH))
+        .expect("load inline_test.pdb");
+
+    let symbol_dump = pdb_file
+        .reconstruct_symbol_by_name(
+            "inline_test::main",
+            PrimitiveReconstructionFlavor::Portable,
+            AccessSpecifierReconstructionFlavor::Always,
+            false,
+            false,
+            true,
+        )
+        .unwrap_or_else(|err| panic!("symbol dumping failed: {err}"));
+    assert_eq!(
+        symbol_dump,
+        "int32_t inline_test::main(); // RVA=0x1040 CodeSize=0x3d\n\
+         // Inlined calls:\n\
+         //  inline_test::compute at /tmp/inl/inline_test.rs:50 // RVA=0x1044-0x1076\n\
+         //    inline_test::record_pair at /tmp/inl/inline_test.rs:43 // RVA=0x1044-0x106c\n\
+         //      inline_test::record_twice at /tmp/inl/inline_test.rs:35 // RVA=0x1044-0x1058\n\
+         //      inline_test::record_twice at /tmp/inl/inline_test.rs:35 // RVA=0x1058-0x106c"
+    );
+}
+
+fn test_symbol_dumping_internal(
+    snapshot_name: &str,
+    symbol_name: &str,
+    print_locals: bool,
+    print_locations: bool,
+) {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let symbol_dump = pdb_file
+        .reconstruct_symbol_by_name(
+            symbol_name,
+            PrimitiveReconstructionFlavor::Portable,
+            AccessSpecifierReconstructionFlavor::Always,
+            print_locals,
+            print_locations,
+            false,
+        )
+        .unwrap_or_else(|err| panic!("symbol dumping failed: {err}"));
+
+    insta::assert_snapshot!(snapshot_name, symbol_dump);
+}
@@ -0,0 +1,106 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{PdbFile, SymbolXrefKind};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_symbol_xrefs_for_type() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let type_index = pdb_file.type_index_by_name("_CONTEXT").expect("type index");
+    let symbol_xrefs: Vec<(String, SymbolXrefKind, Option<String>)> = pdb_file
+        .get_symbol_xrefs_for_type(type_index)
+        .expect("symbol xrefs")
+        .into_iter()
+        .map(|symbol_xref| {
+            (
+                symbol_xref.symbol_name,
+                symbol_xref.kind,
+                symbol_xref.variable_name,
+            )
+        })
+        .collect();
+    assert_eq!(
+        symbol_xrefs,
+        vec![
+            (
+                "GS_ContextRecord".to_string(),
+                SymbolXrefKind::Variable,
+                None
+            ),
+            (
+                "__GSHandlerCheck".to_string(),
+                SymbolXrefKind::Parameter,
+                None
+            ),
+            (
+                "__GSHandlerCheck_EH4".to_string(),
+                SymbolXrefKind::Parameter,
+                None
+            ),
+            (
+                "capture_current_context".to_string(),
+                SymbolXrefKind::Parameter,
+                None
+            ),
+            (
+                "capture_previous_context".to_string(),
+                SymbolXrefKind::Parameter,
+                None
+            ),
+            (
+                "__scrt_fastfail".to_string(),
+                SymbolXrefKind::LocalVariable,
+                Some("context_record".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_symbol_xrefs_for_return_type() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let type_index = pdb_file
+        .type_index_by_name("_EXCEPTION_DISPOSITION")
+        .expect("type index");
+    let symbol_xrefs = pdb_file
+        .get_symbol_xrefs_for_type(type_index)
+        .expect("symbol xrefs");
+    assert!(symbol_xrefs
+        .iter()
+        .any(|symbol_xref| symbol_xref.symbol_name == "__GSHandlerCheck"
+            && symbol_xref.kind == SymbolXrefKind::ReturnValue));
+}
+
+#[test]
+fn test_symbol_xrefs_for_unknown_type() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert!(pdb_file
+        .type_index_by_name("resym_test::UnknownType")
+        .is_err());
+}
+
+#[test]
+fn test_symbol_xrefs_are_unique() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    for type_name in ["_CONTEXT", "_EXCEPTION_DISPOSITION"] {
+        let type_index = pdb_file.type_index_by_name(type_name).expect("type index");
+        let symbol_xrefs = pdb_file
+            .get_symbol_xrefs_for_type(type_index)
+            .expect("symbol xrefs");
+        for (i, lhs) in symbol_xrefs.iter().enumerate() {
+            for rhs in &symbol_xrefs[i + 1..] {
+                assert!(
+                    lhs.symbol_index != rhs.symbol_index
+                        || lhs.kind != rhs.kind
+                        || lhs.variable_name != rhs.variable_name,
+                    "duplicated symbol xref: {lhs:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::{env, fs, path::Path, process::Command};
+
+use resym_core::{
+    pdb_file::PdbFile,
+    pdb_types::{
+        include_c_headers_for_flavor, include_headers_for_flavor,
+        AccessSpecifierReconstructionFlavor, DataFormatConfiguration, OutputLanguage,
+        PrimitiveReconstructionFlavor, SizePrintFlavor,
+    },
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+/// Environment variable used to select the compiler used to check the
+/// reconstructed output (e.g., `RESYM_TEST_CC=clang-18`).
+const TEST_COMPILER_ENV_VAR: &str = "RESYM_TEST_CC";
+const TEST_COMPILERS: &[&str] = &["clang", "gcc"];
+
+/// Formatting options which affect the validity of the reconstructed output.
+struct TestConfiguration {
+    size_print_flavor: SizePrintFlavor,
+    integers_as_hexadecimal: bool,
+    print_vftables: bool,
+    print_padding: bool,
+    print_offset_asserts: bool,
+}
+
+const TEST_CONFIGURATIONS: &[TestConfiguration] = &[
+    // Default options
+    TestConfiguration {
+        size_print_flavor: SizePrintFlavor::Comment,
+        integers_as_hexadecimal: true,
+        print_vftables: false,
+        print_padding: false,
+        print_offset_asserts: false,
+    },
+    TestConfiguration {
+        size_print_flavor: SizePrintFlavor::Comment,
+        integers_as_hexadecimal: true,
+        print_vftables: true,
+        print_padding: true,
+        print_offset_asserts: false,
+    },
+    TestConfiguration {
+        size_print_flavor: SizePrintFlavor::StaticAssert,
+        integers_as_hexadecimal: false,
+        print_vftables: false,
+        print_padding: false,
+        print_offset_asserts: false,
+    },
+    TestConfiguration {
+        size_print_flavor: SizePrintFlavor::Comment,
+        integers_as_hexadecimal: true,
+        print_vftables: true,
+        print_padding: false,
+        print_offset_asserts: true,
+    },
+    TestConfiguration {
+        size_print_flavor: SizePrintFlavor::StaticAssert,
+        integers_as_hexadecimal: true,
+        print_vftables: true,
+        print_padding: true,
+        print_offset_asserts: true,
+    },
+];
+
+#[test]
+fn test_type_compilation_c_portable() {
+    test_type_compilation_internal(
+        "type_compilation_c_portable",
+        OutputLanguage::C,
+        PrimitiveReconstructionFlavor::Portable,
+        false,
+        false,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "`long` is only 32-bit wide on Windows")]
+fn test_type_compilation_c_raw() {
+    test_type_compilation_internal(
+        "type_compilation_c_raw",
+        OutputLanguage::C,
+        PrimitiveReconstructionFlavor::Raw,
+        false,
+        false,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "`__int64` is only available on Windows")]
+fn test_type_compilation_c_msvc() {
+    test_type_compilation_internal(
+        "type_compilation_c_msvc",
+        OutputLanguage::C,
+        PrimitiveReconstructionFlavor::Msvc,
+        false,
+        false,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "<Windows.h> requires the Windows SDK")]
+fn test_type_compilation_c_microsoft() {
+    test_type_compilation_internal(
+        "type_compilation_c_microsoft",
+        OutputLanguage::C,
+        PrimitiveReconstructionFlavor::Microsoft,
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_compilation_cpp_portable() {
+    test_type_compilation_internal(
+        "type_compilation_cpp_portable",
+        OutputLanguage::Cpp,
+        PrimitiveReconstructionFlavor::Portable,
+        true,
+        true,
+    );
+}
+
+#[test]
+#[ignore = "qualified names cannot be declared outside of their enclosing namespaces, \
+            and types from the standard library conflict with its headers"]
+fn test_type_compilation_cpp_default() {
+    test_type_compilation_internal(
+        "type_compilation_cpp_default",
+        OutputLanguage::Cpp,
+        PrimitiveReconstructionFlavor::Portable,
+        false,
+        false,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "`long` is only 32-bit wide on Windows")]
+fn test_type_compilation_cpp_raw() {
+    test_type_compilation_internal(
+        "type_compilation_cpp_raw",
+        OutputLanguage::Cpp,
+        PrimitiveReconstructionFlavor::Raw,
+        true,
+        true,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "`__int64` is only available on Windows")]
+fn test_type_compilation_cpp_msvc() {
+    test_type_compilation_internal(
+        "type_compilation_cpp_msvc",
+        OutputLanguage::Cpp,
+        PrimitiveReconstructionFlavor::Msvc,
+        true,
+        true,
+    );
+}
+
+#[test]
+#[cfg_attr(not(windows), ignore = "<Windows.h> requires the Windows SDK")]
+fn test_type_compilation_cpp_microsoft() {
+    test_type_compilation_internal(
+        "type_compilation_cpp_microsoft",
+        OutputLanguage::Cpp,
+        PrimitiveReconstructionFlavor::Microsoft,
+        true,
+        true,
+    );
+}
+
+/// Reconstruct all the types of the test PDB as C or C++ declarations and
+/// check that they compile with a local C compiler driver, found in `PATH` or
+/// set with `RESYM_TEST_CC`.
+fn test_type_compilation_internal(
+    test_name: &str,
+    output_language: OutputLanguage,
+    primitives_flavor: PrimitiveReconstructionFlavor,
+    print_namespaces: bool,
+    ignore_std_types: bool,
+) {
+    let compiler = TestCompiler::find().unwrap_or_else(|| {
+        panic!("{test_name}: no C compiler found, set {TEST_COMPILER_ENV_VAR} to select one")
+    });
+    let compiler_args = compiler
+        .arguments_for_flavor(output_language, primitives_flavor)
+        .unwrap_or_else(|| {
+            panic!(
+                "{test_name}: '{}' cannot compile the {primitives_flavor:?} flavor",
+                compiler.path
+            )
+        });
+    let (print_access_specifiers, source_extension) = match output_language {
+        OutputLanguage::Cpp => (AccessSpecifierReconstructionFlavor::Always, "cpp"),
+        _ => (AccessSpecifierReconstructionFlavor::Disabled, "c"),
+    };
+
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+    for (i, configuration) in TEST_CONFIGURATIONS.iter().enumerate() {
+        let reconstructed_types = pdb_file
+            .reconstruct_all_types(
+                output_language,
+                &DataFormatConfiguration {
+                    print_access_specifiers,
+                    size_print_flavor: configuration.size_print_flavor,
+                    integers_as_hexadecimal: configuration.integers_as_hexadecimal,
+                    print_vftables: configuration.print_vftables,
+                    print_padding: configuration.print_padding,
+                    print_offset_asserts: configuration.print_offset_asserts,
+                    print_namespaces,
+                    primitive_flavor: primitives_flavor,
+                    ..Default::default()
+                },
+                ignore_std_types,
+            )
+            .unwrap_or_else(|err| panic!("reconstruct all types: {err}"));
+        let headers = match output_language {
+            OutputLanguage::Cpp => include_headers_for_flavor(
+                primitives_flavor,
+                ignore_std_types,
+                configuration.print_offset_asserts,
+            ),
+            _ => include_c_headers_for_flavor(primitives_flavor),
+        };
+        let source = format!("{headers}{reconstructed_types}");
+
+        let source_path = env::temp_dir().join(format!(
+            "{test_name}-{i}-{}.{source_extension}",
+            std::process::id()
+        ));
+        fs::write(&source_path, &source).expect("write translation unit");
+        let output = Command::new(&compiler.path)
+            .args(&compiler_args)
+            .arg(&source_path)
+            .output()
+            .expect("run compiler");
+        let _ = fs::remove_file(&source_path);
+
+        if !output.status.success() {
+            let diagnostics = String::from_utf8_lossy(&output.stderr);
+            panic!(
+                "{test_name}-{i}: failed to compile '{}':\n{diagnostics}",
+                first_failing_type(&source, &source_path, &diagnostics).unwrap_or("<unknown type>")
+            );
+        }
+    }
+}
+
+struct TestCompiler {
+    path: String,
+    /// Whether the compiler targets Windows, like the test PDB
+    targets_windows: bool,
+    is_clang: bool,
+}
+
+impl TestCompiler {
+    fn find() -> Option<Self> {
+        let candidates = match env::var(TEST_COMPILER_ENV_VAR) {
+            Ok(path) => vec![path],
+            Err(_) => TEST_COMPILERS.iter().map(|path| path.to_string()).collect(),
+        };
+
+        candidates.into_iter().find_map(|path| {
+            let output = Command::new(&path).arg("--version").output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let is_clang = String::from_utf8_lossy(&output.stdout).contains("clang");
+            let output = Command::new(&path).arg("-dumpmachine").output().ok()?;
+            let target = String::from_utf8_lossy(&output.stdout).to_lowercase();
+            let targets_windows = ["windows", "mingw", "cygwin"]
+                .iter()
+                .any(|os| target.contains(os));
+
+            Some(Self {
+                path,
+                targets_windows,
+                is_clang,
+            })
+        })
+    }
+
+    /// Return the arguments needed to check the output of the given flavor,
+    /// or `None` if this compiler cannot check it.
+    fn arguments_for_flavor(
+        &self,
+        output_language: OutputLanguage,
+        flavor: PrimitiveReconstructionFlavor,
+    ) -> Option<Vec<&str>> {
+        let mut args = match output_language {
+            OutputLanguage::C => vec!["-std=c11", "-fsyntax-only"],
+            // `char8_t` is a C++20 keyword, enabled on its own
+            OutputLanguage::Cpp => vec!["-x", "c++", "-std=c++17", "-fchar8_t", "-fsyntax-only"],
+            _ => return None,
+        };
+        match flavor {
+            PrimitiveReconstructionFlavor::Portable => {
+                // `wchar_t` is 16-bit wide on Windows
+                if !self.targets_windows {
+                    args.push("-fshort-wchar");
+                }
+            }
+            // `long` is 32-bit wide on Windows
+            PrimitiveReconstructionFlavor::Raw if self.targets_windows => {}
+            // `__int64` is an extension
+            PrimitiveReconstructionFlavor::Msvc if self.targets_windows => {
+                if self.is_clang {
+                    args.push("-fms-extensions");
+                }
+            }
+            // <Windows.h> is only available with the Windows SDK
+            PrimitiveReconstructionFlavor::Microsoft if cfg!(windows) && self.targets_windows => {}
+            _ => return None,
+        }
+
+        Some(args)
+    }
+}
+
+/// Find the type declared around the first line reported in `diagnostics`.
+fn first_failing_type<'s>(
+    source: &'s str,
+    source_path: &Path,
+    diagnostics: &str,
+) -> Option<&'s str> {
+    let file_prefix = format!("{}:", source_path.display());
+    let line_number: usize = diagnostics
+        .lines()
+        .filter(|line| line.contains("error"))
+        .find_map(|line| line.strip_prefix(&file_prefix))
+        .and_then(|location| location.split(':').next())
+        .and_then(|line_number| line_number.parse().ok())?;
+
+    // Declarations start on a line of their own, while their static assertions
+    // follow them
+    source
+        .lines()
+        .take(line_number)
+        .filter_map(|line| {
+            ["struct ", "class ", "union ", "enum "]
+                .iter()
+                .find_map(|keyword| line.strip_prefix(keyword))
+                .filter(|declaration| declaration.contains('{'))
+                .and_then(|declaration| declaration.split_whitespace().next())
+        })
+        .last()
+}
@@ -4,7 +4,8 @@ use resym_core::{
     diffing::diff_type_by_name,
     pdb_file::PdbFile,
     pdb_types::{
-        AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor, SizePrintFlavor,
+        AccessSpecifierReconstructionFlavor, DataFormatConfiguration,
+        PrimitiveReconstructionFlavor, SizePrintFlavor,
     },
 };
 
@@ -30,14 +31,17 @@ fn test_struct_diffing() {
             &pdb_file_from,
             &pdb_file_to,
             test_case_type_name,
-            PrimitiveReconstructionFlavor::Portable,
-            AccessSpecifierReconstructionFlavor::Disabled,
-            SizePrintFlavor::Comment,
+            &DataFormatConfiguration {
+                print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                size_print_flavor: SizePrintFlavor::Comment,
+                integers_as_hexadecimal: false,
+                print_offset_info: true,
+                print_brackets_new_line: false,
+                primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+                ..Default::default()
+            },
             false, // print_header
             false, // reconstruct_dependencies
-            false, // integers_as_hexadecimal
-            true,  // print_offset_info
-            false, // print_brackets_new_line
             false, // ignore_std_types
         )
         .expect("diff generation");
@@ -56,14 +60,17 @@ fn test_struct_diffing_inexistent_type() {
         &pdb_file_from,
         &pdb_file_to,
         INEXISTENT_TYPE_NAME,
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Always,
-        SizePrintFlavor::Comment,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: false,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false, // print_header
         false, // reconstruct_dependencies
-        false, // integers_as_hexadecimal
-        true,  // print_offset_info
-        false, // print_brackets_new_line
         false, // ignore_std_types
     )
     .is_err());
@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use resym_core::pdb_file::{PdbFile, TypeDependencyKind, TypeGraphEdge, TypeGraphNode};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_type_graph_by_name() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    let type_graph = pdb_file
+        .type_graph_by_name("_EXCEPTION_POINTERS", Some(1), false)
+        .expect("type graph");
+    assert_eq!(type_graph.root, 0x11d4);
+    assert_eq!(
+        type_graph.nodes,
+        vec![
+            TypeGraphNode {
+                index: 0x11d4,
+                name: "_EXCEPTION_POINTERS".to_string(),
+                depth: 0,
+            },
+            TypeGraphNode {
+                index: 0x10d5,
+                name: "_CONTEXT".to_string(),
+                depth: 1,
+            },
+            TypeGraphNode {
+                index: 0x111d,
+                name: "_EXCEPTION_RECORD".to_string(),
+                depth: 1,
+            },
+        ]
+    );
+    assert_eq!(
+        type_graph.edges,
+        vec![
+            TypeGraphEdge {
+                from: 0x11d4,
+                to: 0x10d5,
+                kind: TypeDependencyKind::Pointer,
+            },
+            TypeGraphEdge {
+                from: 0x11d4,
+                to: 0x111d,
+                kind: TypeDependencyKind::Pointer,
+            },
+        ]
+    );
+
+    // Dependencies of types at the maximum depth aren't resolved
+    let type_graph = pdb_file
+        .type_graph_by_name("_EXCEPTION_POINTERS", Some(0), false)
+        .expect("type graph");
+    assert_eq!(type_graph.nodes.len(), 1);
+    assert!(type_graph.edges.is_empty());
+
+    // Types embedded by value are followed when the depth allows it
+    let type_graph = pdb_file
+        .type_graph_by_name("_EXCEPTION_POINTERS", None, false)
+        .expect("type graph");
+    assert!(type_graph.edges.contains(&TypeGraphEdge {
+        from: 0x10d5,
+        to: 0x1042,
+        kind: TypeDependencyKind::Embedded,
+    }));
+}
+
+#[test]
+fn test_type_graph_base_classes() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // Base classes are ignored by default
+    let type_graph = pdb_file
+        .type_graph_by_name("resym_test::PureVirtualClassSpecialized", None, false)
+        .expect("type graph");
+    assert_eq!(type_graph.nodes.len(), 1);
+
+    let type_graph = pdb_file
+        .type_graph_by_name("resym_test::PureVirtualClassSpecialized", None, true)
+        .expect("type graph");
+    assert_eq!(type_graph.nodes.len(), 2);
+    assert!(type_graph.edges.contains(&TypeGraphEdge {
+        from: 0x17b2,
+        to: 0x1782,
+        kind: TypeDependencyKind::BaseClass,
+    }));
+}
+
+#[test]
+fn test_type_graph_unknown_type() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    assert!(pdb_file
+        .type_graph_by_name("resym_test::UnknownType", None, false)
+        .is_err());
+}
+
+#[test]
+fn test_type_neighbourhood_by_index() {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    // _CONTEXT
+    let type_graph = pdb_file
+        .type_neighbourhood_by_index(0x10d5, false)
+        .expect("type graph");
+    assert_eq!(type_graph.root, 0x10d5);
+    // Outgoing references
+    assert!(type_graph.edges.contains(&TypeGraphEdge {
+        from: 0x10d5,
+        to: 0x1042,
+        kind: TypeDependencyKind::Embedded,
+    }));
+    // Incoming references
+    assert!(type_graph.edges.contains(&TypeGraphEdge {
+        from: 0x11d4,
+        to: 0x10d5,
+        kind: TypeDependencyKind::Pointer,
+    }));
+    assert!(type_graph.nodes.contains(&TypeGraphNode {
+        index: 0x11d4,
+        name: "_EXCEPTION_POINTERS".to_string(),
+        depth: 1,
+    }));
+    // Only direct neighbours are included
+    assert!(type_graph.nodes.iter().all(|node| node.depth <= 1));
+
+    // resym_test::PureVirtualClass is the base class of resym_test::PureVirtualClassSpecialized
+    let type_graph = pdb_file
+        .type_neighbourhood_by_index(0x1782, false)
+        .expect("type graph");
+    assert!(!type_graph.edges.iter().any(|edge| edge.from == 0x17b2));
+    let type_graph = pdb_file
+        .type_neighbourhood_by_index(0x1782, true)
+        .expect("type graph");
+    assert!(type_graph.edges.contains(&TypeGraphEdge {
+        from: 0x17b2,
+        to: 0x1782,
+        kind: TypeDependencyKind::BaseClass,
+    }));
+}
@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use resym_core::{
+    frontend::ReconstructedFiles,
+    pdb_file::PdbFile,
+    pdb_types::{DataFormatConfiguration, HeaderSplitFlavor, OutputLanguage},
+};
+
+const TEST_PDB_FILE_PATH: &str = "tests/data/test.pdb";
+
+#[test]
+fn test_type_header_splitting_by_namespace() {
+    let reconstructed_files = reconstruct_headers_by_namespace(false);
+
+    // One header per top-level namespace
+    assert_eq!(
+        header_paths(&reconstructed_files),
+        [
+            "CodeViewInfo.h",
+            "global_namespace.h",
+            "resym_test.h",
+            "std.h"
+        ]
+    );
+    for (header_path, header) in &reconstructed_files {
+        assert!(
+            header.starts_with("#pragma once\n"),
+            "{}: missing include guard",
+            header_path.display()
+        );
+    }
+    let namespace_header = &reconstructed_files[Path::new("resym_test.h")];
+    assert!(namespace_header.contains("namespace resym_test {\n"));
+    assert!(namespace_header.contains("struct StructTest {"));
+}
+
+#[test]
+fn test_type_header_splitting_by_namespace_includes() {
+    let reconstructed_files = reconstruct_headers_by_namespace(false);
+
+    // Headers include the headers defining the types they embed
+    assert_eq!(
+        included_header_paths(&reconstructed_files, "resym_test.h"),
+        ["global_namespace.h"]
+    );
+    assert_eq!(
+        included_header_paths(&reconstructed_files, "std.h"),
+        ["global_namespace.h"]
+    );
+    assert!(included_header_paths(&reconstructed_files, "global_namespace.h").is_empty());
+    assert!(included_header_paths(&reconstructed_files, "CodeViewInfo.h").is_empty());
+}
+
+#[test]
+fn test_type_header_splitting_by_namespace_global_namespace() {
+    let reconstructed_files = reconstruct_headers_by_namespace(false);
+
+    // Types without a namespace are declared in a header of their own
+    let global_namespace_header = &reconstructed_files[Path::new("global_namespace.h")];
+    assert!(global_namespace_header.contains("\nstruct _GUID {"));
+    assert!(!global_namespace_header.contains("\nnamespace "));
+    for (header_path, header) in &reconstructed_files {
+        if header_path != Path::new("global_namespace.h") {
+            assert!(
+                !header.contains("\nstruct _GUID {"),
+                "{}: _GUID declared outside of the global namespace header",
+                header_path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_type_header_splitting_by_namespace_ignore_std_types() {
+    let reconstructed_files = reconstruct_headers_by_namespace(true);
+
+    // Types from the standard library aren't reconstructed
+    assert_eq!(
+        header_paths(&reconstructed_files),
+        ["CodeViewInfo.h", "global_namespace.h", "resym_test.h"]
+    );
+}
+
+fn reconstruct_headers_by_namespace(ignore_std_types: bool) -> ReconstructedFiles {
+    let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
+
+    pdb_file
+        .reconstruct_all_types_into_headers(
+            HeaderSplitFlavor::Namespace,
+            OutputLanguage::Cpp,
+            &DataFormatConfiguration {
+                print_namespaces: true,
+                ..Default::default()
+            },
+            ignore_std_types,
+        )
+        .unwrap_or_else(|err| panic!("reconstruct all types into headers: {err}"))
+}
+
+fn header_paths(reconstructed_files: &ReconstructedFiles) -> Vec<String> {
+    reconstructed_files
+        .keys()
+        .map(|header_path| header_path.display().to_string())
+        .collect()
+}
+
+/// Return the paths of the headers included by the header at `header_path`.
+fn included_header_paths<'f>(
+    reconstructed_files: &'f ReconstructedFiles,
+    header_path: &str,
+) -> Vec<&'f str> {
+    reconstructed_files[Path::new(header_path)]
+        .lines()
+        .filter_map(|line| line.strip_prefix("#include \""))
+        .filter_map(|line| line.strip_suffix('"'))
+        .collect()
+}
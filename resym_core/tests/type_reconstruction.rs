@@ -2,6 +2,7 @@ use std::path::Path;
 
 use resym_core::{
     pdb_file::PdbFile, pdb_types::AccessSpecifierReconstructionFlavor,
+    pdb_types::DataFormatConfiguration, pdb_types::OutputLanguage,
     pdb_types::PrimitiveReconstructionFlavor, pdb_types::SizePrintFlavor,
 };
 
@@ -31,18 +32,23 @@ const TEST_CASES: &[&str] = &[
     "resym_test::BitFieldsTest7",
     "resym_test::NestedStructUnionRegression1",
     "resym_test::NtdllRegression1",
+    "resym_test::PureVirtualClass",
 ];
 
 #[test]
 fn test_type_reconstruction_portable_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_portable_access_specifiers",
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Always,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -52,12 +58,16 @@ fn test_type_reconstruction_portable_access_specifiers() {
 fn test_type_reconstruction_microsoft_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_microsoft_access_specifiers",
-        PrimitiveReconstructionFlavor::Microsoft,
-        AccessSpecifierReconstructionFlavor::Always,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -67,12 +77,16 @@ fn test_type_reconstruction_microsoft_access_specifiers() {
 fn test_type_reconstruction_raw_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_raw_access_specifiers",
-        PrimitiveReconstructionFlavor::Raw,
-        AccessSpecifierReconstructionFlavor::Always,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Raw,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -82,12 +96,16 @@ fn test_type_reconstruction_raw_access_specifiers() {
 fn test_type_reconstruction_msvc_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_msvc_access_specifiers",
-        PrimitiveReconstructionFlavor::Msvc,
-        AccessSpecifierReconstructionFlavor::Always,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Msvc,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -97,12 +115,16 @@ fn test_type_reconstruction_msvc_access_specifiers() {
 fn test_type_reconstruction_automatic_access_specifiers() {
     test_type_reconstruction_internal(
         "type_reconstruction_automatic_access_specifiers",
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Automatic,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Automatic,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -112,12 +134,16 @@ fn test_type_reconstruction_automatic_access_specifiers() {
 fn test_type_reconstruction_no_size_info() {
     test_type_reconstruction_internal(
         "test_type_reconstruction_no_size_info",
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Automatic,
-        SizePrintFlavor::Disabled,
-        false,
-        true,
-        true,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Automatic,
+            size_print_flavor: SizePrintFlavor::Disabled,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -127,12 +153,16 @@ fn test_type_reconstruction_no_size_info() {
 fn test_type_reconstruction_no_offset_info() {
     test_type_reconstruction_internal(
         "test_type_reconstruction_no_offset_info",
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Automatic,
-        SizePrintFlavor::Comment,
-        false,
-        true,
-        false,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Automatic,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: false,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -142,12 +172,174 @@ fn test_type_reconstruction_no_offset_info() {
 fn test_type_reconstruction_no_comments() {
     test_type_reconstruction_internal(
         "test_type_reconstruction_no_comments",
-        PrimitiveReconstructionFlavor::Portable,
-        AccessSpecifierReconstructionFlavor::Automatic,
-        SizePrintFlavor::Disabled,
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Automatic,
+            size_print_flavor: SizePrintFlavor::Disabled,
+            integers_as_hexadecimal: true,
+            print_offset_info: false,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_rust() {
+    test_type_reconstruction_internal(
+        "test_type_reconstruction_rust",
+        OutputLanguage::Rust,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::StaticAssert,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_c() {
+    test_type_reconstruction_internal(
+        "test_type_reconstruction_c",
+        OutputLanguage::C,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::StaticAssert,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_vftables: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_json() {
+    test_type_reconstruction_internal(
+        "test_type_reconstruction_json",
+        OutputLanguage::Json,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_vftables() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_vftables",
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_vftables: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_padding() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_padding",
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_padding: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_offset_asserts() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_offset_asserts",
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::StaticAssert,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_offset_asserts: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
+        false,
+        false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_namespaces() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_namespaces",
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_namespaces: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         true,
         false,
+    );
+}
+
+#[test]
+fn test_type_reconstruction_source_locations() {
+    test_type_reconstruction_internal(
+        "type_reconstruction_source_locations",
+        OutputLanguage::Cpp,
+        &DataFormatConfiguration {
+            print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+            size_print_flavor: SizePrintFlavor::Comment,
+            integers_as_hexadecimal: true,
+            print_offset_info: true,
+            print_brackets_new_line: false,
+            print_source_locations: true,
+            primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+            ..Default::default()
+        },
         false,
         false,
     );
@@ -155,13 +347,9 @@ fn test_type_reconstruction_no_comments() {
 
 fn test_type_reconstruction_internal(
     test_name: &str,
-    primitives_flavor: PrimitiveReconstructionFlavor,
-    print_access_specifiers: AccessSpecifierReconstructionFlavor,
-    print_size_info: SizePrintFlavor,
+    output_language: OutputLanguage,
+    fmt_configuration: &DataFormatConfiguration,
     reconstruct_dependencies: bool,
-    integers_as_hexadecimal: bool,
-    print_offset_info: bool,
-    print_brackets_new_line: bool,
     ignore_std_types: bool,
 ) {
     let pdb_file = PdbFile::load_from_file(Path::new(TEST_PDB_FILE_PATH)).expect("load test.pdb");
@@ -169,13 +357,9 @@ fn test_type_reconstruction_internal(
         let (reconstructed_type, _) = pdb_file
             .reconstruct_type_by_name(
                 test_case_type_name,
-                primitives_flavor,
-                print_access_specifiers,
-                print_size_info,
+                output_language,
+                fmt_configuration,
                 reconstruct_dependencies,
-                integers_as_hexadecimal,
-                print_offset_info,
-                print_brackets_new_line,
                 ignore_std_types,
             )
             .unwrap_or_else(|_| panic!("reconstruct type: {test_case_type_name}"));
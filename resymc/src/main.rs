@@ -3,19 +3,25 @@ mod resymc_app;
 mod resymc_options;
 mod syntax_highlighting;
 
+use std::io::{self, BufRead};
+
 use anyhow::Result;
 use resym_core::pdb_types::{
-    AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor, SizePrintFlavor,
+    AccessSpecifierReconstructionFlavor, DataFormatConfiguration, GraphFormat, HeaderSplitFlavor,
+    OutputFormat, OutputLanguage, PrimitiveReconstructionFlavor, SizePrintFlavor,
 };
 use structopt::StructOpt;
 
 use crate::resymc_app::ResymcApp;
-use crate::resymc_options::ResymcOptions;
+use crate::resymc_options::{parse_rva, ResymcOptions};
 
 const DEFAULT_PRIMITIVE_FLAVOR: PrimitiveReconstructionFlavor = PrimitiveReconstructionFlavor::Msvc;
 const DEFAULT_ACCESS_SPECIFIER_FLAVOR: AccessSpecifierReconstructionFlavor =
     AccessSpecifierReconstructionFlavor::Always;
 const DEFAULT_PRINT_SIZE_FLAVOR: SizePrintFlavor = SizePrintFlavor::Comment;
+const DEFAULT_OUTPUT_LANGUAGE: OutputLanguage = OutputLanguage::Cpp;
+const DEFAULT_OUTPUT_FORMAT: OutputFormat = OutputFormat::Text;
+const DEFAULT_GRAPH_FORMAT: GraphFormat = GraphFormat::Dot;
 
 fn main() -> Result<()> {
     env_logger::init();
@@ -28,21 +34,27 @@ fn main() -> Result<()> {
             pdb_path,
             type_name_filter,
             output_file_path,
+            format,
             case_insensitive,
             use_regex,
             ignore_std_types,
+            source_path,
         } => app.list_types_command(
             pdb_path,
             type_name_filter,
             case_insensitive,
             use_regex,
             ignore_std_types,
+            source_path,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
             output_file_path,
         ),
         ResymcOptions::Dump {
             pdb_path,
             type_name,
             output_file_path,
+            format,
+            output_language,
             primitive_types_flavor,
             print_access_specifiers,
             size_print_flavor,
@@ -51,19 +63,33 @@ fn main() -> Result<()> {
             integers_as_hexadecimal,
             print_offset_info,
             print_brackets_new_line,
+            print_vftables,
+            print_padding,
+            print_offset_asserts,
+            print_namespaces,
+            print_source_locations,
             ignore_std_types,
             highlight_syntax,
         } => app.dump_types_command(
             pdb_path,
             Some(type_name),
-            primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
-            print_access_specifiers.unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
-            size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
+            type_output_language(format, output_language)?,
+            DataFormatConfiguration {
+                print_access_specifiers: print_access_specifiers
+                    .unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
+                size_print_flavor: size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
+                integers_as_hexadecimal,
+                print_offset_info,
+                print_brackets_new_line,
+                print_vftables,
+                print_padding,
+                print_offset_asserts,
+                print_namespaces,
+                print_source_locations,
+                primitive_flavor: primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
+            },
             print_header,
             print_dependencies,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
             ignore_std_types,
             highlight_syntax,
             output_file_path,
@@ -71,6 +97,8 @@ fn main() -> Result<()> {
         ResymcOptions::DumpAll {
             pdb_path,
             output_file_path,
+            format,
+            output_language,
             primitive_types_flavor,
             print_access_specifiers,
             size_print_flavor,
@@ -78,23 +106,57 @@ fn main() -> Result<()> {
             integers_as_hexadecimal,
             print_offset_info,
             print_brackets_new_line,
+            print_vftables,
+            print_padding,
+            print_offset_asserts,
+            print_namespaces,
+            print_source_locations,
             ignore_std_types,
             highlight_syntax,
-        } => app.dump_types_command(
-            pdb_path,
-            None,
-            primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
-            print_access_specifiers.unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
-            size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
-            print_header,
-            false,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
-            ignore_std_types,
-            highlight_syntax,
-            output_file_path,
-        ),
+            split_by_header,
+            split_by_namespace,
+        } => {
+            let fmt_configuration = DataFormatConfiguration {
+                print_access_specifiers: print_access_specifiers
+                    .unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
+                size_print_flavor: size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
+                integers_as_hexadecimal,
+                print_offset_info,
+                print_brackets_new_line,
+                print_vftables,
+                print_padding,
+                print_offset_asserts,
+                print_namespaces,
+                print_source_locations,
+                primitive_flavor: primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
+            };
+            let header_split = split_by_header
+                .map(|path| (HeaderSplitFlavor::SourceFile, path))
+                .or(split_by_namespace.map(|path| (HeaderSplitFlavor::Namespace, path)));
+            if let Some((header_split_flavor, output_directory_path)) = header_split {
+                app.dump_types_into_headers_command(
+                    pdb_path,
+                    header_split_flavor,
+                    type_output_language(format, output_language)?,
+                    fmt_configuration,
+                    print_header,
+                    ignore_std_types,
+                    output_directory_path,
+                )
+            } else {
+                app.dump_types_command(
+                    pdb_path,
+                    None,
+                    type_output_language(format, output_language)?,
+                    fmt_configuration,
+                    print_header,
+                    false,
+                    ignore_std_types,
+                    highlight_syntax,
+                    output_file_path,
+                )
+            }
+        }
         ResymcOptions::Diff {
             from_pdb_path,
             to_pdb_path,
@@ -108,20 +170,33 @@ fn main() -> Result<()> {
             integers_as_hexadecimal,
             print_offset_info,
             print_brackets_new_line,
+            print_vftables,
+            print_padding,
+            print_offset_asserts,
+            print_namespaces,
+            print_source_locations,
             ignore_std_types,
             highlight_syntax,
         } => app.diff_type_command(
             from_pdb_path,
             to_pdb_path,
             type_name,
-            primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
-            print_access_specifiers.unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
-            size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
+            DataFormatConfiguration {
+                print_access_specifiers: print_access_specifiers
+                    .unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
+                size_print_flavor: size_print_flavor.unwrap_or(DEFAULT_PRINT_SIZE_FLAVOR),
+                integers_as_hexadecimal,
+                print_offset_info,
+                print_brackets_new_line,
+                print_vftables,
+                print_padding,
+                print_offset_asserts,
+                print_namespaces,
+                print_source_locations,
+                primitive_flavor: primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
+            },
             print_header,
             print_dependencies,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
             ignore_std_types,
             highlight_syntax,
             output_file_path,
@@ -130,6 +205,7 @@ fn main() -> Result<()> {
             pdb_path,
             module_path_filter,
             output_file_path,
+            format,
             case_insensitive,
             use_regex,
         } => app.list_modules_command(
@@ -137,6 +213,7 @@ fn main() -> Result<()> {
             module_path_filter,
             case_insensitive,
             use_regex,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
             output_file_path,
         ),
         ResymcOptions::DumpModule {
@@ -175,10 +252,31 @@ fn main() -> Result<()> {
             highlight_syntax,
             output_file_path,
         ),
+        ResymcOptions::ListFiles {
+            pdb_path,
+            output_file_path,
+            format,
+        } => app.list_files_command(
+            pdb_path,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::ListFileFunctions {
+            pdb_path,
+            file_path,
+            output_file_path,
+            format,
+        } => app.list_file_functions_command(
+            pdb_path,
+            file_path,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
         ResymcOptions::ListSymbols {
             pdb_path,
             symbol_name_filter,
             output_file_path,
+            format,
             case_insensitive,
             use_regex,
             ignore_std_types,
@@ -188,6 +286,7 @@ fn main() -> Result<()> {
             case_insensitive,
             use_regex,
             ignore_std_types,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
             output_file_path,
         ),
         ResymcOptions::DumpSymbol {
@@ -197,6 +296,9 @@ fn main() -> Result<()> {
             primitive_types_flavor,
             print_access_specifiers,
             print_header,
+            print_locals,
+            locations,
+            print_inlinees,
             highlight_syntax,
         } => app.dump_symbol_command(
             pdb_path,
@@ -204,6 +306,9 @@ fn main() -> Result<()> {
             primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
             print_access_specifiers.unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
             print_header,
+            print_locals,
+            locations,
+            print_inlinees,
             highlight_syntax,
             output_file_path,
         ),
@@ -213,6 +318,9 @@ fn main() -> Result<()> {
             primitive_types_flavor,
             print_access_specifiers,
             print_header,
+            print_locals,
+            locations,
+            print_inlinees,
             highlight_syntax,
         } => app.dump_symbol_command(
             pdb_path,
@@ -220,6 +328,9 @@ fn main() -> Result<()> {
             primitive_types_flavor.unwrap_or(DEFAULT_PRIMITIVE_FLAVOR),
             print_access_specifiers.unwrap_or(DEFAULT_ACCESS_SPECIFIER_FLAVOR),
             print_header,
+            print_locals,
+            locations,
+            print_inlinees,
             highlight_syntax,
             output_file_path,
         ),
@@ -242,5 +353,183 @@ fn main() -> Result<()> {
             highlight_syntax,
             output_file_path,
         ),
+        ResymcOptions::ListInlineSites {
+            pdb_path,
+            function_name,
+            output_file_path,
+            format,
+        } => app.list_inline_sites_command(
+            pdb_path,
+            function_name,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::Symbolize {
+            pdb_path,
+            rvas,
+            output_file_path,
+            format,
+        } => {
+            // Read RVAs from stdin for batch use
+            let rvas = if rvas.is_empty() {
+                read_rvas(io::stdin().lock())?
+            } else {
+                rvas
+            };
+            app.symbolize_command(
+                pdb_path,
+                rvas,
+                format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+                output_file_path,
+            )
+        }
+        ResymcOptions::WhichModule {
+            pdb_path,
+            rva,
+            output_file_path,
+            format,
+        } => app.which_module_command(
+            pdb_path,
+            rva,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::ListContributions {
+            pdb_path,
+            module,
+            output_file_path,
+            format,
+        } => app.list_contributions_command(
+            pdb_path,
+            module,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::SizeReport {
+            pdb_path,
+            output_file_path,
+            format,
+            top,
+        } => app.size_report_command(
+            pdb_path,
+            top,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::Info {
+            pdb_path,
+            output_file_path,
+            format,
+        } => app.info_command(
+            pdb_path,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::Streams {
+            pdb_path,
+            output_file_path,
+            format,
+        } => app.streams_command(
+            pdb_path,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::StreamDump {
+            pdb_path,
+            stream,
+            output_file_path,
+            raw,
+        } => app.stream_dump_command(pdb_path, stream, raw, output_file_path),
+        ResymcOptions::DiffSizes {
+            from_pdb_path,
+            to_pdb_path,
+            output_file_path,
+            format,
+            threshold,
+            budget,
+        } => app.diff_sizes_command(
+            from_pdb_path,
+            to_pdb_path,
+            threshold.unwrap_or_default(),
+            budget,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::DumpRecords {
+            pdb_path,
+            output_file_path,
+            types,
+            ids,
+            symbols,
+        } => {
+            // Dump all records if no record kind has been selected
+            let dump_all = !(types || ids || symbols);
+            app.dump_records_command(
+                pdb_path,
+                types || dump_all,
+                ids || dump_all,
+                symbols || dump_all,
+                output_file_path,
+            )
+        }
+        ResymcOptions::Graph {
+            pdb_path,
+            type_name,
+            output_file_path,
+            depth,
+            format,
+            base_classes,
+        } => app.graph_command(
+            pdb_path,
+            type_name,
+            depth,
+            base_classes,
+            format.unwrap_or(DEFAULT_GRAPH_FORMAT),
+            output_file_path,
+        ),
+        ResymcOptions::Xrefs {
+            pdb_path,
+            type_name,
+            output_file_path,
+            symbols,
+            format,
+        } => app.xrefs_command(
+            pdb_path,
+            type_name,
+            symbols,
+            format.unwrap_or(DEFAULT_OUTPUT_FORMAT),
+            output_file_path,
+        ),
+    }
+}
+
+/// Read RVAs from the given reader, one per line. Empty lines are ignored.
+fn read_rvas(reader: impl BufRead) -> Result<Vec<u32>> {
+    let mut rvas = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        rvas.push(parse_rva(&line).map_err(|err| anyhow::anyhow!("Invalid RVA '{line}': {err}"))?);
+    }
+
+    Ok(rvas)
+}
+
+/// Types are serialized instead of being reconstructed when the JSON format
+/// is requested, in which case no other output language can be specified.
+fn type_output_language(
+    format: Option<OutputFormat>,
+    output_language: Option<OutputLanguage>,
+) -> Result<OutputLanguage> {
+    match (format.unwrap_or(DEFAULT_OUTPUT_FORMAT), output_language) {
+        (OutputFormat::Text, output_language) => {
+            Ok(output_language.unwrap_or(DEFAULT_OUTPUT_LANGUAGE))
+        }
+        (OutputFormat::Json, None | Some(OutputLanguage::Json)) => Ok(OutputLanguage::Json),
+        (OutputFormat::Json, Some(_)) => Err(anyhow::anyhow!(
+            "'--format json' cannot be combined with another '--output-language'"
+        )),
     }
 }
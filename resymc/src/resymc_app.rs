@@ -1,14 +1,30 @@
-use std::{fs::File, io::Write, path::PathBuf, sync::Arc};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{anyhow, Result};
 use resym_core::{
-    backend::{Backend, BackendCommand, PDBSlot},
-    frontend::FrontendCommand,
+    backend::{Backend, BackendCommand, PDBSlot, StreamSource},
+    diffing::{SizeChange, SizeDiff},
+    frontend::{FrontendCommand, ReconstructedFiles},
+    hexdump::hexdump,
+    pdb_file::{
+        ChecksumKind, InlineCallSite, PdbMetadata, SectionContribution, SizeReport, SourceFileInfo,
+        StreamInfo, SymbolXref, SymbolXrefKind, SymbolizedAddress, TypeDependencyKind, TypeGraph,
+    },
     pdb_types::AccessSpecifierReconstructionFlavor,
+    pdb_types::DataFormatConfiguration,
+    pdb_types::GraphFormat,
+    pdb_types::HeaderSplitFlavor,
+    pdb_types::OutputFormat,
+    pdb_types::OutputLanguage,
     pdb_types::PrimitiveReconstructionFlavor,
-    pdb_types::SizePrintFlavor,
     syntax_highlighting::CodeTheme,
 };
+use serde_json::json;
 
 use crate::{frontend::CLIFrontendController, syntax_highlighting::highlight_code};
 
@@ -44,6 +60,8 @@ impl ResymcApp {
         case_insensitive: bool,
         use_regex: bool,
         ignore_std_types: bool,
+        source_path_filter: Option<String>,
+        output_format: OutputFormat,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
         // Request the backend to load the PDB
@@ -68,13 +86,20 @@ impl ResymcApp {
             ignore_std_types,
             // TODO(ergrelet): allow users to control these filters
             Default::default(),
+            source_path_filter,
         ))?;
         // Wait for the backend to finish filtering types
         if let FrontendCommand::ListTypesResult(type_list) =
             self.frontend_controller.rx_ui.recv()?
         {
             // Dump output
-            if let Some(output_file_path) = output_file_path {
+            if output_format == OutputFormat::Json {
+                let type_list: Vec<_> = type_list
+                    .into_iter()
+                    .map(|(type_name, type_index)| json!({"name": type_name, "index": type_index}))
+                    .collect();
+                dump_json_output(&type_list, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
                 let mut output_file = File::create(output_file_path)?;
                 for (type_name, _) in type_list {
                     writeln!(output_file, "{type_name}")?;
@@ -95,14 +120,10 @@ impl ResymcApp {
         &self,
         pdb_path: PathBuf,
         type_name: Option<String>,
-        primitive_types_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
+        output_language: OutputLanguage,
+        fmt_configuration: DataFormatConfiguration,
         print_header: bool,
         print_dependencies: bool,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
         ignore_std_types: bool,
         highlight_syntax: bool,
         output_file_path: Option<PathBuf>,
@@ -125,27 +146,19 @@ impl ResymcApp {
                 .send_command(BackendCommand::ReconstructTypeByName(
                     PDB_MAIN_SLOT,
                     type_name,
-                    primitive_types_flavor,
-                    print_access_specifiers,
-                    size_print_flavor,
+                    output_language,
+                    fmt_configuration,
                     print_header,
                     print_dependencies,
-                    integers_as_hexadecimal,
-                    print_offset_info,
-                    print_brackets_new_line,
                     ignore_std_types,
                 ))?;
         } else {
             self.backend
                 .send_command(BackendCommand::ReconstructAllTypes(
                     PDB_MAIN_SLOT,
-                    primitive_types_flavor,
-                    print_access_specifiers,
-                    size_print_flavor,
+                    output_language,
+                    fmt_configuration,
                     print_header,
-                    integers_as_hexadecimal,
-                    print_offset_info,
-                    print_brackets_new_line,
                     ignore_std_types,
                 ))?;
         }
@@ -159,7 +172,13 @@ impl ResymcApp {
                 let mut output_file = File::create(output_file_path)?;
                 output_file.write_all(reconstructed_type.as_bytes())?;
             } else if highlight_syntax {
-                let theme = CodeTheme::default();
+                let mut theme = CodeTheme::default();
+                match output_language {
+                    OutputLanguage::Cpp => {}
+                    OutputLanguage::C => theme.language_syntax = "c".to_string(),
+                    OutputLanguage::Rust => theme.language_syntax = "rs".to_string(),
+                    OutputLanguage::Json => theme.language_syntax = "json".to_string(),
+                }
                 if let Some(colorized_reconstructed_type) =
                     highlight_code(&theme, &reconstructed_type, None)
                 {
@@ -175,20 +194,59 @@ impl ResymcApp {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn dump_types_into_headers_command(
+        &self,
+        pdb_path: PathBuf,
+        header_split_flavor: HeaderSplitFlavor,
+        output_language: OutputLanguage,
+        fmt_configuration: DataFormatConfiguration,
+        print_header: bool,
+        ignore_std_types: bool,
+        output_directory_path: PathBuf,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Queue a request for the backend to reconstruct all types
+        self.backend
+            .send_command(BackendCommand::ReconstructAllTypesIntoHeaders(
+                PDB_MAIN_SLOT,
+                header_split_flavor,
+                output_language,
+                fmt_configuration,
+                print_header,
+                ignore_std_types,
+            ))?;
+        // Wait for the backend to finish reconstructing the types
+        if let FrontendCommand::ReconstructTypeFilesResult(reconstructed_files_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            // Dump output
+            write_output_files(&output_directory_path, reconstructed_files_result?)
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn diff_type_command(
         &self,
         from_pdb_path: PathBuf,
         to_pdb_path: PathBuf,
         type_name: String,
-        primitive_types_flavor: PrimitiveReconstructionFlavor,
-        print_access_specifiers: AccessSpecifierReconstructionFlavor,
-        size_print_flavor: SizePrintFlavor,
+        fmt_configuration: DataFormatConfiguration,
         print_header: bool,
         print_dependencies: bool,
-        integers_as_hexadecimal: bool,
-        print_offset_info: bool,
-        print_brackets_new_line: bool,
         ignore_std_types: bool,
         highlight_syntax: bool,
         output_file_path: Option<PathBuf>,
@@ -234,14 +292,9 @@ impl ResymcApp {
             PDB_MAIN_SLOT,
             PDB_DIFF_TO_SLOT,
             type_name,
-            primitive_types_flavor,
-            print_access_specifiers,
-            size_print_flavor,
+            fmt_configuration,
             print_header,
             print_dependencies,
-            integers_as_hexadecimal,
-            print_offset_info,
-            print_brackets_new_line,
             ignore_std_types,
         ))?;
         // Wait for the backend to finish
@@ -286,6 +339,7 @@ impl ResymcApp {
         module_path_filter: String,
         case_insensitive: bool,
         use_regex: bool,
+        output_format: OutputFormat,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
         // Request the backend to load the PDB
@@ -313,7 +367,13 @@ impl ResymcApp {
         {
             // Dump output
             let module_list = module_list_result?;
-            if let Some(output_file_path) = output_file_path {
+            if output_format == OutputFormat::Json {
+                let module_list: Vec<_> = module_list
+                    .into_iter()
+                    .map(|(module_path, module_id)| json!({"path": module_path, "id": module_id}))
+                    .collect();
+                dump_json_output(&module_list, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
                 let mut output_file = File::create(output_file_path)?;
                 for (module_path, module_id) in module_list {
                     writeln!(output_file, "Mod {module_id:04} | '{module_path}'")?;
@@ -330,6 +390,112 @@ impl ResymcApp {
         }
     }
 
+    pub fn list_files_command(
+        &self,
+        pdb_path: PathBuf,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Queue a request for the backend to return the list of source files
+        self.backend
+            .send_command(BackendCommand::ListSourceFiles(PDB_MAIN_SLOT))?;
+        // Wait for the backend to finish listing source files
+        if let FrontendCommand::ListSourceFilesResult(source_file_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            // Dump output
+            let source_file_list = source_file_list_result?;
+            if output_format == OutputFormat::Json {
+                dump_json_output(&source_file_list, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                for source_file in &source_file_list {
+                    writeln!(output_file, "{}", fmt_source_file(source_file))?;
+                }
+            } else {
+                for source_file in &source_file_list {
+                    println!("{}", fmt_source_file(source_file));
+                }
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
+    pub fn list_file_functions_command(
+        &self,
+        pdb_path: PathBuf,
+        file_path: String,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Queue a request for the backend to return the functions of the file
+        self.backend
+            .send_command(BackendCommand::ListSourceFileFunctions(
+                PDB_MAIN_SLOT,
+                file_path,
+            ))?;
+        // Wait for the backend to finish listing functions
+        if let FrontendCommand::ListSourceFileFunctionsResult(function_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            // Dump output
+            let function_list = function_list_result?;
+            if output_format == OutputFormat::Json {
+                let function_list: Vec<_> = function_list
+                    .into_iter()
+                    .map(|(function_name, (module_id, symbol_index))| {
+                        json!({
+                            "name": function_name,
+                            "module_id": module_id,
+                            "symbol_index": symbol_index,
+                        })
+                    })
+                    .collect();
+                dump_json_output(&function_list, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                for (function_name, _) in function_list {
+                    writeln!(output_file, "{function_name}")?;
+                }
+            } else {
+                for (function_name, _) in function_list {
+                    println!("{function_name}");
+                }
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!("Invalid response received from the backend?"))
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn dump_module_command(
         &self,
@@ -487,6 +653,7 @@ impl ResymcApp {
         case_insensitive: bool,
         use_regex: bool,
         ignore_std_types: bool,
+        output_format: OutputFormat,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
         // Request the backend to load the PDB
@@ -518,7 +685,19 @@ impl ResymcApp {
             self.frontend_controller.rx_ui.recv()?
         {
             // Dump output
-            if let Some(output_file_path) = output_file_path {
+            if output_format == OutputFormat::Json {
+                let symbol_list: Vec<_> = symbol_list
+                    .into_iter()
+                    .map(|(symbol_name, (module_id, symbol_index))| {
+                        json!({
+                            "name": symbol_name,
+                            "module_id": module_id,
+                            "symbol_index": symbol_index,
+                        })
+                    })
+                    .collect();
+                dump_json_output(&symbol_list, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
                 let mut output_file = File::create(output_file_path)?;
                 for (symbol_name, _) in symbol_list {
                     writeln!(output_file, "{symbol_name}")?;
@@ -545,6 +724,9 @@ impl ResymcApp {
         primitive_types_flavor: PrimitiveReconstructionFlavor,
         print_access_specifiers: AccessSpecifierReconstructionFlavor,
         print_header: bool,
+        print_locals: bool,
+        print_locations: bool,
+        print_inlinees: bool,
         highlight_syntax: bool,
         output_file_path: Option<PathBuf>,
     ) -> Result<()> {
@@ -571,6 +753,9 @@ impl ResymcApp {
                     primitive_types_flavor,
                     print_access_specifiers,
                     print_header,
+                    print_locals,
+                    print_locations,
+                    print_inlinees,
                 ))?;
         } else {
             self.backend
@@ -579,6 +764,9 @@ impl ResymcApp {
                     primitive_types_flavor,
                     print_access_specifiers,
                     print_header,
+                    print_locals,
+                    print_locations,
+                    print_inlinees,
                 ))?;
         }
         // Wait for the backend to finish filtering types
@@ -706,161 +894,1945 @@ impl ResymcApp {
             ))
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::fs;
-
-    use super::*;
 
-    use tempdir::TempDir;
+    pub fn list_inline_sites_command(
+        &self,
+        pdb_path: PathBuf,
+        function_name: String,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
 
-    const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
-    const TEST_PDB_FROM_FILE_PATH: &str = "../resym_core/tests/data/test_diff_from.pdb";
-    const TEST_PDB_TO_FILE_PATH: &str = "../resym_core/tests/data/test_diff_to.pdb";
+        // Queue a request for the backend to find the inline call sites
+        self.backend
+            .send_command(BackendCommand::ListInlineCallSites(
+                PDB_MAIN_SLOT,
+                function_name,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ListInlineCallSitesResult(inline_call_sites_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let inline_call_sites = inline_call_sites_result?;
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&inline_call_sites, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                for inline_call_site in &inline_call_sites {
+                    writeln!(output_file, "{}", fmt_inline_call_site(inline_call_site))?;
+                }
+            } else {
+                for inline_call_site in &inline_call_sites {
+                    println!("{}", fmt_inline_call_site(inline_call_site));
+                }
+            }
 
-    // List types
-    #[test]
-    fn list_types_command_invalid_pdb_path() {
-        let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path = PathBuf::new();
-        // The command should fail
-        assert!(app
-            .list_types_command(
-                pdb_path,
-                "resym_test::StructTest".to_string(),
-                false,
-                false,
-                false,
-                None,
-            )
-            .is_err());
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "ListInlineCallSitesResult expected. Invalid response received from the backend?"
+            ))
+        }
     }
 
-    #[test]
-    fn list_types_command_stdio_successful() {
-        let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
-        // The command should succeed
-        assert!(app
-            .list_types_command(
-                pdb_path,
-                "resym_test::StructTest".to_string(),
-                true,
-                true,
-                true,
-                None,
-            )
-            .is_ok());
-    }
+    pub fn symbolize_command(
+        &self,
+        pdb_path: PathBuf,
+        rvas: Vec<u32>,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
 
-    #[test]
-    fn list_types_command_file_successful() {
-        let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
-        let tmp_dir =
-            TempDir::new("list_types_command_file_successful").expect("TempDir creation failed");
-        let output_path = tmp_dir.path().join("output.txt");
-        // The command should succeed
-        assert!(app
-            .list_types_command(
-                pdb_path,
-                "resym_test::ClassWithNestedDeclarationsTest".to_string(),
-                false,
-                false,
-                false,
-                Some(output_path.clone()),
-            )
-            .is_ok());
+        // Queue a request for the backend to symbolize the given addresses
+        self.backend
+            .send_command(BackendCommand::SymbolizeAddresses(PDB_MAIN_SLOT, rvas))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::SymbolizeAddressesResult(symbolized_addresses_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let symbolized_addresses = symbolized_addresses_result?;
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&symbolized_addresses, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                for symbolized_address in &symbolized_addresses {
+                    writeln!(
+                        output_file,
+                        "{}",
+                        fmt_symbolized_address(symbolized_address)
+                    )?;
+                }
+            } else {
+                for symbolized_address in &symbolized_addresses {
+                    println!("{}", fmt_symbolized_address(symbolized_address));
+                }
+            }
 
-        // Check output file's content
-        let output = fs::read_to_string(output_path).expect("Failed to read output file");
-        assert_eq!(
-            output,
-            concat!(
-                "resym_test::ClassWithNestedDeclarationsTest::NestEnum\n",
-                "resym_test::ClassWithNestedDeclarationsTest\n",
-                "resym_test::ClassWithNestedDeclarationsTest::NestedUnion\n",
-                "resym_test::ClassWithNestedDeclarationsTest::NestedClass\n",
-                "resym_test::ClassWithNestedDeclarationsTest::NestedStruct\n"
-            )
-        );
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "SymbolizeAddressesResult expected. Invalid response received from the backend?"
+            ))
+        }
     }
 
-    // Dump types
-    #[test]
-    fn dump_types_command_invalid_pdb_path() {
-        let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path = PathBuf::new();
+    pub fn which_module_command(
+        &self,
+        pdb_path: PathBuf,
+        rva: u32,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to find the contribution
+        self.backend
+            .send_command(BackendCommand::FindSectionContribution(PDB_MAIN_SLOT, rva))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::FindSectionContributionResult(section_contribution) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&section_contribution, output_file_path)?;
+            } else {
+                let output = if let Some(section_contribution) = &section_contribution {
+                    format!(
+                        "0x{rva:x}: {}",
+                        fmt_section_contribution(section_contribution)
+                    )
+                } else {
+                    format!("0x{rva:x}: ??")
+                };
+                if let Some(output_file_path) = output_file_path {
+                    let mut output_file = File::create(output_file_path)?;
+                    writeln!(output_file, "{output}")?;
+                } else {
+                    println!("{output}");
+                }
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "FindSectionContributionResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn list_contributions_command(
+        &self,
+        pdb_path: PathBuf,
+        module_path: Option<String>,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to list the contributions
+        self.backend
+            .send_command(BackendCommand::ListSectionContributions(
+                PDB_MAIN_SLOT,
+                module_path,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ListSectionContributionsResult(section_contributions) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&section_contributions, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                for section_contribution in &section_contributions {
+                    writeln!(
+                        output_file,
+                        "{}",
+                        fmt_section_contribution(section_contribution)
+                    )?;
+                }
+            } else {
+                for section_contribution in &section_contributions {
+                    println!("{}", fmt_section_contribution(section_contribution));
+                }
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "ListSectionContributionsResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn size_report_command(
+        &self,
+        pdb_path: PathBuf,
+        top: Option<usize>,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to generate the report
+        self.backend
+            .send_command(BackendCommand::GenerateSizeReport(PDB_MAIN_SLOT))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::GenerateSizeReportResult(size_report_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let mut size_report = size_report_result?;
+            if let Some(top) = top {
+                size_report.modules.truncate(top);
+                size_report.object_files.truncate(top);
+                size_report.namespaces.truncate(top);
+                size_report.template_families.truncate(top);
+            }
+
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&size_report, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                writeln!(output_file, "{}", fmt_size_report(&size_report))?;
+            } else {
+                println!("{}", fmt_size_report(&size_report));
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "GenerateSizeReportResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn info_command(
+        &self,
+        pdb_path: PathBuf,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to retrieve the PDB's metadata
+        self.backend
+            .send_command(BackendCommand::GetPdbMetadata(PDB_MAIN_SLOT))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::GetPdbMetadataResult(pdb_metadata_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let pdb_metadata = pdb_metadata_result?;
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&pdb_metadata, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                writeln!(output_file, "{}", fmt_pdb_metadata(&pdb_metadata))?;
+            } else {
+                println!("{}", fmt_pdb_metadata(&pdb_metadata));
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "GetPdbMetadataResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn streams_command(
+        &self,
+        pdb_path: PathBuf,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Note: streams are read directly from the file, PDBs which cannot be
+        // loaded can still be inspected
+        let stream_source = StreamSource::Path(pdb_path);
+        let stream_list = self.list_streams(&stream_source)?;
+        // Dump output
+        if output_format == OutputFormat::Json {
+            dump_json_output(&stream_list, output_file_path)?;
+        } else if let Some(output_file_path) = output_file_path {
+            let mut output_file = File::create(output_file_path)?;
+            for stream in stream_list {
+                writeln!(output_file, "{}", fmt_stream(&stream))?;
+            }
+        } else {
+            for stream in stream_list {
+                println!("{}", fmt_stream(&stream));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn stream_dump_command(
+        &self,
+        pdb_path: PathBuf,
+        stream: String,
+        raw: bool,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Note: streams are read directly from the file, PDBs which cannot be
+        // loaded can still be inspected
+        let stream_source = StreamSource::Path(pdb_path);
+        // Streams can be designated by index or by name
+        let stream_index = match stream.parse::<u16>() {
+            Ok(stream_index) => stream_index,
+            Err(_) => self
+                .list_streams(&stream_source)?
+                .into_iter()
+                .find(|stream_info| stream_info.name.as_ref() == Some(&stream))
+                .map(|stream_info| stream_info.index)
+                .ok_or_else(|| anyhow!("Stream not found: {}", stream))?,
+        };
+
+        // Queue a request for the backend to read the stream
+        self.backend.send_command(BackendCommand::ReadStream(
+            stream_source,
+            stream_index,
+            None,
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ReadStreamResult(_, stream_data_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let stream_data = stream_data_result?;
+            // Dump output
+            if raw {
+                if let Some(output_file_path) = output_file_path {
+                    fs::write(output_file_path, stream_data)?;
+                } else {
+                    std::io::stdout().write_all(&stream_data)?;
+                }
+            } else if let Some(output_file_path) = output_file_path {
+                fs::write(output_file_path, hexdump(&stream_data))?;
+            } else {
+                print!("{}", hexdump(&stream_data));
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "ReadStreamResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    fn list_streams(&self, stream_source: &StreamSource) -> Result<Vec<StreamInfo>> {
+        // Queue a request for the backend to list streams
+        self.backend
+            .send_command(BackendCommand::ListStreams(stream_source.clone()))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::ListStreamsResult(stream_list_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            Ok(stream_list_result?)
+        } else {
+            Err(anyhow!(
+                "ListStreamsResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn diff_sizes_command(
+        &self,
+        from_pdb_path: PathBuf,
+        to_pdb_path: PathBuf,
+        threshold: u64,
+        budget: Option<u64>,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the first PDB
+        self.backend.send_command(BackendCommand::LoadPDBFromPath(
+            PDB_MAIN_SLOT,
+            from_pdb_path.clone(),
+        ))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!(
+                    "Failed to load PDB '{}': {}",
+                    from_pdb_path.display(),
+                    err
+                ));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Request the backend to load the second PDB
+        self.backend.send_command(BackendCommand::LoadPDBFromPath(
+            PDB_DIFF_TO_SLOT,
+            to_pdb_path.clone(),
+        ))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!(
+                    "Failed to load PDB '{}': {}",
+                    to_pdb_path.display(),
+                    err
+                ));
+            }
+        } else {
+            return Err(anyhow!("Invalid response received from the backend?"));
+        }
+
+        // Queue a request for the backend to diff symbol sizes
+        self.backend.send_command(BackendCommand::DiffSymbolSizes(
+            PDB_MAIN_SLOT,
+            PDB_DIFF_TO_SLOT,
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::DiffSymbolSizesResult(size_diff_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let mut size_diff = size_diff_result?;
+            size_diff
+                .symbols
+                .retain(|symbol| symbol.delta().unsigned_abs() >= threshold);
+
+            // Dump output
+            if output_format == OutputFormat::Json {
+                dump_json_output(&size_diff, output_file_path)?;
+            } else if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                writeln!(output_file, "{}", fmt_size_diff(&size_diff))?;
+            } else {
+                println!("{}", fmt_size_diff(&size_diff));
+            }
+
+            // Make the command fail when the size budget is exceeded
+            if let Some(budget) = budget {
+                if size_diff.total_delta() > budget as i64 {
+                    return Err(anyhow!(
+                        "Total size grew by {} bytes, which exceeds the budget of {} bytes",
+                        size_diff.total_delta(),
+                        budget
+                    ));
+                }
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "DiffSymbolSizesResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn dump_records_command(
+        &self,
+        pdb_path: PathBuf,
+        dump_types: bool,
+        dump_ids: bool,
+        dump_symbols: bool,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to dump the records
+        self.backend.send_command(BackendCommand::DumpRecords(
+            PDB_MAIN_SLOT,
+            dump_types,
+            dump_ids,
+            dump_symbols,
+        ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::DumpRecordsResult(records_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let records = records_result?;
+            // Dump output
+            if let Some(output_file_path) = output_file_path {
+                fs::write(output_file_path, records)?;
+            } else {
+                print!("{records}");
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "DumpRecordsResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn graph_command(
+        &self,
+        pdb_path: PathBuf,
+        type_name: String,
+        max_depth: Option<usize>,
+        include_base_classes: bool,
+        graph_format: GraphFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to build the graph
+        self.backend
+            .send_command(BackendCommand::GetTypeGraphByName(
+                PDB_MAIN_SLOT,
+                type_name,
+                max_depth,
+                include_base_classes,
+            ))?;
+        // Wait for the backend to finish
+        if let FrontendCommand::GetTypeGraphResult(type_graph_result) =
+            self.frontend_controller.rx_ui.recv()?
+        {
+            let type_graph = type_graph_result?;
+            // Dump output
+            let graph_output = match graph_format {
+                GraphFormat::Json => return dump_json_output(&type_graph, output_file_path),
+                GraphFormat::Dot => fmt_type_graph_dot(&type_graph),
+                GraphFormat::GraphMl => fmt_type_graph_graphml(&type_graph),
+            };
+            if let Some(output_file_path) = output_file_path {
+                let mut output_file = File::create(output_file_path)?;
+                writeln!(output_file, "{graph_output}")?;
+            } else {
+                println!("{graph_output}");
+            }
+
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "GetTypeGraphResult expected. Invalid response received from the backend?"
+            ))
+        }
+    }
+
+    pub fn xrefs_command(
+        &self,
+        pdb_path: PathBuf,
+        type_name: String,
+        list_symbols: bool,
+        output_format: OutputFormat,
+        output_file_path: Option<PathBuf>,
+    ) -> Result<()> {
+        // Request the backend to load the PDB
+        self.backend
+            .send_command(BackendCommand::LoadPDBFromPath(PDB_MAIN_SLOT, pdb_path))?;
+        // Wait for the backend to finish loading the PDB
+        if let FrontendCommand::LoadPDBResult(result) = self.frontend_controller.rx_ui.recv()? {
+            if let Err(err) = result {
+                return Err(anyhow!("Failed to load PDB: {}", err));
+            }
+        } else {
+            return Err(anyhow!(
+                "LoadPDBFromPath expected. Invalid response received from the backend?"
+            ));
+        }
+
+        // Queue a request for the backend to find the cross-references
+        self.backend.send_command(if list_symbols {
+            BackendCommand::ListSymbolCrossReferencesByName(PDB_MAIN_SLOT, type_name)
+        } else {
+            BackendCommand::ListTypeCrossReferencesByName(PDB_MAIN_SLOT, type_name)
+        })?;
+        // Wait for the backend to finish
+        let xref_lines: Vec<String> = match self.frontend_controller.rx_ui.recv()? {
+            FrontendCommand::ListTypeCrossReferencesResult(xref_list_result) => {
+                let mut xref_list = xref_list_result?;
+                xref_list.sort_unstable();
+                if output_format == OutputFormat::Json {
+                    return dump_json_output(&xref_list, output_file_path);
+                }
+                xref_list
+                    .into_iter()
+                    .map(|(type_name, _)| type_name)
+                    .collect()
+            }
+            FrontendCommand::ListSymbolCrossReferencesResult(xref_list_result) => {
+                let xref_list = xref_list_result?;
+                if output_format == OutputFormat::Json {
+                    return dump_json_output(&xref_list, output_file_path);
+                }
+                xref_list.iter().map(fmt_symbol_xref).collect()
+            }
+            _ => {
+                return Err(anyhow!(
+                    "ListTypeCrossReferencesResult or ListSymbolCrossReferencesResult expected. Invalid response received from the backend?"
+                ));
+            }
+        };
+
+        // Dump output
+        if let Some(output_file_path) = output_file_path {
+            let mut output_file = File::create(output_file_path)?;
+            for xref_line in &xref_lines {
+                writeln!(output_file, "{xref_line}")?;
+            }
+        } else {
+            for xref_line in &xref_lines {
+                println!("{xref_line}");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Format a type graph in Graphviz's DOT language. Pointer edges are dashed
+/// and base class edges end with an empty arrow head.
+fn fmt_type_graph_dot(type_graph: &TypeGraph) -> String {
+    let mut lines = vec![
+        "digraph types {".to_string(),
+        "    node [shape=box];".to_string(),
+    ];
+    for node in &type_graph.nodes {
+        lines.push(format!(
+            "    \"{:#x}\" [label=\"{}\"{}];",
+            node.index,
+            escape_dot_string(&node.name),
+            if node.index == type_graph.root {
+                ", style=bold"
+            } else {
+                ""
+            }
+        ));
+    }
+    for edge in &type_graph.edges {
+        lines.push(format!(
+            "    \"{:#x}\" -> \"{:#x}\" [{}];",
+            edge.from,
+            edge.to,
+            match edge.kind {
+                TypeDependencyKind::Embedded => "style=solid",
+                TypeDependencyKind::Pointer => "style=dashed",
+                TypeDependencyKind::BaseClass => "style=solid, arrowhead=empty",
+            }
+        ));
+    }
+    lines.push("}".to_string());
+
+    lines.join("\n")
+}
+
+/// Format a type graph as a GraphML document, with the name of types and the
+/// kind of dependencies as attributes.
+fn fmt_type_graph_graphml(type_graph: &TypeGraph) -> String {
+    let mut lines = vec![
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">".to_string(),
+        "  <key id=\"name\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>".to_string(),
+        "  <key id=\"depth\" for=\"node\" attr.name=\"depth\" attr.type=\"int\"/>".to_string(),
+        "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>".to_string(),
+        format!(
+            "  <graph id=\"{:#x}\" edgedefault=\"directed\">",
+            type_graph.root
+        ),
+    ];
+    for node in &type_graph.nodes {
+        lines.push(format!(
+            "    <node id=\"{:#x}\"><data key=\"name\">{}</data><data key=\"depth\">{}</data></node>",
+            node.index,
+            escape_xml_string(&node.name),
+            node.depth
+        ));
+    }
+    for edge in &type_graph.edges {
+        lines.push(format!(
+            "    <edge source=\"{:#x}\" target=\"{:#x}\"><data key=\"kind\">{}</data></edge>",
+            edge.from,
+            edge.to,
+            fmt_type_dependency_kind(edge.kind)
+        ));
+    }
+    lines.push("  </graph>".to_string());
+    lines.push("</graphml>".to_string());
+
+    lines.join("\n")
+}
+
+fn fmt_type_dependency_kind(type_dependency_kind: TypeDependencyKind) -> &'static str {
+    match type_dependency_kind {
+        TypeDependencyKind::Embedded => "embedded",
+        TypeDependencyKind::Pointer => "pointer",
+        TypeDependencyKind::BaseClass => "base class",
+    }
+}
+
+fn escape_dot_string(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml_string(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Format a stream as its index and size, followed by its name if known.
+fn fmt_stream(stream: &StreamInfo) -> String {
+    let size = stream
+        .size
+        .map_or("-".to_string(), |size| format!("{size} bytes"));
+    match &stream.name {
+        Some(name) => format!("{:>5} {:>14} | {}", stream.index, size, name),
+        None => format!("{:>5} {:>14}", stream.index, size),
+    }
+}
+
+/// Format a PDB's metadata as one property per line, followed by the
+/// well-known streams and their size.
+fn fmt_pdb_metadata(pdb_metadata: &PdbMetadata) -> String {
+    let mut lines = vec![
+        format!("GUID: {}", pdb_metadata.guid),
+        format!("Age: {}", pdb_metadata.age),
+        format!(
+            "DBI age: {}",
+            pdb_metadata
+                .dbi_age
+                .map_or("None".to_string(), |dbi_age| dbi_age.to_string())
+        ),
+        format!("Signature: 0x{:08x}", pdb_metadata.signature),
+        format!("Machine type: {}", pdb_metadata.machine_type),
+        format!("PDB version: {}", pdb_metadata.pdb_version),
+        format!("DBI version: {}", pdb_metadata.dbi_version),
+        format!("Types: {}", pdb_metadata.type_count),
+        format!("Ids: {}", pdb_metadata.id_count),
+        format!("Global symbols: {}", pdb_metadata.symbol_count),
+        format!("Modules: {}", pdb_metadata.module_count),
+        match (pdb_metadata.stream_count, pdb_metadata.total_stream_size) {
+            (Some(stream_count), Some(total_stream_size)) => {
+                format!("Streams: {stream_count} ({total_stream_size} bytes)")
+            }
+            _ => "Streams: unknown".to_string(),
+        },
+        String::default(),
+    ];
+    for stream in &pdb_metadata.streams {
+        lines.push(match stream.index {
+            Some(index) => match stream.size {
+                Some(size) => format!("{:<18} {:>6} {:>12} bytes", stream.name, index, size),
+                None => format!("{:<18} {:>6} {:>12}", stream.name, index, "unknown size"),
+            },
+            None => format!("{:<18} {:>6}", stream.name, "-"),
+        });
+    }
+
+    lines.join("\n")
+}
+
+/// Format a size diff as the total size change, followed by one table per
+/// category with the sizes (in bytes) before and after.
+fn fmt_size_diff(size_diff: &SizeDiff) -> String {
+    let mut lines = vec![
+        format!(
+            "Total: {} -> {} ({:+})",
+            size_diff.total_from,
+            size_diff.total_to,
+            size_diff.total_delta()
+        ),
+        String::default(),
+    ];
+    for (category_name, entries) in [
+        ("Modules", &size_diff.modules),
+        ("Namespaces", &size_diff.namespaces),
+    ] {
+        lines.push(format!("{category_name}:"));
+        lines.push(format!("{:>12} {:>12} {:>12}  Name", "From", "To", "Delta"));
+        for entry in entries {
+            lines.push(format!(
+                "{:>12} {:>12} {:>+12}  {}",
+                entry.size_from,
+                entry.size_to,
+                entry.delta(),
+                entry.name
+            ));
+        }
+        lines.push(String::default());
+    }
+
+    lines.push("Symbols:".to_string());
+    lines.push(format!(
+        "{:>12} {:>12} {:>12}  {:<8} Name",
+        "From", "To", "Delta", "Change"
+    ));
+    for symbol in &size_diff.symbols {
+        lines.push(format!(
+            "{:>12} {:>12} {:>+12}  {:<8} {}",
+            symbol.size_from,
+            symbol.size_to,
+            symbol.delta(),
+            fmt_size_change(symbol.change),
+            symbol.name
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn fmt_size_change(size_change: SizeChange) -> &'static str {
+    match size_change {
+        SizeChange::Added => "Added",
+        SizeChange::Removed => "Removed",
+        SizeChange::Grown => "Grown",
+        SizeChange::Shrunk => "Shrunk",
+    }
+}
+
+/// Format a size report as one table per category, with the size (in bytes)
+/// and the number of symbols or contributions of each entry.
+fn fmt_size_report(size_report: &SizeReport) -> String {
+    let categories = [
+        ("Modules", &size_report.modules),
+        ("Object files", &size_report.object_files),
+        ("Namespaces", &size_report.namespaces),
+        ("Template families", &size_report.template_families),
+    ];
+
+    let mut lines = vec![];
+    for (category_name, entries) in categories {
+        lines.push(format!("{category_name}:"));
+        lines.push(format!("{:>12} {:>8}  Name", "Size", "Count"));
+        for entry in entries {
+            lines.push(format!(
+                "{:>12} {:>8}  {}",
+                entry.size, entry.count, entry.name
+            ));
+        }
+        lines.push(String::default());
+    }
+
+    lines.join("\n")
+}
+
+/// Format a section contribution as its section and RVA range, followed by
+/// the module which contributed it.
+fn fmt_section_contribution(section_contribution: &SectionContribution) -> String {
+    format!(
+        "{} 0x{:x}-0x{:x} (0x{:x} bytes) | Mod {:04} | '{}'",
+        section_contribution.section_name,
+        section_contribution.rva,
+        section_contribution.rva + section_contribution.size,
+        section_contribution.size,
+        section_contribution.module_index,
+        section_contribution.module_name,
+    )
+}
+
+/// Format a source file as its path, followed by its checksum and the IDs of
+/// the modules which reference it.
+fn fmt_source_file(source_file: &SourceFileInfo) -> String {
+    let module_ids: Vec<String> = source_file
+        .modules
+        .iter()
+        .map(|(_, module_id)| format!("{module_id:04}"))
+        .collect();
+    let checksum = if source_file.checksum_kind == ChecksumKind::None {
+        source_file.checksum_kind.to_string()
+    } else {
+        format!("{}:{}", source_file.checksum_kind, source_file.checksum)
+    };
+
+    format!(
+        "'{}' | {} | Mod {}",
+        source_file.file_path,
+        checksum,
+        module_ids.join(",")
+    )
+}
+
+/// Format an inline call site as the caller's name, followed by the location
+/// and code ranges of the inlined code.
+fn fmt_symbol_xref(symbol_xref: &SymbolXref) -> String {
+    match symbol_xref.kind {
+        SymbolXrefKind::Variable => format!("{} | variable", symbol_xref.symbol_name),
+        SymbolXrefKind::Parameter => format!("{} | parameter", symbol_xref.symbol_name),
+        SymbolXrefKind::ReturnValue => format!("{} | return value", symbol_xref.symbol_name),
+        SymbolXrefKind::LocalVariable => format!(
+            "{} | local variable {}",
+            symbol_xref.symbol_name,
+            symbol_xref.variable_name.as_deref().unwrap_or_default()
+        ),
+    }
+}
+
+fn fmt_inline_call_site(inline_call_site: &InlineCallSite) -> String {
+    let inlined_call = &inline_call_site.inlined_call;
+    let source_location = inlined_call
+        .source_location
+        .as_ref()
+        .map(|source_location| format!(" at {source_location}"))
+        .unwrap_or_default();
+    let code_ranges: Vec<String> = inlined_call
+        .code_ranges
+        .iter()
+        .map(|(start_rva, end_rva)| format!("0x{start_rva:x}-0x{end_rva:x}"))
+        .collect();
+
+    format!(
+        "{}{} | RVA={}",
+        inline_call_site.caller_name,
+        source_location,
+        code_ranges.join(",")
+    )
+}
+
+/// Format a symbolized address like `addr2line` does, with one line per
+/// frame (innermost first).
+fn fmt_symbolized_address(symbolized_address: &SymbolizedAddress) -> String {
+    let address_prefix = format!("0x{:x}: ", symbolized_address.rva);
+    if symbolized_address.frames.is_empty() {
+        return format!("{address_prefix}??");
+    }
+
+    let mut frame_lines = vec![];
+    for (i, frame) in symbolized_address.frames.iter().enumerate() {
+        let function_offset = frame
+            .function_offset
+            .map(|offset| format!("+0x{offset:x}"))
+            .unwrap_or_default();
+        let source_location = frame
+            .source_location
+            .as_ref()
+            .map(|source_location| format!(" at {source_location}"))
+            .unwrap_or_default();
+        let frame_prefix = if i == 0 {
+            address_prefix.clone()
+        } else {
+            format!("{}(inlined by) ", " ".repeat(address_prefix.len()))
+        };
+        frame_lines.push(format!(
+            "{frame_prefix}{}{function_offset}{source_location}",
+            frame.function_name
+        ));
+    }
+
+    frame_lines.join("\n")
+}
+
+/// Write `value` as pretty-printed JSON to the given file, or to stdout if no
+/// file is given.
+fn dump_json_output<T: serde::Serialize>(
+    value: &T,
+    output_file_path: Option<PathBuf>,
+) -> Result<()> {
+    let json_output = serde_json::to_string_pretty(value)?;
+    if let Some(output_file_path) = output_file_path {
+        let mut output_file = File::create(output_file_path)?;
+        writeln!(output_file, "{json_output}")?;
+    } else {
+        println!("{json_output}");
+    }
+
+    Ok(())
+}
+
+/// Write the given files into `output_directory_path`, creating
+/// subdirectories as needed.
+fn write_output_files(
+    output_directory_path: &Path,
+    reconstructed_files: ReconstructedFiles,
+) -> Result<()> {
+    for (file_path, data) in reconstructed_files {
+        let file_path = output_directory_path.join(file_path);
+        if let Some(parent_directory_path) = file_path.parent() {
+            fs::create_dir_all(parent_directory_path)?;
+        }
+        let mut output_file = File::create(file_path)?;
+        output_file.write_all(data.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    use resym_core::pdb_types::SizePrintFlavor;
+    use tempdir::TempDir;
+
+    const TEST_PDB_FILE_PATH: &str = "../resym_core/tests/data/test.pdb";
+    /// Offset of the DBI stream's header in the test PDB
+    const TEST_PDB_DBI_HEADER_OFFSET: usize = 191 * 4096;
+    const TEST_PDB_FROM_FILE_PATH: &str = "../resym_core/tests/data/test_diff_from.pdb";
+    const TEST_PDB_TO_FILE_PATH: &str = "../resym_core/tests/data/test_diff_to.pdb";
+
+    // List types
+    #[test]
+    fn list_types_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .list_types_command(
+                pdb_path,
+                "resym_test::StructTest".to_string(),
+                false,
+                false,
+                false,
+                None,
+                OutputFormat::Text,
+                None,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn list_types_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should succeed
+        assert!(app
+            .list_types_command(
+                pdb_path,
+                "resym_test::StructTest".to_string(),
+                true,
+                true,
+                true,
+                None,
+                OutputFormat::Text,
+                None,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn list_types_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("list_types_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .list_types_command(
+                pdb_path,
+                "resym_test::ClassWithNestedDeclarationsTest".to_string(),
+                false,
+                false,
+                false,
+                None,
+                OutputFormat::Text,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "resym_test::ClassWithNestedDeclarationsTest::NestEnum\n",
+                "resym_test::ClassWithNestedDeclarationsTest\n",
+                "resym_test::ClassWithNestedDeclarationsTest::NestedUnion\n",
+                "resym_test::ClassWithNestedDeclarationsTest::NestedClass\n",
+                "resym_test::ClassWithNestedDeclarationsTest::NestedStruct\n"
+            )
+        );
+    }
+
+    #[test]
+    fn list_types_command_source_path_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("list_types_command_source_path_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .list_types_command(
+                pdb_path,
+                "resym_test::ClassWithNestedDeclarationsTest".to_string(),
+                false,
+                false,
+                false,
+                Some("*/symbol_zoo/*.CPP".to_string()),
+                OutputFormat::Text,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "resym_test::ClassWithNestedDeclarationsTest\n",
+                "resym_test::ClassWithNestedDeclarationsTest::NestedUnion\n",
+                "resym_test::ClassWithNestedDeclarationsTest::NestedClass\n",
+            )
+        );
+    }
+
+    #[test]
+    fn list_types_command_json_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("list_types_command_json_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.json");
+        // The command should succeed
+        assert!(app
+            .list_types_command(
+                pdb_path,
+                "resym_test::ClassWithNestedDeclarationsTest::Nested".to_string(),
+                false,
+                false,
+                false,
+                None,
+                OutputFormat::Json,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        let output: serde_json::Value =
+            serde_json::from_str(&output).expect("Failed to parse output file");
+        assert_eq!(
+            output,
+            json!([
+                {
+                    "name": "resym_test::ClassWithNestedDeclarationsTest::NestedUnion",
+                    "index": 6248,
+                },
+                {
+                    "name": "resym_test::ClassWithNestedDeclarationsTest::NestedClass",
+                    "index": 6254,
+                },
+                {
+                    "name": "resym_test::ClassWithNestedDeclarationsTest::NestedStruct",
+                    "index": 6256,
+                },
+            ])
+        );
+    }
+
+    // Dump types
+    #[test]
+    fn dump_types_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .dump_types_command(
+                pdb_path,
+                None,
+                OutputLanguage::Cpp,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // print_dependencies
+                false, // ignore_std_types
+                false, // highlight_syntax
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn dump_types_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+
+        // The command should succeed
+        assert!(app
+            .dump_types_command(
+                pdb_path,
+                None,
+                OutputLanguage::Cpp,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: true,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                true, // print_header
+                true, // print_dependencies
+                true, // ignore_std_types
+                true, // highlight_syntax
+                None
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn dump_types_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("dump_types_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+
+        // The command should succeed
+        assert!(app
+            .dump_types_command(
+                pdb_path,
+                Some("resym_test::ClassWithNestedDeclarationsTest".to_string()),
+                OutputLanguage::Cpp,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // print_dependencies
+                false, // ignore_std_types
+                false, // highlight_syntax
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!("\nclass resym_test::ClassWithNestedDeclarationsTest { /* Size=0x1 */\n};\n")
+        );
+    }
+
+    #[test]
+    fn dump_types_command_rust_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("dump_types_command_rust_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.rs");
+
+        // The command should succeed
+        assert!(app
+            .dump_types_command(
+                pdb_path,
+                Some("resym_test::ClassWithNestedDeclarationsTest".to_string()),
+                OutputLanguage::Rust,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::StaticAssert,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // print_dependencies
+                false, // ignore_std_types
+                false, // highlight_syntax
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "\n#[repr(C)]\n",
+                "#[derive(Clone, Copy)]\n",
+                "pub struct resym_test__ClassWithNestedDeclarationsTest {\n",
+                "    /* 0x0000 */ pub _pad_0x0: [u8; 1],\n",
+                "}\n",
+                "const _: () = assert!(core::mem::size_of::<resym_test__ClassWithNestedDeclarationsTest>() == 1); // 0x1\n"
+            )
+        );
+    }
+
+    // Dump types into headers
+    #[test]
+    fn dump_types_into_headers_command_rust_unsupported() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("dump_types_into_headers_command_rust_unsupported")
+            .expect("TempDir creation failed");
+
+        // The command should fail
+        assert!(app
+            .dump_types_into_headers_command(
+                pdb_path,
+                HeaderSplitFlavor::SourceFile,
+                OutputLanguage::Rust,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // ignore_std_types
+                tmp_dir.path().to_owned(),
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn dump_types_into_headers_command_source_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("dump_types_into_headers_command_source_file_successful")
+            .expect("TempDir creation failed");
+
+        // The command should succeed
+        assert!(app
+            .dump_types_into_headers_command(
+                pdb_path,
+                HeaderSplitFlavor::SourceFile,
+                OutputLanguage::Cpp,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // ignore_std_types
+                tmp_dir.path().to_owned(),
+            )
+            .is_ok());
+
+        // Check the header mirroring the test program's source file
+        let output = fs::read_to_string(
+            tmp_dir
+                .path()
+                .join("C/Users/Henry/source/repos/symbol_zoo/symbol_zoo.cpp"),
+        )
+        .expect("Failed to read output file");
+        assert!(output.starts_with(concat!(
+            "#pragma once\n",
+            "\n",
+            "#include \"../../../../../../D/a/_work/1/s/src/ExternalAPIs/WindowsSDKInc/c/Include/10.0.22000.0/um/winnt.h\"\n",
+        )));
+        assert!(output
+            .contains("\nclass resym_test::ClassWithNestedDeclarationsTest { /* Size=0x1 */\n"));
+        // The included header should exist as well
+        assert!(tmp_dir
+            .path()
+            .join("D/a/_work/1/s/src/ExternalAPIs/WindowsSDKInc/c/Include/10.0.22000.0/um/winnt.h")
+            .is_file());
+    }
+
+    #[test]
+    fn dump_types_into_headers_command_namespace_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("dump_types_into_headers_command_namespace_successful")
+            .expect("TempDir creation failed");
+
+        // The command should succeed
+        assert!(app
+            .dump_types_into_headers_command(
+                pdb_path,
+                HeaderSplitFlavor::Namespace,
+                OutputLanguage::Cpp,
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // ignore_std_types
+                tmp_dir.path().to_owned(),
+            )
+            .is_ok());
+
+        // Check the header of the test program's namespace
+        let output = fs::read_to_string(tmp_dir.path().join("resym_test.h"))
+            .expect("Failed to read output file");
+        assert!(output.starts_with("#pragma once\n\n#include \"global_namespace.h\"\n"));
+        assert!(output.contains("\nstruct resym_test::StructTest;\n"));
+        assert!(output
+            .contains("\nclass resym_test::ClassWithNestedDeclarationsTest { /* Size=0x1 */\n"));
+        // Types of the global namespace are declared in their own header
+        let output = fs::read_to_string(tmp_dir.path().join("global_namespace.h"))
+            .expect("Failed to read output file");
+        assert!(output.starts_with("#pragma once\n"));
+        assert!(!output.contains("resym_test::"));
+    }
+
+    // Diff type
+    #[test]
+    fn diff_type_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::new();
+        let pdb_path_to = PathBuf::new();
+
+        // The command should fail
+        assert!(app
+            .diff_type_command(
+                pdb_path_from,
+                pdb_path_to,
+                "".to_string(),
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // print_dependencies
+                false, // ignore_std_types
+                false, // highlight_syntax
+                None   // output_file_path
+            )
+            .is_err());
+    }
+    #[test]
+    fn diff_type_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
+        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+
+        // The command should succeed
+        assert!(app
+            .diff_type_command(
+                pdb_path_from,
+                pdb_path_to,
+                "UserStructAddAndReplace".to_string(),
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Always,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Microsoft,
+                    ..Default::default()
+                },
+                true, // print_header
+                true, // print_dependencies
+                true, // ignore_std_types
+                true, // highlight_syntax
+                None  // output_file_path
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn diff_type_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
+        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+
+        let tmp_dir =
+            TempDir::new("diff_type_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+
+        // The command should succeed
+        assert!(app
+            .diff_type_command(
+                pdb_path_from,
+                pdb_path_to,
+                "UserStructAddAndReplace".to_string(),
+                DataFormatConfiguration {
+                    print_access_specifiers: AccessSpecifierReconstructionFlavor::Disabled,
+                    size_print_flavor: SizePrintFlavor::Comment,
+                    integers_as_hexadecimal: false,
+                    print_offset_info: true,
+                    print_brackets_new_line: false,
+                    primitive_flavor: PrimitiveReconstructionFlavor::Portable,
+                    ..Default::default()
+                },
+                false, // print_header
+                false, // print_dependencies
+                false, // ignore_std_types
+                false, // highlight_syntax
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                " \n-struct UserStructAddAndReplace { /* Size=0x10 */\n",
+                "-  /* 0x0000 */ int32_t field1;\n-  /* 0x0004 */ char field2;\n",
+                "-  /* 0x0008 */ void* field3;\n+struct UserStructAddAndReplace { /* Size=0x28 */\n",
+                "+  /* 0x0000 */ int32_t before1;\n+  /* 0x0004 */ int32_t field1;\n",
+                "+  /* 0x0008 */ int32_t between12;\n+  /* 0x000c */ char field2;\n",
+                "+  /* 0x0010 */ int32_t between23;\n+  /* 0x0018 */ void* field3;\n",
+                "+  /* 0x0020 */ int32_t after3;\n };\n",
+            )
+        );
+    }
+
+    // List modules
+    #[test]
+    fn list_modules_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .list_modules_command(
+                pdb_path,
+                "*".to_string(),
+                false,
+                false,
+                OutputFormat::Text,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn list_modules_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should succeed
+        assert!(app
+            .list_modules_command(
+                pdb_path,
+                "*".to_string(),
+                true,
+                true,
+                OutputFormat::Text,
+                None
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn list_modules_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("list_modules_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .list_modules_command(
+                pdb_path,
+                "*".to_string(),
+                false,
+                false,
+                OutputFormat::Text,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "Mod 0048 | '* Linker Generated Manifest RES *'\n",
+                "Mod 0053 | '* Linker *'\n"
+            )
+        );
+    }
+
+    // Dump module
+    #[test]
+    fn dump_module_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .dump_types_command(
+            .dump_module_command(
                 pdb_path,
-                None,
+                9, // exe_main.obj
                 PrimitiveReconstructionFlavor::Microsoft,
                 AccessSpecifierReconstructionFlavor::Disabled,
-                SizePrintFlavor::Comment,
-                false, // print_header
-                false, // print_dependencies
-                false, // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                false, // ignore_std_types
-                false, // highlight_syntax
+                false,
+                false,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn dump_module_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should succeed
+        assert!(app
+            .dump_module_command(
+                pdb_path,
+                9, // exe_main.obj
+                PrimitiveReconstructionFlavor::Microsoft,
+                AccessSpecifierReconstructionFlavor::Always,
+                true,
+                true,
+                None
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn dump_module_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("dump_module_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .dump_module_command(
+                pdb_path,
+                27, // default_local_stdio_options.obj
+                PrimitiveReconstructionFlavor::Portable,
+                AccessSpecifierReconstructionFlavor::Disabled,
+                false,
+                false,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "using namespace std;\n",
+                "using PUWSTR_C = const __unaligned wchar_t*;\n",
+                "using TP_CALLBACK_ENVIRON_V3 = _TP_CALLBACK_ENVIRON_V3;\n",
+                "uint64_t* __local_stdio_scanf_options(); // RVA=0x14670 CodeSize=0x8\n",
+                "static uint64_t _OptionsStorage; // RVA=0x1eb28 \n",
+                "void __scrt_initialize_default_local_stdio_options(); // RVA=0x14680 CodeSize=0x45\n",
+            )
+        );
+    }
+
+    // Diff module
+    #[test]
+    fn diff_module_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::new();
+        let pdb_path_to = PathBuf::new();
+
+        // The command should fail
+        assert!(app
+            .diff_module_command(
+                pdb_path_from,
+                pdb_path_to,
+                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\exe_main.obj".to_string(),
+                PrimitiveReconstructionFlavor::Microsoft,
+                AccessSpecifierReconstructionFlavor::Disabled,
+                false,
+                false,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn diff_module_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
+        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+
+        // The command should succeed
+        assert!(app
+            .diff_module_command(
+                pdb_path_from,
+                pdb_path_to,
+                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\exe_main.obj".to_string(),
+                PrimitiveReconstructionFlavor::Microsoft,
+                AccessSpecifierReconstructionFlavor::Always,
+                true,
+                true,
+                None
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn diff_module_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
+        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+
+        let tmp_dir =
+            TempDir::new("diff_module_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+
+        // The command should succeed
+        assert!(app
+            .diff_module_command(
+                pdb_path_from,
+                pdb_path_to,
+                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\default_local_stdio_options.obj".to_string(),
+                PrimitiveReconstructionFlavor::Portable,
+                AccessSpecifierReconstructionFlavor::Disabled,
+                false,
+                false,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                " using namespace std;\n",
+                " using PUWSTR_C = const __unaligned wchar_t*;\n",
+                " using TP_CALLBACK_ENVIRON_V3 = _TP_CALLBACK_ENVIRON_V3;\n",
+                " uint64_t* __local_stdio_scanf_options(); // RVA=0x13c30 CodeSize=0x8\n",
+                " static uint64_t _OptionsStorage; // RVA=0x1c898 \n",
+                " void __scrt_initialize_default_local_stdio_options(); // RVA=0x13c40 CodeSize=0x45\n",
+            )
+        );
+    }
+
+    // List symbols
+    #[test]
+    fn list_symbols_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .list_symbols_command(
+                pdb_path,
+                "*".to_string(),
+                false,
+                false,
+                false,
+                OutputFormat::Text,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn list_symbols_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should succeed
+        assert!(app
+            .list_symbols_command(
+                pdb_path,
+                "*".to_string(),
+                true,
+                true,
+                true,
+                OutputFormat::Text,
+                None
+            )
+            .is_ok());
+    }
+
+    #[test]
+    fn list_symbols_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("list_modules_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .list_symbols_command(
+                pdb_path,
+                "resym_test::UnionTest".to_string(),
+                false,
+                false,
+                false,
+                OutputFormat::Text,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            concat!(
+                "resym_test::UnionTest::UnionTest\n",
+                "resym_test::UnionTest::~UnionTest\n"
+            )
+        );
+    }
+
+    // Dump symbol
+    #[test]
+    fn dump_symbol_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .dump_symbol_command(
+                pdb_path,
+                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
+                PrimitiveReconstructionFlavor::Microsoft,
+                AccessSpecifierReconstructionFlavor::Disabled,
+                false,
+                false,
+                false,
+                false,
+                false,
                 None
             )
             .is_err());
     }
 
     #[test]
-    fn dump_types_command_stdio_successful() {
+    fn dump_symbol_command_stdio_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
-
         // The command should succeed
         assert!(app
-            .dump_types_command(
+            .dump_symbol_command(
                 pdb_path,
-                None,
+                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
                 PrimitiveReconstructionFlavor::Microsoft,
                 AccessSpecifierReconstructionFlavor::Always,
-                SizePrintFlavor::Comment,
-                true,  // print_header
-                true,  // print_dependencies
-                true,  // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                true,  // ignore_std_types
-                true,  // highlight_syntax
+                true,
+                false,
+                false,
+                false,
+                true,
                 None
             )
             .is_ok());
     }
 
     #[test]
-    fn dump_types_command_file_successful() {
+    fn dump_symbol_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
         let tmp_dir =
-            TempDir::new("dump_types_command_file_successful").expect("TempDir creation failed");
+            TempDir::new("dump_module_command_file_successful").expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
-
         // The command should succeed
         assert!(app
-            .dump_types_command(
+            .dump_symbol_command(
                 pdb_path,
-                Some("resym_test::ClassWithNestedDeclarationsTest".to_string()),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                SizePrintFlavor::Comment,
-                false, // print_header
-                false, // print_dependencies
-                false, // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                false, // ignore_std_types
-                false, // highlight_syntax
+                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
+                PrimitiveReconstructionFlavor::Portable,
+                AccessSpecifierReconstructionFlavor::Always,
+                false,
+                false,
+                false,
+                false,
+                false,
                 Some(output_path.clone()),
             )
             .is_ok());
@@ -869,90 +2841,73 @@ mod tests {
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
         assert_eq!(
             output,
-            concat!("\nclass resym_test::ClassWithNestedDeclarationsTest { /* Size=0x1 */\n};\n")
+            "__cdecl resym_test::UnionTest::~UnionTest(void); // RVA=0x11bc0 ",
         );
     }
 
-    // Diff type
+    // Diff symbol
     #[test]
-    fn diff_type_command_invalid_pdb_path() {
+    fn diff_symbol_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path_from = PathBuf::new();
         let pdb_path_to = PathBuf::new();
 
         // The command should fail
         assert!(app
-            .diff_type_command(
+            .diff_symbol_command(
                 pdb_path_from,
                 pdb_path_to,
-                "".to_string(),
+                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
                 PrimitiveReconstructionFlavor::Microsoft,
                 AccessSpecifierReconstructionFlavor::Disabled,
-                SizePrintFlavor::Comment,
-                false, // print_header
-                false, // print_dependencies
-                false, // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                false, // ignore_std_types
-                false, // highlight_syntax
-                None   // output_file_path
+                false,
+                false,
+                None
             )
             .is_err());
     }
+
     #[test]
-    fn diff_type_command_stdio_successful() {
+    fn diff_symbol_command_stdio_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
         let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
 
         // The command should succeed
         assert!(app
-            .diff_type_command(
+            .diff_symbol_command(
                 pdb_path_from,
                 pdb_path_to,
-                "UserStructAddAndReplace".to_string(),
+                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
                 PrimitiveReconstructionFlavor::Microsoft,
                 AccessSpecifierReconstructionFlavor::Always,
-                SizePrintFlavor::Comment,
-                true,  // print_header
-                true,  // print_dependencies
-                false, // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                true,  // ignore_std_types
-                true,  // highlight_syntax
-                None   // output_file_path
+                true,
+                true,
+                None
             )
             .is_ok());
     }
 
     #[test]
-    fn diff_type_command_file_successful() {
+    fn diff_symbol_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
         let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
 
         let tmp_dir =
-            TempDir::new("diff_type_command_file_successful").expect("TempDir creation failed");
+            TempDir::new("diff_module_command_file_successful").expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
 
         // The command should succeed
         assert!(app
-            .diff_type_command(
+            .diff_symbol_command(
                 pdb_path_from,
                 pdb_path_to,
-                "UserStructAddAndReplace".to_string(),
+                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
                 PrimitiveReconstructionFlavor::Portable,
                 AccessSpecifierReconstructionFlavor::Disabled,
-                SizePrintFlavor::Comment,
-                false, // print_header
-                false, // print_dependencies
-                false, // integers_as_hexadecimal
-                true,  // print_offset_info
-                false, // print_brackets_new_line
-                false, // ignore_std_types
-                false, // highlight_syntax
+                false,
+                false,
                 Some(output_path.clone()),
             )
             .is_ok());
@@ -961,259 +2916,518 @@ mod tests {
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
         assert_eq!(
             output,
-            concat!(
-                " \n-struct UserStructAddAndReplace { /* Size=0x10 */\n",
-                "-  /* 0x0000 */ int32_t field1;\n-  /* 0x0004 */ char field2;\n",
-                "-  /* 0x0008 */ void* field3;\n+struct UserStructAddAndReplace { /* Size=0x28 */\n",
-                "+  /* 0x0000 */ int32_t before1;\n+  /* 0x0004 */ int32_t field1;\n",
-                "+  /* 0x0008 */ int32_t between12;\n+  /* 0x000c */ char field2;\n",
-                "+  /* 0x0010 */ int32_t between23;\n+  /* 0x0018 */ void* field3;\n",
-                "+  /* 0x0020 */ int32_t after3;\n };\n",
-            )
+            " int __cdecl _RTC_GetSrcLine(unsigned char *, wchar_t *, unsigned long, int *, wchar_t *, unsigned long); // RVA=0x14c90 \n",
         );
     }
 
-    // List modules
+    // Symbolize
     #[test]
-    fn list_modules_command_invalid_pdb_path() {
+    fn list_files_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .list_modules_command(pdb_path, "*".to_string(), false, false, None)
+            .list_files_command(pdb_path, OutputFormat::Text, None)
             .is_err());
     }
 
     #[test]
-    fn list_modules_command_stdio_successful() {
+    fn list_files_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("list_files_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .list_modules_command(pdb_path, "*".to_string(), true, true, None)
+            .list_files_command(pdb_path, OutputFormat::Text, Some(output_path.clone()))
             .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.lines().any(|line| {
+            line == "'D:\\a\\_work\\1\\s\\src\\vctools\\crt\\vcstartup\\src\\rtc\\error.cpp' | \
+                SHA256:10b3cf85f7060e8d0600678095a650826d1c4b281bd54562b822252c3700ed3c | Mod 0010"
+        }));
     }
 
     #[test]
-    fn list_modules_command_file_successful() {
+    fn list_file_functions_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .list_file_functions_command(
+                pdb_path,
+                "error.cpp".to_string(),
+                OutputFormat::Text,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn list_file_functions_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
-        let tmp_dir =
-            TempDir::new("list_modules_command_file_successful").expect("TempDir creation failed");
+        let tmp_dir = TempDir::new("list_file_functions_command_file_successful")
+            .expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .list_modules_command(
+            .list_file_functions_command(
                 pdb_path,
-                "*".to_string(),
-                false,
-                false,
+                "error.cpp".to_string(),
+                OutputFormat::Text,
                 Some(output_path.clone()),
             )
             .is_ok());
 
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.lines().any(|line| line == "failwithmessage"));
+    }
+
+    #[test]
+    fn which_module_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .which_module_command(pdb_path, 0x12f40, OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn which_module_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("which_module_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .which_module_command(
+                pdb_path,
+                0x12f40,
+                OutputFormat::Text,
+                Some(output_path.clone())
+            )
+            .is_ok());
+
         // Check output file's content
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
         assert_eq!(
             output,
-            concat!(
-                "Mod 0048 | '* Linker Generated Manifest RES *'\n",
-                "Mod 0053 | '* Linker *'\n"
-            )
+            "0x12f40: .text 0x12f30-0x13190 (0x260 bytes) | Mod 0010 | \
+             'D:\\a\\_work\\1\\s\\Intermediate\\crt\\vcstartup\\build\\xmd\\msvcrt_kernel32\\msvcrt_kernel32.nativeproj\\objd\\amd64\\error.obj'\n"
         );
     }
 
-    // Dump module
     #[test]
-    fn dump_module_command_invalid_pdb_path() {
+    fn list_contributions_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .list_contributions_command(pdb_path, None, OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn list_contributions_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("list_contributions_command_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .list_contributions_command(
+                pdb_path,
+                Some(
+                    "C:\\Users\\Henry\\source\\repos\\symbol_zoo\\x64\\Debug\\symbol_zoo.obj"
+                        .to_string()
+                ),
+                OutputFormat::Text,
+                Some(output_path.clone()),
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            ".text 0x11850-0x118f5 (0xa5 bytes) | Mod 0000 | \
+             'C:\\Users\\Henry\\source\\repos\\symbol_zoo\\x64\\Debug\\symbol_zoo.obj'\n"
+        ));
+        assert!(output.lines().all(|line| line.contains("| Mod 0000 |")));
+    }
+
+    #[test]
+    fn size_report_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .size_report_command(pdb_path, None, OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn size_report_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("size_report_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .size_report_command(
+                pdb_path,
+                Some(1),
+                OutputFormat::Text,
+                Some(output_path.clone())
+            )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            "Modules:\n\
+            \x20       Size    Count  Name\n\
+            \x20       5330       69  C:\\Users\\Henry\\source\\repos\\symbol_zoo\\x64\\Debug\\symbol_zoo.obj\n\
+            \n\
+            Object files:\n"
+        ));
+    }
+
+    #[test]
+    fn streams_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .streams_command(pdb_path, OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn streams_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("streams_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .streams_command(pdb_path, OutputFormat::Text, Some(output_path.clone()))
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            "    0       48 bytes | Old MSF directory\n\
+            \x20   1      199 bytes | PDB\n\
+            \x20   2   210416 bytes | TPI\n"
+        ));
+        assert!(output.contains("\n  114              -\n"));
+    }
+
+    #[test]
+    fn streams_command_corrupted_pdb_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let tmp_dir = TempDir::new("streams_command_corrupted_pdb_successful")
+            .expect("TempDir creation failed");
+        // Corrupt the DBI stream's header, the PDB cannot be loaded anymore
+        let mut pdb_data =
+            fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH))
+                .expect("Failed to read test PDB");
+        pdb_data[TEST_PDB_DBI_HEADER_OFFSET..TEST_PDB_DBI_HEADER_OFFSET + 64].fill(0xFF);
+        let pdb_path = tmp_dir.path().join("corrupted.pdb");
+        fs::write(&pdb_path, pdb_data).expect("Failed to write corrupted PDB");
+        assert!(app
+            .info_command(pdb_path.clone(), OutputFormat::Text, None)
+            .is_err());
+
+        // Streams can still be listed and dumped
+        let output_path = tmp_dir.path().join("output.txt");
+        assert!(app
+            .streams_command(
+                pdb_path.clone(),
+                OutputFormat::Text,
+                Some(output_path.clone())
+            )
+            .is_ok());
+        let output = fs::read_to_string(&output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            "    0       48 bytes | Old MSF directory\n\
+            \x20   1      199 bytes | PDB\n\
+            \x20   2   210416 bytes | TPI\n"
+        ));
+        assert!(output.contains("\n  111    10284 bytes | TPI hash\n"));
+        assert!(app
+            .stream_dump_command(pdb_path, "/LinkInfo".to_string(), false, Some(output_path))
+            .is_ok());
+    }
+
+    #[test]
+    fn streams_command_truncated_pdb() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let tmp_dir =
+            TempDir::new("streams_command_truncated_pdb").expect("TempDir creation failed");
+        // The stream directory is stored at the end of the test PDB
+        let pdb_data = fs::read(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH))
+            .expect("Failed to read test PDB");
+        let pdb_path = tmp_dir.path().join("truncated.pdb");
+        fs::write(&pdb_path, &pdb_data[..pdb_data.len() / 2])
+            .expect("Failed to write truncated PDB");
+        // The commands should fail
+        assert!(app
+            .streams_command(pdb_path.clone(), OutputFormat::Text, None)
+            .is_err());
+        assert!(app
+            .stream_dump_command(pdb_path, "12".to_string(), false, None)
+            .is_err());
+    }
+
+    #[test]
+    fn stream_dump_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .dump_module_command(
+            .stream_dump_command(pdb_path, "1".to_string(), false, None)
+            .is_err());
+    }
+
+    #[test]
+    fn stream_dump_command_invalid_stream() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should fail
+        assert!(app
+            .stream_dump_command(pdb_path, "/unknown".to_string(), false, None)
+            .is_err());
+    }
+
+    #[test]
+    fn stream_dump_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("stream_dump_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .stream_dump_command(
                 pdb_path,
-                9, // exe_main.obj
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Disabled,
+                "/LinkInfo".to_string(),
                 false,
-                false,
-                None
+                Some(output_path.clone())
             )
-            .is_err());
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            "00000000: 70 06 00 00 02 00 00 00  18 00 00 00 3f 00 00 00  p...........?...\n\
+            00000010: 7a 00 00 00 fa 00 00 00  43 3a 5c 55 73 65 72 73  z.......C:\\Users\n"
+        ));
     }
 
     #[test]
-    fn dump_module_command_stdio_successful() {
+    fn stream_dump_command_raw_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("stream_dump_command_raw_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.bin");
         // The command should succeed
         assert!(app
-            .dump_module_command(
-                pdb_path,
-                9, // exe_main.obj
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Always,
-                true,
-                true,
-                None
-            )
+            .stream_dump_command(pdb_path, "12".to_string(), true, Some(output_path.clone()))
             .is_ok());
+
+        // Check output file's content
+        let output = fs::read(output_path).expect("Failed to read output file");
+        assert_eq!(output.len(), 400);
+        assert!(output.starts_with(b".textbss"));
     }
 
     #[test]
-    fn dump_module_command_file_successful() {
+    fn info_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .info_command(pdb_path, OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn info_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
         let tmp_dir =
-            TempDir::new("dump_module_command_file_successful").expect("TempDir creation failed");
+            TempDir::new("info_command_file_successful").expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .dump_module_command(
-                pdb_path,
-                27, // default_local_stdio_options.obj
-                PrimitiveReconstructionFlavor::Portable,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
-                false,
-                Some(output_path.clone()),
-            )
+            .info_command(pdb_path, OutputFormat::Text, Some(output_path.clone()))
             .is_ok());
 
         // Check output file's content
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
-        assert_eq!(
-            output,
-            concat!(
-                "using namespace std;\n",
-                "using PUWSTR_C = const __unaligned wchar_t*;\n",
-                "using TP_CALLBACK_ENVIRON_V3 = _TP_CALLBACK_ENVIRON_V3;\n",
-                "uint64_t* (__local_stdio_scanf_options)(); // RVA=0x14670 CodeSize=0x8\n",
-                "static uint64_t _OptionsStorage; // RVA=0x1eb28 \n",
-                "void (__scrt_initialize_default_local_stdio_options)(); // RVA=0x14680 CodeSize=0x45\n",
-            )
-        );
+        assert!(output.starts_with(
+            "GUID: 24F10FEF-8588-4660-A5C7-BF4F2CCBADCE\n\
+            Age: 1\n\
+            DBI age: 1\n\
+            Signature: 0x65ed20f7\n\
+            Machine type: Amd64\n"
+        ));
+        assert!(output.contains("\nSection headers        12          400 bytes\n"));
     }
 
-    // Diff module
     #[test]
-    fn diff_module_command_invalid_pdb_path() {
+    fn diff_sizes_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path_from = PathBuf::new();
         let pdb_path_to = PathBuf::new();
-
         // The command should fail
         assert!(app
-            .diff_module_command(
+            .diff_sizes_command(
                 pdb_path_from,
                 pdb_path_to,
-                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\exe_main.obj".to_string(),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
-                false,
+                0,
+                None,
+                OutputFormat::Text,
                 None
             )
             .is_err());
     }
 
     #[test]
-    fn diff_module_command_stdio_successful() {
+    fn diff_sizes_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
         let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
-
+        let tmp_dir =
+            TempDir::new("diff_sizes_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .diff_module_command(
+            .diff_sizes_command(
                 pdb_path_from,
                 pdb_path_to,
-                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\exe_main.obj".to_string(),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Always,
-                true,
-                true,
-                None
+                8,
+                Some(0),
+                OutputFormat::Text,
+                Some(output_path.clone())
             )
             .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with("Total: 23122 -> 23120 (-2)\n"));
+        assert!(output.ends_with(
+            "Symbols:\n\
+            \x20       From           To        Delta  Change   Name\n\
+            \x20          0            8           +8  Added    __xc_a\n\
+            \x20          0            8           +8  Added    __xc_z\n\
+            \x20          0            8           +8  Added    __xi_a\n\
+            \x20          0            8           +8  Added    __xi_z\n"
+        ));
     }
 
     #[test]
-    fn diff_module_command_file_successful() {
+    fn diff_sizes_command_budget_exceeded() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
-        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
-
+        // Note: diffing in reverse, the total size grows by 2 bytes
+        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
         let tmp_dir =
-            TempDir::new("diff_module_command_file_successful").expect("TempDir creation failed");
-        let output_path = tmp_dir.path().join("output.txt");
-
-        // The command should succeed
+            TempDir::new("diff_sizes_command_budget_exceeded").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.json");
+        // The command should fail
         assert!(app
-            .diff_module_command(
+            .diff_sizes_command(
                 pdb_path_from,
                 pdb_path_to,
-                "d:\\a01\\_work\\43\\s\\Intermediate\\vctools\\msvcrt.nativeproj_607447030\\objd\\amd64\\default_local_stdio_options.obj".to_string(),
-                PrimitiveReconstructionFlavor::Portable,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
-                false,
-                Some(output_path.clone()),
+                0,
+                Some(1),
+                OutputFormat::Json,
+                Some(output_path.clone())
             )
-            .is_ok());
+            .is_err());
 
-        // Check output file's content
+        // The diff should be dumped anyway
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
-        assert_eq!(
-            output,
-            concat!(
-                " using namespace std;\n",
-                " using PUWSTR_C = const __unaligned wchar_t*;\n",
-                " using TP_CALLBACK_ENVIRON_V3 = _TP_CALLBACK_ENVIRON_V3;\n",
-                " uint64_t* (__local_stdio_scanf_options)(); // RVA=0x13c30 CodeSize=0x8\n",
-                " static uint64_t _OptionsStorage; // RVA=0x1c898 \n",
-                " void (__scrt_initialize_default_local_stdio_options)(); // RVA=0x13c40 CodeSize=0x45\n",
-            )
-        );
+        assert!(output.contains("\"total_to\": 23122"));
     }
 
-    // List symbols
     #[test]
-    fn list_symbols_command_invalid_pdb_path() {
+    fn dump_records_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .list_symbols_command(pdb_path, "*".to_string(), false, false, false, None)
+            .dump_records_command(pdb_path, true, true, true, None)
             .is_err());
     }
 
     #[test]
-    fn list_symbols_command_stdio_successful() {
+    fn dump_records_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("dump_records_command_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .list_symbols_command(pdb_path, "*".to_string(), true, true, true, None)
+            .dump_records_command(pdb_path, true, false, false, Some(output_path.clone()))
             .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert!(output.starts_with(
+            "*** TYPES\n\
+            \n\
+            0x1000 : Length = 42, Leaf = 0x1203 LF_FIELDLIST\n"
+        ));
+        assert!(!output.contains("*** IDS"));
+        assert!(!output.contains("*** SYMBOLS"));
     }
 
     #[test]
-    fn list_symbols_command_file_successful() {
+    fn xrefs_command_invalid_pdb_path() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .xrefs_command(
+                pdb_path,
+                "_CONTEXT".to_string(),
+                false,
+                OutputFormat::Text,
+                None
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn xrefs_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
         let tmp_dir =
-            TempDir::new("list_modules_command_file_successful").expect("TempDir creation failed");
+            TempDir::new("xrefs_command_file_successful").expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
         // The command should succeed
         assert!(app
-            .list_symbols_command(
+            .xrefs_command(
                 pdb_path,
-                "resym_test::UnionTest".to_string(),
-                false,
+                "_CONTEXT".to_string(),
                 false,
-                false,
-                Some(output_path.clone()),
+                OutputFormat::Text,
+                Some(output_path.clone())
             )
             .is_ok());
 
@@ -1221,141 +3435,192 @@ mod tests {
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
         assert_eq!(
             output,
-            concat!(
-                "resym_test::UnionTest::UnionTest\n",
-                "resym_test::UnionTest::~UnionTest\n"
+            "_DISPATCHER_CONTEXT\n_EXCEPTION_POINTERS\n_EXCEPTION_REGISTRATION_RECORD\n"
+        );
+    }
+
+    #[test]
+    fn xrefs_command_symbols_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("xrefs_command_symbols_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.txt");
+        // The command should succeed
+        assert!(app
+            .xrefs_command(
+                pdb_path,
+                "_CONTEXT".to_string(),
+                true,
+                OutputFormat::Text,
+                Some(output_path.clone())
             )
+            .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            "GS_ContextRecord | variable\n\
+            __GSHandlerCheck | parameter\n\
+            __GSHandlerCheck_EH4 | parameter\n\
+            capture_current_context | parameter\n\
+            capture_previous_context | parameter\n\
+            __scrt_fastfail | local variable context_record\n"
         );
     }
 
-    // Dump symbol
     #[test]
-    fn dump_symbol_command_invalid_pdb_path() {
+    fn graph_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .dump_symbol_command(
+            .graph_command(
                 pdb_path,
-                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
+                "_EXCEPTION_POINTERS".to_string(),
+                None,
                 false,
+                GraphFormat::Dot,
                 None
             )
             .is_err());
     }
 
     #[test]
-    fn dump_symbol_command_stdio_successful() {
+    fn graph_command_dot_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir =
+            TempDir::new("graph_command_dot_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.dot");
         // The command should succeed
         assert!(app
-            .dump_symbol_command(
+            .graph_command(
                 pdb_path,
-                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Always,
-                true,
-                true,
-                None
+                "_EXCEPTION_POINTERS".to_string(),
+                Some(1),
+                false,
+                GraphFormat::Dot,
+                Some(output_path.clone())
             )
             .is_ok());
+
+        // Check output file's content
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(
+            output,
+            "digraph types {\n\
+            \x20   node [shape=box];\n\
+            \x20   \"0x11d4\" [label=\"_EXCEPTION_POINTERS\", style=bold];\n\
+            \x20   \"0x10d5\" [label=\"_CONTEXT\"];\n\
+            \x20   \"0x111d\" [label=\"_EXCEPTION_RECORD\"];\n\
+            \x20   \"0x11d4\" -> \"0x10d5\" [style=dashed];\n\
+            \x20   \"0x11d4\" -> \"0x111d\" [style=dashed];\n\
+            }\n"
+        );
     }
 
     #[test]
-    fn dump_symbol_command_file_successful() {
+    fn graph_command_graphml_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
         let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
         let tmp_dir =
-            TempDir::new("dump_module_command_file_successful").expect("TempDir creation failed");
-        let output_path = tmp_dir.path().join("output.txt");
+            TempDir::new("graph_command_graphml_file_successful").expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.graphml");
         // The command should succeed
         assert!(app
-            .dump_symbol_command(
+            .graph_command(
                 pdb_path,
-                Some("??1UnionTest@resym_test@@QEAA@XZ".to_string()),
-                PrimitiveReconstructionFlavor::Portable,
-                AccessSpecifierReconstructionFlavor::Always,
-                false,
-                false,
-                Some(output_path.clone()),
+                "resym_test::PureVirtualClassSpecialized".to_string(),
+                None,
+                true,
+                GraphFormat::GraphMl,
+                Some(output_path.clone())
             )
             .is_ok());
 
         // Check output file's content
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
-        assert_eq!(
-            output,
-            "__cdecl resym_test::UnionTest::~UnionTest(void); // RVA=0x11bc0 ",
-        );
+        assert!(output.contains(
+            "<node id=\"0x1782\"><data key=\"name\">resym_test::PureVirtualClass</data><data key=\"depth\">1</data></node>\n"
+        ));
+        assert!(output.contains(
+            "<edge source=\"0x17b2\" target=\"0x1782\"><data key=\"kind\">base class</data></edge>\n"
+        ));
     }
 
-    // Diff symbol
     #[test]
-    fn diff_symbol_command_invalid_pdb_path() {
+    fn list_inline_sites_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path_from = PathBuf::new();
-        let pdb_path_to = PathBuf::new();
-
+        let pdb_path = PathBuf::new();
         // The command should fail
         assert!(app
-            .diff_symbol_command(
-                pdb_path_from,
-                pdb_path_to,
-                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
-                false,
+            .list_inline_sites_command(
+                pdb_path,
+                "find_pe_section".to_string(),
+                OutputFormat::Text,
                 None
             )
             .is_err());
     }
 
     #[test]
-    fn diff_symbol_command_stdio_successful() {
+    fn list_inline_sites_command_file_successful() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
-        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
-
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        let tmp_dir = TempDir::new("list_inline_sites_command_file_successful")
+            .expect("TempDir creation failed");
+        let output_path = tmp_dir.path().join("output.json");
         // The command should succeed
         assert!(app
-            .diff_symbol_command(
-                pdb_path_from,
-                pdb_path_to,
-                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
-                PrimitiveReconstructionFlavor::Microsoft,
-                AccessSpecifierReconstructionFlavor::Always,
-                true,
-                true,
-                None
+            .list_inline_sites_command(
+                pdb_path,
+                "find_pe_section".to_string(),
+                OutputFormat::Json,
+                Some(output_path.clone()),
             )
             .is_ok());
+
+        // Check output file's content
+        // Note: the test PDB doesn't contain any inline site
+        let output = fs::read_to_string(output_path).expect("Failed to read output file");
+        assert_eq!(output.trim(), "[]");
     }
 
     #[test]
-    fn diff_symbol_command_file_successful() {
+    fn symbolize_command_invalid_pdb_path() {
         let app = ResymcApp::new().expect("ResymcApp creation failed");
-        let pdb_path_from = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FROM_FILE_PATH);
-        let pdb_path_to = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_TO_FILE_PATH);
+        let pdb_path = PathBuf::new();
+        // The command should fail
+        assert!(app
+            .symbolize_command(pdb_path, vec![0x12f40], OutputFormat::Text, None)
+            .is_err());
+    }
+
+    #[test]
+    fn symbolize_command_stdio_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
+        // The command should succeed
+        assert!(app
+            .symbolize_command(pdb_path, vec![0x12f40], OutputFormat::Text, None)
+            .is_ok());
+    }
 
+    #[test]
+    fn symbolize_command_file_successful() {
+        let app = ResymcApp::new().expect("ResymcApp creation failed");
+        let pdb_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TEST_PDB_FILE_PATH);
         let tmp_dir =
-            TempDir::new("diff_module_command_file_successful").expect("TempDir creation failed");
+            TempDir::new("symbolize_command_file_successful").expect("TempDir creation failed");
         let output_path = tmp_dir.path().join("output.txt");
-
         // The command should succeed
         assert!(app
-            .diff_symbol_command(
-                pdb_path_from,
-                pdb_path_to,
-                "?_RTC_GetSrcLine@@YAHPEAEPEA_WKPEAH1K@Z".to_string(),
-                PrimitiveReconstructionFlavor::Portable,
-                AccessSpecifierReconstructionFlavor::Disabled,
-                false,
-                false,
+            .symbolize_command(
+                pdb_path,
+                vec![0x12f40, 0x1],
+                OutputFormat::Text,
                 Some(output_path.clone()),
             )
             .is_ok());
@@ -1364,7 +3629,8 @@ mod tests {
         let output = fs::read_to_string(output_path).expect("Failed to read output file");
         assert_eq!(
             output,
-            " int __cdecl _RTC_GetSrcLine(unsigned char *, wchar_t *, unsigned long, int *, wchar_t *, unsigned long); // RVA=0x14c90 \n",
+            "0x12f40: failwithmessage+0x10 at D:\\a\\_work\\1\\s\\src\\vctools\\crt\\vcstartup\\src\\rtc\\error.cpp:133\n\
+             0x1: ??\n",
         );
     }
 }
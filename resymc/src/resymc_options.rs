@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
 use resym_core::pdb_types::{
-    AccessSpecifierReconstructionFlavor, PrimitiveReconstructionFlavor, SizePrintFlavor,
+    AccessSpecifierReconstructionFlavor, GraphFormat, OutputFormat, OutputLanguage,
+    PrimitiveReconstructionFlavor, SizePrintFlavor,
 };
 use structopt::StructOpt;
 
@@ -21,6 +22,9 @@ pub enum ResymcOptions {
         type_name_filter: String,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
         /// Do not match case
         #[structopt(short = "i", long)]
         case_insensitive: bool,
@@ -30,6 +34,10 @@ pub enum ResymcOptions {
         /// Filter out types in the `std` namespace
         #[structopt(short = "s", long)]
         ignore_std_types: bool,
+        /// Only list types declared in files whose path matches this glob
+        /// pattern (e.g., "*/winnt.h")
+        #[structopt(long)]
+        source_path: Option<String>,
     },
     /// Dump type from a given PDB file
     Dump {
@@ -39,6 +47,12 @@ pub enum ResymcOptions {
         type_name: String,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+        /// Language of the reconstructed types (C++, C or Rust)
+        #[structopt(short = "l", long)]
+        output_language: Option<OutputLanguage>,
         /// Representation of primitive types
         #[structopt(short = "f", long)]
         primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
@@ -63,6 +77,21 @@ pub enum ResymcOptions {
         /// Print Brackets On New Line
         #[structopt(short = "pnl", long)]
         print_brackets_new_line: bool,
+        /// Print Virtual Function Tables
+        #[structopt(long)]
+        print_vftables: bool,
+        /// Print Explicit Padding Members
+        #[structopt(long)]
+        print_padding: bool,
+        /// Print Static Assertions On Member Offsets
+        #[structopt(long)]
+        print_offset_asserts: bool,
+        /// Print Namespace Blocks Instead Of Qualified Type Names
+        #[structopt(long)]
+        print_namespaces: bool,
+        /// Print The Source File And Line Declaring Each Type
+        #[structopt(long)]
+        print_source_locations: bool,
         /// Filter out types in the `std` namespace
         #[structopt(short = "s", long)]
         ignore_std_types: bool,
@@ -76,6 +105,12 @@ pub enum ResymcOptions {
         pdb_path: PathBuf,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+        /// Language of the reconstructed types (C++, C or Rust)
+        #[structopt(short = "l", long)]
+        output_language: Option<OutputLanguage>,
         /// Representation of primitive types
         #[structopt(short = "f", long)]
         primitive_types_flavor: Option<PrimitiveReconstructionFlavor>,
@@ -97,12 +132,34 @@ pub enum ResymcOptions {
         /// Print Brackets On New Line
         #[structopt(short = "pnl", long)]
         print_brackets_new_line: bool,
+        /// Print Virtual Function Tables
+        #[structopt(long)]
+        print_vftables: bool,
+        /// Print Explicit Padding Members
+        #[structopt(long)]
+        print_padding: bool,
+        /// Print Static Assertions On Member Offsets
+        #[structopt(long)]
+        print_offset_asserts: bool,
+        /// Print Namespace Blocks Instead Of Qualified Type Names
+        #[structopt(long)]
+        print_namespaces: bool,
+        /// Print The Source File And Line Declaring Each Type
+        #[structopt(long)]
+        print_source_locations: bool,
         /// Filter out types in the `std` namespace
         #[structopt(short = "s", long)]
         ignore_std_types: bool,
         /// Highlight C++ output
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
+        /// Write one header per source file declaring types into the given
+        /// directory, mirroring the original directory layout
+        #[structopt(long, conflicts_with = "output-file-path")]
+        split_by_header: Option<PathBuf>,
+        /// Write one header per top-level namespace into the given directory
+        #[structopt(long, conflicts_with_all = &["output-file-path", "split-by-header"])]
+        split_by_namespace: Option<PathBuf>,
     },
     /// Compute diff for a type between two given PDB files
     Diff {
@@ -138,6 +195,21 @@ pub enum ResymcOptions {
         /// Print Brackets On New Line
         #[structopt(short = "pnl", long)]
         print_brackets_new_line: bool,
+        /// Print Virtual Function Tables
+        #[structopt(long)]
+        print_vftables: bool,
+        /// Print Explicit Padding Members
+        #[structopt(long)]
+        print_padding: bool,
+        /// Print Static Assertions On Member Offsets
+        #[structopt(long)]
+        print_offset_asserts: bool,
+        /// Print Namespace Blocks Instead Of Qualified Type Names
+        #[structopt(long)]
+        print_namespaces: bool,
+        /// Print The Source File And Line Declaring Each Type
+        #[structopt(long)]
+        print_source_locations: bool,
         /// Filter out types in the `std` namespace
         #[structopt(short = "s", long)]
         ignore_std_types: bool,
@@ -153,6 +225,9 @@ pub enum ResymcOptions {
         module_path_filter: String,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
         /// Do not match case
         #[structopt(short = "i", long)]
         case_insensitive: bool,
@@ -204,6 +279,28 @@ pub enum ResymcOptions {
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
     },
+    /// List source files referenced by a given PDB file
+    ListFiles {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// List functions defined in a given source file
+    ListFileFunctions {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the source file (or its trailing components, e.g., its name)
+        file_path: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
     /// List symbols from a given PDB file
     ListSymbols {
         /// Path to the PDB file
@@ -212,6 +309,9 @@ pub enum ResymcOptions {
         symbol_name_filter: String,
         /// Path of the output file
         output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
         /// Do not match case
         #[structopt(short = "i", long)]
         case_insensitive: bool,
@@ -239,6 +339,17 @@ pub enum ResymcOptions {
         /// Print C++ access specifiers
         #[structopt(short = "a", long)]
         print_access_specifiers: Option<AccessSpecifierReconstructionFlavor>,
+        /// Print the local variables of procedures, per lexical block
+        #[structopt(long)]
+        print_locals: bool,
+        /// Print the address ranges in which parameters and local variables
+        /// are live, and where they're stored in each range (implies
+        /// `--print-locals`)
+        #[structopt(long)]
+        locations: bool,
+        /// Print the tree of functions inlined in procedures
+        #[structopt(long)]
+        print_inlinees: bool,
         /// Highlight C++ output
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
@@ -258,6 +369,17 @@ pub enum ResymcOptions {
         /// Print C++ access specifiers
         #[structopt(short = "a", long)]
         print_access_specifiers: Option<AccessSpecifierReconstructionFlavor>,
+        /// Print the local variables of procedures, per lexical block
+        #[structopt(long)]
+        print_locals: bool,
+        /// Print the address ranges in which parameters and local variables
+        /// are live, and where they're stored in each range (implies
+        /// `--print-locals`)
+        #[structopt(long)]
+        locations: bool,
+        /// Print the tree of functions inlined in procedures
+        #[structopt(long)]
+        print_inlinees: bool,
         /// Highlight C++ output
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
@@ -285,4 +407,184 @@ pub enum ResymcOptions {
         #[structopt(short = "H", long)]
         highlight_syntax: bool,
     },
+    /// List the procedures in which a given function has been inlined
+    ListInlineSites {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the inlined function
+        function_name: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// Map addresses to the symbols, source lines and inlined functions they
+    /// belong to
+    Symbolize {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// RVAs to symbolize, in hexadecimal (read from stdin, one per line,
+        /// if none are given)
+        #[structopt(parse(try_from_str = parse_rva))]
+        rvas: Vec<u32>,
+        /// Path of the output file
+        #[structopt(short = "o", long)]
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// Find the module which contributed the code or data at a given address
+    WhichModule {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// RVA to look up, in hexadecimal
+        #[structopt(parse(try_from_str = parse_rva))]
+        rva: u32,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// List the ranges of the image contributed by modules
+    ListContributions {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Only list the contributions of the module (or static library) at
+        /// this path
+        #[structopt(long)]
+        module: Option<String>,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// Report the size of the image attributed to modules, object files,
+    /// namespaces and template families
+    SizeReport {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+        /// Only report the N largest entries of each category
+        #[structopt(long)]
+        top: Option<usize>,
+    },
+    /// Print information identifying a PDB file and a summary of its content
+    Info {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// List the MSF streams of a PDB file
+    Streams {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+    /// Dump the content of an MSF stream, given its index or its name
+    StreamDump {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Index or name of the stream (e.g., 7 or /names)
+        stream: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Extract the raw content of the stream instead of an hexdump
+        #[structopt(long)]
+        raw: bool,
+    },
+    /// Compare the size of functions and data between two PDB files
+    DiffSizes {
+        /// Path of the PDB file to compute the diff from
+        from_pdb_path: PathBuf,
+        /// Path of the PDB file to compute the diff to
+        to_pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+        /// Only report symbols whose size changed by at least N bytes
+        #[structopt(long)]
+        threshold: Option<u64>,
+        /// Fail if the total size grew by more than N bytes
+        #[structopt(long)]
+        budget: Option<u64>,
+    },
+    /// Dump raw CodeView records, in a format close to cvdump's (dumps
+    /// everything if no record kind is selected)
+    DumpRecords {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Dump type records (TPI stream)
+        #[structopt(long)]
+        types: bool,
+        /// Dump id records (IPI stream)
+        #[structopt(long)]
+        ids: bool,
+        /// Dump the symbol records of each module
+        #[structopt(long)]
+        symbols: bool,
+    },
+    /// Export the graph of the types a given type depends on
+    Graph {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the type to start from
+        type_name: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// Maximum number of levels of dependencies to follow
+        #[structopt(long)]
+        depth: Option<usize>,
+        /// Format of the output (DOT, GraphML or JSON)
+        #[structopt(long)]
+        format: Option<GraphFormat>,
+        /// Include edges to base classes
+        #[structopt(long)]
+        base_classes: bool,
+    },
+    /// List the types or the symbols which use a given type
+    Xrefs {
+        /// Path to the PDB file
+        pdb_path: PathBuf,
+        /// Name of the type to look for
+        type_name: String,
+        /// Path of the output file
+        output_file_path: Option<PathBuf>,
+        /// List the variables and functions using the type instead of the
+        /// types referencing it
+        #[structopt(long)]
+        symbols: bool,
+        /// Format of the output (text or JSON)
+        #[structopt(long)]
+        format: Option<OutputFormat>,
+    },
+}
+
+/// Parse an RVA written in hexadecimal, with or without a `0x` prefix.
+pub fn parse_rva(rva: &str) -> Result<u32, std::num::ParseIntError> {
+    let rva = rva.trim();
+    let rva = rva
+        .strip_prefix("0x")
+        .or_else(|| rva.strip_prefix("0X"))
+        .unwrap_or(rva);
+    u32::from_str_radix(rva, 16)
 }